@@ -0,0 +1 @@
+drop table grading_policy;
@@ -0,0 +1,11 @@
+create table grading_policy (
+              awesome_person_id integer primary key,
+              max_distance integer not null default 10 check (max_distance >= 1),
+              well_known_threshold float8 not null default 0.98
+                check (well_known_threshold > 0 and well_known_threshold <= 1),
+              close_distance integer not null default 3 check (close_distance >= 0),
+              perfect_weight float8 not null default 2.0 check (perfect_weight > 0),
+              updated timestamptz not null default now(),
+              constraint fk_grading_policy_awesome_person
+                foreign key (awesome_person_id) references awesome_person(id)
+);
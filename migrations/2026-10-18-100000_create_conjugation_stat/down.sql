@@ -0,0 +1 @@
+drop table conjugation_stat;
@@ -0,0 +1,11 @@
+create table conjugation_stat (
+              awesome_person_id integer not null,
+              tense varchar not null,
+              person varchar not null,
+              attempts integer not null default 0 check (attempts >= 0),
+              correct_attempts integer not null default 0 check (correct_attempts >= 0),
+              updated timestamptz not null default now(),
+              primary key (awesome_person_id, tense, person),
+              constraint fk_conjugation_stat_awesome_person
+                foreign key (awesome_person_id) references awesome_person(id)
+);
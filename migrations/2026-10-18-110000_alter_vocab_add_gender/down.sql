@@ -0,0 +1 @@
+alter table vocab drop column gender;
@@ -0,0 +1 @@
+alter table vocab add column gender varchar;
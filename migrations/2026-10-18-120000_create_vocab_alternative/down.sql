@@ -0,0 +1,9 @@
+alter table vocab add column alternatives varchar default '';
+
+update vocab v
+set alternatives = (select string_agg(a.alternative, ', ' order by a.id)
+                    from vocab_alternative a
+                    where a.vocab_id = v.id)
+where exists (select 1 from vocab_alternative a where a.vocab_id = v.id);
+
+drop table vocab_alternative;
@@ -0,0 +1,18 @@
+create table vocab_alternative (
+              id serial primary key,
+              vocab_id integer not null,
+              alternative varchar not null,
+              register varchar,
+              unique (vocab_id, alternative),
+              constraint fk_vocab_alternative_vocab
+                foreign key (vocab_id) references vocab(id) on delete cascade
+);
+create index vocab_alternative_lower_alternative on vocab_alternative (lower(alternative));
+
+-- Each entry of the comma separated alternatives becomes a row
+insert into vocab_alternative (vocab_id, alternative)
+select distinct v.id, trim(split.alternative)
+from vocab v, regexp_split_to_table(v.alternatives, ',') as split(alternative)
+where trim(split.alternative) <> '';
+
+alter table vocab drop column alternatives;
@@ -0,0 +1,3 @@
+alter table awesome_person drop column preferred_variant;
+alter table vocab_alternative drop column lang_variant;
+alter table vocab drop column lang_variant;
@@ -0,0 +1,4 @@
+-- BCP-47 style variant codes, e.g. es-ES or es-MX, empty or null when not specific to a variant
+alter table vocab add column lang_variant varchar;
+alter table vocab_alternative add column lang_variant varchar;
+alter table awesome_person add column preferred_variant varchar;
@@ -0,0 +1,2 @@
+drop table study_session_item;
+drop table study_session;
@@ -0,0 +1,27 @@
+create table study_session (
+              id serial primary key,
+              awesome_person_id integer not null,
+              started timestamptz not null default now(),
+              finished timestamptz,
+              num_answers integer not null default 0 check (num_answers >= 0),
+              num_correct integer not null default 0 check (num_correct >= 0),
+              constraint fk_study_session_awesome_person
+                foreign key (awesome_person_id) references awesome_person(id)
+);
+create index study_session_awesome_person_idx on study_session (awesome_person_id);
+create table study_session_item (
+              study_session_id integer not null,
+              vocab_study_id integer not null,
+              attempts integer not null default 0 check (attempts >= 0),
+              correct_attempts integer not null default 0 check (correct_attempts >= 0),
+              percentage_before float8 not null default 0,
+              percentage_after float8 not null default 0,
+              missed boolean not null default false,
+              redrilled boolean not null default false,
+              created timestamptz not null default now(),
+              primary key (study_session_id, vocab_study_id),
+              constraint fk_study_session_item_study_session
+                foreign key (study_session_id) references study_session(id) on delete cascade,
+              constraint fk_study_session_item_vocab_study
+                foreign key (vocab_study_id) references vocab_study(id) on delete cascade
+);
@@ -0,0 +1,4 @@
+alter table vocab_study drop column user_hint;
+alter table vocab_study drop column suspended;
+alter table vocab_study drop column leech;
+alter table vocab_study drop column lapses;
@@ -0,0 +1,5 @@
+alter table vocab_study add column lapses integer not null default 0 check (lapses >= 0);
+alter table vocab_study add column leech boolean not null default false;
+alter table vocab_study add column suspended boolean not null default false;
+-- The awesome person's own hint, e.g. a mnemonic for a leech
+alter table vocab_study add column user_hint varchar;
@@ -0,0 +1,3 @@
+drop table vocab_attempt;
+alter table awesome_person drop column reviews_per_day;
+alter table awesome_person drop column new_per_day;
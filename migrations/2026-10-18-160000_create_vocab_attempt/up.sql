@@ -0,0 +1,15 @@
+alter table awesome_person add column new_per_day integer not null default 20 check (new_per_day >= 0);
+alter table awesome_person add column reviews_per_day integer not null default 200 check (reviews_per_day >= 0);
+create table vocab_attempt (
+              id serial primary key,
+              awesome_person_id integer not null,
+              vocab_study_id integer not null,
+              new_word boolean not null,
+              correct boolean not null,
+              attempted timestamptz not null default now(),
+              constraint fk_vocab_attempt_awesome_person
+                foreign key (awesome_person_id) references awesome_person(id),
+              constraint fk_vocab_attempt_vocab_study
+                foreign key (vocab_study_id) references vocab_study(id) on delete cascade
+);
+create index vocab_attempt_awesome_person_attempted_idx on vocab_attempt (awesome_person_id, attempted);
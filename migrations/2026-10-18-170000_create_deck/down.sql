@@ -0,0 +1,2 @@
+drop table deck_vocab;
+drop table deck;
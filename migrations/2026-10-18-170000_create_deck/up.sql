@@ -0,0 +1,23 @@
+create table deck (
+              id serial primary key,
+              owner_id integer not null,
+              name varchar not null check (name <> ''),
+              description varchar,
+              known_lang_code varchar not null,
+              learning_lang_code varchar not null,
+              created timestamptz not null default now(),
+              constraint fk_deck_owner
+                foreign key (owner_id) references awesome_person(id),
+              constraint deck_owner_name_unique unique (owner_id, name)
+);
+create table deck_vocab (
+              deck_id integer not null,
+              vocab_id integer not null,
+              added timestamptz not null default now(),
+              primary key (deck_id, vocab_id),
+              constraint fk_deck_vocab_deck
+                foreign key (deck_id) references deck(id) on delete cascade,
+              constraint fk_deck_vocab_vocab
+                foreign key (vocab_id) references vocab(id) on delete cascade
+);
+create index deck_vocab_vocab_idx on deck_vocab (vocab_id);
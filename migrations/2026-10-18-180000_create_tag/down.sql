@@ -0,0 +1,2 @@
+drop table vocab_tag;
+drop table tag;
@@ -0,0 +1,24 @@
+create table tag (
+              id serial primary key,
+              name varchar not null unique check (name <> '' and name = lower(trim(name)))
+);
+create table vocab_tag (
+              vocab_id integer not null,
+              tag_id integer not null,
+              primary key (vocab_id, tag_id),
+              constraint fk_vocab_tag_vocab
+                foreign key (vocab_id) references vocab(id) on delete cascade,
+              constraint fk_vocab_tag_tag
+                foreign key (tag_id) references tag(id) on delete cascade
+);
+create index vocab_tag_tag_idx on vocab_tag (tag_id);
+
+-- Each skill becomes a tag of its vocab.
+insert into tag (name)
+select distinct lower(trim(skill)) from vocab
+where skill is not null and trim(skill) <> ''
+on conflict do nothing;
+insert into vocab_tag (vocab_id, tag_id)
+select v.id, t.id from vocab v
+join tag t on t.name = lower(trim(v.skill))
+on conflict do nothing;
@@ -0,0 +1,4 @@
+drop index vocab_alternative_trgm_idx;
+drop index vocab_first_lang_trgm_idx;
+drop index vocab_learning_lang_trgm_idx;
+drop function f_unaccent(text);
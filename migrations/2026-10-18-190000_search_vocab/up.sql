@@ -0,0 +1,15 @@
+create extension if not exists pg_trgm;
+create extension if not exists unaccent;
+
+-- unaccent is only stable, the search indexes need an immutable function.
+create or replace function f_unaccent(text) returns text
+    language sql immutable strict parallel safe
+    set search_path = palabras, public
+    as $$ select unaccent('unaccent'::regdictionary, $1) $$;
+
+create index vocab_learning_lang_trgm_idx on vocab
+    using gin (f_unaccent(lower(learning_lang)) gin_trgm_ops);
+create index vocab_first_lang_trgm_idx on vocab
+    using gin (f_unaccent(lower(first_lang)) gin_trgm_ops);
+create index vocab_alternative_trgm_idx on vocab_alternative
+    using gin (f_unaccent(lower(alternative)) gin_trgm_ops);
@@ -1,19 +1,33 @@
+use crate::error::PalabrasError;
+use crate::secrets::{DbLink, SecretProvider};
+use async_trait::async_trait;
 use aws_config::{self, BehaviorVersion, Region};
 use aws_sdk_secretsmanager;
-use serde::Deserialize;
-use std::env;
 
-#[derive(Deserialize)]
-pub struct DbLink {
-    pub username: String,
-    pub password: String,
-    pub dbname: String,
-    pub host: String,
-    pub port: String,
+/// Reads the database connection info from AWS Secrets Manager.
+///
+/// # Fields
+///
+/// - `db_link`: The id of the secret, holding the connection info in the [`DbLink`] shape.
+/// - `region`: The AWS region the secret is kept in.
+pub struct AwsSecretProvider {
+    pub db_link: String,
+    pub region: String,
 }
 
-async fn lookup_url(db_link: String, region_str: String) -> Result<Option<String>, String> {
-    let region = Region::new(region_str.clone());
+#[async_trait]
+impl SecretProvider for AwsSecretProvider {
+    /// Implementation, see trait for details [`SecretProvider::database_url`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    async fn database_url(&self) -> Result<String, PalabrasError> {
+        lookup_url(&self.db_link, &self.region).await
+    }
+}
+
+async fn lookup_url(db_link: &str, region_str: &str) -> Result<String, PalabrasError> {
+    let region = Region::new(region_str.to_string());
 
     let config = aws_config::defaults(BehaviorVersion::v2023_11_09())
         .region(region)
@@ -24,46 +38,19 @@ async fn lookup_url(db_link: String, region_str: String) -> Result<Option<String
 
     let response = asm
         .get_secret_value()
-        .secret_id(db_link.clone())
+        .secret_id(db_link)
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| PalabrasError::Config(e.to_string()))?;
 
-    if response.secret_string().is_none() {
-        return Err(format!(
+    let secret = response.secret_string().ok_or_else(|| {
+        PalabrasError::Config(format!(
             "Failed to find {} at region {}",
             db_link, region_str
-        ));
-    }
-
-    let db_link: DbLink = serde_json::from_str(response.secret_string().unwrap_or_default())
-        .map_err(|err| err.to_string())?;
-
-    let url = format!(
-        "postgres://{}:{}@{}:{}/{}",
-        db_link.username, db_link.password, db_link.host, db_link.port, db_link.dbname
-    );
+        ))
+    })?;
 
-    Ok(Some(url))
-}
-
-fn fallback_database_url() -> String {
-    println!("!!!USING FALLBACK DATABASE URL!!!");
-
-    env::var("PAL_DATABASE_URL").expect("fallback env var DATABASE_URL was hoped for but not found")
-}
-
-pub async fn find_the_database() -> String {
-    let db_link = env::var("PAL_DB_LINK").unwrap_or_default();
-    let region = env::var("PAL_REGION").unwrap_or_default();
+    let db_link: DbLink = serde_json::from_str(secret)?;
 
-    if db_link.is_empty() || region.is_empty() {
-        fallback_database_url()
-    } else {
-        if let Ok(Some(url)) = lookup_url(db_link, region).await {
-            url
-        } else {
-            fallback_database_url()
-        }
-    }
+    Ok(db_link.to_url())
 }
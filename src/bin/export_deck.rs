@@ -0,0 +1,67 @@
+use dotenv::dotenv;
+use palabras::config::app_config::init_app_config;
+use palabras::dal::db_connection::{
+    establish_connection_pool_with_config, verify_connection_migrate_db,
+};
+use palabras::error::PalabrasError;
+use palabras::secrets::find_the_database;
+use palabras::sl::sync_vocab::export_deck;
+use std::env;
+use std::error::Error;
+
+/// Exports the vocab of a deck to a CSV file, which `import_deck` reads back.
+///
+/// The file has a header line, `learning,infinitive,pos,skill,first,tags`, and a line for each vocab in the
+/// order it was added to the deck, with its tags separated by semicolons. The file must not already exist.
+///
+/// # Environment
+/// See the documentation of [`main`], which also describes the `--config` and `--set` arguments.
+///
+/// # Arguments
+///
+/// - `argv[1]`: The identifier of the deck.
+/// - `argv[2]` (optional): The path to the export file. If not specified, defaults to
+///   `"data/deck_<id>.csv"`.
+/// - `argv[3..]` (optional): Tags, only the vocab of the deck with every one of them is exported.
+///
+/// # Errors
+///
+/// Returns an error if it encounters issues loading environment variables, connecting to the
+/// database, performing the migration, or exporting the deck.
+///
+/// # Example Usage
+///
+/// ```sh
+/// cargo run --bin export_deck 1 "data/kitchen.csv"
+/// cargo run --bin export_deck 1 "data/kitchen_a2.csv" a2
+/// ```
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv().ok(); // Load environment variables from .env file
+    let (config, args) = init_app_config(env::args().skip(1).collect())?;
+
+    let Some(deck_id) = args.first() else {
+        return Err(PalabrasError::Validation(
+            "Usage: export_deck <deck id> [csv file] [tag...]".to_string(),
+        )
+        .into());
+    };
+    let deck_id: i32 = deck_id
+        .parse()
+        .map_err(|_| PalabrasError::Validation(format!("Not a deck id: {}", deck_id)))?;
+    let export_file = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| format!("data/deck_{}.csv", deck_id));
+
+    let db_url = find_the_database(&config.database).await?;
+    establish_connection_pool_with_config(db_url, &config.database.pool_config());
+    verify_connection_migrate_db()?;
+
+    let tags = args.get(2..).unwrap_or_default();
+
+    let num_exported = export_deck(deck_id, tags, &export_file)?;
+    println!("Exported {} vocabs to {}", num_exported, export_file);
+
+    Ok(())
+}
@@ -1,9 +1,14 @@
 use async_graphql::{EmptySubscription, Schema};
-use palabras::gql::studies::{MutationRoot, QueryRoot};
+use palabras::gql::root::{MutationRoot, QueryRoot};
 
 // Assuming you have defined your QueryRoot and other types
 async fn export_schema() -> async_graphql::Result<()> {
-    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription).finish();
+    let schema = Schema::build(
+        QueryRoot::default(),
+        MutationRoot::default(),
+        EmptySubscription,
+    )
+    .finish();
 
     // Generate the schema in SDL format
     let sdl = schema.sdl();
@@ -1,6 +1,9 @@
 use dotenv::dotenv;
-use palabras::aws::glue::find_the_database;
-use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::config::app_config::init_app_config;
+use palabras::dal::db_connection::{
+    establish_connection_pool_with_config, verify_connection_migrate_db,
+};
+use palabras::secrets::find_the_database;
 use palabras::sl::sync_vocab::export_missing_first_lang_pairs;
 use std::env;
 use std::error::Error;
@@ -15,7 +18,7 @@ use std::error::Error;
 /// Vocab words with missing first language fields are exported, no matter what user uploaded them.
 ///
 /// # Environment
-/// See the documentation of [`main`].
+/// See the documentation of [`main`], which also describes the `--config` and `--set` arguments.
 ///
 /// # Arguments
 ///
@@ -49,17 +52,15 @@ use std::error::Error;
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok(); // Load environment variables from .env file
-    let db_url = find_the_database().await;
-    establish_connection_pool(db_url);
+    let (config, args) = init_app_config(env::args().skip(1).collect())?;
+    let db_url = find_the_database(&config.database).await?;
+    establish_connection_pool_with_config(db_url, &config.database.pool_config());
     verify_connection_migrate_db()?;
 
-    let args: Vec<String> = env::args().collect();
-
-    let export_file = if args.len() < 2 {
-        "data/export.csv".to_string()
-    } else {
-        args[1].clone()
-    };
+    let export_file = args
+        .first()
+        .cloned()
+        .unwrap_or_else(|| "data/export.csv".to_string());
 
     // TODO Add modes to this for various types of exports,
     // TODO alternative export filters, specific languages or specific user vocabs
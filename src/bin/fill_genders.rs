@@ -0,0 +1,43 @@
+use dotenv::dotenv;
+use palabras::config::app_config::init_app_config;
+use palabras::config::load_translations_config;
+use palabras::dal::db_connection::{
+    establish_connection_pool_with_config, verify_connection_migrate_db,
+};
+use palabras::secrets::find_the_database;
+use palabras::sl::sync_vocab::fill_missing_genders;
+use std::env;
+use std::error::Error;
+
+/// Admin tool filling the missing grammatical gender of noun vocabs.
+///
+/// The gender of each single word vocab without one is looked up in the dictionaries of the
+/// translations config that have a `gender_regex`, for example the `{f}` of `añadidura` in the XML
+/// dictionary. Once filled, answers to the noun are checked for the right article.
+///
+/// # Environment
+/// See the documentation of [`main`], which also describes the `--config` and `--set` arguments.
+///
+/// # Errors
+///
+/// Returns an error if it encounters issues loading environment variables, connecting to the
+/// database, performing the migration, or updating the vocabs.
+///
+/// # Example Usage
+///
+/// ```sh
+/// cargo run --bin fill_genders
+/// ```
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv().ok(); // Load environment variables from .env file
+    let (config, _) = init_app_config(env::args().skip(1).collect())?;
+    let db_url = find_the_database(&config.database).await?;
+    establish_connection_pool_with_config(db_url, &config.database.pool_config());
+    verify_connection_migrate_db()?;
+
+    let num_updated = fill_missing_genders(load_translations_config()?)?;
+    println!("Filled the gender of {} vocabs", num_updated);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use dotenv::dotenv;
+use palabras::config::app_config::init_app_config;
+use palabras::dal::db_connection::{
+    establish_connection_pool_with_config, verify_connection_migrate_db,
+};
+use palabras::secrets::find_the_database;
+use palabras::sl::sync_vocab::fill_missing_infinitives;
+use std::env;
+use std::error::Error;
+
+/// Admin tool filling the missing infinitives of Spanish verb vocabs.
+///
+/// New vocabs are analysed by the conjugation engine when they are imported. This tool runs the
+/// same analysis over single word Spanish vocabs already in the database that have no infinitive,
+/// filling the infinitive and, when it is empty, the hint, for example `salir, yo, presente`.
+///
+/// # Environment
+/// See the documentation of [`main`], which also describes the `--config` and `--set` arguments.
+///
+/// # Errors
+///
+/// Returns an error if it encounters issues loading environment variables, connecting to the
+/// database, performing the migration, or updating the vocabs.
+///
+/// # Example Usage
+///
+/// ```sh
+/// cargo run --bin fill_infinitives
+/// ```
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv().ok(); // Load environment variables from .env file
+    let (config, _) = init_app_config(env::args().skip(1).collect())?;
+    let db_url = find_the_database(&config.database).await?;
+    establish_connection_pool_with_config(db_url, &config.database.pool_config());
+    verify_connection_migrate_db()?;
+
+    let num_updated = fill_missing_infinitives()?;
+    println!("Filled the infinitives of {} vocabs", num_updated);
+
+    Ok(())
+}
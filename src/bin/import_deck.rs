@@ -0,0 +1,62 @@
+use dotenv::dotenv;
+use palabras::config::app_config::init_app_config;
+use palabras::config::init_language_packs;
+use palabras::dal::db_connection::{
+    establish_connection_pool_with_config, verify_connection_migrate_db,
+};
+use palabras::error::PalabrasError;
+use palabras::secrets::find_the_database;
+use palabras::sl::sync_vocab::import_deck;
+use std::env;
+use std::error::Error;
+
+/// Imports the vocab of a CSV file into a deck.
+///
+/// Each line of the file has the learning language, infinitive, part of speech, skill and known language,
+/// after a header line, the format written by `export_deck`. Vocab already in the database is reused and
+/// any other vocab is imported in the languages of the deck. The owner of the deck starts studying the
+/// vocab that is new to them.
+///
+/// # Environment
+/// See the documentation of [`main`], which also describes the `--config` and `--set` arguments.
+///
+/// # Arguments
+///
+/// - `argv[1]`: The identifier of the deck, see the `createDeck` mutation.
+/// - `argv[2]`: The path to the CSV file.
+///
+/// # Errors
+///
+/// Returns an error if it encounters issues loading environment variables, connecting to the
+/// database, performing the migration, reading the file or importing the vocab.
+///
+/// # Example Usage
+///
+/// ```sh
+/// cargo run --bin import_deck 1 "data/kitchen.csv"
+/// ```
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv().ok(); // Load environment variables from .env file
+    let (config, args) = init_app_config(env::args().skip(1).collect())?;
+    init_language_packs()?;
+
+    let (Some(deck_id), Some(import_file)) = (args.first(), args.get(1)) else {
+        return Err(PalabrasError::Validation(
+            "Usage: import_deck <deck id> <csv file>".to_string(),
+        )
+        .into());
+    };
+    let deck_id: i32 = deck_id
+        .parse()
+        .map_err(|_| PalabrasError::Validation(format!("Not a deck id: {}", deck_id)))?;
+
+    let db_url = find_the_database(&config.database).await?;
+    establish_connection_pool_with_config(db_url, &config.database.pool_config());
+    verify_connection_migrate_db()?;
+
+    let num_added = import_deck(deck_id, import_file)?;
+    println!("Added {} vocabs to deck {}", num_added, deck_id);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use dotenv::dotenv;
+use palabras::config::app_config::init_app_config;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{
+    establish_connection_pool_with_config, verify_connection_migrate_db,
+};
+use palabras::secrets::find_the_database;
+use std::env;
+use std::error::Error;
+
+/// Admin tool recalculating the stats of every awesome person.
+///
+/// The totals on each awesome person, `num_known`, `num_correct`, `num_incorrect` and
+/// `total_percentage`, are kept up to date as answers are graded. This tool rebuilds them
+/// from the vocab studies, which repairs totals that have drifted, for example the inflated
+/// `num_known` counts left by earlier versions.
+///
+/// # Environment
+/// See the documentation of [`main`], which also describes the `--config` and `--set` arguments.
+///
+/// # Errors
+///
+/// Returns an error if it encounters issues loading environment variables, connecting to the
+/// database, performing the migration, or updating the stats.
+///
+/// # Example Usage
+///
+/// ```sh
+/// cargo run --bin recalc_awesome_stats
+/// ```
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    dotenv().ok(); // Load environment variables from .env file
+    let (config, _) = init_app_config(env::args().skip(1).collect())?;
+    let db_url = find_the_database(&config.database).await?;
+    establish_connection_pool_with_config(db_url, &config.database.pool_config());
+    verify_connection_migrate_db()?;
+
+    let num_updated = DbAwesomePersonRepository.recalculate_awesome_person_stats()?;
+    println!("Recalculated the stats of {} awesome people", num_updated);
+
+    Ok(())
+}
@@ -1,12 +1,40 @@
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+use crossterm::terminal::{
+    self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use crossterm::{cursor, execute, queue};
 use dotenv::dotenv;
-use palabras::aws::glue::find_the_database;
-use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
-use palabras::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use palabras::config::app_config::init_app_config;
+use palabras::config::{init_language_packs, language_packs};
+use palabras::dal::db_connection::{
+    establish_connection_pool_with_config, verify_connection_migrate_db,
+};
+use palabras::error::PalabrasError;
+use palabras::models::{Vocab, VocabStudy};
+use palabras::secrets::find_the_database;
+use palabras::sl::articles::GenderArticles;
+use palabras::sl::conjugation::{ConjugationDrill, Tense};
+use palabras::sl::daily_quota::{DailyLimits, DailyQuotaService};
+use palabras::sl::fuzzy_match_vocab::{Answering, LearnVocab, StudyFilter, VocabFuzzyMatch};
+use palabras::sl::phrase_match::{char_diff, CharDiff};
+use palabras::sl::study_session::{SessionSummary, StudySessionService, StudySessions};
 use std::error::Error;
-use std::io::Write;
-use std::{env, io};
+use std::io::{self, Stdout, Write};
+use std::{env, process};
+use strsim::levenshtein;
 
-/// Entry point for the Vocab Learning CLI application.
+const USAGE: &str = "Usage: shell_study <learner> [mode] [--learner <id>] [--count <n>] \
+[--mode translate|articles|conjugation] [--direction to-learning|to-known] [--pos <pos>] \
+[--skill <skill>] [--lang <code>] [--deck <id>] [--tag <tag>]...";
+
+// The start of the feedback to a right answer, see LearnVocab::determine_match_prompt.
+const PERFECT_MATCH: &str = "Perfect Match!";
+
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// Entry point for the Vocab Learning CLI application, a terminal UI for studying.
 ///
 /// This function initializes the application environment, verifies and migrates the database
 /// schema as necessary, and starts the learning session for a specified `awesome_person_id`.
@@ -14,59 +42,892 @@ use std::{env, io};
 /// study progress based on the user's guesses.
 ///
 /// # Environment
-/// See the documentation of [`main`].
+/// See the documentation of [`main`], which also describes the `--config` and `--set` arguments.
 ///
 /// # Behavior
 ///
-/// - Retrieves the study set for the `awesome_person_id`.
-/// - For each vocab item in the study set, displays a prompt for the user to enter a translation.
-/// - Reads the user's input and calculates the similarity distance between the guessed word and the correct translation.
-/// - Updates the vocabulary study stats based on the user's guess.
-/// - Displays feedback about the correctness of the guess and the updated correctness percentage.
+/// - Retrieves the study set for the `awesome_person_id`, narrowed by any filters.
+/// - Shows a progress bar and the running session score above each prompt.
+/// - Reads the translation and grades it, updating the vocabulary study stats.
+/// - Displays the feedback and, for a wrong answer, a colored character by character comparison with
+///   the closest correct answer: green is right, red is wrong or extra and yellow is missing.
+/// - Records the answers in a study session. The words missed are drilled once more at the end, then
+///   the session closes and the words answered are listed with the change of their percentage correct.
+///
+/// While answering, these keys are commands:
+///
+/// - `Tab` reveals the hint, part of speech and, for verbs, the infinitive.
+/// - `Ctrl-A` shows the alternative answers.
+/// - `Ctrl-N` edits the notes kept for the vocab, `Enter` saves them and `Esc` cancels.
+/// - `Ctrl-S` skips the vocab, it is not graded.
+/// - `Esc` or `Ctrl-C` quits, the missed words are not drilled again and the session summary is shown.
+///
+/// # Arguments
+///
+/// - `<learner>` or `--learner <id>`: The awesome_person_id, required.
+/// - `[mode]` or `--mode <mode>`: `translate` by default. Drill verb conjugations with `conjugation`,
+///   the session ends with the accuracy of each tense drilled. With `articles` nouns with a known
+///   gender are answered with their article, e.g. `la mano`.
+/// - `--count <n>`: The number of vocab to study, 10 by default.
+/// - `--direction <direction>`: `to-learning` by default, translating into the learning language. With
+///   `to-known` the learning language is shown and translated into the known language.
+/// - `--pos <pos>`, `--skill <skill>` and `--lang <code>`: Only study vocab with this part of speech,
+///   skill or learning language, see [`StudyFilter`].
+/// - `--deck <id>`: Only study the vocab in this deck of the learner, see the `createDeck` mutation.
+/// - `--tag <tag>`: Only study the vocab with this tag, repeat it for vocab with every one of the tags.
 ///
 /// # Errors
 ///
 /// This function returns an `Err` if any step of the process fails, including database connection
-/// issues, reading from stdin, or any other internal error.
-///
-/// Change the awesome_person_id from it default of 1 with the only argument.
+/// issues, invalid arguments, reading from the terminal, or any other internal error.
 ///
 /// ```sh
 /// cargo run --bin shell_study 1
-/// }
+/// cargo run --bin shell_study 1 conjugation
+/// cargo run --bin shell_study 1 articles
+/// cargo run --bin shell_study -- --learner 1 --count 20 --direction to-known --pos noun
+/// cargo run --bin shell_study -- --learner 1 --deck 3
+/// cargo run --bin shell_study -- --learner 1 --tag food --tag a2
 /// ```
 #[tokio::main]
 pub async fn main() -> Result<(), Box<dyn Error>> {
     dotenv().ok(); // Load environment variables from .env file
-    let db_url = find_the_database().await;
-    establish_connection_pool(db_url);
+    let (config, args) = init_app_config(env::args().skip(1).collect())?;
+    init_language_packs()?;
+    let study_args = match StudyArgs::parse(&args) {
+        Ok(study_args) => study_args,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let db_url = find_the_database(&config.database).await?;
+    establish_connection_pool_with_config(db_url, &config.database.pool_config());
     verify_connection_migrate_db()?;
 
-    let args: Vec<String> = env::args().collect();
-    let awesome_person_id = if args.len() < 2 {
-        1
-    } else {
-        args[1].clone().parse::<i32>().unwrap()
+    let match_service = VocabFuzzyMatch::instance();
+    let cards = load_cards(match_service, &study_args)?;
+    if cards.is_empty() {
+        match study_args.mode {
+            Mode::Conjugation => println!("No verbs to drill, verbs need an infinitive"),
+            _ => {
+                let quota =
+                    DailyQuotaService::instance().get_daily_quota(study_args.awesome_person_id)?;
+                if quota.new_remaining() == 0 && quota.reviews_remaining() == 0 {
+                    println!("Today's new words and reviews are done, come back tomorrow");
+                } else {
+                    println!("Nothing to study, try fewer filters");
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    // Conjugation drills are kept in the conjugation stats rather than a study session.
+    let sessions = StudySessionService::instance();
+    let session_id = match study_args.mode {
+        Mode::Conjugation => None,
+        _ => Some(
+            sessions
+                .start_study_session(study_args.awesome_person_id)?
+                .id,
+        ),
     };
 
-    let match_service = VocabFuzzyMatch::instance();
-    let study_set = match_service.get_vocab_to_learn(awesome_person_id, 10)?;
-    for (vocab_study, vocab) in study_set {
-        println!();
+    let mut score = Score::default();
+    let summary = {
+        let mut terminal = Terminal::open()?;
+        let redrill_kinds: Vec<(i32, CardKind)> = cards
+            .iter()
+            .map(|card| (card.vocab_study.id, card.kind.clone()))
+            .collect();
+        let completed = study(
+            &mut terminal,
+            match_service,
+            session_id,
+            "Study",
+            cards,
+            &mut score,
+        )?;
+
+        match session_id {
+            Some(session_id) => {
+                // The missed words are drilled once more before the session closes.
+                let summary = sessions.finish_study_session(session_id, !completed)?;
+                if !summary.redrill.is_empty() {
+                    let redrill = summary
+                        .redrill
+                        .into_iter()
+                        .filter_map(|(vocab_study, vocab)| {
+                            let (_, kind) = redrill_kinds
+                                .iter()
+                                .find(|(vocab_study_id, _)| *vocab_study_id == vocab_study.id)?;
+                            Some(Card {
+                                vocab_study,
+                                vocab,
+                                kind: kind.clone(),
+                            })
+                        })
+                        .collect();
+                    study(
+                        &mut terminal,
+                        match_service,
+                        Some(session_id),
+                        "Missed words",
+                        redrill,
+                        &mut score,
+                    )?;
+                    // One round of re-drills, words skipped or missed again do not keep it open.
+                    Some(sessions.finish_study_session(session_id, true)?)
+                } else {
+                    Some(summary)
+                }
+            }
+            None => None,
+        }
+    };
+
+    println!("{}", score.summary());
+    if let Some(summary) = summary {
+        print_session_summary(&summary);
+    }
+    if study_args.mode == Mode::Conjugation {
+        print_conjugation_stats(match_service, study_args.awesome_person_id)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Mode {
+    Translate,
+    Articles,
+    Conjugation,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Direction {
+    ToLearning,
+    ToKnown,
+}
+
+// The command line arguments left after the configuration arguments.
+struct StudyArgs {
+    awesome_person_id: i32,
+    count: i64,
+    mode: Mode,
+    direction: Direction,
+    filter: StudyFilter,
+}
+
+impl StudyArgs {
+    // Parses the arguments described by main, a value is either the next argument or after `=`,
+    // e.g. `--count 20` or `--count=20`.
+    fn parse(args: &[String]) -> Result<StudyArgs, PalabrasError> {
+        let mut awesome_person_id = None;
+        let mut study_args = StudyArgs {
+            awesome_person_id: 0,
+            count: 10,
+            mode: Mode::Translate,
+            direction: Direction::ToLearning,
+            filter: StudyFilter::default(),
+        };
+
+        let mut positional = 0;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            if !flag.starts_with("--") {
+                match positional {
+                    0 => awesome_person_id = Some(parse_number("learner", flag)?),
+                    1 => study_args.mode = parse_mode(flag)?,
+                    _ => {
+                        return Err(PalabrasError::Validation(format!(
+                            "Unexpected argument {}",
+                            flag
+                        )))
+                    }
+                }
+                positional += 1;
+                continue;
+            }
+
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| PalabrasError::Validation(format!("{} needs a value", flag)))
+            };
+            match flag {
+                "--learner" => awesome_person_id = Some(parse_number("learner", &value()?)?),
+                "--count" => {
+                    study_args.count = parse_number("count", &value()?)?;
+                    if study_args.count < 1 {
+                        return Err(PalabrasError::Validation(format!(
+                            "count must be at least 1, found {}",
+                            study_args.count
+                        )));
+                    }
+                }
+                "--mode" => study_args.mode = parse_mode(&value()?)?,
+                "--direction" => {
+                    study_args.direction = match value()?.as_str() {
+                        "to-learning" | "learning" => Direction::ToLearning,
+                        "to-known" | "known" => Direction::ToKnown,
+                        other => {
+                            return Err(PalabrasError::Validation(format!(
+                                "Unknown direction {}, expected to-learning or to-known",
+                                other
+                            )))
+                        }
+                    }
+                }
+                "--pos" => study_args.filter.pos = Some(value()?),
+                "--skill" => study_args.filter.skill = Some(value()?),
+                "--lang" => study_args.filter.learning_lang_code = Some(value()?),
+                "--deck" => study_args.filter.deck_id = Some(parse_number("deck", &value()?)?),
+                "--tag" => study_args.filter.tags.push(value()?),
+                _ => {
+                    return Err(PalabrasError::Validation(format!(
+                        "Unknown argument {}",
+                        flag
+                    )))
+                }
+            }
+        }
+
+        study_args.awesome_person_id = awesome_person_id.ok_or_else(|| {
+            PalabrasError::Validation("The learner is required, e.g. shell_study 1".to_string())
+        })?;
+        Ok(study_args)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, PalabrasError> {
+    value.trim().parse().map_err(|_| {
+        PalabrasError::Validation(format!("{} must be a number, found {}", name, value))
+    })
+}
+
+fn parse_mode(value: &str) -> Result<Mode, PalabrasError> {
+    match value {
+        "translate" => Ok(Mode::Translate),
+        "articles" => Ok(Mode::Articles),
+        "conjugation" => Ok(Mode::Conjugation),
+        other => Err(PalabrasError::Validation(format!(
+            "Unknown mode {}, expected translate, articles or conjugation",
+            other
+        ))),
+    }
+}
+
+// A vocab to study and how it is answered.
+struct Card {
+    vocab_study: VocabStudy,
+    vocab: Vocab,
+    kind: CardKind,
+}
+
+#[derive(Clone)]
+enum CardKind {
+    Translate { with_article: bool },
+    Reverse,
+    Conjugate(ConjugationDrill),
+}
+
+impl Card {
+    fn prompt(&self) -> String {
+        match &self.kind {
+            CardKind::Translate { with_article: true } => {
+                format!("Translate, with the article: '{}'", self.vocab.first_lang)
+            }
+            CardKind::Translate { .. } => format!("Translate: '{}'", self.vocab.first_lang),
+            CardKind::Reverse => format!("Translate: '{}'", self.vocab.learning_lang),
+            CardKind::Conjugate(drill) => format!("Conjugate: {}", drill.prompt()),
+        }
+    }
+
+    fn hint(&self) -> String {
+        let vocab = &self.vocab;
+        let hints: Vec<String> = [
+            ("your hint", self.vocab_study.user_hint.clone()),
+            ("hint", vocab.hint.clone()),
+            ("pos", vocab.pos.clone()),
+            ("infinitive", vocab.infinitive.clone()),
+            ("variant", vocab.lang_variant.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| {
+            let value = value.unwrap_or_default();
+            (!value.trim().is_empty()).then(|| format!("{}: {}", label, value.trim()))
+        })
+        .collect();
+
+        if hints.is_empty() {
+            "No hint for this one".to_string()
+        } else {
+            hints.join("    ")
+        }
+    }
+
+    fn grade(
+        &self,
+        service: &VocabFuzzyMatch,
+        session_id: Option<i32>,
+        entered: String,
+    ) -> Result<String, PalabrasError> {
+        let (vocab_id, vocab_study_id) = (self.vocab.id, self.vocab_study.id);
+        match (&self.kind, session_id) {
+            (CardKind::Translate { with_article }, Some(session_id)) => service
+                .check_session_response(
+                    session_id,
+                    vocab_id,
+                    vocab_study_id,
+                    entered,
+                    Answering::Learning {
+                        with_article: *with_article,
+                    },
+                ),
+            (CardKind::Reverse, Some(session_id)) => service.check_session_response(
+                session_id,
+                vocab_id,
+                vocab_study_id,
+                entered,
+                Answering::Known,
+            ),
+            (
+                CardKind::Translate {
+                    with_article: false,
+                },
+                None,
+            ) => service.check_response(vocab_id, vocab_study_id, entered),
+            (CardKind::Translate { with_article: true }, None) => {
+                service.check_response_with_article(vocab_id, vocab_study_id, entered)
+            }
+            (CardKind::Reverse, None) => {
+                service.check_known_response(vocab_id, vocab_study_id, entered)
+            }
+            (CardKind::Conjugate(drill), _) => service.check_conjugation(
+                vocab_id,
+                vocab_study_id,
+                drill.person,
+                drill.tense,
+                entered,
+            ),
+        }
+    }
+
+    // The right answers, the first is the usual one.
+    fn answers(&self, alternatives: &[String]) -> Vec<String> {
+        let vocab = &self.vocab;
+        match &self.kind {
+            CardKind::Translate { with_article } => {
+                let article = vocab
+                    .gender
+                    .as_deref()
+                    .filter(|_| *with_article)
+                    .and_then(|gender| {
+                        GenderArticles::for_gender(
+                            language_packs().pack_for(&vocab.learning_lang_code),
+                            gender,
+                        )
+                    })
+                    .filter(|articles| articles.split(&vocab.learning_lang).0.is_none())
+                    .map(|articles| format!("{} ", articles.expected()))
+                    .unwrap_or_default();
+                std::iter::once(&vocab.learning_lang)
+                    .chain(alternatives)
+                    .map(|answer| format!("{}{}", article, answer.trim()))
+                    .collect()
+            }
+            CardKind::Reverse => vocab
+                .first_lang
+                .split(',')
+                .map(|answer| answer.trim().to_string())
+                .collect(),
+            CardKind::Conjugate(drill) => drill.answer().into_iter().collect(),
+        }
+    }
+}
+
+fn load_cards(service: &VocabFuzzyMatch, args: &StudyArgs) -> Result<Vec<Card>, PalabrasError> {
+    if args.mode == Mode::Conjugation {
+        return Ok(service
+            .get_conjugation_drills(args.awesome_person_id, args.count)?
+            .into_iter()
+            .map(|(vocab_study, vocab, drill)| Card {
+                vocab_study,
+                vocab,
+                kind: CardKind::Conjugate(drill),
+            })
+            .collect());
+    }
+
+    Ok(service
+        .get_filtered_vocab_to_learn(args.awesome_person_id, args.count, &args.filter)?
+        .into_iter()
+        .map(|(vocab_study, vocab)| {
+            let kind = if args.direction == Direction::ToKnown {
+                CardKind::Reverse
+            } else {
+                CardKind::Translate {
+                    with_article: args.mode == Mode::Articles
+                        && !vocab.gender.clone().unwrap_or_default().is_empty(),
+                }
+            };
+            Card {
+                vocab_study,
+                vocab,
+                kind,
+            }
+        })
+        .collect())
+}
+
+// The running score of the session.
+#[derive(Default)]
+struct Score {
+    answered: usize,
+    perfect: usize,
+    skipped: usize,
+}
+
+impl Score {
+    fn summary(&self) -> String {
+        let percentage = if self.answered == 0 {
+            0.0
+        } else {
+            self.perfect as f64 * 100.0 / self.answered as f64
+        };
+
+        format!(
+            "Score {}/{} ({:.0}%), skipped {}",
+            self.perfect, self.answered, percentage, self.skipped
+        )
+    }
+}
+
+// A line of colored text segments.
+type Line = Vec<(Color, String)>;
+
+fn plain(text: impl Into<String>) -> Line {
+    vec![(Color::Reset, text.into())]
+}
+
+fn colored(color: Color, text: impl Into<String>) -> Line {
+    vec![(color, text.into())]
+}
+
+// The terminal in raw mode on the alternate screen, both are restored when dropped, also on an error.
+struct Terminal {
+    out: Stdout,
+}
+
+impl Terminal {
+    fn open() -> io::Result<Terminal> {
+        let mut out = io::stdout();
+        enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen)?;
+        Ok(Terminal { out })
+    }
+
+    fn draw(&mut self, lines: &[Line]) -> io::Result<()> {
+        // Long lines are cut at the width of the terminal, when it is known.
+        let width = match terminal::size()? {
+            (0, _) => usize::MAX,
+            (width, _) => width as usize,
+        };
+        queue!(self.out, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+        for line in lines {
+            let mut remaining = width;
+            for (color, text) in line {
+                let text: String = text.chars().take(remaining).collect();
+                remaining -= text.chars().count();
+                queue!(
+                    self.out,
+                    SetForegroundColor(*color),
+                    Print(text),
+                    ResetColor
+                )?;
+            }
+            queue!(self.out, Print("\r\n"))?;
+        }
+        self.out.flush()
+    }
+
+    fn read_key(&mut self) -> io::Result<KeyEvent> {
+        loop {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Release {
+                    return Ok(key);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(self.out, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+// What a key press asks for.
+enum Command {
+    Type(char),
+    Erase,
+    Enter,
+    Hint,
+    Alternatives,
+    EditNote,
+    Skip,
+    Quit,
+    Cancel,
+    None,
+}
+
+fn command(key: KeyEvent) -> Command {
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Char('c') if control => Command::Quit,
+        KeyCode::Char('a') if control => Command::Alternatives,
+        KeyCode::Char('n') if control => Command::EditNote,
+        KeyCode::Char('s') if control => Command::Skip,
+        KeyCode::Char(c) if !control => Command::Type(c),
+        KeyCode::Backspace => Command::Erase,
+        KeyCode::Enter => Command::Enter,
+        KeyCode::Tab => Command::Hint,
+        KeyCode::Esc => Command::Cancel,
+        _ => Command::None,
+    }
+}
+
+// The state of the card being studied.
+#[derive(Default)]
+struct CardState {
+    input: String,
+    note: Option<String>,
+    shown: Vec<Line>,
+    feedback: Vec<Line>,
+    answered: bool,
+}
+
+// Studies the cards, answers are recorded in the study session when there is one. False once quit.
+fn study(
+    terminal: &mut Terminal,
+    service: &VocabFuzzyMatch,
+    session_id: Option<i32>,
+    title: &str,
+    cards: Vec<Card>,
+    score: &mut Score,
+) -> Result<bool, Box<dyn Error>> {
+    let total = cards.len();
+
+    for (index, mut card) in cards.into_iter().enumerate() {
+        let mut state = CardState::default();
+        loop {
+            terminal.draw(&screen(title, &card, &state, score, index, total))?;
+
+            let command = command(terminal.read_key()?);
+            if let Some(note) = state.note.as_mut() {
+                match command {
+                    Command::Type(c) => note.push(c),
+                    Command::Erase => {
+                        note.pop();
+                    }
+                    Command::Enter => {
+                        card.vocab_study = service.update_user_notes(card.vocab_study.id, note)?;
+                        state.note = None;
+                        state.shown.push(colored(Color::Cyan, "Notes saved"));
+                    }
+                    Command::Cancel => state.note = None,
+                    Command::Quit => return Ok(false),
+                    _ => {}
+                }
+                continue;
+            }
+
+            match command {
+                Command::Type(c) if !state.answered => state.input.push(c),
+                Command::Erase if !state.answered => {
+                    state.input.pop();
+                }
+                Command::Enter if state.answered => break,
+                Command::Enter if !state.input.trim().is_empty() => {
+                    let prompt = card.grade(service, session_id, state.input.clone())?;
+                    score.answered += 1;
+                    if prompt.starts_with(PERFECT_MATCH) {
+                        score.perfect += 1;
+                        state.feedback.push(colored(Color::Green, prompt));
+                    } else {
+                        state.feedback.push(colored(Color::Red, prompt));
+                        let alternatives = alternatives(service, &card)?;
+                        let answers = card.answers(&alternatives);
+                        if let Some(closest) = closest_answer(&answers, &state.input) {
+                            state.feedback.push(diff_line(closest, &state.input));
+                        }
+                    }
+                    state.answered = true;
+                }
+                Command::Hint => state.shown.push(colored(Color::Cyan, card.hint())),
+                Command::Alternatives => {
+                    let alternatives = alternatives(service, &card)?;
+                    let line = if alternatives.is_empty() {
+                        "No alternatives".to_string()
+                    } else {
+                        format!("Also accepted: {}", alternatives.join(", "))
+                    };
+                    state.shown.push(colored(Color::Cyan, line));
+                }
+                Command::EditNote => {
+                    state.note = Some(card.vocab_study.user_notes.clone().unwrap_or_default())
+                }
+                Command::Skip if !state.answered => {
+                    score.skipped += 1;
+                    break;
+                }
+                Command::Skip => break,
+                Command::Quit | Command::Cancel => return Ok(false),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+fn screen(
+    title: &str,
+    card: &Card,
+    state: &CardState,
+    score: &Score,
+    index: usize,
+    total: usize,
+) -> Vec<Line> {
+    let done = index * PROGRESS_BAR_WIDTH / total;
+    let mut lines = vec![
+        vec![
+            (Color::Green, "█".repeat(done)),
+            (Color::DarkGrey, "░".repeat(PROGRESS_BAR_WIDTH - done)),
+            (
+                Color::Reset,
+                format!("  {} {}/{}    {}", title, index + 1, total, score.summary()),
+            ),
+        ],
+        plain(""),
+        colored(Color::White, card.prompt()),
+    ];
+
+    let notes = card.vocab_study.user_notes.clone().unwrap_or_default();
+    if !notes.is_empty() {
+        lines.push(colored(Color::DarkGrey, format!("your notes: {}", notes)));
+    }
+    lines.extend(state.shown.iter().cloned());
+    lines.push(plain(""));
+
+    if let Some(note) = &state.note {
+        lines.push(plain(format!("notes> {}_", note)));
+        lines.push(plain(""));
+        lines.push(colored(Color::DarkGrey, "Enter save · Esc cancel"));
+        return lines;
+    }
+
+    let cursor = if state.answered { "" } else { "_" };
+    lines.push(plain(format!("> {}{}", state.input, cursor)));
+    lines.push(plain(""));
+    lines.extend(state.feedback.iter().cloned());
+    lines.push(plain(""));
+    lines.push(colored(
+        Color::DarkGrey,
+        if state.answered {
+            "Enter next · Tab hint · Ctrl-A alternatives · Ctrl-N note · Esc quit"
+        } else {
+            "Enter answer · Tab hint · Ctrl-A alternatives · Ctrl-N note · Ctrl-S skip · Esc quit"
+        },
+    ));
+
+    lines
+}
+
+fn alternatives(service: &VocabFuzzyMatch, card: &Card) -> Result<Vec<String>, PalabrasError> {
+    if !matches!(card.kind, CardKind::Translate { .. }) {
+        return Ok(Vec::new());
+    }
+
+    Ok(service
+        .get_alternatives(card.vocab.id)?
+        .into_iter()
+        .map(|alternative| alternative.alternative)
+        .collect())
+}
+
+// The answer closest to what was entered, to compare it with.
+fn closest_answer<'a>(answers: &'a [String], entered: &str) -> Option<&'a str> {
+    let entered = entered.trim().to_lowercase();
+    answers
+        .iter()
+        .min_by_key(|answer| levenshtein(&answer.to_lowercase(), &entered))
+        .map(String::as_str)
+}
+
+// The entered answer colored against the expected one, the missing characters in place.
+fn diff_line(expected: &str, entered: &str) -> Line {
+    let mut line = plain("Yours: ");
+    line.extend(
+        char_diff(expected, entered)
+            .into_iter()
+            .map(|diff| match diff {
+                CharDiff::Same(c) => (Color::Green, c.to_string()),
+                CharDiff::Wrong { given, .. } => (Color::Red, given.to_string()),
+                CharDiff::Missing(c) => (Color::Yellow, c.to_string()),
+                CharDiff::Extra(c) => (Color::Red, c.to_string()),
+            }),
+    );
+
+    line
+}
+
+// The words answered in the session with the change of their percentage correct, the missed words first.
+fn print_session_summary(summary: &SessionSummary) {
+    let mut items: Vec<_> = summary.items.iter().collect();
+    items.sort_by_key(|(item, _)| !item.missed);
+    for (item, vocab) in items {
         println!(
-            "{}",
-            match_service.determine_prompt(&vocab, &vocab_study.user_notes.unwrap_or_default())
+            "{} {} — {}: {:.0}% → {:.0}% ({:+.0})",
+            if item.missed { "✗" } else { "✓" },
+            vocab.learning_lang,
+            vocab.first_lang,
+            item.percentage_before * 100.0,
+            item.percentage_after * 100.0,
+            item.percentage_change() * 100.0
         );
+    }
+}
 
-        io::stdout().flush().unwrap(); // Ensure the prompt is displayed before reading input
-        let mut guess = String::new(); // Create a mutable variable to store the input
+// The awesome person's accuracy for each tense drilled.
+fn print_conjugation_stats(
+    match_service: &VocabFuzzyMatch,
+    awesome_person_id: i32,
+) -> Result<(), PalabrasError> {
+    let stats = match_service.get_conjugation_stats(awesome_person_id)?;
+    for tense in Tense::ALL {
+        let (attempts, correct_attempts) = stats
+            .iter()
+            .filter(|stat| stat.tense == tense.key())
+            .fold((0, 0), |(attempts, correct), stat| {
+                (attempts + stat.attempts, correct + stat.correct_attempts)
+            });
+        if attempts > 0 {
+            println!(
+                "{}: {:.0}% of {} drills",
+                tense,
+                correct_attempts as f64 * 100.0 / attempts as f64,
+                attempts
+            );
+        }
+    }
 
-        io::stdin().read_line(&mut guess)?;
+    Ok(())
+}
 
-        let prompt = match_service.check_response(vocab.id, vocab_study.id, guess)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        println!("{}", &prompt);
+    fn parse(args: &[&str]) -> Result<StudyArgs, PalabrasError> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        StudyArgs::parse(&args)
     }
 
-    Ok(())
+    #[test]
+    fn unit_test_parse_positionals() {
+        let study_args = parse(&["3"]).expect("The learner alone should parse");
+        assert_eq!(study_args.awesome_person_id, 3);
+        assert_eq!(study_args.count, 10);
+        assert_eq!(study_args.mode, Mode::Translate);
+        assert_eq!(study_args.direction, Direction::ToLearning);
+
+        let study_args = parse(&["3", "conjugation"]).expect("The learner and mode should parse");
+        assert_eq!(study_args.awesome_person_id, 3);
+        assert_eq!(study_args.mode, Mode::Conjugation);
+    }
+
+    #[test]
+    fn unit_test_parse_flags() {
+        let study_args = parse(&[
+            "--learner",
+            "4",
+            "--count",
+            "20",
+            "--mode",
+            "articles",
+            "--direction",
+            "to-known",
+            "--pos",
+            "noun",
+            "--skill",
+            "Food",
+            "--lang",
+            "fr",
+            "--deck",
+            "7",
+            "--tag",
+            "food",
+            "--tag",
+            "a2",
+        ])
+        .expect("Every flag should parse");
+        assert_eq!(study_args.awesome_person_id, 4);
+        assert_eq!(study_args.count, 20);
+        assert_eq!(study_args.mode, Mode::Articles);
+        assert_eq!(study_args.direction, Direction::ToKnown);
+        assert_eq!(study_args.filter.pos, Some("noun".to_string()));
+        assert_eq!(study_args.filter.skill, Some("Food".to_string()));
+        assert_eq!(study_args.filter.learning_lang_code, Some("fr".to_string()));
+        assert_eq!(study_args.filter.deck_id, Some(7));
+        assert_eq!(study_args.filter.tags, vec!["food", "a2"]);
+    }
+
+    #[test]
+    fn unit_test_parse_inline_values() {
+        let study_args = parse(&["--learner=5", "--count=3", "--tag=x=y", "--direction=known"])
+            .expect("Values after = should parse");
+        assert_eq!(study_args.awesome_person_id, 5);
+        assert_eq!(study_args.count, 3);
+        assert_eq!(study_args.filter.tags, vec!["x=y"]);
+        assert_eq!(study_args.direction, Direction::ToKnown);
+
+        // A flag overrides the positional learner
+        let study_args = parse(&["2", "--learner=5"]).expect("Both learners should parse");
+        assert_eq!(study_args.awesome_person_id, 5);
+    }
+
+    #[test]
+    fn unit_test_parse_errors() {
+        let invalid = [
+            vec![],
+            vec!["--count", "5"],
+            vec!["x"],
+            vec!["1", "translate", "extra"],
+            vec!["1", "spelling"],
+            vec!["1", "--count"],
+            vec!["1", "--count", "0"],
+            vec!["1", "--count=many"],
+            vec!["1", "--direction", "sideways"],
+            vec!["1", "--deck", "first"],
+            vec!["1", "--unknown", "value"],
+        ];
+        for args in invalid {
+            assert!(
+                matches!(parse(&args), Err(PalabrasError::Validation(_))),
+                "{:?} should have been rejected",
+                args
+            );
+        }
+    }
 }
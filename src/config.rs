@@ -1,17 +1,48 @@
+pub mod app_config;
+
+use crate::config::app_config::app_config;
 use crate::dal::file_access::load_buffer_from_file;
+use crate::error::PalabrasError;
+use crate::models::{GradingPolicy, Vocab, VocabStudy};
+use crate::sl::fuzzy_match_vocab::{
+    CLOSE_DISTANCE, MAX_DISTANCE, PERFECT_WEIGHT, WELL_KNOWN_THRESHOLD,
+};
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::OnceLock;
 
-/// Configuration for Duolingo vocabulary import.
+/// A named category of words, hinted when a phrase contains one of its comma separated `instances`.
+#[derive(Deserialize)]
+pub struct WordCategory {
+    pub name: String,
+    pub instances: String,
+}
+
+/// Pronoun categories are word categories, hinted before any other.
+pub type Pronoun = WordCategory;
+
+/// Configuration for Duolingo vocabulary import, the language pack of a single learning language.
 ///
 /// This struct defines the configuration needed to import vocabulary from a Duolingo JSON file.
 /// It includes details such as the file name of the Duolingo vocabulary JSON,
 /// optional suffixes for plural forms, and suffixes for matching non-verbs.
+/// Packs are looked up by language code, see [`LanguagePackRegistry`].
 ///
 /// # Fields
 ///
 /// - `duo_vocab_json_file_name`: The file name of the Duolingo vocabulary JSON to be imported.
 /// - `plural_suffix`: An optional string specifying the suffix used to identify plural forms of words. This reduces redundant words.
 /// - `non_verb_matching_suffixes`: An optional string specifying suffixes used for matching non-verbs. This reduces redundant words.
+/// - `pronouns`: Optional pronoun categories, hinted when a phrase contains one of their instances.
+/// - `word_categories`: Optional further categories hinted the same way after the pronouns, e.g. prepositions,
+///   question words or negation. Instances may be several words, e.g. `por qué`.
+/// - `free_word_order`: Optional. Whether the words of a phrase may be answered in another order, e.g. `dónde Ana está`
+///   for `dónde está Ana`. Phrases are graded in order by default.
+/// - `articles`: Optional articles keyed by grammatical gender, e.g. `"f": "la, una, las, unas"`. The first is the
+///   one shown in feedback. Answers to nouns with a known gender are checked against them.
+/// - `variants`: Optional names of the regional variants keyed by their BCP-47 style code, e.g.
+///   `"es-MX": "Mexico"`, used in feedback about answers from another variant.
 ///
 /// # Example
 ///
@@ -21,14 +52,22 @@ use serde::Deserialize;
 /// let config = VocabConfig {
 ///     plural_suffix: Some("s".to_string()),
 ///     non_verb_matching_suffixes: Some("o,a,os,as,e,es".to_string()),
-///     pronouns: None
+///     pronouns: None,
+///     word_categories: None,
+///     free_word_order: None,
+///     articles: None,
+///     variants: None,
 /// };
 ///
 /// // This config will not combine similar words
 /// let config = VocabConfig {
 ///     plural_suffix: None,
 ///     non_verb_matching_suffixes: None,
-///     pronouns: None
+///     pronouns: None,
+///     word_categories: None,
+///     free_word_order: None,
+///     articles: None,
+///     variants: None,
 /// };
 /// ```
 ///
@@ -36,48 +75,153 @@ use serde::Deserialize;
 /// the basic setup required to import vocabulary from a Duolingo JSON file, with optional
 /// configurations for handling plural forms and non-verb word suffixes.
 
-#[derive(Deserialize)]
-pub struct Pronoun {
-    pub name: String,
-    pub instances: String,
-}
-
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
 pub struct VocabConfig {
     pub plural_suffix: Option<String>,
     pub non_verb_matching_suffixes: Option<String>,
     pub pronouns: Option<Vec<Pronoun>>,
+    pub word_categories: Option<Vec<WordCategory>>,
+    pub free_word_order: Option<bool>,
+    pub articles: Option<HashMap<String, String>>,
+    pub variants: Option<HashMap<String, String>>,
 }
 
-static VOCAB_CONFIG_FILENAME: &str = "vocab_config.json";
+impl VocabConfig {
+    /// The name of a regional variant, e.g. `Mexico` for `es-MX`, or the code itself when the pack does not name it.
+    pub fn variant_name<'a>(&'a self, lang_variant: &'a str) -> &'a str {
+        self.variants
+            .iter()
+            .flatten()
+            .find(|(code, _)| code.eq_ignore_ascii_case(lang_variant))
+            .map_or(lang_variant, |(_, name)| name.as_str())
+    }
+}
 
-/// Loads the Duolingo vocabulary configuration from a JSON file.
+// Used for any language without a pack, nothing is combined and no pronouns are hinted.
+static EMPTY_LANGUAGE_PACK: VocabConfig = VocabConfig {
+    plural_suffix: None,
+    non_verb_matching_suffixes: None,
+    pronouns: None,
+    word_categories: None,
+    free_word_order: None,
+    articles: None,
+    variants: None,
+};
+
+/// The language packs, a [`VocabConfig`] for each learning language keyed by its language code.
 ///
-/// This function reads the Duolingo vocabulary configuration from a file named `vocab_config.json`
-/// located in the project's root directory. The configuration specifies how vocabulary data
-/// from Duolingo should be processed, including handling of plural forms and non-verb matching suffixes.
+/// Each pack is a JSON file named after its language code, e.g. `lang/es.json` or `lang/fr.json`,
+/// in the directory named by `files.language_packs` in the [`app_config::AppConfig`].
 ///
-/// # Returns
+/// # Example
+///
+/// ```
+/// use palabras::config::{LanguagePackRegistry, VocabConfig};
+/// use std::collections::HashMap;
+///
+/// let mut packs = HashMap::new();
+/// packs.insert(
+///     "fr".to_string(),
+///     VocabConfig {
+///         plural_suffix: Some("s".to_string()),
+///         ..Default::default()
+///     },
+/// );
+/// let registry = LanguagePackRegistry::new(packs);
+///
+/// assert_eq!(registry.pack_for("FR").plural_suffix, Some("s".to_string()));
+/// // Languages without a pack get an empty one
+/// assert_eq!(registry.pack_for("de").plural_suffix, None);
+/// ```
+#[derive(Default)]
+pub struct LanguagePackRegistry {
+    packs: HashMap<String, VocabConfig>,
+}
+
+impl LanguagePackRegistry {
+    /// Creates a registry from packs keyed by language code.
+    pub fn new(packs: HashMap<String, VocabConfig>) -> Self {
+        Self {
+            packs: packs
+                .into_iter()
+                .map(|(code, pack)| (code.to_lowercase(), pack))
+                .collect(),
+        }
+    }
+
+    /// Loads every `<language code>.json` file in `dir` as a language pack.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PalabrasError::Io` if the directory or a pack cannot be read, or `PalabrasError::Config`
+    /// naming the file if a pack cannot be parsed.
+    pub fn load_from_dir(dir: &str) -> Result<Self, PalabrasError> {
+        let mut packs = HashMap::new();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(code) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+
+            let file_name = path.to_string_lossy();
+            let reader = load_buffer_from_file(&file_name)?;
+            let pack: VocabConfig = serde_json::from_reader(reader).map_err(|err| {
+                PalabrasError::Config(format!("Failed to parse {}: {}", file_name, err))
+            })?;
+            packs.insert(code.to_string(), pack);
+        }
+
+        Ok(Self::new(packs))
+    }
+
+    /// The pack for a language code, or an empty pack if there is none.
+    pub fn pack_for(&self, lang_code: &str) -> &VocabConfig {
+        self.packs
+            .get(&lang_code.to_lowercase())
+            .unwrap_or(&EMPTY_LANGUAGE_PACK)
+    }
+
+    /// The language codes with a pack, sorted.
+    pub fn lang_codes(&self) -> Vec<&str> {
+        let mut codes: Vec<&str> = self.packs.keys().map(String::as_str).collect();
+        codes.sort();
+        codes
+    }
+}
+
+static LANGUAGE_PACKS: OnceLock<LanguagePackRegistry> = OnceLock::new();
+
+/// Loads the language packs from the configured directory and makes them available through
+/// [`language_packs`].
 ///
-/// Returns a `Result` containing either:
-/// - `Ok(VocabConfig)`: The loaded `VocabConfig` instance if the file was successfully read and deserialized.
-/// - `Err(String)`: An error message string if there was an issue loading or parsing the configuration file.
+/// The server and the tools grading or importing vocab call this once at startup, so a missing or broken
+/// pack stops them before hints, article checks and free word order are silently lost.
 ///
 /// # Errors
 ///
-/// This function can return an error if:
-/// - The `vocab_config.json` file does not exist.
-/// - There is an issue reading the file.
-/// - The JSON data in the file does not match the `VocabConfig` structure.
-///
-/// This example shows how to call `load_vocab_config` to load the Duolingo vocabulary configuration.
-/// It handles the result by printing the loaded configuration on success or an error message on failure.
-pub fn load_vocab_config() -> Result<VocabConfig, String> {
-    // Deserialize into config list
-    let reader = load_buffer_from_file(VOCAB_CONFIG_FILENAME).map_err(|err| err.to_string())?;
-    let configs: VocabConfig = serde_json::from_reader(reader).map_err(|err| err.to_string())?;
+/// Returns the error of [`LanguagePackRegistry::load_from_dir`].
+pub fn init_language_packs() -> Result<&'static LanguagePackRegistry, PalabrasError> {
+    let registry = LanguagePackRegistry::load_from_dir(&app_config().files.language_packs)?;
 
-    Ok(configs)
+    Ok(LANGUAGE_PACKS.get_or_init(|| registry))
+}
+
+/// The language packs of the running application.
+///
+/// If [`init_language_packs`] has not been called, as in tests, the packs are loaded on first use.
+///
+/// # Panics
+///
+/// Panics if the packs are then missing or invalid.
+pub fn language_packs() -> &'static LanguagePackRegistry {
+    LANGUAGE_PACKS.get_or_init(|| {
+        LanguagePackRegistry::load_from_dir(&app_config().files.language_packs)
+            .unwrap_or_else(|err| panic!("The language packs could not be loaded. {}", err))
+    })
 }
 
 /// Configuration for loading translation pairs from various file formats.
@@ -95,6 +239,8 @@ pub fn load_vocab_config() -> Result<VocabConfig, String> {
 /// - `first_index`: The index (starting from 0) of the column containing the primary language (translation) words in a delimited file.
 /// - `learning_regex`: An optional regular expression pattern used to extract the learning language words from non-delimited files.
 /// - `first_regex`: An optional regular expression pattern used to extract the primary language (translation) words from non-delimited files.
+/// - `gender_regex`: An optional regular expression pattern capturing the grammatical gender, `m` or `f`, of the learning language word
+///   before it in a non-delimited file.
 ///
 /// # Example
 ///
@@ -111,6 +257,7 @@ pub fn load_vocab_config() -> Result<VocabConfig, String> {
 ///     first_index: 1,
 ///     learning_regex: None,
 ///     first_regex: None,
+///     gender_regex: None,
 /// };
 ///
 /// // Example of a TranslationsConfig for a file requiring regex extraction
@@ -122,12 +269,13 @@ pub fn load_vocab_config() -> Result<VocabConfig, String> {
 ///     first_index: 0, // Not used in regex extraction
 ///     learning_regex: Some("<span class='learning'>\\s*(.+?)\\s*</span>".to_string()),
 ///     first_regex: Some("<span class='first'>\\s*(.+?)\\s*</span>".to_string()),
+///     gender_regex: Some("<span class='gender'>([mf])</span>".to_string()),
 /// };
 /// ```
 ///
 /// This struct is designed to be flexible, allowing for the configuration of both simple delimited
 /// files and more complex structured files requiring regular expressions for data extraction.
-#[derive(Deserialize)]
+#[derive(Deserialize, Default)]
 pub struct TranslationsConfig {
     pub file_name: String,
     pub header_lines: usize,
@@ -136,34 +284,20 @@ pub struct TranslationsConfig {
     pub first_index: usize,
     pub learning_regex: Option<String>,
     pub first_regex: Option<String>,
+    pub gender_regex: Option<String>,
 }
 
-impl Default for TranslationsConfig {
-    fn default() -> Self {
-        Self {
-            file_name: Default::default(),
-            header_lines: Default::default(),
-            delimiter: Default::default(),
-            learning_index: Default::default(),
-            first_index: Default::default(),
-            learning_regex: None,
-            first_regex: None,
-        }
-    }
-}
-
-static TRANSLATIONS_CONFIG_FILENAME: &str = "translations_config.json";
-
 /// Loads translation configurations from a JSON file.
 ///
-/// This function reads a JSON file specified by `TRANSLATIONS_CONFIG_FILENAME`, which contains
+/// This function reads the JSON file named by `files.translations_config` in the
+/// [`app_config::AppConfig`], `translations_config.json` by default. It contains
 /// an array of `TranslationsConfig` objects. Each `TranslationsConfig` object defines how to
 /// parse and load translation pairs from a specific file. This allows for loading multiple
 /// translation data sources with varying formats.
 ///
 /// # Returns
 ///
-/// A `Result` wrapping a vector of `TranslationsConfig` if successful, or an error message string if an error occurs.
+/// A `Result` wrapping a vector of `TranslationsConfig` if successful, or a `PalabrasError` if an error occurs.
 ///
 /// # Errors
 ///
@@ -174,22 +308,276 @@ static TRANSLATIONS_CONFIG_FILENAME: &str = "translations_config.json";
 ///
 /// # Example
 ///
-/// Assuming a JSON file named `translations_config.json` exists in the same directory and is
-/// correctly formatted, you can load the configurations as follows:
+/// Assuming the translations config file exists and is correctly formatted, you can load the configurations as follows:
 ///
 ///
 /// This example demonstrates basic usage of `load_translations_config` to load translation
 /// configurations and iterate over them. Each configuration can then be used to
 /// load translation pairs from its respective file.
 ///
-/// Note: Ensure the `translations_config.json` file is correctly formatted and accessible at the
-/// path specified by `TRANSLATIONS_CONFIG_FILENAME`.
-pub fn load_translations_config() -> Result<Option<Vec<TranslationsConfig>>, String> {
+/// Note: Ensure the translations config file is correctly formatted and accessible at the
+/// path specified by `files.translations_config`.
+pub fn load_translations_config() -> Result<Option<Vec<TranslationsConfig>>, PalabrasError> {
     // Deserialize into config list
-    let reader =
-        load_buffer_from_file(TRANSLATIONS_CONFIG_FILENAME).map_err(|err| err.to_string())?;
-    let configs: Vec<TranslationsConfig> =
-        serde_json::from_reader(reader).map_err(|err| err.to_string())?;
+    let reader = load_buffer_from_file(&app_config().files.translations_config)?;
+    let configs: Vec<TranslationsConfig> = serde_json::from_reader(reader)?;
 
     Ok(Some(configs))
 }
+
+/// The default grading thresholds, used for any awesome person without a stored [`GradingPolicy`].
+///
+/// These are the `[grading]` section of the [`app_config::AppConfig`], every field is optional and
+/// missing fields fall back to the built-in defaults.
+///
+/// # Fields
+///
+/// - `max_distance`: The distance given to the worst possible answer.
+/// - `well_known_threshold`: Percentage correct above which a vocab is considered well known.
+/// - `close_distance`: Answers up to this distance are reported as close.
+/// - `perfect_weight`: How many answers a perfect match counts as.
+///
+/// # Example
+///
+/// ```
+/// use palabras::config::GradingPolicyConfig;
+///
+/// let config = GradingPolicyConfig {
+///     close_distance: 2,
+///     ..Default::default()
+/// };
+/// let policy = config.policy_for(7);
+/// assert_eq!(policy.awesome_person_id, 7);
+/// assert_eq!(policy.close_distance, 2);
+/// ```
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct GradingPolicyConfig {
+    pub max_distance: i32,
+    pub well_known_threshold: f64,
+    pub close_distance: i32,
+    pub perfect_weight: f64,
+}
+
+impl Default for GradingPolicyConfig {
+    fn default() -> Self {
+        Self {
+            max_distance: MAX_DISTANCE as i32,
+            well_known_threshold: WELL_KNOWN_THRESHOLD,
+            close_distance: CLOSE_DISTANCE as i32,
+            perfect_weight: PERFECT_WEIGHT,
+        }
+    }
+}
+
+impl GradingPolicyConfig {
+    /// Creates a policy for the given awesome person using these default values.
+    pub fn policy_for(&self, awesome_person_id: i32) -> GradingPolicy {
+        GradingPolicy {
+            awesome_person_id,
+            max_distance: self.max_distance,
+            well_known_threshold: self.well_known_threshold,
+            close_distance: self.close_distance,
+            perfect_weight: self.perfect_weight,
+            updated: chrono::Utc::now(),
+        }
+    }
+}
+
+/// The rule for detecting leeches, vocab failed so often that studying them is no longer worthwhile.
+///
+/// These are the `[leech]` section of the [`app_config::AppConfig`], every field is optional and
+/// missing fields fall back to the built-in defaults.
+///
+/// # Fields
+///
+/// - `max_lapses`: A vocab study with this many lapses, answers in a row that were not a perfect match, is a leech.
+/// - `min_attempts`: The number of answers before `min_percentage` applies.
+/// - `min_percentage`: A vocab study answered at least `min_attempts` times with a percentage correct
+///   below this is a leech.
+/// - `suspend`: Whether leeches are suspended when detected, leaving them out of the study set.
+///
+/// # Example
+///
+/// ```
+/// use palabras::config::LeechConfig;
+/// use palabras::models::VocabStudy;
+///
+/// let rule = LeechConfig::default();
+/// let struggling = VocabStudy {
+///     attempts: Some(12),
+///     percentage_correct: Some(0.1),
+///     lapses: 3,
+///     ..Default::default()
+/// };
+/// assert!(rule.is_leech(&struggling));
+///
+/// let learning = VocabStudy {
+///     attempts: Some(4),
+///     percentage_correct: Some(0.1),
+///     lapses: 3,
+///     ..Default::default()
+/// };
+/// assert!(!rule.is_leech(&learning));
+/// ```
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LeechConfig {
+    pub max_lapses: i32,
+    pub min_attempts: i32,
+    pub min_percentage: f64,
+    pub suspend: bool,
+}
+
+impl Default for LeechConfig {
+    fn default() -> Self {
+        Self {
+            max_lapses: 8,
+            min_attempts: 10,
+            min_percentage: 0.2,
+            suspend: true,
+        }
+    }
+}
+
+impl LeechConfig {
+    /// Whether the vocab study breaks the rule, by its lapses or by a low percentage correct.
+    pub fn is_leech(&self, vocab_study: &VocabStudy) -> bool {
+        vocab_study.lapses >= self.max_lapses
+            || (vocab_study.attempts.unwrap_or_default() >= self.min_attempts
+                && vocab_study.percentage_correct.unwrap_or_default() < self.min_percentage)
+    }
+
+    /// The settings that make the rule meaningless, e.g. no lapses allowed at all.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.max_lapses < 1 {
+            problems.push(format!(
+                "leech.max_lapses must be at least 1, found {}",
+                self.max_lapses
+            ));
+        }
+        if self.min_attempts < 1 {
+            problems.push(format!(
+                "leech.min_attempts must be at least 1, found {}",
+                self.min_attempts
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.min_percentage) {
+            problems.push(format!(
+                "leech.min_percentage must be between 0 and 1, found {}",
+                self.min_percentage
+            ));
+        }
+        problems
+    }
+}
+
+/// The ratios the study list is composed with, see [`crate::sl::study_composer::StudyComposer`].
+///
+/// These are the `[composer]` section of the [`app_config::AppConfig`], every field is optional and
+/// missing fields fall back to the built-in defaults. The vocab of a study list is grouped by part of speech,
+/// skill and whether it is a single word or a phrase, and the groups are interleaved. The share of a group
+/// is the product of its ratios, so with the defaults 3 nouns are studied for every adjective.
+///
+/// # Fields
+///
+/// - `pos_ratios`: The ratio of each part of speech, compared ignoring case.
+/// - `skill_ratios`: The ratio of each skill, compared ignoring case.
+/// - `other_ratio`: The ratio of a part of speech or skill that is not listed, or missing.
+/// - `word_ratio`: The ratio of single words.
+/// - `phrase_ratio`: The ratio of phrases, vocab of more than one learning word.
+/// - `seed`: Optional. Makes the composed study lists repeatable, for testing. Without a seed the order of
+///   the groups varies from list to list.
+///
+/// # Example
+///
+/// ```
+/// use palabras::config::ComposerConfig;
+/// use palabras::models::Vocab;
+///
+/// let ratios = ComposerConfig::default();
+/// let noun = Vocab {
+///     pos: Some("Noun".to_string()),
+///     num_learning_words: 1,
+///     ..Default::default()
+/// };
+/// let phrase = Vocab {
+///     num_learning_words: 3,
+///     ..Default::default()
+/// };
+/// assert_eq!(ratios.ratio_of(&noun), 9);
+/// assert_eq!(ratios.ratio_of(&phrase), 1);
+/// ```
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ComposerConfig {
+    pub pos_ratios: HashMap<String, u32>,
+    pub skill_ratios: HashMap<String, u32>,
+    pub other_ratio: u32,
+    pub word_ratio: u32,
+    pub phrase_ratio: u32,
+    pub seed: Option<u64>,
+}
+
+impl Default for ComposerConfig {
+    fn default() -> Self {
+        Self {
+            pos_ratios: HashMap::from([
+                ("noun".to_string(), 3),
+                ("verb".to_string(), 2),
+                ("adjective".to_string(), 1),
+            ]),
+            skill_ratios: HashMap::new(),
+            other_ratio: 1,
+            word_ratio: 3,
+            phrase_ratio: 1,
+            seed: None,
+        }
+    }
+}
+
+impl ComposerConfig {
+    /// The share of the study list for the group of a vocab, the product of its ratios.
+    pub fn ratio_of(&self, vocab: &Vocab) -> u32 {
+        let ratio = |ratios: &HashMap<String, u32>, value: Option<&str>| {
+            let value = value.unwrap_or_default().trim();
+            ratios
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(value))
+                .map_or(self.other_ratio, |(_, ratio)| *ratio)
+        };
+        let length_ratio = if vocab.num_learning_words > 1 {
+            self.phrase_ratio
+        } else {
+            self.word_ratio
+        };
+
+        ratio(&self.pos_ratios, vocab.pos.as_deref())
+            * ratio(&self.skill_ratios, vocab.skill.as_deref())
+            * length_ratio
+    }
+
+    /// The ratios that would leave a group out of every study list, a ratio of 0.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut check = |name: String, ratio: u32| {
+            if ratio < 1 {
+                problems.push(format!(
+                    "composer.{} must be at least 1, found {}",
+                    name, ratio
+                ));
+            }
+        };
+        for (pos, ratio) in &self.pos_ratios {
+            check(format!("pos_ratios.{}", pos), *ratio);
+        }
+        for (skill, ratio) in &self.skill_ratios {
+            check(format!("skill_ratios.{}", skill), *ratio);
+        }
+        check("other_ratio".to_string(), self.other_ratio);
+        check("word_ratio".to_string(), self.word_ratio);
+        check("phrase_ratio".to_string(), self.phrase_ratio);
+        problems
+    }
+}
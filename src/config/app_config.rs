@@ -0,0 +1,454 @@
+use crate::config::{ComposerConfig, GradingPolicyConfig, LeechConfig};
+use crate::dal::db_connection::DbPoolConfig;
+use crate::error::PalabrasError;
+use crate::sl::fuzzy_match_vocab::validate_grading_policy;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+// The settings shared by the server and every tool, loaded in layers. Each layer overrides the one before:
+// the built-in defaults, the config file, `PAL_*` env vars and finally `--set` command line arguments.
+// Secrets, like the database URL, are not part of the config, see `crate::secrets`.
+
+/// The settings of the application.
+///
+/// # Fields
+///
+/// - `server`: Where the GraphQL server listens.
+/// - `database`: How the database is found and how connections are pooled.
+/// - `files`: The paths of the other config files.
+/// - `grading`: The default grading thresholds, see [`GradingPolicyConfig`].
+/// - `leech`: The rule for detecting leeches, see [`LeechConfig`].
+/// - `composer`: The ratios the study list is composed with, see [`ComposerConfig`].
+///
+/// # Example
+///
+/// ```
+/// use palabras::config::app_config::AppConfig;
+///
+/// let config = AppConfig::from_toml_str(
+///     r#"
+///     [server]
+///     addr = "127.0.0.1:4000"
+///
+///     [grading]
+///     close_distance = 2
+///     "#,
+/// )
+/// .expect("Config should have parsed");
+/// assert_eq!(config.server.addr, "127.0.0.1:4000");
+/// assert_eq!(config.grading.close_distance, 2);
+/// assert_eq!(config.database.pool_size, 10);
+/// ```
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub files: FilesConfig,
+    pub grading: GradingPolicyConfig,
+    pub leech: LeechConfig,
+    pub composer: ComposerConfig,
+}
+
+/// Settings of the GraphQL server.
+///
+/// - `addr`: The `IP:PORT` the server listens on.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub addr: String,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            addr: "0.0.0.0:3000".to_string(),
+        }
+    }
+}
+
+/// Settings for finding and connecting to the database.
+///
+/// - `secret_provider`: One of `env`, `file` or `aws`, when `None` it is chosen from the other settings,
+///   see [`crate::secrets::secret_provider_for`].
+/// - `secrets_file`: The JSON file read by the `file` secret provider.
+/// - `db_link`: The AWS Secrets Manager secret read by the `aws` secret provider.
+/// - `region`: The AWS region of `db_link`.
+/// - `pool_size`: Maximum number of pooled connections.
+/// - `min_idle`: Number of idle connections to keep open, defaults to `pool_size` when `None`.
+/// - `connection_timeout_secs`: Seconds to wait for a free connection.
+/// - `statement_timeout_ms`: Milliseconds a single statement may run, `0` disables the timeout.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub secret_provider: Option<String>,
+    pub secrets_file: String,
+    pub db_link: Option<String>,
+    pub region: Option<String>,
+    pub pool_size: u32,
+    pub min_idle: Option<u32>,
+    pub connection_timeout_secs: u64,
+    pub statement_timeout_ms: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        let pool = DbPoolConfig::default();
+        Self {
+            secret_provider: None,
+            secrets_file: "db_link.json".to_string(),
+            db_link: None,
+            region: None,
+            pool_size: pool.max_size,
+            min_idle: pool.min_idle,
+            connection_timeout_secs: pool.connection_timeout.as_secs(),
+            statement_timeout_ms: pool
+                .statement_timeout
+                .map_or(0, |timeout| timeout.as_millis() as u64),
+        }
+    }
+}
+
+impl DatabaseConfig {
+    /// The connection pool settings described by this config.
+    pub fn pool_config(&self) -> DbPoolConfig {
+        DbPoolConfig {
+            max_size: self.pool_size,
+            min_idle: self.min_idle,
+            connection_timeout: Duration::from_secs(self.connection_timeout_secs),
+            statement_timeout: match self.statement_timeout_ms {
+                0 => None,
+                millis => Some(Duration::from_millis(millis)),
+            },
+        }
+    }
+}
+
+/// The paths of the config files used when importing vocab.
+///
+/// - `language_packs`: The directory of language packs, see [`crate::config::LanguagePackRegistry`].
+/// - `translations_config`: See [`crate::config::load_translations_config`].
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilesConfig {
+    pub language_packs: String,
+    pub translations_config: String,
+}
+
+impl Default for FilesConfig {
+    fn default() -> Self {
+        Self {
+            language_packs: "lang".to_string(),
+            translations_config: "translations_config.json".to_string(),
+        }
+    }
+}
+
+static DEFAULT_CONFIG_FILENAME: &str = "palabras.toml";
+
+/// Every setting that can be overridden, by its `--set` key and its env var.
+pub static OVERRIDABLE_SETTINGS: [(&str, &str); 23] = [
+    ("server.addr", "PAL_SERVER_ADDR"),
+    ("database.secret_provider", "PAL_SECRET_PROVIDER"),
+    ("database.secrets_file", "PAL_SECRETS_FILE"),
+    ("database.db_link", "PAL_DB_LINK"),
+    ("database.region", "PAL_REGION"),
+    ("database.pool_size", "PAL_DB_POOL_SIZE"),
+    ("database.min_idle", "PAL_DB_MIN_IDLE"),
+    (
+        "database.connection_timeout_secs",
+        "PAL_DB_CONNECTION_TIMEOUT_SECS",
+    ),
+    (
+        "database.statement_timeout_ms",
+        "PAL_DB_STATEMENT_TIMEOUT_MS",
+    ),
+    ("files.language_packs", "PAL_LANGUAGE_PACKS_DIR"),
+    ("files.translations_config", "PAL_TRANSLATIONS_CONFIG_FILE"),
+    ("grading.max_distance", "PAL_GRADING_MAX_DISTANCE"),
+    (
+        "grading.well_known_threshold",
+        "PAL_GRADING_WELL_KNOWN_THRESHOLD",
+    ),
+    ("grading.close_distance", "PAL_GRADING_CLOSE_DISTANCE"),
+    ("grading.perfect_weight", "PAL_GRADING_PERFECT_WEIGHT"),
+    ("leech.max_lapses", "PAL_LEECH_MAX_LAPSES"),
+    ("leech.min_attempts", "PAL_LEECH_MIN_ATTEMPTS"),
+    ("leech.min_percentage", "PAL_LEECH_MIN_PERCENTAGE"),
+    ("leech.suspend", "PAL_LEECH_SUSPEND"),
+    ("composer.other_ratio", "PAL_COMPOSER_OTHER_RATIO"),
+    ("composer.word_ratio", "PAL_COMPOSER_WORD_RATIO"),
+    ("composer.phrase_ratio", "PAL_COMPOSER_PHRASE_RATIO"),
+    ("composer.seed", "PAL_COMPOSER_SEED"),
+];
+
+impl AppConfig {
+    /// Parses a config in TOML format, any missing setting keeps its default.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PalabrasError::Config` if the TOML is malformed or has an unknown setting.
+    pub fn from_toml_str(toml_str: &str) -> Result<AppConfig, PalabrasError> {
+        toml::from_str(toml_str).map_err(|err| PalabrasError::Config(err.to_string()))
+    }
+
+    /// Reads a config file, JSON if the file name ends in `.json`, otherwise TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PalabrasError::Io` if the file cannot be read, or `PalabrasError::Config` naming the
+    /// file if it cannot be parsed.
+    pub fn from_file(path: &str) -> Result<AppConfig, PalabrasError> {
+        let contents = fs::read_to_string(path)?;
+        let parsed = if path.ends_with(".json") {
+            serde_json::from_str(&contents).map_err(|err| err.to_string())
+        } else {
+            toml::from_str(&contents).map_err(|err| err.to_string())
+        };
+
+        parsed.map_err(|err| PalabrasError::Config(format!("Failed to parse {}: {}", path, err)))
+    }
+
+    /// Loads the config in layers, then validates it.
+    ///
+    /// The config file is the one given with `--config`, otherwise the one named by `PAL_CONFIG_FILE`,
+    /// otherwise `palabras.toml` if it exists. Settings are then overridden by their env vars and
+    /// by `--set key=value` arguments, see [`OVERRIDABLE_SETTINGS`].
+    ///
+    /// # Parameters
+    ///
+    /// * `args` - The command line arguments, without the program name.
+    ///
+    /// # Returns
+    ///
+    /// Returns the config and the remaining arguments, which are left for the tool to interpret.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PalabrasError::Config` describing every problem found, or `PalabrasError::Io` if
+    /// the config file cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use palabras::config::app_config::AppConfig;
+    ///
+    /// let args = vec!["--set".to_string(), "grading.max_distance=12".to_string(), "7".to_string()];
+    /// let (config, remaining) = AppConfig::load(args).expect("Config should have loaded");
+    /// assert_eq!(config.grading.max_distance, 12);
+    /// assert_eq!(remaining, vec!["7".to_string()]);
+    ///
+    /// let args = vec!["--set".to_string(), "database.pool_size=0".to_string()];
+    /// assert!(AppConfig::load(args).is_err());
+    /// ```
+    pub fn load(args: Vec<String>) -> Result<(AppConfig, Vec<String>), PalabrasError> {
+        let mut config_file = None;
+        let mut cli_overrides = Vec::new();
+        let mut remaining = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--config" => config_file = Some(expect_value(&arg, args.next())?),
+                "--set" => cli_overrides.push(expect_value(&arg, args.next())?),
+                _ => {
+                    if let Some(path) = arg.strip_prefix("--config=") {
+                        config_file = Some(path.to_string());
+                    } else if let Some(setting) = arg.strip_prefix("--set=") {
+                        cli_overrides.push(setting.to_string());
+                    } else {
+                        remaining.push(arg);
+                    }
+                }
+            }
+        }
+
+        let config_file = config_file.or_else(|| env::var("PAL_CONFIG_FILE").ok());
+        let mut config = match config_file {
+            Some(path) => AppConfig::from_file(&path)?,
+            None if Path::new(DEFAULT_CONFIG_FILENAME).exists() => {
+                AppConfig::from_file(DEFAULT_CONFIG_FILENAME)?
+            }
+            None => AppConfig::default(),
+        };
+
+        config.apply_env_overrides()?;
+        for setting in cli_overrides {
+            let (key, value) = setting.split_once('=').ok_or_else(|| {
+                PalabrasError::Config(format!("--set expects key=value, found '{}'", setting))
+            })?;
+            config.set(key.trim(), value.trim(), &format!("--set {}", key.trim()))?;
+        }
+
+        config.validate()?;
+        Ok((config, remaining))
+    }
+
+    /// Overrides settings with any of the env vars in [`OVERRIDABLE_SETTINGS`] that are set and not empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PalabrasError::Config` naming the env var if its value cannot be parsed.
+    pub fn apply_env_overrides(&mut self) -> Result<(), PalabrasError> {
+        for (key, env_var) in OVERRIDABLE_SETTINGS {
+            match env::var(env_var) {
+                Ok(value) if !value.trim().is_empty() => self.set(key, value.trim(), env_var)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the settings make sense together.
+    ///
+    /// # Errors
+    ///
+    /// Returns a single `PalabrasError::Config` listing every invalid setting.
+    pub fn validate(&self) -> Result<(), PalabrasError> {
+        let mut problems = Vec::new();
+
+        if let Err(err) = SocketAddr::from_str(&self.server.addr) {
+            problems.push(format!(
+                "server.addr must be IP:PORT, found '{}': {}",
+                self.server.addr, err
+            ));
+        }
+        if let Some(provider) = &self.database.secret_provider {
+            if !["env", "file", "aws"].contains(&provider.as_str()) {
+                problems.push(format!(
+                    "database.secret_provider must be env, file or aws, found '{}'",
+                    provider
+                ));
+            }
+        }
+        if self.database.pool_size < 1 {
+            problems.push("database.pool_size must be at least 1".to_string());
+        }
+        if let Some(min_idle) = self.database.min_idle {
+            if min_idle > self.database.pool_size {
+                problems.push(format!(
+                    "database.min_idle must not be above database.pool_size {}, found {}",
+                    self.database.pool_size, min_idle
+                ));
+            }
+        }
+        if let Err(err) = validate_grading_policy(&self.grading.policy_for(0)) {
+            problems.push(format!("grading: {}", err));
+        }
+        problems.extend(self.leech.problems());
+        problems.extend(self.composer.problems());
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(PalabrasError::Config(format!(
+                "Invalid configuration: {}",
+                problems.join("; ")
+            )))
+        }
+    }
+
+    // Sets a single setting from text, `source` names where the value came from for error messages.
+    fn set(&mut self, key: &str, value: &str, source: &str) -> Result<(), PalabrasError> {
+        match key {
+            "server.addr" => self.server.addr = value.to_string(),
+            "database.secret_provider" => self.database.secret_provider = Some(value.to_string()),
+            "database.secrets_file" => self.database.secrets_file = value.to_string(),
+            "database.db_link" => self.database.db_link = Some(value.to_string()),
+            "database.region" => self.database.region = Some(value.to_string()),
+            "database.pool_size" => self.database.pool_size = parse_setting(source, value)?,
+            "database.min_idle" => self.database.min_idle = Some(parse_setting(source, value)?),
+            "database.connection_timeout_secs" => {
+                self.database.connection_timeout_secs = parse_setting(source, value)?
+            }
+            "database.statement_timeout_ms" => {
+                self.database.statement_timeout_ms = parse_setting(source, value)?
+            }
+            "files.language_packs" => self.files.language_packs = value.to_string(),
+            "files.translations_config" => self.files.translations_config = value.to_string(),
+            "grading.max_distance" => self.grading.max_distance = parse_setting(source, value)?,
+            "grading.well_known_threshold" => {
+                self.grading.well_known_threshold = parse_setting(source, value)?
+            }
+            "grading.close_distance" => self.grading.close_distance = parse_setting(source, value)?,
+            "grading.perfect_weight" => self.grading.perfect_weight = parse_setting(source, value)?,
+            "leech.max_lapses" => self.leech.max_lapses = parse_setting(source, value)?,
+            "leech.min_attempts" => self.leech.min_attempts = parse_setting(source, value)?,
+            "leech.min_percentage" => self.leech.min_percentage = parse_setting(source, value)?,
+            "leech.suspend" => self.leech.suspend = parse_setting(source, value)?,
+            "composer.other_ratio" => self.composer.other_ratio = parse_setting(source, value)?,
+            "composer.word_ratio" => self.composer.word_ratio = parse_setting(source, value)?,
+            "composer.phrase_ratio" => self.composer.phrase_ratio = parse_setting(source, value)?,
+            "composer.seed" => self.composer.seed = Some(parse_setting(source, value)?),
+            _ => {
+                return Err(PalabrasError::Config(format!(
+                    "{} is not a known setting",
+                    source
+                )))
+            }
+        }
+        Ok(())
+    }
+}
+
+fn expect_value(flag: &str, value: Option<String>) -> Result<String, PalabrasError> {
+    value.ok_or_else(|| PalabrasError::Config(format!("{} expects a value", flag)))
+}
+
+fn parse_setting<T>(source: &str, value: &str) -> Result<T, PalabrasError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.parse::<T>().map_err(|err| {
+        PalabrasError::Config(format!(
+            "{} has an invalid value '{}': {}",
+            source, value, err
+        ))
+    })
+}
+
+static APP_CONFIG: OnceLock<AppConfig> = OnceLock::new();
+
+/// Loads the config with [`AppConfig::load`] and makes it available through [`app_config`].
+///
+/// Each binary calls this once at startup, before anything reads the config.
+///
+/// # Returns
+///
+/// Returns the loaded config and the arguments left for the tool to interpret.
+///
+/// # Errors
+///
+/// Returns the error of [`AppConfig::load`], or `PalabrasError::Config` if the config was already in use.
+pub fn init_app_config(
+    args: Vec<String>,
+) -> Result<(&'static AppConfig, Vec<String>), PalabrasError> {
+    let (config, remaining) = AppConfig::load(args)?;
+    APP_CONFIG.set(config).map_err(|_| {
+        PalabrasError::Config("The configuration was already initialised".to_string())
+    })?;
+
+    Ok((app_config(), remaining))
+}
+
+/// The config of the running application.
+///
+/// If [`init_app_config`] has not been called, as in tests, the config file and env vars are loaded
+/// without any command line arguments.
+///
+/// # Panics
+///
+/// Panics if the config is then invalid, rather than running with settings the operator did not ask for.
+pub fn app_config() -> &'static AppConfig {
+    APP_CONFIG.get_or_init(|| match AppConfig::load(Vec::new()) {
+        Ok((config, _)) => config,
+        Err(err) => panic!("The configuration is invalid. {}", err),
+    })
+}
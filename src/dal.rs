@@ -1,5 +1,6 @@
 pub mod awesome_person;
 pub mod db_connection;
 pub mod file_access;
+pub mod unit_of_work;
 pub mod vocab;
 pub(crate) mod vocab_study;
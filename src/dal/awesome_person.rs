@@ -1,9 +1,20 @@
-use crate::dal::db_connection::{error_to_string, get_connection};
-use crate::models::{AwesomePerson, NewAwesomePerson};
+use crate::dal::db_connection::get_connection;
+use crate::error::PalabrasError;
+use crate::models::{
+    AwesomePerson, ConjugationStat, Deck, DeckVocab, GradingPolicy, NewAwesomePerson, NewDeck,
+    NewStudySession, StudySession, StudySessionItem, Vocab, VocabStudy,
+};
 use crate::schema::palabras::awesome_person::dsl::awesome_person;
 use crate::schema::palabras::awesome_person::dsl::*;
-use diesel::ExpressionMethods;
-use diesel::{OptionalExtension, QueryDsl, RunQueryDsl};
+use crate::schema::palabras::{
+    conjugation_stat, deck, deck_vocab, grading_policy, study_session, study_session_item, vocab,
+    vocab_attempt, vocab_study,
+};
+use chrono::{DateTime, Utc};
+use diesel::{ExpressionMethods, NullableExpressionMethods};
+use diesel::{
+    OptionalExtension, PgConnection, QueryDsl, QueryResult, RunQueryDsl, SelectableHelper,
+};
 
 /// Trait for interacting with awesome person records in a database.
 ///
@@ -20,7 +31,10 @@ pub trait AwesomePersonRepository: Send + Sync {
     ///
     /// Returns `Ok(Some(AwesomePerson))` if an awesome person record with the specified `id` exists,
     /// Ok(None) if not found or an error if the query fails.
-    fn get_awesome_person_by_id(&self, awesome_id: i32) -> Result<Option<AwesomePerson>, String>;
+    fn get_awesome_person_by_id(
+        &self,
+        awesome_id: i32,
+    ) -> Result<Option<AwesomePerson>, PalabrasError>;
 
     /// Retrieves a single awesome person record by their lookup code.
     ///
@@ -35,7 +49,7 @@ pub trait AwesomePersonRepository: Send + Sync {
     fn get_awesome_person_by_code(
         &self,
         look_up_code: String,
-    ) -> Result<Option<AwesomePerson>, String>;
+    ) -> Result<Option<AwesomePerson>, PalabrasError>;
 
     /// Updates an existing `AwesomePerson` record in the database based on the provided `AwesomePerson` instance.
     ///
@@ -46,7 +60,45 @@ pub trait AwesomePersonRepository: Send + Sync {
     /// # Returns
     ///
     /// Returns the number of records updated in the database, or an error if the update operation fails.
-    fn update_awesome_person(&self, stats: AwesomePerson) -> Result<usize, String>;
+    fn update_awesome_person(&self, stats: AwesomePerson) -> Result<usize, PalabrasError>;
+
+    /// Replaces the daily limits of an awesome person in a single update, leaving the progress
+    /// counters a concurrent grading may be changing alone.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_id` - The primary key of the awesome person.
+    /// * `new_per_day` - The most new words a day.
+    /// * `reviews_per_day` - The most reviews a day.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(AwesomePerson)` with the updated record, `PalabrasError::NotFound` if the awesome person
+    /// does not exist, or another error if the update fails.
+    fn update_daily_limits(
+        &self,
+        awesome_id: i32,
+        new_per_day: i32,
+        reviews_per_day: i32,
+    ) -> Result<AwesomePerson, PalabrasError>;
+
+    /// Replaces the preferred regional variant of an awesome person in a single update, see
+    /// [`AwesomePersonRepository::update_daily_limits`].
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_id` - The primary key of the awesome person.
+    /// * `lang_variant` - The preferred variant, e.g. `es-419`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(AwesomePerson)` with the updated record, `PalabrasError::NotFound` if the awesome person
+    /// does not exist, or another error if the update fails.
+    fn update_preferred_variant(
+        &self,
+        awesome_id: i32,
+        lang_variant: &str,
+    ) -> Result<AwesomePerson, PalabrasError>;
 
     /// Creates a new `AwesomePerson` record in the database based on the provided `NewAwesomePerson` instance.
     ///
@@ -57,13 +109,255 @@ pub trait AwesomePersonRepository: Send + Sync {
     /// # Returns
     ///
     /// Returns `Ok(AwesomePerson)` if the awesome person record was created with a newly assigned `id`,
-    /// or an error if create fails.
+    /// `PalabrasError::Conflict` if the `sec_code` is already taken, or another error if create fails.
     fn create_awesome_person(
         &self,
         new_awesome_person: &NewAwesomePerson,
-    ) -> Result<AwesomePerson, String>;
+    ) -> Result<AwesomePerson, PalabrasError>;
+
+    /// Recalculates the aggregate stats of every awesome person from their vocab studies.
+    ///
+    /// The running totals kept on `AwesomePerson` can drift, for instance from older versions
+    /// that counted a word as newly known every time it was answered. This rebuilds them:
+    /// - `num_known`: The number of vocab studies that are `well_known`.
+    /// - `num_correct`: The sum of `correct_attempts`.
+    /// - `num_incorrect`: The sum of `attempts` less `correct_attempts`.
+    /// - `total_percentage`: `num_correct` over all attempts, 0 without any attempts.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of awesome person records updated, or an error if the update fails.
+    fn recalculate_awesome_person_stats(&self) -> Result<usize, PalabrasError>;
+
+    /// Retrieves the grading policy stored for an awesome person.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_id` - The primary key (`id`) of the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(GradingPolicy))` if the awesome person has their own policy,
+    /// Ok(None) if they use the default policy, or an error if the query fails.
+    fn get_grading_policy(&self, awesome_id: i32) -> Result<Option<GradingPolicy>, PalabrasError>;
+
+    /// Creates or replaces the grading policy of an awesome person.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy` - The policy to store, keyed by its `awesome_person_id`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the stored `GradingPolicy`, `PalabrasError::Conflict` if the awesome person does not exist,
+    /// `PalabrasError::Validation` if a value is out of range, or another error if the save fails.
+    fn save_grading_policy(&self, policy: &GradingPolicy) -> Result<GradingPolicy, PalabrasError>;
+
+    /// Retrieves an awesome person's conjugation drill accuracy, one record per tense and person drilled.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_id` - The primary key (`id`) of the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `ConjugationStat` records, empty if nothing has been drilled, or an error if the query fails.
+    fn get_conjugation_stats(&self, awesome_id: i32)
+        -> Result<Vec<ConjugationStat>, PalabrasError>;
+
+    /// Records the answer to a conjugation drill.
+    ///
+    /// The attempt is counted in a single statement, creating the record for the tense and person
+    /// when needed, so concurrent answers are not lost.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_id` - The primary key (`id`) of the awesome person.
+    /// * `tense` - The key of the tense drilled.
+    /// * `person` - The key of the person drilled.
+    /// * `correct` - Whether the answer was a perfect match.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated `ConjugationStat`, `PalabrasError::Conflict` if the awesome person does not exist,
+    /// or another error if the save fails.
+    fn record_conjugation_attempt(
+        &self,
+        awesome_id: i32,
+        tense: &str,
+        person: &str,
+        correct: bool,
+    ) -> Result<ConjugationStat, PalabrasError>;
+
+    /// Starts a new study session for an awesome person.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_id` - The primary key (`id`) of the awesome person studying.
+    ///
+    /// # Returns
+    ///
+    /// Returns the open `StudySession` with a newly assigned `id`, `PalabrasError::Conflict` if the
+    /// awesome person does not exist, or another error if create fails.
+    fn create_study_session(&self, awesome_id: i32) -> Result<StudySession, PalabrasError>;
+
+    /// Retrieves a single study session by its primary key.
+    ///
+    /// # Parameters
+    ///
+    /// * `study_session_id` - The primary key (`id`) of the study session.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(StudySession))` if the study session exists,
+    /// Ok(None) if not found or an error if the query fails.
+    fn get_study_session(
+        &self,
+        study_session_id: i32,
+    ) -> Result<Option<StudySession>, PalabrasError>;
+
+    /// Retrieves the vocab studies answered in a study session, in the order they were first answered.
+    ///
+    /// # Parameters
+    ///
+    /// * `study_session_id` - The primary key (`id`) of the study session.
+    ///
+    /// # Returns
+    ///
+    /// Returns each `StudySessionItem` with its `VocabStudy` and `Vocab`, empty if nothing was answered,
+    /// or an error if the query fails.
+    fn get_study_session_items(
+        &self,
+        study_session_id: i32,
+    ) -> Result<Vec<(StudySessionItem, VocabStudy, Vocab)>, PalabrasError>;
+
+    /// Closes a study session, setting its `finished` timestamp.
+    ///
+    /// A session that is already finished keeps its timestamp.
+    ///
+    /// # Parameters
+    ///
+    /// * `study_session_id` - The primary key (`id`) of the study session.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of records updated, 0 if the session was already finished or does not exist,
+    /// or an error if the update fails.
+    fn finish_study_session(&self, study_session_id: i32) -> Result<usize, PalabrasError>;
+
+    /// Counts the answers in an awesome person's attempt history since a point in time, e.g. the start of today.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_id` - The primary key (`id`) of the awesome person.
+    /// * `since` - The earliest answers counted.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of new words introduced, first answers of a vocab study, and the number of
+    /// reviews, every other answer, or an error if the query fails.
+    fn count_attempts_since(
+        &self,
+        awesome_id: i32,
+        since: DateTime<Utc>,
+    ) -> Result<(i64, i64), PalabrasError>;
+
+    /// Creates a new deck of vocab for an awesome person.
+    ///
+    /// # Parameters
+    ///
+    /// * `new_deck` - The deck to create, owned by its `owner_id`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `Deck` with a newly assigned `id`, `PalabrasError::Conflict` if the owner already has a
+    /// deck of the same name or does not exist, or another error if create fails.
+    fn create_deck(&self, new_deck: &NewDeck) -> Result<Deck, PalabrasError>;
+
+    /// Retrieves a single deck by its primary key.
+    ///
+    /// # Parameters
+    ///
+    /// * `deck_id` - The primary key (`id`) of the deck.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(Deck))` if the deck exists, Ok(None) if not found or an error if the query fails.
+    fn get_deck(&self, deck_id: i32) -> Result<Option<Deck>, PalabrasError>;
+
+    /// Retrieves the decks of an awesome person, by name.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_id` - The primary key (`id`) of the owner.
+    ///
+    /// # Returns
+    ///
+    /// Returns each `Deck` with the number of vocab in it, empty without any decks, or an error if the query fails.
+    fn get_decks(&self, awesome_id: i32) -> Result<Vec<(Deck, i64)>, PalabrasError>;
+
+    /// Adds vocab to a deck, vocab already in the deck is left as it is.
+    ///
+    /// # Parameters
+    ///
+    /// * `deck_id` - The primary key (`id`) of the deck.
+    /// * `vocab_ids` - The primary keys of the vocab to add.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of vocab added, `PalabrasError::Conflict` if the deck or a vocab does not exist,
+    /// or another error if the insert fails.
+    fn add_vocab_to_deck(&self, deck_id: i32, vocab_ids: &[i32]) -> Result<usize, PalabrasError>;
+
+    /// Removes vocab from a deck, the vocab itself is kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `deck_id` - The primary key (`id`) of the deck.
+    /// * `vocab_ids` - The primary keys of the vocab to remove.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of vocab removed, or an error if the delete fails.
+    fn remove_vocab_from_deck(
+        &self,
+        deck_id: i32,
+        vocab_ids: &[i32],
+    ) -> Result<usize, PalabrasError>;
+
+    /// Retrieves the vocab in a deck, in the order it was added.
+    ///
+    /// # Parameters
+    ///
+    /// * `deck_id` - The primary key (`id`) of the deck.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `Vocab` of the deck, empty if the deck is empty or does not exist, or an error if the query fails.
+    fn get_deck_vocab(&self, deck_id: i32) -> Result<Vec<Vocab>, PalabrasError>;
 }
 
+static RECALCULATE_STATS_SQL: &str = "
+UPDATE palabras.awesome_person ap
+SET num_known = totals.num_known,
+    num_correct = totals.num_correct,
+    num_incorrect = totals.num_incorrect,
+    total_percentage = CASE
+        WHEN totals.num_correct + totals.num_incorrect > 0
+        THEN totals.num_correct::float8 / (totals.num_correct + totals.num_incorrect)
+        ELSE 0 END,
+    updated = now()
+FROM (
+    SELECT p.id,
+        COUNT(vs.id) FILTER (WHERE vs.well_known) AS num_known,
+        COALESCE(SUM(COALESCE(vs.correct_attempts, 0)), 0) AS num_correct,
+        COALESCE(SUM(COALESCE(vs.attempts, 0) - COALESCE(vs.correct_attempts, 0)), 0) AS num_incorrect
+    FROM palabras.awesome_person p
+    LEFT JOIN palabras.vocab_study vs ON vs.awesome_person_id = p.id
+    GROUP BY p.id
+) totals
+WHERE ap.id = totals.id";
+
 pub struct DbAwesomePersonRepository;
 
 impl AwesomePersonRepository for DbAwesomePersonRepository {
@@ -71,13 +365,16 @@ impl AwesomePersonRepository for DbAwesomePersonRepository {
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests for this module.
-    fn get_awesome_person_by_id(&self, awesome_id: i32) -> Result<Option<AwesomePerson>, String> {
+    fn get_awesome_person_by_id(
+        &self,
+        awesome_id: i32,
+    ) -> Result<Option<AwesomePerson>, PalabrasError> {
         let mut conn = get_connection()?;
         awesome_person
             .find(awesome_id)
             .first(&mut conn)
             .optional()
-            .map_err(|err| error_to_string(err))
+            .map_err(PalabrasError::from)
     }
 
     /// Implementation, see trait for details [`AwesomePersonRepository::get_awesome_person_by_code`]
@@ -87,30 +384,70 @@ impl AwesomePersonRepository for DbAwesomePersonRepository {
     fn get_awesome_person_by_code(
         &self,
         sec_code_search: String,
-    ) -> Result<Option<AwesomePerson>, String> {
+    ) -> Result<Option<AwesomePerson>, PalabrasError> {
         let mut conn = get_connection()?;
         awesome_person
             .filter(sec_code.eq(sec_code_search))
             .first(&mut conn)
             .optional()
-            .map_err(|err| error_to_string(err))
+            .map_err(PalabrasError::from)
     }
 
     /// Implementation, see trait for details [`AwesomePersonRepository::update_awesome_person`]
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests for this module.
-    fn update_awesome_person(&self, updating: AwesomePerson) -> Result<usize, String> {
+    fn update_awesome_person(&self, updating: AwesomePerson) -> Result<usize, PalabrasError> {
         let mut conn = get_connection()?;
 
         let num_updated = diesel::update(awesome_person.find(updating.id))
             .set(&updating)
-            .execute(&mut conn)
-            .map_err(|err| err.to_string())?;
+            .execute(&mut conn)?;
 
         Ok(num_updated)
     }
 
+    /// Implementation, see trait for details [`AwesomePersonRepository::update_daily_limits`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn update_daily_limits(
+        &self,
+        awesome_id: i32,
+        limit_new: i32,
+        limit_reviews: i32,
+    ) -> Result<AwesomePerson, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        diesel::update(awesome_person.find(awesome_id))
+            .set((
+                new_per_day.eq(limit_new),
+                reviews_per_day.eq(limit_reviews),
+                updated.eq(Utc::now()),
+            ))
+            .get_result(&mut conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_id))
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::update_preferred_variant`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn update_preferred_variant(
+        &self,
+        awesome_id: i32,
+        lang_variant: &str,
+    ) -> Result<AwesomePerson, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        diesel::update(awesome_person.find(awesome_id))
+            .set((preferred_variant.eq(lang_variant), updated.eq(Utc::now())))
+            .get_result(&mut conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_id))
+    }
+
     /// Implementation, see trait for details [`AwesomePersonRepository::create_awesome_person`]
     ///
     /// For advanced usage and mock implementations, please refer to
@@ -118,13 +455,325 @@ impl AwesomePersonRepository for DbAwesomePersonRepository {
     fn create_awesome_person(
         &self,
         new_awesome_person: &NewAwesomePerson,
-    ) -> Result<AwesomePerson, String> {
+    ) -> Result<AwesomePerson, PalabrasError> {
         let mut conn = get_connection()?;
         let inserted = diesel::insert_into(awesome_person)
             .values(new_awesome_person)
-            .get_result(&mut conn)
-            .map_err(|err| err.to_string())?;
+            .get_result(&mut conn)?;
+
+        Ok(inserted)
+    }
+    /// Implementation, see trait for details [`AwesomePersonRepository::recalculate_awesome_person_stats`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn recalculate_awesome_person_stats(&self) -> Result<usize, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        let num_updated = diesel::sql_query(RECALCULATE_STATS_SQL).execute(&mut conn)?;
+
+        Ok(num_updated)
+    }
+    /// Implementation, see trait for details [`AwesomePersonRepository::get_grading_policy`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_grading_policy(&self, awesome_id: i32) -> Result<Option<GradingPolicy>, PalabrasError> {
+        let mut conn = get_connection()?;
+        grading_policy::table
+            .find(awesome_id)
+            .first(&mut conn)
+            .optional()
+            .map_err(PalabrasError::from)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::save_grading_policy`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn save_grading_policy(&self, policy: &GradingPolicy) -> Result<GradingPolicy, PalabrasError> {
+        let mut conn = get_connection()?;
+        let saved = diesel::insert_into(grading_policy::table)
+            .values(policy)
+            .on_conflict(grading_policy::awesome_person_id)
+            .do_update()
+            .set(policy)
+            .get_result(&mut conn)?;
+
+        Ok(saved)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::get_conjugation_stats`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_conjugation_stats(
+        &self,
+        awesome_id: i32,
+    ) -> Result<Vec<ConjugationStat>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let stats = conjugation_stat::table
+            .filter(conjugation_stat::awesome_person_id.eq(awesome_id))
+            .get_results(&mut conn)?;
+
+        Ok(stats)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::record_conjugation_attempt`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn record_conjugation_attempt(
+        &self,
+        awesome_id: i32,
+        tense: &str,
+        person: &str,
+        correct: bool,
+    ) -> Result<ConjugationStat, PalabrasError> {
+        let mut conn = get_connection()?;
+        let recorded = diesel::insert_into(conjugation_stat::table)
+            .values(&ConjugationStat {
+                awesome_person_id: awesome_id,
+                tense: tense.to_string(),
+                person: person.to_string(),
+                attempts: 1,
+                correct_attempts: correct as i32,
+                updated: Utc::now(),
+            })
+            .on_conflict((
+                conjugation_stat::awesome_person_id,
+                conjugation_stat::tense,
+                conjugation_stat::person,
+            ))
+            .do_update()
+            .set((
+                conjugation_stat::attempts.eq(conjugation_stat::attempts + 1),
+                conjugation_stat::correct_attempts
+                    .eq(conjugation_stat::correct_attempts + correct as i32),
+                conjugation_stat::updated.eq(Utc::now()),
+            ))
+            .get_result(&mut conn)?;
+
+        Ok(recorded)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::create_study_session`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn create_study_session(&self, awesome_id: i32) -> Result<StudySession, PalabrasError> {
+        let mut conn = get_connection()?;
+        let inserted = diesel::insert_into(study_session::table)
+            .values(&NewStudySession {
+                awesome_person_id: awesome_id,
+                started: Utc::now(),
+            })
+            .get_result(&mut conn)?;
+
+        Ok(inserted)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::get_study_session`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_study_session(
+        &self,
+        study_session_id: i32,
+    ) -> Result<Option<StudySession>, PalabrasError> {
+        let mut conn = get_connection()?;
+        study_session::table
+            .find(study_session_id)
+            .first(&mut conn)
+            .optional()
+            .map_err(PalabrasError::from)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::get_study_session_items`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_study_session_items(
+        &self,
+        study_session_id: i32,
+    ) -> Result<Vec<(StudySessionItem, VocabStudy, Vocab)>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let items = study_session_item::table
+            .inner_join(vocab_study::table.inner_join(vocab::table))
+            .filter(study_session_item::study_session_id.eq(study_session_id))
+            .order((
+                study_session_item::created,
+                study_session_item::vocab_study_id,
+            ))
+            .select((
+                StudySessionItem::as_select(),
+                VocabStudy::as_select(),
+                Vocab::as_select(),
+            ))
+            .load(&mut conn)?;
+
+        Ok(items)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::finish_study_session`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn finish_study_session(&self, study_session_id: i32) -> Result<usize, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        let num_updated = diesel::update(
+            study_session::table
+                .find(study_session_id)
+                .filter(study_session::finished.is_null()),
+        )
+        .set(study_session::finished.eq(Utc::now()))
+        .execute(&mut conn)?;
+
+        Ok(num_updated)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::count_attempts_since`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn count_attempts_since(
+        &self,
+        awesome_id: i32,
+        since: DateTime<Utc>,
+    ) -> Result<(i64, i64), PalabrasError> {
+        let mut conn = get_connection()?;
+
+        let counts: Vec<(bool, i64)> = vocab_attempt::table
+            .filter(vocab_attempt::awesome_person_id.eq(awesome_id))
+            .filter(vocab_attempt::attempted.ge(since))
+            .group_by(vocab_attempt::new_word)
+            .select((vocab_attempt::new_word, diesel::dsl::count_star()))
+            .load(&mut conn)?;
+
+        let count = |new_word: bool| {
+            counts
+                .iter()
+                .find(|(counted, _)| *counted == new_word)
+                .map_or(0, |(_, num)| *num)
+        };
+
+        Ok((count(true), count(false)))
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::create_deck`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn create_deck(&self, new_deck: &NewDeck) -> Result<Deck, PalabrasError> {
+        let mut conn = get_connection()?;
+        let inserted = diesel::insert_into(deck::table)
+            .values(new_deck)
+            .get_result(&mut conn)?;
 
         Ok(inserted)
     }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::get_deck`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_deck(&self, deck_id: i32) -> Result<Option<Deck>, PalabrasError> {
+        let mut conn = get_connection()?;
+        deck::table
+            .find(deck_id)
+            .first(&mut conn)
+            .optional()
+            .map_err(PalabrasError::from)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::get_decks`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_decks(&self, awesome_id: i32) -> Result<Vec<(Deck, i64)>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let decks = deck::table
+            .left_join(deck_vocab::table)
+            .filter(deck::owner_id.eq(awesome_id))
+            .group_by(deck::id)
+            .select((
+                Deck::as_select(),
+                diesel::dsl::count(deck_vocab::vocab_id.nullable()),
+            ))
+            .order((deck::name, deck::id))
+            .load(&mut conn)?;
+
+        Ok(decks)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::add_vocab_to_deck`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn add_vocab_to_deck(&self, deck_id: i32, vocab_ids: &[i32]) -> Result<usize, PalabrasError> {
+        let mut conn = get_connection()?;
+        let num_added = insert_deck_vocab(&mut conn, deck_id, vocab_ids)?;
+
+        Ok(num_added)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::remove_vocab_from_deck`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn remove_vocab_from_deck(
+        &self,
+        deck_id: i32,
+        vocab_ids: &[i32],
+    ) -> Result<usize, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        let num_removed = diesel::delete(
+            deck_vocab::table
+                .filter(deck_vocab::deck_id.eq(deck_id))
+                .filter(deck_vocab::vocab_id.eq_any(vocab_ids)),
+        )
+        .execute(&mut conn)?;
+
+        Ok(num_removed)
+    }
+
+    /// Implementation, see trait for details [`AwesomePersonRepository::get_deck_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_deck_vocab(&self, deck_id: i32) -> Result<Vec<Vocab>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let vocabs = deck_vocab::table
+            .inner_join(vocab::table)
+            .filter(deck_vocab::deck_id.eq(deck_id))
+            .order((deck_vocab::added, deck_vocab::vocab_id))
+            .select(Vocab::as_select())
+            .load(&mut conn)?;
+
+        Ok(vocabs)
+    }
+}
+
+/// Adds vocab to a deck on an open connection, see [`AwesomePersonRepository::add_vocab_to_deck`].
+pub(crate) fn insert_deck_vocab(
+    conn: &mut PgConnection,
+    deck_id: i32,
+    vocab_ids: &[i32],
+) -> QueryResult<usize> {
+    let added = Utc::now();
+    let rows: Vec<DeckVocab> = vocab_ids
+        .iter()
+        .map(|vocab_id| DeckVocab {
+            deck_id,
+            vocab_id: *vocab_id,
+            added,
+        })
+        .collect();
+
+    diesel::insert_into(deck_vocab::table)
+        .values(&rows)
+        .on_conflict_do_nothing()
+        .execute(conn)
 }
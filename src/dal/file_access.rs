@@ -1,5 +1,6 @@
 use crate::config::TranslationsConfig;
-use crate::models::Vocab;
+use crate::error::PalabrasError;
+use crate::models::{NewVocab, Vocab};
 use regex::Regex;
 use std::collections::HashMap;
 use std::error::Error;
@@ -11,7 +12,7 @@ use std::string::ToString;
 
 pub fn find_first_lang_translations(
     config: &TranslationsConfig,
-) -> Result<HashMap<String, String>, String> {
+) -> Result<HashMap<String, String>, PalabrasError> {
     let buf_reader = load_buffer_from_file(&config.file_name)?;
 
     // Skip the header
@@ -27,22 +28,20 @@ pub fn find_first_lang_translations(
 pub fn find_with_pattern(
     lines: &mut Skip<Lines<BufReader<File>>>,
     config: &TranslationsConfig,
-) -> Result<HashMap<String, String>, String> {
+) -> Result<HashMap<String, String>, PalabrasError> {
     let learning_regex = Regex::new(
         config
             .learning_regex
             .as_ref()
-            .ok_or("Learning regex is required")?,
-    )
-    .map_err(|e| e.to_string())?;
+            .ok_or_else(|| PalabrasError::Config("Learning regex is required".to_string()))?,
+    )?;
 
     let first_regex = Regex::new(
         config
             .first_regex
             .as_ref()
-            .ok_or("First Lang regex is required")?,
-    )
-    .map_err(|e| e.to_string())?;
+            .ok_or_else(|| PalabrasError::Config("First Lang regex is required".to_string()))?,
+    )?;
 
     let mut translation_map: HashMap<String, String> = HashMap::new();
 
@@ -51,7 +50,7 @@ pub fn find_with_pattern(
     let mut pending_first: Option<String> = None;
 
     for line_result in lines {
-        let line = line_result.map_err(|e| e.to_string())?;
+        let line = line_result?;
         let trimmed_line = line.trim();
         if trimmed_line.is_empty() {
             continue;
@@ -61,28 +60,96 @@ pub fn find_with_pattern(
         pending_first = capture_phrase(pending_first.clone(), &first_regex, trimmed_line);
 
         // Check if both captures are found, either on this line or from previous lines
-        match (&pending_learning, &pending_first) {
-            (Some(learning), Some(first)) => {
-                translation_map.insert(learning.clone(), first.clone());
+        // Do nothing if either or both captures are still pending
+        if let (Some(learning), Some(first)) = (&pending_learning, &pending_first) {
+            translation_map.insert(learning.clone(), first.clone());
 
-                // Reset pending captures after successful match
-                pending_learning = None;
-                pending_first = None;
-            }
-            _ => {} // Do nothing if either or both captures are still pending
+            // Reset pending captures after successful match
+            pending_learning = None;
+            pending_first = None;
         }
     }
 
     Ok(translation_map)
 }
 
+/// Finds the grammatical gender of the learning language words in a non-delimited file.
+///
+/// Each match of `gender_regex` gives the gender of the closest `learning_regex` match before it,
+/// words without a gender, such as verbs, are skipped. The first gender found for a word is kept.
+///
+/// # Returns
+///
+/// A map of learning language words to their gender, empty if `gender_regex` is not configured.
+///
+/// # Errors
+///
+/// Returns `PalabrasError::Io` if the file cannot be read, or `PalabrasError::Config` if the
+/// learning regex is missing.
+///
+/// # Example
+///
+/// ```
+/// use palabras::config::TranslationsConfig;
+/// use palabras::dal::file_access::find_genders;
+///
+/// # fn main() -> Result<(), palabras::error::PalabrasError> {
+/// let config = TranslationsConfig {
+///     file_name: "data/mananoreboton/short-es-en.xml".to_string(),
+///     header_lines: 4,
+///     learning_regex: Some("<c>([^<]+)</c>".to_string()),
+///     gender_regex: Some("<t>\\{([mf])\\}".to_string()),
+///     ..Default::default()
+/// };
+///
+/// let genders = find_genders(&config)?;
+/// assert_eq!(genders.get("añadidura").map(String::as_str), Some("f"));
+/// assert!(genders.get("añadir").is_none());
+/// # Ok(())
+/// # }
+/// ```
+pub fn find_genders(config: &TranslationsConfig) -> Result<HashMap<String, String>, PalabrasError> {
+    let mut gender_map: HashMap<String, String> = HashMap::new();
+    let Some(gender_regex) = &config.gender_regex else {
+        return Ok(gender_map);
+    };
+    let gender_regex = Regex::new(gender_regex)?;
+    let learning_regex = Regex::new(
+        config
+            .learning_regex
+            .as_ref()
+            .ok_or_else(|| PalabrasError::Config("Learning regex is required".to_string()))?,
+    )?;
+
+    let buf_reader = load_buffer_from_file(&config.file_name)?;
+    let mut pending_learning: Option<String> = None;
+
+    for line_result in buf_reader.lines().skip(config.header_lines) {
+        let line = line_result?;
+        let trimmed_line = line.trim();
+
+        // A new word replaces one without a gender
+        if let Some(learning) = capture_phrase(None, &learning_regex, trimmed_line) {
+            pending_learning = Some(learning);
+        }
+
+        if let Some(gender) = capture_phrase(None, &gender_regex, trimmed_line) {
+            if let Some(learning) = pending_learning.take() {
+                gender_map.entry(learning).or_insert(gender);
+            }
+        }
+    }
+
+    Ok(gender_map)
+}
+
 fn capture_phrase(pending: Option<String>, r: &Regex, line: &str) -> Option<String> {
     // Short circuit if we already have some value.
     if pending.is_some() {
         return pending;
     }
 
-    r.captures(&line)
+    r.captures(line)
         .and_then(|caps| caps.get(1))
         .map(|m| m.as_str().to_string())
 }
@@ -90,9 +157,12 @@ fn capture_phrase(pending: Option<String>, r: &Regex, line: &str) -> Option<Stri
 pub fn find_with_splitter(
     lines: &mut Skip<Lines<BufReader<File>>>,
     config: &TranslationsConfig,
-) -> Result<HashMap<String, String>, String> {
+) -> Result<HashMap<String, String>, PalabrasError> {
     if config.learning_index.eq(&config.first_index) {
-        return Err(format!("Indices are both {}.", config.learning_index));
+        return Err(PalabrasError::Config(format!(
+            "Indices are both {}.",
+            config.learning_index
+        )));
     }
 
     let mut translation_map: HashMap<String, String> = HashMap::new();
@@ -108,12 +178,12 @@ pub fn find_with_splitter(
         };
 
         if fields.len() < config.learning_index || fields.len() < config.first_index {
-            return Err(format!(
+            return Err(PalabrasError::Config(format!(
                 "Found {} fields, but learning_index {} or first_index {} is out of range.",
                 fields.len(),
                 config.learning_index,
                 config.first_index
-            ));
+            )));
         }
 
         translation_map
@@ -130,7 +200,7 @@ pub fn write_missing_first_export(
     pairs: Vec<Vocab>,
 ) -> Result<(), Box<dyn Error>> {
     let mut buf_writer = open_writing_file_buffer(file_path)?;
-    buf_writer.write(CSV_HEADER.as_ref())?;
+    buf_writer.write_all(CSV_HEADER.as_ref())?;
 
     pairs.iter().try_for_each(|pair| -> io::Result<()> {
         let out_line = format!(
@@ -148,6 +218,149 @@ pub fn write_missing_first_export(
     Ok(())
 }
 
+static DECK_CSV_HEADER: &str = "learning,infinitive,pos,skill,first,tags\n";
+
+/// Reads the vocab of a deck from a CSV file in the format of [`write_deck_export`].
+///
+/// The first line is a header and is skipped, as are empty lines. Each other line has the learning language,
+/// infinitive, part of speech, skill and known language, the same columns as an LLM import, e.g.
+/// `es,infinitive,pos,skill,en`, and optionally the tags separated by semicolons, e.g. `food;a2`.
+/// A field holding a comma is quoted, a quote in a quoted field is doubled.
+///
+/// # Returns
+///
+/// The vocab of each line, without language codes, with its tags, or `PalabrasError::Validation` naming
+/// the first line without a learning and known language.
+///
+/// # Example
+///
+/// ```rust
+/// # fn main() -> Result<(), palabras::error::PalabrasError> {
+/// use palabras::dal::file_access::find_deck_vocab;
+///
+/// let vocabs = find_deck_vocab("tests/data/es_en_mapping/llm_import.csv")?;
+/// let (vocab, tags) = &vocabs[0];
+/// assert_eq!(vocab.learning_lang, "miraste");
+/// assert_eq!(vocab.infinitive, Some("mirar".to_string()));
+/// assert_eq!(vocab.first_lang, "you looked");
+/// assert!(tags.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub fn find_deck_vocab(file_name: &str) -> Result<Vec<(NewVocab, Vec<String>)>, PalabrasError> {
+    let buf_reader = load_buffer_from_file(file_name)?;
+    let mut vocabs = Vec::new();
+
+    for (index, line) in buf_reader.lines().enumerate().skip(1) {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = split_csv_line(&line);
+        let field = |i: usize| {
+            fields
+                .get(i)
+                .map(|field| field.trim())
+                .filter(|field| !field.is_empty())
+                .map(str::to_string)
+        };
+        let (Some(learning_lang), Some(first_lang)) = (field(0), field(4)) else {
+            return Err(PalabrasError::Validation(format!(
+                "line {} of {} needs a learning and a known language",
+                index + 1,
+                file_name
+            )));
+        };
+
+        let tags = field(5)
+            .map(|tags| {
+                tags.split(';')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        vocabs.push((
+            NewVocab {
+                learning_lang,
+                first_lang,
+                infinitive: field(1),
+                pos: field(2),
+                skill: field(3),
+                ..Default::default()
+            },
+            tags,
+        ));
+    }
+
+    Ok(vocabs)
+}
+
+/// Writes the vocab of a deck to a new CSV file, which [`find_deck_vocab`] reads back.
+///
+/// The CSV file is created with this header: `learning,infinitive,pos,skill,first,tags\n`
+pub fn write_deck_export(
+    file_path: &str,
+    vocabs: &[(Vocab, Vec<String>)],
+) -> Result<(), Box<dyn Error>> {
+    let mut buf_writer = open_writing_file_buffer(file_path)?;
+    buf_writer.write_all(DECK_CSV_HEADER.as_ref())?;
+
+    vocabs
+        .iter()
+        .try_for_each(|(vocab, tags)| -> io::Result<()> {
+            let out_line = format!(
+                "{},{},{},{},{},{}\n",
+                csv_field(&vocab.learning_lang),
+                csv_field(vocab.infinitive.as_deref().unwrap_or_default()),
+                csv_field(vocab.pos.as_deref().unwrap_or_default()),
+                csv_field(vocab.skill.as_deref().unwrap_or_default()),
+                csv_field(&vocab.first_lang),
+                csv_field(&tags.join(";"))
+            );
+
+            buf_writer.write_all(out_line.as_bytes())
+        })?;
+
+    buf_writer.flush()?;
+
+    Ok(())
+}
+
+// Quotes a field holding a comma or a quote, doubling its quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// Splits a CSV line on the commas outside quotes, a doubled quote in a quoted field is a quote.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
 /// Loads a file into a `BufReader` for efficient reading.
 ///
 /// This function opens a file specified by `file_name` and wraps it in a `BufReader`.
@@ -172,37 +385,36 @@ pub fn write_missing_first_export(
 /// - The current user lacks the necessary permissions to read the file.
 /// - There are system-level errors in accessing the file (e.g., issues with the file system).
 ///
-/// The error is returned as a `String` describing the nature of the failure.
+/// The error is returned as `PalabrasError::Io` describing the nature of the failure.
 ///
 /// # Example
 ///
 /// ```rust
 /// use std::io::{self, BufRead};
 ///
-/// # fn main() -> Result<(), String> {
+/// # fn main() -> Result<(), palabras::error::PalabrasError> {
 /// use palabras::dal::file_access::load_buffer_from_file;
 ///
 /// let file_name = "tests/data/test_file.txt";
 /// let reader = load_buffer_from_file(file_name)?;
 ///
 /// for line in reader.lines() {
-///     let line = line.map_err(|err| err.to_string())?;
+///     let line = line?;
 ///     println!("{}", line);
 /// }
 /// # Ok(())
 /// # }
 /// ```
-pub fn load_buffer_from_file(file_name: &str) -> Result<BufReader<File>, String> {
-    let file = File::open(file_name).map_err(|err| err.to_string())?;
+pub fn load_buffer_from_file(file_name: &str) -> Result<BufReader<File>, PalabrasError> {
+    let file = File::open(file_name)?;
     Ok(BufReader::new(file))
 }
 
-pub fn open_writing_file_buffer(path: &str) -> Result<BufWriter<File>, String> {
+pub fn open_writing_file_buffer(path: &str) -> Result<BufWriter<File>, PalabrasError> {
     let file = OpenOptions::new()
         .write(true)
         .create_new(true) // This ensures the file is created only if it does not exist
-        .open(path)
-        .map_err(|err| err.to_string())?;
+        .open(path)?;
 
     Ok(BufWriter::new(file))
 }
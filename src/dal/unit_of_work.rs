@@ -0,0 +1,448 @@
+use crate::dal::awesome_person::insert_deck_vocab;
+use crate::dal::db_connection::get_connection;
+use crate::dal::vocab::insert_vocab_tags;
+use crate::error::PalabrasError;
+use crate::models::{
+    AwesomePerson, Deck, GradingPolicy, NewVocab, NewVocabAttempt, NewVocabStudy, StudySession,
+    StudySessionItem, Vocab, VocabAlternative, VocabStudy,
+};
+use crate::schema::palabras::awesome_person::dsl::awesome_person;
+use crate::schema::palabras::grading_policy;
+use crate::schema::palabras::vocab::dsl::vocab;
+use crate::schema::palabras::vocab_alternative;
+use crate::schema::palabras::vocab_study::dsl::vocab_study;
+use crate::schema::palabras::{deck, study_session, study_session_item, vocab_attempt};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::PgConnection;
+
+// Groups the reads and writes of a single grading into one database transaction.
+// Rows that are read to be modified are locked until the transaction commits, so
+// concurrent answers for the same awesome person can no longer overwrite each other.
+// Adding vocab to a deck is the other unit, a file is imported whole or not at all and the owner of
+// the deck studies all of the vocab added.
+
+/// The operations available while grading a response inside a unit of work.
+///
+/// All calls made through one `GradingTransaction` share a single connection and either
+/// commit together or roll back together. The `lock_*` functions take a row lock that is
+/// held until the transaction ends, always lock the vocab study, then the awesome person, then
+/// the study session to keep the lock order consistent.
+pub trait GradingTransaction {
+    /// Gets a single vocab using its primary key.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vocab)` if found, `PalabrasError::NotFound` if no record matches the given `vocab_id`,
+    /// or another `PalabrasError` if the query fails.
+    fn get_vocab(&mut self, vocab_id: i32) -> Result<Vocab, PalabrasError>;
+
+    /// Gets the alternative answers of a vocab, in the order they were added.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<VocabAlternative>)`, empty if the vocab has none, or a `PalabrasError` if the query fails.
+    fn get_vocab_alternatives(
+        &mut self,
+        vocab_id: i32,
+    ) -> Result<Vec<VocabAlternative>, PalabrasError>;
+
+    /// Gets and locks a single vocab study using its primary key.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(VocabStudy)` if found, `PalabrasError::NotFound` if no record matches the given
+    /// `vocab_study_id`, or another `PalabrasError` if the query fails.
+    fn lock_vocab_study(&mut self, vocab_study_id: i32) -> Result<VocabStudy, PalabrasError>;
+
+    /// Gets and locks a single awesome person using their primary key.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(AwesomePerson)` if found, `PalabrasError::NotFound` if no record matches the given
+    /// `awesome_person_id`, or another `PalabrasError` if the query fails.
+    fn lock_awesome_person(
+        &mut self,
+        awesome_person_id: i32,
+    ) -> Result<AwesomePerson, PalabrasError>;
+
+    /// Gets and locks a single study session using its primary key.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(StudySession)` if found, `PalabrasError::NotFound` if no record matches the given
+    /// `study_session_id`, or another `PalabrasError` if the query fails.
+    fn lock_study_session(&mut self, study_session_id: i32) -> Result<StudySession, PalabrasError>;
+
+    /// Gets the record of a vocab study answered in a study session.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(StudySessionItem))` if the vocab study was answered in the session before,
+    /// `Ok(None)` if not, or a `PalabrasError` if the query fails.
+    fn get_study_session_item(
+        &mut self,
+        study_session_id: i32,
+        vocab_study_id: i32,
+    ) -> Result<Option<StudySessionItem>, PalabrasError>;
+
+    /// Gets the grading policy stored for an awesome person.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Some(GradingPolicy))` if the awesome person has their own policy, `Ok(None)` if
+    /// the default policy applies, or a `PalabrasError` if the query fails.
+    fn get_grading_policy(
+        &mut self,
+        awesome_person_id: i32,
+    ) -> Result<Option<GradingPolicy>, PalabrasError>;
+
+    /// Updates an existing `VocabStudy` record, returning the number of records updated.
+    fn update_vocab_study(&mut self, updating: VocabStudy) -> Result<usize, PalabrasError>;
+
+    /// Updates an existing `AwesomePerson` record, returning the number of records updated.
+    fn update_awesome_person(&mut self, updating: AwesomePerson) -> Result<usize, PalabrasError>;
+
+    /// Updates an existing `StudySession` record, returning the number of records updated.
+    fn update_study_session(&mut self, updating: StudySession) -> Result<usize, PalabrasError>;
+
+    /// Creates or replaces the record of a vocab study answered in a study session,
+    /// returning the number of records saved.
+    fn save_study_session_item(&mut self, item: &StudySessionItem) -> Result<usize, PalabrasError>;
+
+    /// Adds an answer to the attempt history of an awesome person, returning the number of records inserted.
+    fn record_vocab_attempt(&mut self, attempt: &NewVocabAttempt) -> Result<usize, PalabrasError>;
+}
+
+/// The operations available while importing a deck, or adding vocab to one, inside a unit of work.
+///
+/// All calls made through one `DeckImportTransaction` share a single connection and either
+/// commit together or roll back together.
+pub trait DeckImportTransaction {
+    /// Gets a single deck using its primary key.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Deck)` if found, `PalabrasError::NotFound` if no record matches the given `deck_id`,
+    /// or another `PalabrasError` if the query fails.
+    fn get_deck(&mut self, deck_id: i32) -> Result<Deck, PalabrasError>;
+
+    /// Gets a single vocab using its primary key.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vocab)` if found, `PalabrasError::NotFound` if no record matches the given `vocab_id`,
+    /// or another `PalabrasError` if the query fails.
+    fn get_vocab(&mut self, vocab_id: i32) -> Result<Vocab, PalabrasError>;
+
+    /// Finds a vocab by its learning language, see
+    /// [`VocabRepository::find_vocab_by_learning_language`](crate::dal::vocab::VocabRepository::find_vocab_by_learning_language).
+    fn find_vocab_by_learning_language(
+        &mut self,
+        learning: &str,
+    ) -> Result<Option<Vocab>, PalabrasError>;
+
+    /// Creates a new `Vocab` record, returning it with its primary key.
+    fn create_vocab(&mut self, new_vocab: &NewVocab) -> Result<Vocab, PalabrasError>;
+
+    /// Tags vocab, creating the tags that do not exist yet, see
+    /// [`VocabRepository::tag_vocab`](crate::dal::vocab::VocabRepository::tag_vocab).
+    fn tag_vocab(
+        &mut self,
+        vocab_ids: &[i32],
+        tag_names: &[String],
+    ) -> Result<usize, PalabrasError>;
+
+    /// Adds vocab to a deck, see
+    /// [`AwesomePersonRepository::add_vocab_to_deck`](crate::dal::awesome_person::AwesomePersonRepository::add_vocab_to_deck).
+    fn add_vocab_to_deck(
+        &mut self,
+        deck_id: i32,
+        vocab_ids: &[i32],
+    ) -> Result<usize, PalabrasError>;
+
+    /// Creates an untested vocab study of each vocab the awesome person is not yet studying.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(usize)` with the number of vocab studies created, a vocab the awesome person already
+    /// studies is skipped, or a `PalabrasError` if the insert fails.
+    fn start_vocab_studies(
+        &mut self,
+        vocab_ids: &[i32],
+        awesome_person_id: i32,
+    ) -> Result<usize, PalabrasError>;
+}
+
+/// Trait for running grading work, and adding vocab to decks, as a single unit.
+///
+/// Implementations decide what "a single unit" means, the database implementation runs the work
+/// inside a transaction while mock implementations used in testing can simply work in memory.
+pub trait UnitOfWork: Send + Sync {
+    /// Runs `work` inside a unit of work.
+    ///
+    /// # Parameters
+    ///
+    /// * `work` - A closure performing the reads and writes through the provided `GradingTransaction`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` once the work has been committed.
+    ///
+    /// # Errors
+    ///
+    /// If `work` returns an error, or the transaction cannot be started or committed, nothing is
+    /// saved and the error is returned.
+    fn grading(
+        &self,
+        work: &mut dyn FnMut(&mut dyn GradingTransaction) -> Result<(), PalabrasError>,
+    ) -> Result<(), PalabrasError>;
+
+    /// Runs `work` importing a deck, or adding vocab to one, inside a unit of work.
+    ///
+    /// # Parameters
+    ///
+    /// * `work` - A closure performing the reads and writes through the provided `DeckImportTransaction`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` once the work has been committed.
+    ///
+    /// # Errors
+    ///
+    /// If `work` returns an error, or the transaction cannot be started or committed, nothing is
+    /// saved and the error is returned.
+    fn deck_import(
+        &self,
+        work: &mut dyn FnMut(&mut dyn DeckImportTransaction) -> Result<(), PalabrasError>,
+    ) -> Result<(), PalabrasError>;
+}
+
+pub struct DbUnitOfWork;
+
+/// Implementation of UnitOfWork
+///
+/// For behavior, see the documentation of [`UnitOfWork`].
+impl UnitOfWork for DbUnitOfWork {
+    /// Implementation, see trait for details [`UnitOfWork::grading`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn grading(
+        &self,
+        work: &mut dyn FnMut(&mut dyn GradingTransaction) -> Result<(), PalabrasError>,
+    ) -> Result<(), PalabrasError> {
+        let mut conn = get_connection()?;
+        conn.transaction(|conn| work(&mut DbGradingTransaction { conn }))
+    }
+
+    /// Implementation, see trait for details [`UnitOfWork::deck_import`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn deck_import(
+        &self,
+        work: &mut dyn FnMut(&mut dyn DeckImportTransaction) -> Result<(), PalabrasError>,
+    ) -> Result<(), PalabrasError> {
+        let mut conn = get_connection()?;
+        conn.transaction(|conn| work(&mut DbDeckImportTransaction { conn }))
+    }
+}
+
+/// A `GradingTransaction` backed by a connection with an open transaction.
+struct DbGradingTransaction<'a> {
+    conn: &'a mut PgConnection,
+}
+
+impl GradingTransaction for DbGradingTransaction<'_> {
+    fn get_vocab(&mut self, vocab_id: i32) -> Result<Vocab, PalabrasError> {
+        vocab
+            .find(vocab_id)
+            .first(self.conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("vocab", vocab_id))
+    }
+
+    fn get_vocab_alternatives(
+        &mut self,
+        vocab_id: i32,
+    ) -> Result<Vec<VocabAlternative>, PalabrasError> {
+        vocab_alternative::table
+            .filter(vocab_alternative::vocab_id.eq(vocab_id))
+            .order(vocab_alternative::id)
+            .get_results(self.conn)
+            .map_err(PalabrasError::from)
+    }
+
+    fn lock_vocab_study(&mut self, vocab_study_id: i32) -> Result<VocabStudy, PalabrasError> {
+        vocab_study
+            .find(vocab_study_id)
+            .for_update()
+            .first(self.conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("vocab study", vocab_study_id))
+    }
+
+    fn lock_awesome_person(
+        &mut self,
+        awesome_person_id: i32,
+    ) -> Result<AwesomePerson, PalabrasError> {
+        awesome_person
+            .find(awesome_person_id)
+            .for_update()
+            .first(self.conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))
+    }
+
+    fn lock_study_session(&mut self, study_session_id: i32) -> Result<StudySession, PalabrasError> {
+        study_session::table
+            .find(study_session_id)
+            .for_update()
+            .first(self.conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("study session", study_session_id))
+    }
+
+    fn get_study_session_item(
+        &mut self,
+        study_session_id: i32,
+        vocab_study_id: i32,
+    ) -> Result<Option<StudySessionItem>, PalabrasError> {
+        study_session_item::table
+            .find((study_session_id, vocab_study_id))
+            .first(self.conn)
+            .optional()
+            .map_err(PalabrasError::from)
+    }
+
+    fn get_grading_policy(
+        &mut self,
+        awesome_person_id: i32,
+    ) -> Result<Option<GradingPolicy>, PalabrasError> {
+        grading_policy::table
+            .find(awesome_person_id)
+            .first(self.conn)
+            .optional()
+            .map_err(PalabrasError::from)
+    }
+
+    fn update_vocab_study(&mut self, updating: VocabStudy) -> Result<usize, PalabrasError> {
+        Ok(diesel::update(vocab_study.find(updating.id))
+            .set(&updating)
+            .execute(self.conn)?)
+    }
+
+    fn update_awesome_person(&mut self, updating: AwesomePerson) -> Result<usize, PalabrasError> {
+        Ok(diesel::update(awesome_person.find(updating.id))
+            .set(&updating)
+            .execute(self.conn)?)
+    }
+
+    fn update_study_session(&mut self, updating: StudySession) -> Result<usize, PalabrasError> {
+        Ok(diesel::update(study_session::table.find(updating.id))
+            .set(&updating)
+            .execute(self.conn)?)
+    }
+
+    fn save_study_session_item(&mut self, item: &StudySessionItem) -> Result<usize, PalabrasError> {
+        Ok(diesel::insert_into(study_session_item::table)
+            .values(item)
+            .on_conflict((
+                study_session_item::study_session_id,
+                study_session_item::vocab_study_id,
+            ))
+            .do_update()
+            .set(item)
+            .execute(self.conn)?)
+    }
+
+    fn record_vocab_attempt(&mut self, attempt: &NewVocabAttempt) -> Result<usize, PalabrasError> {
+        Ok(diesel::insert_into(vocab_attempt::table)
+            .values(attempt)
+            .execute(self.conn)?)
+    }
+}
+
+/// A `DeckImportTransaction` backed by a connection with an open transaction.
+struct DbDeckImportTransaction<'a> {
+    conn: &'a mut PgConnection,
+}
+
+impl DeckImportTransaction for DbDeckImportTransaction<'_> {
+    fn get_deck(&mut self, deck_id: i32) -> Result<Deck, PalabrasError> {
+        deck::table
+            .find(deck_id)
+            .first(self.conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("deck", deck_id))
+    }
+
+    fn get_vocab(&mut self, vocab_id: i32) -> Result<Vocab, PalabrasError> {
+        vocab
+            .find(vocab_id)
+            .first(self.conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("vocab", vocab_id))
+    }
+
+    fn find_vocab_by_learning_language(
+        &mut self,
+        learning: &str,
+    ) -> Result<Option<Vocab>, PalabrasError> {
+        use crate::schema::palabras::vocab::dsl::learning_lang;
+
+        vocab
+            .filter(learning_lang.eq(learning))
+            .first(self.conn)
+            .optional()
+            .map_err(PalabrasError::from)
+    }
+
+    fn create_vocab(&mut self, new_vocab: &NewVocab) -> Result<Vocab, PalabrasError> {
+        Ok(diesel::insert_into(vocab)
+            .values(new_vocab)
+            .get_result(self.conn)?)
+    }
+
+    fn tag_vocab(
+        &mut self,
+        vocab_ids: &[i32],
+        tag_names: &[String],
+    ) -> Result<usize, PalabrasError> {
+        Ok(insert_vocab_tags(self.conn, vocab_ids, tag_names)?)
+    }
+
+    fn add_vocab_to_deck(
+        &mut self,
+        deck_id: i32,
+        vocab_ids: &[i32],
+    ) -> Result<usize, PalabrasError> {
+        Ok(insert_deck_vocab(self.conn, deck_id, vocab_ids)?)
+    }
+
+    fn start_vocab_studies(
+        &mut self,
+        vocab_ids: &[i32],
+        awesome_person_id: i32,
+    ) -> Result<usize, PalabrasError> {
+        use crate::schema::palabras::vocab_study::dsl;
+
+        let new_vocab_studies: Vec<NewVocabStudy> = vocab_ids
+            .iter()
+            .map(|vocab_id| NewVocabStudy {
+                vocab_id: *vocab_id,
+                awesome_person_id,
+                created: Utc::now(),
+                ..Default::default()
+            })
+            .collect();
+
+        // A vocab study saved meanwhile, or already there, is left as it is.
+        Ok(diesel::insert_into(vocab_study)
+            .values(&new_vocab_studies)
+            .on_conflict((dsl::vocab_id, dsl::awesome_person_id))
+            .do_nothing()
+            .execute(self.conn)?)
+    }
+}
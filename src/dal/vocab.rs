@@ -1,12 +1,69 @@
-use crate::dal::db_connection::{error_to_string, get_connection};
-use crate::models::{NewVocab, Vocab};
+use crate::dal::db_connection::get_connection;
+use crate::error::PalabrasError;
+use crate::models::{
+    NewTag, NewVocab, NewVocabAlternative, Tag, Vocab, VocabAlternative, VocabMatch, VocabSearch,
+    VocabTag,
+};
 use crate::schema::palabras::vocab::dsl::vocab;
 use crate::schema::palabras::vocab::dsl::*;
+use crate::schema::palabras::{tag, vocab_alternative, vocab_tag};
 use diesel::prelude::*;
-use diesel::RunQueryDsl;
+use diesel::sql_types::{BigInt, Double, Integer, Nullable, Text};
+use diesel::{PgConnection, RunQueryDsl};
 
-/// The data mapping layer. Diesel is used to query and update vocabs.
-/// Connections are pulled from a static singleton pool for each operation.
+sql_function!(fn lower(x: Text) -> Text);
+
+// Each searched field of a vocab is matched against the text without accents. A field equal to the text ranks 3, a
+// prefix 2 plus its similarity and any other field similar enough to pass the pg_trgm threshold its similarity alone.
+// The best field ranks the vocab. Matches are paged by their rank, learning language and id, after those of the
+// last match of the previous page.
+const SEARCH_VOCAB_SQL: &str = r#"
+WITH q AS (
+    SELECT f_unaccent(lower($1)) AS t, f_unaccent(lower($2)) AS prefix
+), field AS (
+    SELECT id AS vocab_id, f_unaccent(lower(learning_lang)) AS text FROM vocab
+    UNION ALL
+    SELECT id, f_unaccent(lower(first_lang)) FROM vocab
+    UNION ALL
+    SELECT vocab_id, f_unaccent(lower(alternative)) FROM vocab_alternative
+), ranked AS (
+    SELECT field.vocab_id,
+        max(CASE
+            WHEN field.text = q.t THEN 3.0
+            WHEN field.text LIKE q.prefix THEN 2.0 + similarity(field.text, q.t)
+            ELSE similarity(field.text, q.t)
+        END)::float8 AS rank
+    FROM field, q
+    WHERE field.text LIKE q.prefix OR field.text % q.t
+    GROUP BY field.vocab_id
+)
+SELECT v.*, ranked.rank, vs.id IS NOT NULL AS enrolled
+FROM ranked
+JOIN vocab v ON v.id = ranked.vocab_id
+LEFT JOIN vocab_study vs ON vs.vocab_id = v.id AND vs.awesome_person_id = $6
+WHERE ($3::text IS NULL OR v.learning_lang_code = $3)
+    AND ($4::text IS NULL OR lower(v.pos) = lower($4))
+    AND ($5::text IS NULL OR EXISTS (
+        SELECT 1 FROM vocab_tag vt JOIN tag ON tag.id = vt.tag_id
+        WHERE vt.vocab_id = v.id AND tag.name = lower(trim($5))
+    ))
+    AND ($7::float8 IS NULL OR ranked.rank < $7
+        OR (ranked.rank = $7 AND (v.learning_lang, v.id) > ($8::text, $9::int4)))
+ORDER BY ranked.rank DESC, v.learning_lang, v.id
+LIMIT $10
+"#;
+
+// The LIKE pattern of a prefix, escaping the wildcards in it.
+fn like_prefix(prefix: &str) -> String {
+    let escaped = prefix
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("{}%", escaped)
+}
+
+// The data mapping layer. Diesel is used to query and update vocabs.
+// Connections are pulled from a static singleton pool for each operation.
 
 /// Trait for accessing vocab records in a database.
 ///
@@ -23,9 +80,9 @@ pub trait VocabRepository: Send + Sync {
     /// # Returns
     ///
     /// Returns `Ok(Vocab)` if a vocab with the specified `vocab_id` exists,
-    /// or a `String` if the query fails (e.g., due to connection issues or if no
-    /// vocab matches the given `vocab_id`).
-    fn get_vocab_by_id(&self, vocab_id: i32) -> Result<Vocab, String>;
+    /// `PalabrasError::NotFound` if no vocab matches the given `vocab_id`,
+    /// or another `PalabrasError` if the query fails (e.g., due to connection issues).
+    fn get_vocab_by_id(&self, vocab_id: i32) -> Result<Vocab, PalabrasError>;
 
     /// Looks up a single vocab by the learning language.
     ///
@@ -40,30 +97,146 @@ pub trait VocabRepository: Send + Sync {
     /// # Returns
     ///
     /// Returns `Ok(Some(Vocab))` if a vocab matching the `learning_lang_search` exists,
-    /// `Ok(None)` if no matching vocab is found, or an `Err(diesel::result::Error)` if there's an issue with the database query.
+    /// `Ok(None)` if no matching vocab is found, or an `Err(PalabrasError)` if there's an issue with the database query.
     fn find_vocab_by_learning_language(
         &self,
         learning_lang_search: String,
-    ) -> Result<Option<Vocab>, String>;
+    ) -> Result<Option<Vocab>, PalabrasError>;
 
-    /// Looks up a single vocab by the searching alternatives.
+    /// Looks up a single vocab by one of its alternatives.
     ///
     /// This function is designed to support scenarios where vocabs need to be retrieved
-    /// based on the `alternatives` field, which is expected to be unique within the dataset.
+    /// based on an alternative answer, see [`VocabAlternative`], which is expected to be unique within the dataset.
+    /// The whole alternative must match, ignoring case, e.g. `examinar` does not find `examinarlo`.
     /// It is particularly useful for processing Duolingo JSON exports in binary programs.
     ///
     /// # Parameters
     ///
-    /// * `alternative_search` - The string used to search for the corresponding vocab.
+    /// * `alternative_search` - The alternative used to search for the corresponding vocab.
     ///
     /// # Returns
     ///
-    /// Returns `Ok(Some(Vocab))` if a vocab matching the `alternative_search` exists,
-    /// `Ok(None)` if no matching vocab is found, or an `Err(diesel::result::Error)` if there's an issue with the database query.
+    /// Returns `Ok(Some(Vocab))` if a vocab with the alternative exists, the first created if several do,
+    /// `Ok(None)` if no matching vocab is found, or an `Err(PalabrasError)` if there's an issue with the database query.
     fn find_vocab_by_alternative(
         &self,
         alternative_search: String,
-    ) -> Result<Option<Vocab>, String>;
+    ) -> Result<Option<Vocab>, PalabrasError>;
+
+    /// Gets the alternative answers of a vocab.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_id` - Primary key of the vocab.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<VocabAlternative>)` in the order they were added, empty if the vocab has none,
+    /// or an `Err(PalabrasError)` if the query fails.
+    fn get_alternatives(&self, vocab_id: i32) -> Result<Vec<VocabAlternative>, PalabrasError>;
+
+    /// Adds an alternative answer to a vocab.
+    ///
+    /// # Parameters
+    ///
+    /// * `new_alternative` - The alternative, with its optional register and region.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(VocabAlternative)` with the saved alternative.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PalabrasError::Conflict` if the vocab already has the alternative or the vocab does not exist,
+    /// or another `PalabrasError` if the insert fails.
+    fn add_alternative(
+        &self,
+        new_alternative: &NewVocabAlternative,
+    ) -> Result<VocabAlternative, PalabrasError>;
+
+    /// Retrieves every tag, by name.
+    ///
+    /// # Returns
+    ///
+    /// Returns each `Tag` with the number of vocab tagged with it, or a `PalabrasError` if the query fails.
+    fn get_tags(&self) -> Result<Vec<(Tag, i64)>, PalabrasError>;
+
+    /// Retrieves the tags of vocabs.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_ids` - The primary keys of the vocabs.
+    ///
+    /// # Returns
+    ///
+    /// Returns each vocab id with one of its tags, by vocab then tag name, or a `PalabrasError` if the query fails.
+    fn get_vocab_tags(&self, vocab_ids: &[i32]) -> Result<Vec<(i32, Tag)>, PalabrasError>;
+
+    /// Looks up the vocabs tagged with every one of the tags.
+    ///
+    /// # Parameters
+    ///
+    /// * `tag_names` - The names of the tags, trimmed and in lowercase.
+    /// * `after` - Optional. Only the vocabs with a greater id are retrieved, the id of the last vocab of the previous page.
+    /// * `limit` - Specifies the maximum number of vocabs to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// Returns the vocabs by id, empty if there are none or `tag_names` is empty, or a `PalabrasError` if the query fails.
+    fn find_vocab_by_tags(
+        &self,
+        tag_names: &[String],
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<Vocab>, PalabrasError>;
+
+    /// Tags vocabs with each of the tags, creating the tags that do not exist yet.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_ids` - The primary keys of the vocabs to tag.
+    /// * `tag_names` - The names of the tags, trimmed and in lowercase.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of tags added, tags a vocab already has are not counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PalabrasError::Conflict` if a vocab does not exist, or another `PalabrasError` if the insert fails.
+    fn tag_vocab(&self, vocab_ids: &[i32], tag_names: &[String]) -> Result<usize, PalabrasError>;
+
+    /// Removes tags from vocabs, the tags themselves are kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_ids` - The primary keys of the vocabs.
+    /// * `tag_names` - The names of the tags, trimmed and in lowercase.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of tags removed, or a `PalabrasError` if the delete fails.
+    fn untag_vocab(&self, vocab_ids: &[i32], tag_names: &[String]) -> Result<usize, PalabrasError>;
+
+    /// Searches the vocab by the prefix of, or the trigram similarity to, the learning language, an alternative or
+    /// the first language. Accents and case are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// * `search` - The text searched for and the filters of the search, see [`VocabSearch`].
+    /// * `after` - Optional. The rank, learning language and id of the last match of the previous page.
+    /// * `limit` - Specifies the maximum number of vocabs to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// Returns the matches, the best ranked first then by learning language and id, or a `PalabrasError`
+    /// if the query fails.
+    fn search_vocab(
+        &self,
+        search: &VocabSearch,
+        after: Option<(f64, String, i32)>,
+        limit: i64,
+    ) -> Result<Vec<VocabMatch>, PalabrasError>;
 
     /// Retrieves a list of `Vocab` records where the `first_lang` fields are empty.
     ///
@@ -80,13 +253,53 @@ pub trait VocabRepository: Send + Sync {
     /// A `Result` containing either:
     /// - `Ok(Vec<Vocab>)`: A vector of `Vocab` instances with empty `first_lang` fields,
     ///   which could be empty if no such records exist.
-    /// - `Err(String)`: An error message string if the database query fails.
+    /// - `Err(PalabrasError)`: If the database query fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PalabrasError::Database` if there's an issue executing the query, including connection problems
+    /// or syntax errors in the query itself.
+    fn get_empty_first_lang(&self, limit: i64) -> Result<Vec<Vocab>, PalabrasError>;
+
+    /// Retrieves single word Spanish vocabs that have no infinitive.
+    ///
+    /// These are the vocabs the conjugation engine may be able to complete, see
+    /// [`fill_missing_infinitives`](crate::sl::sync_vocab::fill_missing_infinitives).
+    ///
+    /// # Parameters
+    ///
+    /// * `limit` - Specifies the maximum number of vocabs to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(Vec<Vocab>)`: The vocabs with a null or empty `infinitive`, which could be empty.
+    /// - `Err(PalabrasError)`: If the database query fails.
     ///
     /// # Errors
     ///
-    /// Returns an error if there's an issue executing the query, including connection problems
-    /// or syntax errors in the query itself. The error is returned as a `String` describing the failure.
-    fn get_empty_first_lang(&self, limit: i64) -> Result<Vec<Vocab>, String>;
+    /// Returns a `PalabrasError::Database` if there's an issue executing the query.
+    fn get_missing_infinitive(&self, limit: i64) -> Result<Vec<Vocab>, PalabrasError>;
+
+    /// Retrieves single word vocabs that have no grammatical gender.
+    ///
+    /// These are the nouns a dictionary may be able to complete, see
+    /// [`fill_missing_genders`](crate::sl::sync_vocab::fill_missing_genders).
+    ///
+    /// # Parameters
+    ///
+    /// * `limit` - Specifies the maximum number of vocabs to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(Vec<Vocab>)`: The vocabs with a null or empty `gender`, which could be empty.
+    /// - `Err(PalabrasError)`: If the database query fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PalabrasError::Database` if there's an issue executing the query.
+    fn get_missing_gender(&self, limit: i64) -> Result<Vec<Vocab>, PalabrasError>;
 
     /// Inserts a new `Vocab` record into the database.
     ///
@@ -103,14 +316,14 @@ pub trait VocabRepository: Send + Sync {
     ///
     /// A `Result` containing either:
     /// - `Ok(Vocab)`: The newly created `Vocab`, including its database-assigned `id`.
-    /// - `Err(String)`: An error message string if the insert operation fails.
+    /// - `Err(PalabrasError)`: If the insert operation fails.
     ///
     /// # Errors
     ///
     /// Returns an error if there's an issue performing the insert operation, including connection problems
-    /// or violations of database constraints (e.g., unique constraints). The error is returned as a `String`
-    /// describing the failure.
-    fn create_vocab(&self, new_vocab: &NewVocab) -> Result<Vocab, String>;
+    /// (`PalabrasError::Database`) or violations of database constraints (e.g., unique constraints are
+    /// reported as `PalabrasError::Conflict`).
+    fn create_vocab(&self, new_vocab: &NewVocab) -> Result<Vocab, PalabrasError>;
 
     /// Updates an existing `Vocab` record in the database.
     ///
@@ -127,14 +340,14 @@ pub trait VocabRepository: Send + Sync {
     ///
     /// A `Result` containing either:
     /// - `Ok(usize)`: The number of records updated in the database, expected to be 1 when successful.
-    /// - `Err(String)`: An error message string if the update operation fails.
+    /// - `Err(PalabrasError)`: If the update operation fails.
     ///
     /// # Errors
     ///
     /// Returns an error if there's an issue performing the update operation, including connection problems,
-    /// attempting to update a record that does not exist, or violations of database constraints. The error
-    /// is returned as a `String` describing the failure.
-    fn update_vocab(&self, updating: Vocab) -> Result<usize, String>;
+    /// or violations of database constraints. Updating a record that does not exist is not an error, the
+    /// returned count is simply 0.
+    fn update_vocab(&self, updating: Vocab) -> Result<usize, PalabrasError>;
 }
 
 pub struct DbVocabRepository;
@@ -147,12 +360,13 @@ impl VocabRepository for DbVocabRepository {
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests for this module.
-    fn get_vocab_by_id(&self, vocab_id: i32) -> Result<Vocab, String> {
+    fn get_vocab_by_id(&self, vocab_id: i32) -> Result<Vocab, PalabrasError> {
         let mut conn = get_connection()?;
         vocab
             .find(vocab_id)
             .first(&mut conn)
-            .map_err(|err| error_to_string(err))
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("vocab", vocab_id))
     }
 
     /// Implementation, see trait for details [`VocabRepository::find_vocab_by_learning_language`]
@@ -162,13 +376,13 @@ impl VocabRepository for DbVocabRepository {
     fn find_vocab_by_learning_language(
         &self,
         learning_lang_search: String,
-    ) -> Result<Option<Vocab>, String> {
+    ) -> Result<Option<Vocab>, PalabrasError> {
         let mut conn = get_connection()?;
         vocab
             .filter(learning_lang.eq(learning_lang_search))
             .first(&mut conn)
             .optional()
-            .map_err(|err| error_to_string(err))
+            .map_err(PalabrasError::from)
     }
 
     /// Implementation, see trait for details [`VocabRepository::find_vocab_by_alternative`]
@@ -178,28 +392,227 @@ impl VocabRepository for DbVocabRepository {
     fn find_vocab_by_alternative(
         &self,
         alternative_search: String,
-    ) -> Result<Option<Vocab>, String> {
+    ) -> Result<Option<Vocab>, PalabrasError> {
         let mut conn = get_connection()?;
 
-        let like_pattern = format!("%{}%", alternative_search);
         vocab
-            .filter(alternatives.ilike(like_pattern))
+            .inner_join(vocab_alternative::table)
+            .filter(
+                lower(vocab_alternative::alternative).eq(alternative_search.trim().to_lowercase()),
+            )
+            .order(id)
+            .select(Vocab::as_select())
             .first(&mut conn)
             .optional()
-            .map_err(|err| error_to_string(err))
+            .map_err(PalabrasError::from)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::get_alternatives`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_alternatives(&self, vocab_id: i32) -> Result<Vec<VocabAlternative>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let alternatives = vocab_alternative::table
+            .filter(vocab_alternative::vocab_id.eq(vocab_id))
+            .order(vocab_alternative::id)
+            .get_results(&mut conn)?;
+
+        Ok(alternatives)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::add_alternative`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn add_alternative(
+        &self,
+        new_alternative: &NewVocabAlternative,
+    ) -> Result<VocabAlternative, PalabrasError> {
+        let mut conn = get_connection()?;
+        let inserted = diesel::insert_into(vocab_alternative::table)
+            .values(new_alternative)
+            .get_result(&mut conn)?;
+
+        Ok(inserted)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::get_tags`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_tags(&self) -> Result<Vec<(Tag, i64)>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let tags = tag::table
+            .left_join(vocab_tag::table)
+            .group_by(tag::id)
+            .select((
+                Tag::as_select(),
+                diesel::dsl::count(vocab_tag::vocab_id.nullable()),
+            ))
+            .order(tag::name)
+            .load(&mut conn)?;
+
+        Ok(tags)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::get_vocab_tags`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_vocab_tags(&self, vocab_ids: &[i32]) -> Result<Vec<(i32, Tag)>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let tags = vocab_tag::table
+            .inner_join(tag::table)
+            .filter(vocab_tag::vocab_id.eq_any(vocab_ids))
+            .order((vocab_tag::vocab_id, tag::name))
+            .select((vocab_tag::vocab_id, Tag::as_select()))
+            .load(&mut conn)?;
+
+        Ok(tags)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::find_vocab_by_tags`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn find_vocab_by_tags(
+        &self,
+        tag_names: &[String],
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<Vocab>, PalabrasError> {
+        if tag_names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = get_connection()?;
+        // Vocab with as many of the tags as there are tags has them all.
+        let tagged = vocab_tag::table
+            .inner_join(tag::table)
+            .filter(tag::name.eq_any(tag_names))
+            .group_by(vocab_tag::vocab_id)
+            .having(diesel::dsl::count_star().eq(tag_names.len() as i64))
+            .select(vocab_tag::vocab_id);
+        let vocabs = vocab
+            .filter(id.eq_any(tagged))
+            .filter(id.gt(after.unwrap_or(i32::MIN)))
+            .order(id)
+            .limit(limit)
+            .get_results(&mut conn)?;
+
+        Ok(vocabs)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::tag_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn tag_vocab(&self, vocab_ids: &[i32], tag_names: &[String]) -> Result<usize, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        conn.transaction(|conn| insert_vocab_tags(conn, vocab_ids, tag_names))
+            .map_err(PalabrasError::from)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::untag_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn untag_vocab(&self, vocab_ids: &[i32], tag_names: &[String]) -> Result<usize, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        let num_removed = diesel::delete(
+            vocab_tag::table
+                .filter(vocab_tag::vocab_id.eq_any(vocab_ids))
+                .filter(
+                    vocab_tag::tag_id.eq_any(
+                        tag::table
+                            .filter(tag::name.eq_any(tag_names))
+                            .select(tag::id),
+                    ),
+                ),
+        )
+        .execute(&mut conn)?;
+
+        Ok(num_removed)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::search_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn search_vocab(
+        &self,
+        search: &VocabSearch,
+        after: Option<(f64, String, i32)>,
+        limit: i64,
+    ) -> Result<Vec<VocabMatch>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let (after_rank, after_learning, after_id) = match after {
+            Some((rank, learning, vocab_id)) => (Some(rank), Some(learning), Some(vocab_id)),
+            None => (None, None, None),
+        };
+
+        let matches = diesel::sql_query(SEARCH_VOCAB_SQL)
+            .bind::<Text, _>(search.text.trim())
+            .bind::<Text, _>(like_prefix(search.text.trim()))
+            .bind::<Nullable<Text>, _>(search.learning_lang_code.as_deref())
+            .bind::<Nullable<Text>, _>(search.pos.as_deref())
+            .bind::<Nullable<Text>, _>(search.tag.as_deref())
+            .bind::<Nullable<Integer>, _>(search.awesome_person_id)
+            .bind::<Nullable<Double>, _>(after_rank)
+            .bind::<Nullable<Text>, _>(after_learning)
+            .bind::<Nullable<Integer>, _>(after_id)
+            .bind::<BigInt, _>(limit)
+            .load::<VocabMatch>(&mut conn)?;
+
+        Ok(matches)
     }
 
     /// Implementation, see trait for details [`VocabRepository::get_empty_first_lang`]
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests for this module.
-    fn get_empty_first_lang(&self, limit: i64) -> Result<Vec<Vocab>, String> {
+    fn get_empty_first_lang(&self, limit: i64) -> Result<Vec<Vocab>, PalabrasError> {
         let mut conn = get_connection()?;
         let vocabs = vocab
             .filter(first_lang.eq(""))
             .limit(limit)
-            .get_results(&mut conn)
-            .map_err(|err| err.to_string())?;
+            .get_results(&mut conn)?;
+
+        Ok(vocabs)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::get_missing_infinitive`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_missing_infinitive(&self, limit: i64) -> Result<Vec<Vocab>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let vocabs = vocab
+            .filter(infinitive.is_null().or(infinitive.eq("")))
+            .filter(learning_lang_code.eq("es"))
+            .filter(num_learning_words.eq(1))
+            .order(id)
+            .limit(limit)
+            .get_results(&mut conn)?;
+
+        Ok(vocabs)
+    }
+
+    /// Implementation, see trait for details [`VocabRepository::get_missing_gender`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_missing_gender(&self, limit: i64) -> Result<Vec<Vocab>, PalabrasError> {
+        let mut conn = get_connection()?;
+        let vocabs = vocab
+            .filter(gender.is_null().or(gender.eq("")))
+            .filter(num_learning_words.eq(1))
+            .order(id)
+            .limit(limit)
+            .get_results(&mut conn)?;
 
         Ok(vocabs)
     }
@@ -208,12 +621,11 @@ impl VocabRepository for DbVocabRepository {
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests for this module.
-    fn create_vocab(&self, new_vocab: &NewVocab) -> Result<Vocab, String> {
+    fn create_vocab(&self, new_vocab: &NewVocab) -> Result<Vocab, PalabrasError> {
         let mut conn = get_connection()?;
         let inserted = diesel::insert_into(vocab)
             .values(new_vocab)
-            .get_result(&mut conn)
-            .map_err(|err| err.to_string())?;
+            .get_result(&mut conn)?;
 
         Ok(inserted)
     }
@@ -222,14 +634,48 @@ impl VocabRepository for DbVocabRepository {
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests for this module.
-    fn update_vocab(&self, updating: Vocab) -> Result<usize, String> {
+    fn update_vocab(&self, updating: Vocab) -> Result<usize, PalabrasError> {
         let mut conn = get_connection()?;
 
         let updated = diesel::update(vocab.find(updating.id))
             .set(&updating)
-            .execute(&mut conn)
-            .map_err(|err| err.to_string())?;
+            .execute(&mut conn)?;
 
         Ok(updated)
     }
 }
+
+/// Tags vocab on an open connection, creating the tags that do not exist yet, see
+/// [`VocabRepository::tag_vocab`]. Run it inside a transaction so the tags and the tagging are saved together.
+pub(crate) fn insert_vocab_tags(
+    conn: &mut PgConnection,
+    vocab_ids: &[i32],
+    tag_names: &[String],
+) -> QueryResult<usize> {
+    let new_tags: Vec<NewTag> = tag_names
+        .iter()
+        .map(|name| NewTag { name: name.clone() })
+        .collect();
+    diesel::insert_into(tag::table)
+        .values(&new_tags)
+        .on_conflict_do_nothing()
+        .execute(conn)?;
+    let tag_ids: Vec<i32> = tag::table
+        .filter(tag::name.eq_any(tag_names))
+        .select(tag::id)
+        .load(conn)?;
+
+    let vocab_tags: Vec<VocabTag> = vocab_ids
+        .iter()
+        .flat_map(|vocab_id| {
+            tag_ids.iter().map(|tag_id| VocabTag {
+                vocab_id: *vocab_id,
+                tag_id: *tag_id,
+            })
+        })
+        .collect();
+    diesel::insert_into(vocab_tag::table)
+        .values(&vocab_tags)
+        .on_conflict_do_nothing()
+        .execute(conn)
+}
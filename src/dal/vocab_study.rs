@@ -1,15 +1,24 @@
-use crate::dal::db_connection::{error_to_string, get_connection};
-use crate::models::{NewVocabStudy, Vocab, VocabStudy};
+use crate::dal::db_connection::get_connection;
+use crate::error::PalabrasError;
+use crate::models::{
+    NewVocabStudy, Vocab, VocabStudy, VocabStudyFilter, VocabStudyKey, VocabStudyOrder,
+};
+use crate::schema::palabras::deck_vocab;
 use crate::schema::palabras::vocab::dsl::vocab;
 use crate::schema::palabras::vocab_study::dsl::vocab_study;
 use crate::schema::palabras::vocab_study::dsl::*;
 
-use crate::schema::palabras::vocab::num_learning_words;
+use crate::schema::palabras::vocab::{learning_lang, learning_lang_code, num_learning_words, pos};
+use chrono::{DateTime, Utc};
 use diesel::prelude::*;
+use diesel::sql_types::{Nullable, SingleValue, Text};
 use diesel::RunQueryDsl;
 
-/// The data mapping layer. Diesel is used to query and update vocab study.
-/// Connections are pulled from a static singleton pool for each operation.
+sql_function!(fn lower(x: Nullable<Text>) -> Nullable<Text>);
+sql_function!(fn coalesce<T: SingleValue>(x: Nullable<T>, y: T) -> T);
+
+// The data mapping layer. Diesel is used to query and update vocab study.
+// Connections are pulled from a static singleton pool for each operation.
 
 /// Trait for accessing vocab study records in a database.
 ///
@@ -26,9 +35,9 @@ pub trait VocabStudyRepository: Send + Sync {
     /// # Returns
     ///
     /// Returns `Ok(VocabStudy)` if a vocab study with the specified `vocab_study_id` exists,
-    /// or a `String` if the query fails (e.g., due to connection issues or if no
-    /// record matches the given `vocab_study_id`).
-    fn get_vocab_study_by_id(&self, vocab_study_id: i32) -> Result<VocabStudy, String>;
+    /// `PalabrasError::NotFound` if no record matches the given `vocab_study_id`,
+    /// or another `PalabrasError` if the query fails (e.g., due to connection issues).
+    fn get_vocab_study_by_id(&self, vocab_study_id: i32) -> Result<VocabStudy, PalabrasError>;
 
     ///
     /// Gets a single vocab study using its two foreign references
@@ -40,19 +49,18 @@ pub trait VocabStudyRepository: Send + Sync {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(VocabStudy)` if a vocab study with the specified ids exists,
-    /// or a `String` if the query fails (e.g., due to connection issues or if no
-    /// record matches the given `vocab_study_id`).
+    /// Returns `Ok(Some(VocabStudy))` if a vocab study with the specified ids exists,
+    /// `Ok(None)` if no record matches, or a `PalabrasError` if the query fails.
     fn get_vocab_study_by_foreign_refs(
         &self,
         v_id: i32,
         ap_id: i32,
-    ) -> Result<Option<VocabStudy>, String>;
+    ) -> Result<Option<VocabStudy>, PalabrasError>;
 
     /// Retrieves a study set of vocabulary pairs for a specified awesome person.
     ///
     /// This function queries the database to find all vocabulary pairs associated with
-    /// the given `awesome_person_id`. It performs an inner join between the `vocab_study`
+    /// the given `awesome_person_id` that are not suspended. It performs an inner join between the `vocab_study`
     /// and `vocab` tables to gather detailed information about each vocabulary item in the
     /// study set.
     ///
@@ -66,8 +74,8 @@ pub trait VocabStudyRepository: Send + Sync {
     /// A `Result` containing either:
     /// - `Ok(Vec<(VocabStudy, Vocab)>)`: A vector of tuples, each containing a `VocabStudy`
     ///   record and its corresponding `Vocab` record, representing the study set for the
-    ///   specified awesome person.
-    /// - `Err(String)`: An error message string if the database query fails. This could be
+    ///   specified awesome person, by vocab study id so the same study set is always in the same order.
+    /// - `Err(PalabrasError)`: If the database query fails. This could be
     ///   due to connection issues, or if the query itself encounters an error.
     ///
     /// # Errors
@@ -75,8 +83,77 @@ pub trait VocabStudyRepository: Send + Sync {
     /// This function will return an error if:
     /// - There is a problem connecting to the database.
     /// - The SQL query fails to execute properly.
-    fn get_study_set(&self, ap_id: i32, max_words: i32)
-        -> Result<Vec<(VocabStudy, Vocab)>, String>;
+    fn get_study_set(
+        &self,
+        ap_id: i32,
+        max_words: i32,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError>;
+
+    /// Retrieves the study set of an awesome person limited to the vocab in a deck, otherwise the same as
+    /// [`VocabStudyRepository::get_study_set`].
+    ///
+    /// # Parameters
+    ///
+    /// - `ap_id`: The identifier of the awesome person for whom the study set is being retrieved.
+    /// - `max_words`: Max number of learning lang words to be included in study set
+    /// - `deck_id`: The identifier of the deck studied.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(Vec<(VocabStudy, Vocab)>)`: Each `VocabStudy` of a vocab in the deck with its `Vocab`, by vocab study id.
+    /// - `Err(PalabrasError)`: If the database query fails.
+    fn get_deck_study_set(
+        &self,
+        ap_id: i32,
+        max_words: i32,
+        deck_id: i32,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError>;
+
+    /// Retrieves the leeches of an awesome person, and any other suspended vocab study.
+    ///
+    /// # Parameters
+    ///
+    /// - `ap_id`: The identifier of the awesome person.
+    /// - `after`: Optional. The lapses and id of the last vocab study of the previous page, only those after it are retrieved.
+    /// - `limit`: The maximum number of vocab studies to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(Vec<(VocabStudy, Vocab)>)`: Each leech or suspended `VocabStudy` with its `Vocab`, the most lapses first,
+    ///   then by id.
+    /// - `Err(PalabrasError)`: If the database query fails.
+    fn get_leeches(
+        &self,
+        ap_id: i32,
+        after: Option<(i32, i32)>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError>;
+
+    /// Retrieves the vocab an awesome person studies, a page at a time.
+    ///
+    /// # Parameters
+    ///
+    /// - `ap_id`: The identifier of the awesome person.
+    /// - `filter`: The [`VocabStudyFilter`] each vocab must pass.
+    /// - `order`: The [`VocabStudyOrder`] of the vocab.
+    /// - `after`: Optional. The key of the last vocab study of the previous page, in the same order.
+    /// - `limit`: The maximum number of vocab studies to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(Vec<(VocabStudy, Vocab)>)`: Each `VocabStudy` with its `Vocab`, suspended ones included, in the order.
+    /// - `Err(PalabrasError)`: If the database query fails.
+    fn get_enrolled_vocab(
+        &self,
+        ap_id: i32,
+        filter: &VocabStudyFilter,
+        order: VocabStudyOrder,
+        after: Option<VocabStudyKey>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError>;
 
     /// Inserts a new `VocabStudy` record into the database.
     ///
@@ -93,15 +170,17 @@ pub trait VocabStudyRepository: Send + Sync {
     ///
     /// A `Result` containing either:
     /// - `Ok(VocabStudy)`: The newly created `VocabStudy`, including its database-assigned `id`.
-    /// - `Err(String)`: An error message string if the insert operation fails.
+    /// - `Err(PalabrasError)`: If the insert operation fails.
     ///
     /// # Errors
     ///
     /// Returns an error if there's an issue performing the insert operation, including connection problems
-    /// or violations of database constraints (e.g., unique constraints, foreign key constraints).
-    /// The error is returned as a `String`
-    /// describing the failure.
-    fn create_vocab_study(&self, new_vocab_study: &NewVocabStudy) -> Result<VocabStudy, String>;
+    /// or violations of database constraints (e.g., unique constraints, foreign key constraints),
+    /// which are reported as `PalabrasError::Conflict`.
+    fn create_vocab_study(
+        &self,
+        new_vocab_study: &NewVocabStudy,
+    ) -> Result<VocabStudy, PalabrasError>;
 
     /// Updates an existing `VocabStudy` record in the database.
     ///
@@ -118,14 +197,75 @@ pub trait VocabStudyRepository: Send + Sync {
     ///
     /// A `Result` containing either:
     /// - `Ok(usize)`: The number of records updated in the database, expected to be 1 when successful.
-    /// - `Err(String)`: An error message string if the update operation fails.
+    /// - `Err(PalabrasError)`: If the update operation fails.
     ///
     /// # Errors
     ///
     /// Returns an error if there's an issue performing the update operation, including connection problems,
-    /// attempting to update a record that does not exist, or violations of database constraints. The error
-    /// is returned as a `String` describing the failure.
-    fn update_vocab_study(&self, updating: VocabStudy) -> Result<usize, String>;
+    /// or violations of database constraints. Updating a record that does not exist is not an error, the
+    /// returned count is simply 0.
+    fn update_vocab_study(&self, updating: VocabStudy) -> Result<usize, PalabrasError>;
+
+    /// Replaces the notes of a vocab study in a single update, leaving its other columns as they are.
+    ///
+    /// Unlike [`VocabStudyRepository::update_vocab_study`] the record is not read first, so a grade
+    /// committed at the same time is not overwritten.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_study_id` - Primary key of the vocab study.
+    /// * `notes` - The new notes.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(VocabStudy)`: The updated vocab study.
+    /// - `Err(PalabrasError)`: `PalabrasError::NotFound` if the vocab study does not exist, or another error
+    ///   if the update fails.
+    fn update_user_notes(
+        &self,
+        vocab_study_id: i32,
+        notes: &str,
+    ) -> Result<VocabStudy, PalabrasError>;
+
+    /// Replaces the user's own hint of a vocab study in a single update, see
+    /// [`VocabStudyRepository::update_user_notes`].
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_study_id` - Primary key of the vocab study.
+    /// * `hint` - The new hint.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(VocabStudy)`: The updated vocab study.
+    /// - `Err(PalabrasError)`: `PalabrasError::NotFound` if the vocab study does not exist, or another error
+    ///   if the update fails.
+    fn update_user_hint(
+        &self,
+        vocab_study_id: i32,
+        hint: &str,
+    ) -> Result<VocabStudy, PalabrasError>;
+
+    /// Suspends or unsuspends a vocab study in a single update, see [`VocabStudyRepository::update_user_notes`].
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_study_id` - Primary key of the vocab study.
+    /// * `is_suspended` - Whether the vocab study is suspended.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(VocabStudy)`: The updated vocab study.
+    /// - `Err(PalabrasError)`: `PalabrasError::NotFound` if the vocab study does not exist, or another error
+    ///   if the update fails.
+    fn update_suspended(
+        &self,
+        vocab_study_id: i32,
+        is_suspended: bool,
+    ) -> Result<VocabStudy, PalabrasError>;
 }
 
 pub struct DbVocabStudyRepository;
@@ -138,12 +278,13 @@ impl VocabStudyRepository for DbVocabStudyRepository {
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests for this module.
-    fn get_vocab_study_by_id(&self, vocab_study_id: i32) -> Result<VocabStudy, String> {
+    fn get_vocab_study_by_id(&self, vocab_study_id: i32) -> Result<VocabStudy, PalabrasError> {
         let mut conn = get_connection()?;
         vocab_study
             .find(vocab_study_id)
             .first(&mut conn)
-            .map_err(|err| error_to_string(err))
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("vocab study", vocab_study_id))
     }
 
     /// Implementation, see trait for details [`VocabStudyRepository::get_vocab_study_by_foreign_refs`]
@@ -154,14 +295,14 @@ impl VocabStudyRepository for DbVocabStudyRepository {
         &self,
         v_id: i32,
         ap_id: i32,
-    ) -> Result<Option<VocabStudy>, String> {
+    ) -> Result<Option<VocabStudy>, PalabrasError> {
         let mut conn = get_connection()?;
 
         vocab_study
             .filter(vocab_id.eq(v_id).and(awesome_person_id.eq(ap_id)))
             .first(&mut conn)
             .optional()
-            .map_err(|err| error_to_string(err))
+            .map_err(PalabrasError::from)
     }
 
     /// Implementation, see trait for details [`VocabStudyRepository::get_study_set`]
@@ -172,15 +313,136 @@ impl VocabStudyRepository for DbVocabStudyRepository {
         &self,
         ap_id: i32,
         max_words: i32,
-    ) -> Result<Vec<(VocabStudy, Vocab)>, String> {
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        let results = vocab_study
+            .inner_join(vocab)
+            .filter(awesome_person_id.eq(ap_id))
+            .filter(num_learning_words.le(max_words))
+            .filter(suspended.eq(false))
+            .order(id)
+            .load::<(VocabStudy, Vocab)>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    /// Implementation, see trait for details [`VocabStudyRepository::get_deck_study_set`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_deck_study_set(
+        &self,
+        ap_id: i32,
+        max_words: i32,
+        deck_id: i32,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
         let mut conn = get_connection()?;
 
         let results = vocab_study
             .inner_join(vocab)
             .filter(awesome_person_id.eq(ap_id))
             .filter(num_learning_words.le(max_words))
-            .load::<(VocabStudy, Vocab)>(&mut conn)
-            .map_err(|err| error_to_string(err))?;
+            .filter(suspended.eq(false))
+            .filter(
+                vocab_id.eq_any(
+                    deck_vocab::table
+                        .filter(deck_vocab::deck_id.eq(deck_id))
+                        .select(deck_vocab::vocab_id),
+                ),
+            )
+            .order(id)
+            .load::<(VocabStudy, Vocab)>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    /// Implementation, see trait for details [`VocabStudyRepository::get_leeches`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_leeches(
+        &self,
+        ap_id: i32,
+        after: Option<(i32, i32)>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        let mut query = vocab_study
+            .inner_join(vocab)
+            .filter(awesome_person_id.eq(ap_id))
+            .filter(leech.or(suspended))
+            .into_boxed();
+        if let Some((after_lapses, after_id)) = after {
+            query = query.filter(
+                lapses
+                    .lt(after_lapses)
+                    .or(lapses.eq(after_lapses).and(id.gt(after_id))),
+            );
+        }
+        let results = query
+            .order((lapses.desc(), id))
+            .limit(limit)
+            .load::<(VocabStudy, Vocab)>(&mut conn)?;
+
+        Ok(results)
+    }
+
+    /// Implementation, see trait for details [`VocabStudyRepository::get_enrolled_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn get_enrolled_vocab(
+        &self,
+        ap_id: i32,
+        filter: &VocabStudyFilter,
+        order: VocabStudyOrder,
+        after: Option<VocabStudyKey>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        let mut query = vocab_study
+            .inner_join(vocab)
+            .filter(awesome_person_id.eq(ap_id))
+            .into_boxed();
+        if let Some(known) = filter.well_known {
+            query = query.filter(well_known.eq(known));
+        }
+        if let Some(lang_code) = &filter.learning_lang_code {
+            query = query.filter(learning_lang_code.eq(lang_code.clone()));
+        }
+        if let Some(part_of_speech) = &filter.pos {
+            query = query.filter(lower(pos).eq(part_of_speech.trim().to_lowercase()));
+        }
+
+        // Each order is keyed by its value and the id, a page starts right after the key of the previous one.
+        let weakness = || coalesce(percentage_correct, 0.0);
+        let recency = || coalesce(last_tested, DateTime::<Utc>::UNIX_EPOCH);
+        query = match after {
+            Some(VocabStudyKey::Weakness(key, after_id)) => query.filter(
+                weakness()
+                    .gt(key)
+                    .or(weakness().eq(key).and(id.gt(after_id))),
+            ),
+            Some(VocabStudyKey::Recency(key, after_id)) => {
+                query.filter(recency().lt(key).or(recency().eq(key).and(id.gt(after_id))))
+            }
+            Some(VocabStudyKey::Alphabetic(key, after_id)) => query.filter(
+                learning_lang
+                    .gt(key.clone())
+                    .or(learning_lang.eq(key).and(id.gt(after_id))),
+            ),
+            None => query,
+        };
+        query = match order {
+            VocabStudyOrder::Weakness => query.order((weakness().asc(), id.asc())),
+            VocabStudyOrder::Recency => query.order((recency().desc(), id.asc())),
+            VocabStudyOrder::Alphabetic => query.order((learning_lang.asc(), id.asc())),
+        };
+
+        let results = query.limit(limit).load::<(VocabStudy, Vocab)>(&mut conn)?;
 
         Ok(results)
     }
@@ -189,12 +451,14 @@ impl VocabStudyRepository for DbVocabStudyRepository {
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests for this module.
-    fn create_vocab_study(&self, new_vocab_study: &NewVocabStudy) -> Result<VocabStudy, String> {
+    fn create_vocab_study(
+        &self,
+        new_vocab_study: &NewVocabStudy,
+    ) -> Result<VocabStudy, PalabrasError> {
         let mut conn = get_connection()?;
         let inserted = diesel::insert_into(vocab_study)
             .values(new_vocab_study)
-            .get_result(&mut conn)
-            .map_err(|err| error_to_string(err))?;
+            .get_result(&mut conn)?;
 
         Ok(inserted)
     }
@@ -203,14 +467,67 @@ impl VocabStudyRepository for DbVocabStudyRepository {
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests for this module.
-    fn update_vocab_study(&self, updating: VocabStudy) -> Result<usize, String> {
+    fn update_vocab_study(&self, updating: VocabStudy) -> Result<usize, PalabrasError> {
         let mut conn = get_connection()?;
 
         let updated = diesel::update(vocab_study.find(updating.id))
             .set(&updating)
-            .execute(&mut conn)
-            .map_err(|e| e.to_string())?;
+            .execute(&mut conn)?;
 
         Ok(updated)
     }
+
+    /// Implementation, see trait for details [`VocabStudyRepository::update_user_notes`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn update_user_notes(
+        &self,
+        vocab_study_id: i32,
+        notes: &str,
+    ) -> Result<VocabStudy, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        diesel::update(vocab_study.find(vocab_study_id))
+            .set(user_notes.eq(notes))
+            .get_result(&mut conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("vocab study", vocab_study_id))
+    }
+
+    /// Implementation, see trait for details [`VocabStudyRepository::update_user_hint`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn update_user_hint(
+        &self,
+        vocab_study_id: i32,
+        hint: &str,
+    ) -> Result<VocabStudy, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        diesel::update(vocab_study.find(vocab_study_id))
+            .set(user_hint.eq(hint))
+            .get_result(&mut conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("vocab study", vocab_study_id))
+    }
+
+    /// Implementation, see trait for details [`VocabStudyRepository::update_suspended`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests for this module.
+    fn update_suspended(
+        &self,
+        vocab_study_id: i32,
+        is_suspended: bool,
+    ) -> Result<VocabStudy, PalabrasError> {
+        let mut conn = get_connection()?;
+
+        diesel::update(vocab_study.find(vocab_study_id))
+            .set(suspended.eq(is_suspended))
+            .get_result(&mut conn)
+            .optional()?
+            .ok_or_else(|| PalabrasError::not_found("vocab study", vocab_study_id))
+    }
 }
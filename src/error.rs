@@ -0,0 +1,141 @@
+use async_graphql::ErrorExtensions;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use std::fmt;
+
+/// The error type shared by the data access, service and GraphQL layers.
+///
+/// Each variant describes a category of failure that a caller may want to react to differently,
+/// for instance showing a "not found" page rather than retrying after a connection failure.
+/// GraphQL clients see the category as `extensions.code` on the returned error, see [`PalabrasError::code`].
+///
+/// # Variants
+///
+/// - `NotFound`: The requested `entity` with the given `id` does not exist.
+/// - `Validation`: The request itself was invalid, e.g. a negative limit.
+/// - `Conflict`: The change clashes with existing data, e.g. a unique constraint.
+/// - `Database`: The database could not be reached or the query failed.
+/// - `Config`: A configuration file or setting is missing or malformed.
+/// - `Io`: Reading or writing a file failed.
+///
+/// # Example
+///
+/// ```
+/// use palabras::error::PalabrasError;
+///
+/// let err = PalabrasError::not_found("awesome person", 42);
+/// assert_eq!(err.code(), "NOT_FOUND");
+/// assert_eq!(err.to_string(), "awesome person with id 42 was not found");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum PalabrasError {
+    NotFound { entity: &'static str, id: String },
+    Validation(String),
+    Conflict(String),
+    Database(String),
+    Config(String),
+    Io(String),
+}
+
+impl PalabrasError {
+    /// Convenience constructor for the `NotFound` variant.
+    pub fn not_found(entity: &'static str, id: impl ToString) -> Self {
+        PalabrasError::NotFound {
+            entity,
+            id: id.to_string(),
+        }
+    }
+
+    /// Convenience constructor for the `NotFound` variant of a look up by a secret, e.g. a look up code.
+    /// The secret is left out of the message as the message reaches GraphQL clients.
+    pub fn not_found_by_secret(entity: &'static str) -> Self {
+        PalabrasError::NotFound {
+            entity,
+            id: String::new(),
+        }
+    }
+
+    /// The machine readable code exposed to GraphQL clients as `extensions.code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PalabrasError::NotFound { .. } => "NOT_FOUND",
+            PalabrasError::Validation(_) => "VALIDATION",
+            PalabrasError::Conflict(_) => "CONFLICT",
+            PalabrasError::Database(_) => "DATABASE",
+            PalabrasError::Config(_) => "CONFIG",
+            PalabrasError::Io(_) => "IO",
+        }
+    }
+}
+
+impl fmt::Display for PalabrasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PalabrasError::NotFound { entity, id } if id.is_empty() => {
+                write!(f, "{} was not found", entity)
+            }
+            PalabrasError::NotFound { entity, id } => {
+                write!(f, "{} with id {} was not found", entity, id)
+            }
+            PalabrasError::Validation(msg) => write!(f, "Invalid request: {}", msg),
+            PalabrasError::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            PalabrasError::Database(msg) => write!(f, "Database error: {}", msg),
+            PalabrasError::Config(msg) => write!(f, "Configuration error: {}", msg),
+            PalabrasError::Io(msg) => write!(f, "IO error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PalabrasError {}
+
+/// Maps Diesel errors onto the closest category. Constraint violations are reported as
+/// conflicts or validation problems, everything else is a database failure.
+impl From<DieselError> for PalabrasError {
+    fn from(err: DieselError) -> Self {
+        match err {
+            DieselError::NotFound => PalabrasError::not_found("record", "unknown"),
+            DieselError::DatabaseError(
+                DatabaseErrorKind::UniqueViolation | DatabaseErrorKind::ForeignKeyViolation,
+                ref info,
+            ) => PalabrasError::Conflict(info.message().to_string()),
+            DieselError::DatabaseError(
+                DatabaseErrorKind::CheckViolation | DatabaseErrorKind::NotNullViolation,
+                ref info,
+            ) => PalabrasError::Validation(info.message().to_string()),
+            _ => PalabrasError::Database(err.to_string()),
+        }
+    }
+}
+
+impl From<diesel::r2d2::PoolError> for PalabrasError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        PalabrasError::Database(err.to_string())
+    }
+}
+
+impl From<std::io::Error> for PalabrasError {
+    fn from(err: std::io::Error) -> Self {
+        PalabrasError::Io(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for PalabrasError {
+    fn from(err: serde_json::Error) -> Self {
+        PalabrasError::Config(err.to_string())
+    }
+}
+
+impl From<regex::Error> for PalabrasError {
+    fn from(err: regex::Error) -> Self {
+        PalabrasError::Config(err.to_string())
+    }
+}
+
+/// Converts the error into an `async_graphql::Error` carrying the code in its extensions.
+///
+/// Resolvers use it with `map_err(|err| err.extend())` so clients can branch on `extensions.code`
+/// instead of parsing the message.
+impl ErrorExtensions for PalabrasError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(self.to_string()).extend_with(|_, e| e.set("code", self.code()))
+    }
+}
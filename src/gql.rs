@@ -1,2 +1,11 @@
+pub mod daily_quota;
+pub mod decks;
+pub mod leeches;
+pub mod my_vocab;
+pub mod paging;
+pub mod root;
 pub mod router;
+pub mod search;
 pub mod studies;
+pub mod study_session;
+pub mod tags;
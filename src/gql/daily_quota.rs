@@ -0,0 +1,45 @@
+use crate::dal::db_connection::run_blocking;
+use crate::gql::studies::AwesomeProfile;
+use crate::sl::daily_quota::{DailyLimits, DailyQuotaService};
+use async_graphql::*;
+
+/// GraphQL Mutations of daily limits
+#[derive(Default)]
+pub struct DailyLimitsMutation;
+
+#[Object]
+impl DailyLimitsMutation {
+    /// Sets an awesome person's daily limits. The study list introduces at most `new_per_day` new words,
+    /// never answered before, and `reviews_per_day` reviews each day, counted against the answers given since
+    /// the start of the day in UTC.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person.
+    /// * `new_per_day` - The maximum number of new words each day.
+    /// * `reviews_per_day` - The maximum number of reviews each day.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the updated `AwesomeProfile` with the new words and reviews remaining today,
+    /// or an error on failure, e.g. `VALIDATION` for a negative limit.
+    async fn update_daily_limits(
+        &self,
+        awesome_id: i32,
+        new_per_day: i32,
+        reviews_per_day: i32,
+    ) -> Result<AwesomeProfile> {
+        let (awesome_person, quota) = run_blocking(move || {
+            let service = DailyQuotaService::instance();
+            let awesome_person =
+                service.update_daily_limits(awesome_id, new_per_day, reviews_per_day)?;
+            let quota = service.get_daily_quota(awesome_id)?;
+
+            Ok((awesome_person, quota))
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(AwesomeProfile::from(awesome_person).with_quota(&quota))
+    }
+}
@@ -0,0 +1,188 @@
+use crate::dal::db_connection::run_blocking;
+use crate::models::{Deck, NewDeck};
+use crate::sl::decks::{DeckService, ManageDecks};
+use async_graphql::*;
+
+/// A deck of vocab of an awesome person, studied with the `deckId` of `getStudyList`.
+///
+/// # Fields
+///
+/// - `id`: The identifier of the deck.
+/// - `owner_id`: The identifier of the awesome person the deck belongs to.
+/// - `name`: The name of the deck.
+/// - `description`: The description of the deck.
+/// - `known_lang_code`: The language known, e.g. `en`.
+/// - `learning_lang_code`: The language learned, e.g. `es`.
+/// - `vocab_count`: The number of vocab in the deck.
+/// - `created`: The timestamp the deck was created.
+#[derive(Clone)]
+pub struct StudyDeck {
+    pub id: i32,
+    pub owner_id: i32,
+    pub name: String,
+    pub description: String,
+    pub known_lang_code: String,
+    pub learning_lang_code: String,
+    pub vocab_count: i64,
+    pub created: String,
+}
+
+impl From<(Deck, i64)> for StudyDeck {
+    fn from((deck, vocab_count): (Deck, i64)) -> Self {
+        StudyDeck {
+            id: deck.id,
+            owner_id: deck.owner_id,
+            name: deck.name,
+            description: deck.description.unwrap_or_default(),
+            known_lang_code: deck.known_lang_code,
+            learning_lang_code: deck.learning_lang_code,
+            vocab_count,
+            created: deck.created.format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+        }
+    }
+}
+
+#[Object]
+impl StudyDeck {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    async fn owner_id(&self) -> i32 {
+        self.owner_id
+    }
+
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn description(&self) -> String {
+        self.description.clone()
+    }
+
+    async fn known_lang_code(&self) -> String {
+        self.known_lang_code.clone()
+    }
+
+    async fn learning_lang_code(&self) -> String {
+        self.learning_lang_code.clone()
+    }
+
+    async fn vocab_count(&self) -> i64 {
+        self.vocab_count
+    }
+
+    async fn created(&self) -> String {
+        self.created.clone()
+    }
+}
+
+/// GraphQL Queries of decks
+#[derive(Default)]
+pub struct DeckQuery;
+
+#[Object]
+impl DeckQuery {
+    /// Retrieves the decks of an awesome person, by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping each `StudyDeck` with the number of vocab in it, or an error on failure,
+    /// e.g. `NOT_FOUND` for an unknown awesome person.
+    async fn get_decks(&self, awesome_id: i32) -> Result<Vec<StudyDeck>> {
+        let decks = run_blocking(move || DeckService::instance().get_decks(awesome_id))
+            .await
+            .map_err(|err| err.extend())?;
+
+        Ok(decks.into_iter().map(StudyDeck::from).collect())
+    }
+}
+
+/// GraphQL Mutations of decks
+#[derive(Default)]
+pub struct DeckMutation;
+
+#[Object]
+impl DeckMutation {
+    /// Creates a deck of vocab for an awesome person.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person the deck belongs to.
+    /// * `name` - The name of the deck, unique for the awesome person.
+    /// * `description` - Optional. What the deck is about.
+    /// * `known_lang_code` - The language known, e.g. `en`.
+    /// * `learning_lang_code` - The language learned, e.g. `es`. Only vocab of both languages can be added.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the empty `StudyDeck`, or an error on failure,
+    /// e.g. `CONFLICT` when the awesome person already has a deck of that name.
+    async fn create_deck(
+        &self,
+        awesome_id: i32,
+        name: String,
+        description: Option<String>,
+        known_lang_code: String,
+        learning_lang_code: String,
+    ) -> Result<StudyDeck> {
+        let deck = run_blocking(move || {
+            DeckService::instance().create_deck(NewDeck {
+                owner_id: awesome_id,
+                name,
+                description,
+                known_lang_code,
+                learning_lang_code,
+            })
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok((deck, 0).into())
+    }
+
+    /// Adds vocab to a deck, vocab already in it is skipped. The owner of the deck starts studying
+    /// any of the vocab that is new to them.
+    ///
+    /// # Arguments
+    ///
+    /// * `deck_id` - The unique identifier of the deck.
+    /// * `vocab_ids` - The unique identifiers of the vocab to add.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the number of vocab added, or an error on failure,
+    /// e.g. `VALIDATION` for a vocab in other languages than the deck.
+    async fn add_to_deck(&self, deck_id: i32, vocab_ids: Vec<i32>) -> Result<i32> {
+        let num_added =
+            run_blocking(move || DeckService::instance().add_to_deck(deck_id, &vocab_ids))
+                .await
+                .map_err(|err| err.extend())?;
+
+        Ok(num_added as i32)
+    }
+
+    /// Removes vocab from a deck, the awesome person keeps studying it outside the deck.
+    ///
+    /// # Arguments
+    ///
+    /// * `deck_id` - The unique identifier of the deck.
+    /// * `vocab_ids` - The unique identifiers of the vocab to remove.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the number of vocab removed, or an error on failure,
+    /// e.g. `NOT_FOUND` for an unknown deck.
+    async fn remove_from_deck(&self, deck_id: i32, vocab_ids: Vec<i32>) -> Result<i32> {
+        let num_removed =
+            run_blocking(move || DeckService::instance().remove_from_deck(deck_id, &vocab_ids))
+                .await
+                .map_err(|err| err.extend())?;
+
+        Ok(num_removed as i32)
+    }
+}
@@ -0,0 +1,225 @@
+use crate::dal::db_connection::run_blocking;
+use crate::gql::paging::{decode_after, keyset_connection, page_size};
+use crate::models::{Vocab, VocabStudy};
+use crate::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use crate::sl::leeches::{LeechService, ManageLeeches};
+use async_graphql::connection::{Connection, OpaqueCursor};
+use async_graphql::*;
+
+/// Represents a leech, a vocab missed so often that it broke the leech rule, or a suspended vocab.
+///
+/// # Fields
+///
+/// - `vocab_id`: The identifier of the vocab.
+/// - `vocab_study_id`: The identifier of the vocab study.
+/// - `learning`: The vocab in the learning language.
+/// - `first_lang`: The vocab in the known language.
+/// - `hint`: The hint of the vocab, shared by everyone studying it.
+/// - `user_notes`: The awesome person's notes for the vocab.
+/// - `user_hint`: The awesome person's own hint for the vocab.
+/// - `attempts`: The number of answers given.
+/// - `correct_attempts`: The number of answers given with a perfect match.
+/// - `lapses`: The number of answers in a row that were not a perfect match.
+/// - `percentage_correct`: The percentage correct of the vocab study.
+/// - `leech`: Whether the vocab study broke the leech rule.
+/// - `suspended`: Whether the vocab study is left out of the study list.
+#[derive(Clone)]
+pub struct Leech {
+    pub vocab_id: i32,
+    pub vocab_study_id: i32,
+    pub learning: String,
+    pub first_lang: String,
+    pub hint: String,
+    pub user_notes: String,
+    pub user_hint: String,
+    pub attempts: i32,
+    pub correct_attempts: i32,
+    pub lapses: i32,
+    pub percentage_correct: f64,
+    pub leech: bool,
+    pub suspended: bool,
+}
+
+impl From<(VocabStudy, Vocab)> for Leech {
+    fn from((vocab_study, vocab): (VocabStudy, Vocab)) -> Self {
+        Leech {
+            vocab_id: vocab.id,
+            vocab_study_id: vocab_study.id,
+            learning: vocab.learning_lang,
+            first_lang: vocab.first_lang,
+            hint: vocab.hint.unwrap_or_default(),
+            user_notes: vocab_study.user_notes.unwrap_or_default(),
+            user_hint: vocab_study.user_hint.unwrap_or_default(),
+            attempts: vocab_study.attempts.unwrap_or_default(),
+            correct_attempts: vocab_study.correct_attempts.unwrap_or_default(),
+            lapses: vocab_study.lapses,
+            percentage_correct: vocab_study.percentage_correct.unwrap_or_default(),
+            leech: vocab_study.leech,
+            suspended: vocab_study.suspended,
+        }
+    }
+}
+
+#[Object]
+impl Leech {
+    async fn vocab_id(&self) -> i32 {
+        self.vocab_id
+    }
+
+    async fn vocab_study_id(&self) -> i32 {
+        self.vocab_study_id
+    }
+
+    async fn learning(&self) -> String {
+        self.learning.clone()
+    }
+
+    async fn first_lang(&self) -> String {
+        self.first_lang.clone()
+    }
+
+    async fn hint(&self) -> String {
+        self.hint.clone()
+    }
+
+    async fn user_notes(&self) -> String {
+        self.user_notes.clone()
+    }
+
+    async fn user_hint(&self) -> String {
+        self.user_hint.clone()
+    }
+
+    async fn attempts(&self) -> i32 {
+        self.attempts
+    }
+
+    async fn correct_attempts(&self) -> i32 {
+        self.correct_attempts
+    }
+
+    async fn lapses(&self) -> i32 {
+        self.lapses
+    }
+
+    async fn percentage_correct(&self) -> f64 {
+        self.percentage_correct
+    }
+
+    async fn leech(&self) -> bool {
+        self.leech
+    }
+
+    async fn suspended(&self) -> bool {
+        self.suspended
+    }
+}
+
+/// GraphQL Queries of leeches
+#[derive(Default)]
+pub struct LeechQuery;
+
+#[Object]
+impl LeechQuery {
+    /// Retrieves an awesome person's leeches, the vocab missed so often that they broke the leech rule
+    /// of the `[leech]` configuration, and any other suspended vocab. Suspended vocab is left out of the study list.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person.
+    /// * `first` - Optional. The maximum number of leeches in the page, see [`page_size`].
+    /// * `after` - Optional. The cursor of the last leech of the previous page.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping a connection of each `Leech`, the most lapses first, or an error on failure,
+    /// e.g. `NOT_FOUND` for an unknown awesome person.
+    async fn get_leeches(
+        &self,
+        awesome_id: i32,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> Result<Connection<OpaqueCursor<(i32, i32)>, Leech>> {
+        let size = page_size(first).map_err(|err| err.extend())?;
+        let after = decode_after::<OpaqueCursor<(i32, i32)>>(after).map_err(|err| err.extend())?;
+        let has_previous_page = after.is_some();
+
+        let leeches = run_blocking(move || {
+            LeechService::instance().get_leeches(
+                awesome_id,
+                after.map(|after| after.0),
+                size as i64 + 1,
+            )
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(keyset_connection(
+            leeches,
+            size,
+            has_previous_page,
+            |(vs, _)| OpaqueCursor((vs.lapses, vs.id)),
+            Leech::from,
+        ))
+    }
+}
+
+/// GraphQL Mutations of leeches
+#[derive(Default)]
+pub struct LeechMutation;
+
+#[Object]
+impl LeechMutation {
+    /// Unsuspends a leech, it is back in the study list. It stays a leech and is not suspended again.
+    ///
+    /// # Arguments
+    ///
+    /// * `vocab_study_id` - The unique identifier of the vocab study.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the updated `Leech`, or an error on failure,
+    /// e.g. `NOT_FOUND` for an unknown vocab study.
+    async fn unsuspend_leech(&self, vocab_study_id: i32) -> Result<Leech> {
+        let leech = run_blocking(move || {
+            LeechService::instance().suspend_vocab_study(vocab_study_id, false)?;
+            VocabFuzzyMatch::instance().get_vocab_stats(vocab_study_id)
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(leech.into())
+    }
+
+    /// Edits a leech to make it easier to remember.
+    ///
+    /// # Arguments
+    ///
+    /// * `vocab_study_id` - The unique identifier of the vocab study.
+    /// * `user_notes` - Optional. The awesome person's notes, empty to remove them.
+    /// * `hint` - Optional. The awesome person's own hint, empty to remove it. The hint of the vocab shared by
+    ///   everyone studying it is not changed.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the updated `Leech`, or an error on failure,
+    /// e.g. `NOT_FOUND` for an unknown vocab study.
+    async fn update_leech(
+        &self,
+        vocab_study_id: i32,
+        user_notes: Option<String>,
+        hint: Option<String>,
+    ) -> Result<Leech> {
+        let leech = run_blocking(move || {
+            LeechService::instance().update_leech(
+                vocab_study_id,
+                user_notes.as_deref(),
+                hint.as_deref(),
+            )
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(leech.into())
+    }
+}
@@ -0,0 +1,274 @@
+use crate::dal::db_connection::run_blocking;
+use crate::error::PalabrasError;
+use crate::gql::paging::{decode_after, keyset_connection, page_size};
+use crate::gql::studies::format_tested;
+use crate::models::{Vocab, VocabStudy, VocabStudyFilter, VocabStudyKey, VocabStudyOrder};
+use crate::sl::my_vocab::{MyVocab, MyVocabService};
+use async_graphql::connection::{Connection, OpaqueCursor};
+use async_graphql::*;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+/// The order of an awesome person's vocab, see [`VocabStudyOrder`].
+///
+/// - `Weakness`: The lowest percentage correct first.
+/// - `Recency`: The vocab tested last first.
+/// - `Alphabetic`: By the vocab in the learning language.
+#[derive(Enum, Copy, Clone, Default, Eq, PartialEq)]
+pub enum MyVocabSort {
+    #[default]
+    Weakness,
+    Recency,
+    Alphabetic,
+}
+
+impl From<MyVocabSort> for VocabStudyOrder {
+    fn from(sort: MyVocabSort) -> Self {
+        match sort {
+            MyVocabSort::Weakness => VocabStudyOrder::Weakness,
+            MyVocabSort::Recency => VocabStudyOrder::Recency,
+            MyVocabSort::Alphabetic => VocabStudyOrder::Alphabetic,
+        }
+    }
+}
+
+/// Filters of an awesome person's vocab, see [`VocabStudyFilter`].
+///
+/// # Fields
+///
+/// - `well_known`: Optional. Only the vocab that is, or is not, well known.
+/// - `lang_code`: Optional. Only vocab of this learning language.
+/// - `pos`: Optional. Only vocab with this part of speech.
+#[derive(InputObject, Default)]
+pub struct MyVocabFilter {
+    pub well_known: Option<bool>,
+    pub lang_code: Option<String>,
+    pub pos: Option<String>,
+}
+
+impl From<MyVocabFilter> for VocabStudyFilter {
+    fn from(filter: MyVocabFilter) -> Self {
+        VocabStudyFilter {
+            well_known: filter.well_known,
+            learning_lang_code: filter.lang_code,
+            pos: filter.pos,
+        }
+    }
+}
+
+/// The cursor of a vocab in `myVocab`, the [`VocabStudyKey`] with the time in microseconds.
+#[derive(Serialize, Deserialize)]
+pub enum EnrolledVocabCursor {
+    Weakness(f64, i32),
+    Recency(i64, i32),
+    Alphabetic(String, i32),
+}
+
+impl From<VocabStudyKey> for EnrolledVocabCursor {
+    fn from(key: VocabStudyKey) -> Self {
+        match key {
+            VocabStudyKey::Weakness(percentage, id) => {
+                EnrolledVocabCursor::Weakness(percentage, id)
+            }
+            VocabStudyKey::Recency(tested, id) => {
+                EnrolledVocabCursor::Recency(tested.timestamp_micros(), id)
+            }
+            VocabStudyKey::Alphabetic(learning, id) => {
+                EnrolledVocabCursor::Alphabetic(learning, id)
+            }
+        }
+    }
+}
+
+impl TryFrom<EnrolledVocabCursor> for VocabStudyKey {
+    type Error = PalabrasError;
+
+    fn try_from(cursor: EnrolledVocabCursor) -> Result<Self, Self::Error> {
+        Ok(match cursor {
+            EnrolledVocabCursor::Weakness(percentage, id) => {
+                VocabStudyKey::Weakness(percentage, id)
+            }
+            EnrolledVocabCursor::Recency(micros, id) => VocabStudyKey::Recency(
+                DateTime::from_timestamp_micros(micros).ok_or_else(|| {
+                    PalabrasError::Validation(format!("invalid cursor time: {}", micros))
+                })?,
+                id,
+            ),
+            EnrolledVocabCursor::Alphabetic(learning, id) => {
+                VocabStudyKey::Alphabetic(learning, id)
+            }
+        })
+    }
+}
+
+/// A vocab an awesome person studies with its progress, the same statistics as [`crate::gql::studies::VocabStats`].
+///
+/// # Fields
+///
+/// - `vocab_id`: The identifier of the vocab.
+/// - `vocab_study_id`: The identifier of the vocab study.
+/// - `learning`: The vocab in the learning language.
+/// - `first_lang`: The vocab in the known language.
+/// - `pos`: The part of speech of the vocab.
+/// - `attempts`: The number of answers given.
+/// - `correct_attempts`: The number of answers given with a perfect match.
+/// - `percentage_correct`: The percentage correct of the vocab study.
+/// - `last_change`: The most recent change in the percentage correct.
+/// - `last_tested`: When the vocab was last answered, empty when never.
+/// - `well_known`: Whether the vocab is well known.
+/// - `notes`: The awesome person's notes for the vocab.
+/// - `suspended`: Whether the vocab study is left out of the study list.
+#[derive(Clone)]
+pub struct EnrolledVocab {
+    pub vocab_id: i32,
+    pub vocab_study_id: i32,
+    pub learning: String,
+    pub first_lang: String,
+    pub pos: String,
+    pub attempts: i32,
+    pub correct_attempts: i32,
+    pub percentage_correct: f64,
+    pub last_change: f64,
+    pub last_tested: String,
+    pub well_known: bool,
+    pub notes: String,
+    pub suspended: bool,
+}
+
+impl From<(VocabStudy, Vocab)> for EnrolledVocab {
+    fn from((vocab_study, vocab): (VocabStudy, Vocab)) -> Self {
+        EnrolledVocab {
+            vocab_id: vocab.id,
+            vocab_study_id: vocab_study.id,
+            learning: vocab.learning_lang,
+            first_lang: vocab.first_lang,
+            pos: vocab.pos.unwrap_or_default(),
+            attempts: vocab_study.attempts.unwrap_or_default(),
+            correct_attempts: vocab_study.correct_attempts.unwrap_or_default(),
+            percentage_correct: vocab_study.percentage_correct.unwrap_or_default(),
+            last_change: vocab_study.last_change.unwrap_or_default(),
+            last_tested: format_tested(vocab_study.last_tested),
+            well_known: vocab_study.well_known,
+            notes: vocab_study.user_notes.unwrap_or_default(),
+            suspended: vocab_study.suspended,
+        }
+    }
+}
+
+#[Object]
+impl EnrolledVocab {
+    async fn vocab_id(&self) -> i32 {
+        self.vocab_id
+    }
+
+    async fn vocab_study_id(&self) -> i32 {
+        self.vocab_study_id
+    }
+
+    async fn learning(&self) -> String {
+        self.learning.clone()
+    }
+
+    async fn first_lang(&self) -> String {
+        self.first_lang.clone()
+    }
+
+    async fn pos(&self) -> String {
+        self.pos.clone()
+    }
+
+    async fn attempts(&self) -> i32 {
+        self.attempts
+    }
+
+    async fn correct_attempts(&self) -> i32 {
+        self.correct_attempts
+    }
+
+    async fn percentage_correct(&self) -> f64 {
+        self.percentage_correct
+    }
+
+    async fn last_change(&self) -> f64 {
+        self.last_change
+    }
+
+    async fn last_tested(&self) -> String {
+        self.last_tested.clone()
+    }
+
+    async fn well_known(&self) -> bool {
+        self.well_known
+    }
+
+    async fn notes(&self) -> String {
+        self.notes.clone()
+    }
+
+    async fn suspended(&self) -> bool {
+        self.suspended
+    }
+}
+
+/// GraphQL Queries of the vocab an awesome person studies
+#[derive(Default)]
+pub struct MyVocabQuery;
+
+#[Object]
+impl MyVocabQuery {
+    /// Lists the vocab an awesome person studies with the progress of each, the same statistics as
+    /// `getVocabStats`, suspended vocab included.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person.
+    /// * `filter` - Optional, see [`MyVocabFilter`].
+    /// * `sort` - Optional. The order of the vocab, see [`MyVocabSort`], the weakest first by default.
+    /// * `first` - Optional. The maximum number of vocab in the page, see [`page_size`].
+    /// * `after` - Optional. The cursor of the last vocab of the previous page, in the same sort.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping a connection of each `EnrolledVocab` in the sort, or an error on failure,
+    /// e.g. `NOT_FOUND` for an unknown awesome person or `VALIDATION` for a cursor of another sort.
+    async fn my_vocab(
+        &self,
+        awesome_id: i32,
+        filter: Option<MyVocabFilter>,
+        sort: Option<MyVocabSort>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> Result<Connection<OpaqueCursor<EnrolledVocabCursor>, EnrolledVocab>> {
+        let size = page_size(first).map_err(|err| err.extend())?;
+        let after = decode_after::<OpaqueCursor<EnrolledVocabCursor>>(after)
+            .and_then(|after| {
+                after
+                    .map(|after| VocabStudyKey::try_from(after.0))
+                    .transpose()
+            })
+            .map_err(|err| err.extend())?;
+        let has_previous_page = after.is_some();
+        let order = VocabStudyOrder::from(sort.unwrap_or_default());
+        let filter = VocabStudyFilter::from(filter.unwrap_or_default());
+
+        let enrolled = run_blocking(move || {
+            MyVocabService::instance().get_enrolled_vocab(
+                awesome_id,
+                &filter,
+                order,
+                after,
+                size as i64 + 1,
+            )
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(keyset_connection(
+            enrolled,
+            size,
+            has_previous_page,
+            |(vs, v)| OpaqueCursor(EnrolledVocabCursor::from(VocabStudyKey::of(order, vs, v))),
+            EnrolledVocab::from,
+        ))
+    }
+}
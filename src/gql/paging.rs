@@ -0,0 +1,89 @@
+use crate::error::PalabrasError;
+use async_graphql::connection::{Connection, CursorType, Edge};
+use async_graphql::OutputType;
+
+// Relay style connections for the list queries. Lists are paged forward, `first` nodes after the `after`
+// cursor. A cursor is the ordering key of a node in its repository query, so a page starts right after the
+// last node of the previous one however the rows before it changed in the meantime.
+
+/// The number of nodes in a page when `first` is not given.
+pub static DEFAULT_PAGE_SIZE: usize = 20;
+
+/// The most nodes a page can have.
+pub static MAX_PAGE_SIZE: usize = 100;
+
+/// The number of nodes in a page, from the `first` argument of a connection.
+///
+/// # Errors
+///
+/// `PalabrasError::Validation` if `first` is less than 1 or more than [`MAX_PAGE_SIZE`].
+pub fn page_size(first: Option<i32>) -> Result<usize, PalabrasError> {
+    match first {
+        None => Ok(DEFAULT_PAGE_SIZE),
+        Some(first) if first >= 1 && first as usize <= MAX_PAGE_SIZE => Ok(first as usize),
+        Some(first) => Err(PalabrasError::Validation(format!(
+            "first must be from 1 to {}, found {}",
+            MAX_PAGE_SIZE, first
+        ))),
+    }
+}
+
+/// Decodes the `after` cursor of a connection.
+///
+/// # Errors
+///
+/// `PalabrasError::Validation` if the cursor was not returned by the same connection.
+pub fn decode_after<C: CursorType>(after: Option<String>) -> Result<Option<C>, PalabrasError> {
+    after
+        .map(|after| {
+            C::decode_cursor(&after)
+                .map_err(|_| PalabrasError::Validation(format!("invalid cursor: {}", after)))
+        })
+        .transpose()
+}
+
+/// Builds a page of a connection from the rows of a keyset query.
+///
+/// The rows are queried with one more row than the page size, an extra row means there is a next page.
+///
+/// # Parameters
+///
+/// * `rows` - The rows after the cursor, in the order of their keys.
+/// * `size` - The number of nodes in the page.
+/// * `has_previous_page` - Whether the page was asked for after a cursor.
+/// * `cursor` - The ordering key of a row, its cursor.
+/// * `node` - Converts a row to its node.
+///
+/// # Example
+///
+/// ```
+/// use async_graphql::connection::CursorType;
+/// use palabras::gql::paging::keyset_connection;
+///
+/// let page = keyset_connection(vec![4, 7, 9], 2, false, |id| *id, |id| id.to_string());
+/// assert!(page.has_next_page);
+/// assert_eq!(page.edges.len(), 2);
+/// assert_eq!(page.edges[1].cursor.encode_cursor(), "7");
+/// assert_eq!(page.edges[1].node, "7");
+/// ```
+pub fn keyset_connection<R, C, N>(
+    rows: Vec<R>,
+    size: usize,
+    has_previous_page: bool,
+    cursor: impl Fn(&R) -> C,
+    node: impl Fn(R) -> N,
+) -> Connection<C, N>
+where
+    C: CursorType + Send + Sync,
+    N: OutputType,
+{
+    let has_next_page = rows.len() > size;
+    let mut connection = Connection::new(has_previous_page, has_next_page);
+    connection.edges.extend(
+        rows.into_iter()
+            .take(size)
+            .map(|row| Edge::new(cursor(&row), node(row))),
+    );
+
+    connection
+}
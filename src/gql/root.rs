@@ -0,0 +1,33 @@
+use crate::gql::daily_quota::DailyLimitsMutation;
+use crate::gql::decks::{DeckMutation, DeckQuery};
+use crate::gql::leeches::{LeechMutation, LeechQuery};
+use crate::gql::my_vocab::MyVocabQuery;
+use crate::gql::search::SearchQuery;
+use crate::gql::studies::{StudyMutation, StudyQuery};
+use crate::gql::study_session::StudySessionMutation;
+use crate::gql::tags::{TagMutation, TagQuery};
+use async_graphql::MergedObject;
+
+// The schema is merged from the objects of each service, the fields of each are in its module.
+
+/// GraphQL Queries
+#[derive(MergedObject, Default)]
+pub struct QueryRoot(
+    StudyQuery,
+    MyVocabQuery,
+    LeechQuery,
+    DeckQuery,
+    TagQuery,
+    SearchQuery,
+);
+
+/// GraphQL Mutations
+#[derive(MergedObject, Default)]
+pub struct MutationRoot(
+    StudyMutation,
+    DailyLimitsMutation,
+    LeechMutation,
+    DeckMutation,
+    TagMutation,
+    StudySessionMutation,
+);
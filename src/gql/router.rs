@@ -1,4 +1,4 @@
-use crate::gql::studies::{MutationRoot, QueryRoot};
+use crate::gql::root::{MutationRoot, QueryRoot};
 use async_graphql::{http::GraphiQLSource, EmptySubscription, Schema};
 use async_graphql_axum::GraphQL;
 use axum::{
@@ -26,7 +26,12 @@ async fn graphiql() -> impl IntoResponse {
 /// * `listener` - A `TcpListener` that the server will accept connections on.
 ///
 pub async fn start_axum(listener: TcpListener) {
-    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription).finish();
+    let schema = Schema::build(
+        QueryRoot::default(),
+        MutationRoot::default(),
+        EmptySubscription,
+    )
+    .finish();
 
     let app = Router::new().route("/gql", get(graphiql).post_service(GraphQL::new(schema)));
 
@@ -0,0 +1,145 @@
+use crate::dal::db_connection::run_blocking;
+use crate::gql::paging::{decode_after, keyset_connection, page_size};
+use crate::models::{VocabMatch, VocabSearch};
+use crate::sl::search::{SearchVocab, VocabSearchService};
+use async_graphql::connection::{Connection, OpaqueCursor};
+use async_graphql::*;
+
+/// A vocab found by `searchVocab`.
+///
+/// # Fields
+///
+/// - `vocab_id`: The identifier of the vocab.
+/// - `learning`: The vocab in the learning language.
+/// - `first_lang`: The vocab in the known language.
+/// - `pos`: The part of speech of the vocab.
+/// - `learning_lang_code`: The language code of the learning language.
+/// - `rank`: How well the vocab matches, see [`VocabMatch`].
+/// - `enrolled`: True when the awesome person searching studies the vocab.
+#[derive(Clone)]
+pub struct VocabSearchResult {
+    pub vocab_id: i32,
+    pub learning: String,
+    pub first_lang: String,
+    pub pos: String,
+    pub learning_lang_code: String,
+    pub rank: f64,
+    pub enrolled: bool,
+}
+
+impl From<VocabMatch> for VocabSearchResult {
+    fn from(found: VocabMatch) -> Self {
+        VocabSearchResult {
+            vocab_id: found.vocab.id,
+            learning: found.vocab.learning_lang,
+            first_lang: found.vocab.first_lang,
+            pos: found.vocab.pos.unwrap_or_default(),
+            learning_lang_code: found.vocab.learning_lang_code,
+            rank: found.rank,
+            enrolled: found.enrolled,
+        }
+    }
+}
+
+#[Object]
+impl VocabSearchResult {
+    async fn vocab_id(&self) -> i32 {
+        self.vocab_id
+    }
+
+    async fn learning(&self) -> String {
+        self.learning.clone()
+    }
+
+    async fn first_lang(&self) -> String {
+        self.first_lang.clone()
+    }
+
+    async fn pos(&self) -> String {
+        self.pos.clone()
+    }
+
+    async fn learning_lang_code(&self) -> String {
+        self.learning_lang_code.clone()
+    }
+
+    async fn rank(&self) -> f64 {
+        self.rank
+    }
+
+    async fn enrolled(&self) -> bool {
+        self.enrolled
+    }
+}
+
+/// GraphQL Queries of the vocab search
+#[derive(Default)]
+pub struct SearchQuery;
+
+#[Object]
+impl SearchQuery {
+    /// Searches the vocab by the prefix of, or the similarity to, its learning language, an alternative
+    /// or its first language. Accents and case are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text searched for.
+    /// * `lang_code` - Optional. Only vocab of this learning language is found.
+    /// * `pos` - Optional. Only vocab with this part of speech is found.
+    /// * `tag` - Optional. Only vocab with this tag is found.
+    /// * `awesome_id` - Optional. The awesome person searching, for the `enrolled` flag of each match.
+    /// * `first` - Optional. The maximum number of matches in the page, see [`page_size`].
+    /// * `after` - Optional. The cursor of the last match of the previous page, of the same search.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping a connection of each `VocabSearchResult`, the best ranked first then by learning
+    /// language and id, or an error on failure, e.g. `VALIDATION` for an empty text.
+    #[allow(clippy::too_many_arguments)]
+    async fn search_vocab(
+        &self,
+        text: String,
+        lang_code: Option<String>,
+        pos: Option<String>,
+        tag: Option<String>,
+        awesome_id: Option<i32>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> Result<Connection<OpaqueCursor<(f64, String, i32)>, VocabSearchResult>> {
+        let size = page_size(first).map_err(|err| err.extend())?;
+        let after =
+            decode_after::<OpaqueCursor<(f64, String, i32)>>(after).map_err(|err| err.extend())?;
+        let has_previous_page = after.is_some();
+
+        let search = VocabSearch {
+            text,
+            learning_lang_code: lang_code,
+            pos,
+            tag,
+            awesome_person_id: awesome_id,
+        };
+        let matches = run_blocking(move || {
+            VocabSearchService::instance().search_vocab(
+                &search,
+                after.map(|after| after.0),
+                size as i64 + 1,
+            )
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(keyset_connection(
+            matches,
+            size,
+            has_previous_page,
+            |found| {
+                OpaqueCursor((
+                    found.rank,
+                    found.vocab.learning_lang.clone(),
+                    found.vocab.id,
+                ))
+            },
+            VocabSearchResult::from,
+        ))
+    }
+}
@@ -1,5 +1,156 @@
-use crate::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use crate::dal::db_connection::run_blocking;
+use crate::error::PalabrasError;
+use crate::gql::paging::{keyset_connection, page_size};
+use crate::models::{AwesomePerson, ConjugationStat, GradingPolicy, Vocab, VocabStudy};
+use crate::sl::conjugation::{ConjugationDrill, Person, Tense};
+use crate::sl::daily_quota::{DailyLimits, DailyQuota, DailyQuotaService};
+use crate::sl::fuzzy_match_vocab::{Answering, LearnVocab, StudyFilter, VocabFuzzyMatch};
+use async_graphql::connection::Connection;
 use async_graphql::*;
+use chrono::{DateTime, Utc};
+
+/// The kind of challenges in a study list.
+///
+/// - `Translate`: Translate the vocab into the learning language, answered with `checkResponse`.
+/// - `Conjugation`: Conjugate a verb for a person and tense, answered with `checkConjugation`.
+#[derive(Enum, Copy, Clone, Default, Eq, PartialEq)]
+pub enum StudyMode {
+    #[default]
+    Translate,
+    Conjugation,
+}
+
+/// Options for the study list.
+///
+/// # Fields
+///
+/// - `mode`: Optional. The kind of challenges to return, translations by default.
+/// - `with_articles`: Optional. Nouns with a known gender are answered with their article, e.g. `la mano`.
+#[derive(InputObject, Default)]
+pub struct StudyListOptions {
+    pub mode: Option<StudyMode>,
+    pub with_articles: Option<bool>,
+}
+
+/// The person of a conjugated verb, see [`Person`].
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ConjugationPerson {
+    Yo,
+    Tu,
+    El,
+    Nosotros,
+    Vosotros,
+    Ellos,
+}
+
+impl From<Person> for ConjugationPerson {
+    fn from(person: Person) -> Self {
+        match person {
+            Person::Yo => ConjugationPerson::Yo,
+            Person::Tu => ConjugationPerson::Tu,
+            Person::El => ConjugationPerson::El,
+            Person::Nosotros => ConjugationPerson::Nosotros,
+            Person::Vosotros => ConjugationPerson::Vosotros,
+            Person::Ellos => ConjugationPerson::Ellos,
+        }
+    }
+}
+
+impl From<ConjugationPerson> for Person {
+    fn from(person: ConjugationPerson) -> Self {
+        match person {
+            ConjugationPerson::Yo => Person::Yo,
+            ConjugationPerson::Tu => Person::Tu,
+            ConjugationPerson::El => Person::El,
+            ConjugationPerson::Nosotros => Person::Nosotros,
+            ConjugationPerson::Vosotros => Person::Vosotros,
+            ConjugationPerson::Ellos => Person::Ellos,
+        }
+    }
+}
+
+/// The tense of a conjugated verb, see [`Tense`].
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ConjugationTense {
+    Presente,
+    Preterito,
+    Imperfecto,
+    Futuro,
+    Condicional,
+    PresenteSubjuntivo,
+}
+
+impl From<Tense> for ConjugationTense {
+    fn from(tense: Tense) -> Self {
+        match tense {
+            Tense::Presente => ConjugationTense::Presente,
+            Tense::Preterito => ConjugationTense::Preterito,
+            Tense::Imperfecto => ConjugationTense::Imperfecto,
+            Tense::Futuro => ConjugationTense::Futuro,
+            Tense::Condicional => ConjugationTense::Condicional,
+            Tense::PresenteSubjuntivo => ConjugationTense::PresenteSubjuntivo,
+        }
+    }
+}
+
+impl From<ConjugationTense> for Tense {
+    fn from(tense: ConjugationTense) -> Self {
+        match tense {
+            ConjugationTense::Presente => Tense::Presente,
+            ConjugationTense::Preterito => Tense::Preterito,
+            ConjugationTense::Imperfecto => Tense::Imperfecto,
+            ConjugationTense::Futuro => Tense::Futuro,
+            ConjugationTense::Condicional => Tense::Condicional,
+            ConjugationTense::PresenteSubjuntivo => Tense::PresenteSubjuntivo,
+        }
+    }
+}
+
+/// The conjugation asked for by a conjugation challenge.
+///
+/// # Fields
+///
+/// - `infinitive`: The verb to conjugate.
+/// - `person`: The person to conjugate for.
+/// - `tense`: The tense to conjugate in.
+/// - `prompt`: The drill as displayed to the user, e.g. `aprender — nosotros, pretérito`.
+#[derive(Clone)]
+pub struct ConjugationChallenge {
+    pub infinitive: String,
+    pub person: ConjugationPerson,
+    pub tense: ConjugationTense,
+    pub prompt: String,
+}
+
+impl From<ConjugationDrill> for ConjugationChallenge {
+    fn from(drill: ConjugationDrill) -> Self {
+        ConjugationChallenge {
+            prompt: drill.prompt(),
+            infinitive: drill.infinitive,
+            person: drill.person.into(),
+            tense: drill.tense.into(),
+        }
+    }
+}
+
+#[Object]
+impl ConjugationChallenge {
+    async fn infinitive(&self) -> String {
+        self.infinitive.clone()
+    }
+
+    async fn person(&self) -> ConjugationPerson {
+        self.person
+    }
+
+    async fn tense(&self) -> ConjugationTense {
+        self.tense
+    }
+
+    async fn prompt(&self) -> String {
+        self.prompt.clone()
+    }
+}
 
 /// Represents a challenge presented to a user for vocabulary practice.
 ///
@@ -11,18 +162,25 @@ use async_graphql::*;
 /// # Fields
 ///
 /// - `vocab_id`: The unique identifier of the vocabulary item being challenged. This relates to a specific
-/// word or phrase in the study material.
+///   word or phrase in the study material.
 /// - `vocab_study_id`: The unique identifier for the user's study history with this vocabulary item,
-/// allowing for tracking of progress and retrieval of user-specific study data.
+///   allowing for tracking of progress and retrieval of user-specific study data.
 /// - `first_lang`: The translation of the word or phrase into the user's first language, used as a prompt.
 /// - `infinitive`: Optional. For verbs, the infinitive form of the word. Empty for non-verb vocabulary items.
 /// - `pos`: Optional. The part of speech of the vocabulary item, aiding in the application of grammatical rules.
 /// - `hint`: Optional. A hint provided to assist users in translating the word or phrase.
 /// - `num_learning_words`: The number of words contained in the `learning_lang` field, calculated for analytical purposes.
 /// - `user_notes`: Optional notes added by the user to aid in recall or provide additional context for the vocabulary word
+/// - `user_hint`: Optional hint of the user's own, e.g. a mnemonic for a leech.
 /// - `correct_attempts`: The number of times the vocabulary word was guessed or recalled correctly by the user.
 /// - `known_lang_code`: Language code for this known language.
 /// - `learning_lang_code`: Language code for this learning language.
+/// - `mode`: The kind of challenge, see [`StudyMode`].
+/// - `conjugation`: Optional. For conjugation challenges, the person and tense to conjugate the verb for.
+/// - `gender`: For nouns, the grammatical gender, `m` or `f`. Empty when unknown.
+/// - `with_article`: Whether the answer should include the article, answered with `checkResponse(withArticle: true)`.
+/// - `lang_variant`: The regional variant the answer is expected in, the awesome person's preferred variant or
+///   else the variant of the vocab. Empty when neither is known.
 #[derive(Clone)]
 pub struct Challenge {
     pub vocab_id: i32,
@@ -33,9 +191,48 @@ pub struct Challenge {
     pub hint: String,
     pub num_learning_words: i32,
     pub user_notes: String,
+    pub user_hint: String,
     pub correct_attempts: i32,
     pub known_lang_code: String,
     pub learning_lang_code: String,
+    pub mode: StudyMode,
+    pub conjugation: Option<ConjugationChallenge>,
+    pub gender: String,
+    pub with_article: bool,
+    pub lang_variant: String,
+}
+
+impl Challenge {
+    // The challenge of a vocab, answered in the preferred variant when there is one.
+    pub(crate) fn new(
+        vs: VocabStudy,
+        v: Vocab,
+        mode: StudyMode,
+        drill: Option<ConjugationDrill>,
+        with_articles: bool,
+        preferred_variant: Option<String>,
+    ) -> Self {
+        let gender = v.gender.unwrap_or_default();
+        Challenge {
+            vocab_id: v.id,
+            vocab_study_id: vs.id,
+            first_lang: v.first_lang,
+            infinitive: v.infinitive.unwrap_or_default(),
+            pos: v.pos.unwrap_or_default(),
+            hint: v.hint.unwrap_or_default(),
+            num_learning_words: v.num_learning_words,
+            user_notes: vs.user_notes.unwrap_or_default(),
+            user_hint: vs.user_hint.unwrap_or_default(),
+            correct_attempts: vs.correct_attempts.unwrap_or_default(),
+            known_lang_code: v.known_lang_code,
+            learning_lang_code: v.learning_lang_code,
+            mode,
+            conjugation: drill.map(ConjugationChallenge::from),
+            with_article: with_articles && mode == StudyMode::Translate && !gender.is_empty(),
+            gender,
+            lang_variant: preferred_variant.or(v.lang_variant).unwrap_or_default(),
+        }
+    }
 }
 
 #[Object]
@@ -61,13 +258,16 @@ impl Challenge {
         self.hint.clone()
     }
     async fn num_learning_words(&self) -> i32 {
-        self.num_learning_words.clone()
+        self.num_learning_words
     }
     async fn user_notes(&self) -> String {
         self.user_notes.clone()
     }
+    async fn user_hint(&self) -> String {
+        self.user_hint.clone()
+    }
     async fn correct_attempts(&self) -> i32 {
-        self.correct_attempts.clone()
+        self.correct_attempts
     }
 
     async fn known_lang_code(&self) -> String {
@@ -77,6 +277,26 @@ impl Challenge {
     async fn learning_lang_code(&self) -> String {
         self.learning_lang_code.clone()
     }
+
+    async fn mode(&self) -> StudyMode {
+        self.mode
+    }
+
+    async fn conjugation(&self) -> Option<ConjugationChallenge> {
+        self.conjugation.clone()
+    }
+
+    async fn gender(&self) -> String {
+        self.gender.clone()
+    }
+
+    async fn with_article(&self) -> bool {
+        self.with_article
+    }
+
+    async fn lang_variant(&self) -> String {
+        self.lang_variant.clone()
+    }
 }
 
 /// Represents the profile of an awesome person with their vocabulary learning statistics.
@@ -96,6 +316,11 @@ impl Challenge {
 /// - `total_percentage`: The overall success rate calculated as the percentage of correct guesses out of the total number of guesses.
 /// - `name`: The name of the user. This field is optional and can be anything the user wants.
 /// - `smallest_vocab`: The minimum length of vocabulary words that are considered for testing. This helps tailor the difficulty of the tests to the user's level.
+/// - `preferred_variant`: The regional variant the user learns, e.g. `es-MX`, empty for none.
+/// - `new_per_day`: The maximum number of new words introduced in the study list each day.
+/// - `reviews_per_day`: The maximum number of reviews in the study list each day.
+/// - `new_remaining_today`: The number of new words that can still be introduced today.
+/// - `reviews_remaining_today`: The number of reviews that can still be answered today.
 ///
 /// # Example
 ///
@@ -109,6 +334,11 @@ impl Challenge {
 ///     total_percentage: 80.0,
 ///     name: String::from("Michelle"),
 ///     smallest_vocab: 4,
+///     preferred_variant: String::from("es-MX"),
+///     new_per_day: 20,
+///     reviews_per_day: 200,
+///     new_remaining_today: 15,
+///     reviews_remaining_today: 120,
 /// };
 /// ```
 #[derive(Clone)]
@@ -120,6 +350,22 @@ pub struct AwesomeProfile {
     pub total_percentage: f64,
     pub name: String,
     pub smallest_vocab: i32,
+    pub preferred_variant: String,
+    pub new_per_day: i32,
+    pub reviews_per_day: i32,
+    pub new_remaining_today: i32,
+    pub reviews_remaining_today: i32,
+}
+
+impl AwesomeProfile {
+    // The profile with the new words and reviews left today, counted against the attempt history.
+    pub(crate) fn with_quota(self, quota: &DailyQuota) -> Self {
+        AwesomeProfile {
+            new_remaining_today: quota.new_remaining() as i32,
+            reviews_remaining_today: quota.reviews_remaining() as i32,
+            ..self
+        }
+    }
 }
 
 #[Object]
@@ -145,6 +391,40 @@ impl AwesomeProfile {
     async fn smallest_vocab(&self) -> i32 {
         self.smallest_vocab
     }
+    async fn preferred_variant(&self) -> String {
+        self.preferred_variant.clone()
+    }
+    async fn new_per_day(&self) -> i32 {
+        self.new_per_day
+    }
+    async fn reviews_per_day(&self) -> i32 {
+        self.reviews_per_day
+    }
+    async fn new_remaining_today(&self) -> i32 {
+        self.new_remaining_today
+    }
+    async fn reviews_remaining_today(&self) -> i32 {
+        self.reviews_remaining_today
+    }
+}
+
+impl From<AwesomePerson> for AwesomeProfile {
+    fn from(awesome_person: AwesomePerson) -> Self {
+        AwesomeProfile {
+            id: awesome_person.id,
+            num_known: awesome_person.num_known.unwrap_or_default(),
+            num_correct: awesome_person.num_correct.unwrap_or_default(),
+            num_incorrect: awesome_person.num_incorrect.unwrap_or_default(),
+            total_percentage: awesome_person.total_percentage.unwrap_or_default(),
+            name: awesome_person.name.unwrap_or_default(),
+            smallest_vocab: awesome_person.smallest_vocab,
+            preferred_variant: awesome_person.preferred_variant.unwrap_or_default(),
+            new_per_day: awesome_person.new_per_day,
+            reviews_per_day: awesome_person.reviews_per_day,
+            new_remaining_today: awesome_person.new_per_day,
+            reviews_remaining_today: awesome_person.reviews_per_day,
+        }
+    }
 }
 
 /// Represents the statistical data related to the study of a specific vocabulary word.
@@ -158,15 +438,15 @@ impl AwesomeProfile {
 ///
 /// - `learning`: The vocabulary word being studied. This field represents the target word or phrase that the user is attempting to learn.
 /// - `attempts`: The total number of attempts made by the user to study or guess the `learning` word.
-/// This metric helps track the amount of effort put into learning the word.
+///   This metric helps track the amount of effort put into learning the word.
 /// - `correct_attempts`: The number of successful attempts where the user correctly guessed or recalled
-/// the `learning` word. This measures the effectiveness of the learning process.
+///   the `learning` word. This measures the effectiveness of the learning process.
 /// - `percentage_correct`: The success rate for the `learning` word, calculated as the percentage of
-/// correct attempts out of the total attempts. This provides a quantitative measure of the user's mastery over the word.
+///   correct attempts out of the total attempts. This provides a quantitative measure of the user's mastery over the word.
 /// - `last_change`: Indicates the most recent change in the success rate (`percentage_correct`).
-/// This metric can help identify recent trends in the user's learning curve, such as improvements or setbacks.
+///   This metric can help identify recent trends in the user's learning curve, such as improvements or setbacks.
 /// - `last_tested`: The timestamp of the last attempt to study the `learning` word. This field helps
-/// track the recency of the user's study efforts and can be used to prompt further review if too much time has elapsed.
+///   track the recency of the user's study efforts and can be used to prompt further review if too much time has elapsed.
 /// # Example
 ///
 /// ```use palabras::gql::studies::VocabStats;
@@ -216,47 +496,223 @@ impl VocabStats {
     }
 }
 
-/// GraphQL Queries
-pub struct QueryRoot;
+// When a vocab was last tested as shown to awesome persons, empty when it never was.
+pub(crate) fn format_tested(last_tested: Option<DateTime<Utc>>) -> String {
+    last_tested
+        .map(|tested| tested.format("%Y-%m-%d %H:%M:%S %Z").to_string())
+        .unwrap_or_default()
+}
+
+/// Represents the grading thresholds applied to an awesome person's responses.
+///
+/// # Fields
+///
+/// - `awesome_person_id`: The awesome person the thresholds apply to.
+/// - `max_distance`: The distance given to the worst possible answer, larger distances are capped to it.
+/// - `well_known_threshold`: Once the percentage correct is above this, the vocab is considered well known.
+/// - `close_distance`: Answers up to this distance are reported as close.
+/// - `perfect_weight`: How many answers a perfect match counts as when averaging the percentage correct.
+///
+/// # Example
+///
+/// ```
+/// use palabras::gql::studies::GradingThresholds;
+/// let grading_thresholds = GradingThresholds {
+///     awesome_person_id: 1,
+///     max_distance: 10,
+///     well_known_threshold: 0.98,
+///     close_distance: 3,
+///     perfect_weight: 2.0,
+/// };
+/// ```
+#[derive(Clone)]
+pub struct GradingThresholds {
+    pub awesome_person_id: i32,
+    pub max_distance: i32,
+    pub well_known_threshold: f64,
+    pub close_distance: i32,
+    pub perfect_weight: f64,
+}
+
+impl From<GradingPolicy> for GradingThresholds {
+    fn from(policy: GradingPolicy) -> Self {
+        GradingThresholds {
+            awesome_person_id: policy.awesome_person_id,
+            max_distance: policy.max_distance,
+            well_known_threshold: policy.well_known_threshold,
+            close_distance: policy.close_distance,
+            perfect_weight: policy.perfect_weight,
+        }
+    }
+}
 
 #[Object]
-impl QueryRoot {
+impl GradingThresholds {
+    async fn awesome_person_id(&self) -> i32 {
+        self.awesome_person_id
+    }
+
+    async fn max_distance(&self) -> i32 {
+        self.max_distance
+    }
+
+    async fn well_known_threshold(&self) -> f64 {
+        self.well_known_threshold
+    }
+
+    async fn close_distance(&self) -> i32 {
+        self.close_distance
+    }
+
+    async fn perfect_weight(&self) -> f64 {
+        self.perfect_weight
+    }
+}
+
+/// Represents an awesome person's accuracy on one person and tense of the conjugation drills.
+///
+/// # Fields
+///
+/// - `person`: The person drilled.
+/// - `tense`: The tense drilled.
+/// - `attempts`: The number of drills answered.
+/// - `correct_attempts`: The number of drills answered with a perfect match.
+/// - `percentage_correct`: `correct_attempts` over `attempts`.
+#[derive(Clone)]
+pub struct ConjugationAccuracy {
+    pub person: ConjugationPerson,
+    pub tense: ConjugationTense,
+    pub attempts: i32,
+    pub correct_attempts: i32,
+    pub percentage_correct: f64,
+}
+
+impl ConjugationAccuracy {
+    // Stats stored under keys this version does not know are skipped.
+    fn from_stat(stat: &ConjugationStat) -> Option<Self> {
+        Some(ConjugationAccuracy {
+            person: Person::from_key(&stat.person)?.into(),
+            tense: Tense::from_key(&stat.tense)?.into(),
+            attempts: stat.attempts,
+            correct_attempts: stat.correct_attempts,
+            percentage_correct: stat.percentage_correct(),
+        })
+    }
+}
+
+#[Object]
+impl ConjugationAccuracy {
+    async fn person(&self) -> ConjugationPerson {
+        self.person
+    }
+
+    async fn tense(&self) -> ConjugationTense {
+        self.tense
+    }
+
+    async fn attempts(&self) -> i32 {
+        self.attempts
+    }
+
+    async fn correct_attempts(&self) -> i32 {
+        self.correct_attempts
+    }
+
+    async fn percentage_correct(&self) -> f64 {
+        self.percentage_correct
+    }
+}
+
+/// GraphQL Queries of studying
+#[derive(Default)]
+pub struct StudyQuery;
+
+#[Object]
+impl StudyQuery {
     /// Fetches a list of vocab study challenges for a specified awesome person.
     ///
     /// This async function retrieves a set of vocab words for the awesome person to study,
-    /// limited by the specified `limit`. Each challenge includes a prompt generated based
+    /// limited by `first`. Each challenge includes a prompt generated based
     /// on the vocab word and any user notes associated with the vocab study.
     ///
+    /// The study list is a connection with a single page. It is composed anew for every request from the
+    /// answers given so far, so rather than paging `after` a challenge a new list is asked for once the
+    /// challenges are answered. `pageInfo.hasNextPage` tells whether more vocab is due than was listed.
+    ///
     /// # Arguments
     ///
     /// * `awesome_id` - The ID of the awesome person for whom to fetch the study challenges.
-    /// * `limit` - The maximum number of challenges to return.
+    /// * `first` - Optional. The maximum number of challenges to return, see [`page_size`].
+    /// * `options` - Optional, see [`StudyListOptions`]. With the `CONJUGATION` mode the challenges are
+    ///   conjugation drills of the awesome person's verbs, weakest persons and tenses first. With
+    ///   `with_articles` the translations of nouns with a known gender are answered with their article.
+    /// * `deck_id` - Optional. Only the vocab in this deck of the awesome person, translations only.
+    /// * `tags` - Optional. Only the vocab with every one of these tags, translations only.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a vector of `Challenge` structs on success, or an error message string on failure.
+    /// A `Result` containing a connection of `Challenge` structs on success, each with its vocab study ID as
+    /// its cursor, or an error on failure.
+    /// Errors carry a machine readable `code` in their extensions, see [`crate::error::PalabrasError::code`].
     /// Each `Challenge` struct includes the vocab ID, vocab study ID, and the generated prompt.
-    async fn get_study_list(&self, awesome_id: i32, limit: i64) -> Result<Vec<Challenge>> {
-        let match_service = VocabFuzzyMatch::instance();
-
-        let mut study_list: Vec<Challenge> = Vec::new();
-
-        let vocab = match_service.get_vocab_to_learn(awesome_id, limit)?;
-        for (vs, v) in vocab {
-            study_list.push(Challenge {
-                vocab_id: v.id,
-                vocab_study_id: vs.id,
-                first_lang: v.first_lang,
-                infinitive: v.infinitive.unwrap_or_default(),
-                pos: v.pos.unwrap_or_default(),
-                hint: v.hint.unwrap_or_default(),
-                num_learning_words: v.num_learning_words,
-                user_notes: vs.user_notes.unwrap_or_default(),
-                correct_attempts: vs.correct_attempts.unwrap_or_default(),
-                known_lang_code: v.known_lang_code,
-                learning_lang_code: v.learning_lang_code,
-            });
-        }
+    async fn get_study_list(
+        &self,
+        awesome_id: i32,
+        first: Option<i32>,
+        options: Option<StudyListOptions>,
+        deck_id: Option<i32>,
+        tags: Option<Vec<String>>,
+    ) -> Result<Connection<i32, Challenge>> {
+        let size = page_size(first).map_err(|err| err.extend())?;
+        // One more than the page tells whether there is more to study.
+        let limit = size as i64 + 1;
+        let options = options.unwrap_or_default();
+        let mode = options.mode.unwrap_or_default();
+        let with_articles = options.with_articles.unwrap_or_default();
+        let filter = StudyFilter {
+            deck_id,
+            tags: tags.unwrap_or_default(),
+            ..Default::default()
+        };
+
+        let (preferred_variant, vocab) = run_blocking(move || {
+            let service = VocabFuzzyMatch::instance();
+            let preferred_variant = service
+                .get_awesome_person(awesome_id)?
+                .preferred_variant
+                .filter(|variant| !variant.is_empty());
+            let vocab = match mode {
+                StudyMode::Conjugation if filter.deck_id.is_some() || !filter.tags.is_empty() => {
+                    return Err(PalabrasError::Validation(
+                        "decks and tags are studied in the TRANSLATE mode".to_string(),
+                    ))
+                }
+                StudyMode::Translate => service
+                    .get_filtered_vocab_to_learn(awesome_id, limit, &filter)?
+                    .into_iter()
+                    .map(|(vs, v)| (vs, v, None))
+                    .collect::<Vec<_>>(),
+                StudyMode::Conjugation => service
+                    .get_conjugation_drills(awesome_id, limit)?
+                    .into_iter()
+                    .map(|(vs, v, drill)| (vs, v, Some(drill)))
+                    .collect(),
+            };
+
+            Ok((preferred_variant, vocab))
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        let study_list = keyset_connection(
+            vocab,
+            size,
+            false,
+            |(vs, _, _)| vs.id,
+            |(vs, v, drill)| {
+                Challenge::new(vs, v, mode, drill, with_articles, preferred_variant.clone())
+            },
+        );
 
         Ok(study_list)
     }
@@ -265,7 +721,7 @@ impl QueryRoot {
     ///
     /// This async function queries the database for the specified awesome person's data,
     /// including their learning statistics and basic profile details. If the awesome person
-    /// cannot be found, it returns default values for each field.
+    /// cannot be found, the error carries the `NOT_FOUND` code in its extensions.
     ///
     /// # Arguments
     ///
@@ -274,21 +730,19 @@ impl QueryRoot {
     /// # Returns
     ///
     /// A `Result` wrapping an `AwesomeProfile` struct containing the awesome person's data on success,
-    /// or an error message string on failure.
+    /// with the new words and reviews remaining today, or an error on failure.
     async fn get_awesome_person(&self, awesome_id: i32) -> Result<AwesomeProfile> {
-        let match_service = VocabFuzzyMatch::instance();
-        let pub_awesome_person = match_service.get_awesome_person(awesome_id)?;
-        let pub_awesome_person = pub_awesome_person.unwrap_or_default();
-
-        Ok(AwesomeProfile {
-            id: pub_awesome_person.id,
-            num_known: pub_awesome_person.num_known.unwrap_or_default(),
-            num_correct: pub_awesome_person.num_correct.unwrap_or_default(),
-            num_incorrect: pub_awesome_person.num_incorrect.unwrap_or_default(),
-            total_percentage: pub_awesome_person.total_percentage.unwrap_or_default(),
-            name: pub_awesome_person.name.unwrap_or_default(),
-            smallest_vocab: pub_awesome_person.smallest_vocab,
+        let (pub_awesome_person, quota) = run_blocking(move || {
+            let service = VocabFuzzyMatch::instance();
+            let awesome_person = service.get_awesome_person(awesome_id)?;
+            let quota = DailyQuotaService::instance().get_daily_quota(awesome_id)?;
+
+            Ok((awesome_person, quota))
         })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(AwesomeProfile::from(pub_awesome_person).with_quota(&quota))
     }
 
     /// Retrieves statistical information for a specific vocabulary study session by its ID.
@@ -304,21 +758,12 @@ impl QueryRoot {
     /// # Returns
     ///
     /// A `Result` wrapping a `VocabStats` struct containing detailed statistics about the study session on success,
-    /// or an error string on failure.
+    /// or an error on failure, e.g. `NOT_FOUND` when the vocab study does not exist.
     async fn get_vocab_stats(&self, vocab_study_id: i32) -> Result<VocabStats> {
-        let match_service = VocabFuzzyMatch::instance();
-
-        let (vocab_study, vocab) = match_service.get_vocab_stats(vocab_study_id)?;
-
-        let last_tested = if vocab_study.last_tested.is_some() {
-            vocab_study
-                .last_tested
-                .unwrap()
-                .format("%Y-%m-%d %H:%M:%S %Z")
-                .to_string()
-        } else {
-            "".to_string()
-        };
+        let (vocab_study, vocab) =
+            run_blocking(move || VocabFuzzyMatch::instance().get_vocab_stats(vocab_study_id))
+                .await
+                .map_err(|err| err.extend())?;
 
         Ok(VocabStats {
             learning: vocab.learning_lang.clone(),
@@ -326,16 +771,58 @@ impl QueryRoot {
             correct_attempts: vocab_study.correct_attempts.unwrap_or_default(),
             percentage_correct: vocab_study.percentage_correct.unwrap_or_default(),
             last_change: vocab_study.last_change.unwrap_or_default(),
-            last_tested,
+            last_tested: format_tested(vocab_study.last_tested),
         })
     }
+
+    /// Retrieves the grading thresholds applied to an awesome person's responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the awesome person's `GradingThresholds`, the configured defaults when they
+    /// have not set their own, or an error on failure, e.g. `NOT_FOUND` for an unknown awesome person.
+    async fn get_grading_thresholds(&self, awesome_id: i32) -> Result<GradingThresholds> {
+        let policy =
+            run_blocking(move || VocabFuzzyMatch::instance().get_grading_policy(awesome_id))
+                .await
+                .map_err(|err| err.extend())?;
+
+        Ok(policy.into())
+    }
+
+    /// Retrieves an awesome person's accuracy in the conjugation drills, for each person and tense drilled.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the `ConjugationAccuracy` of each person and tense drilled, ordered by tense
+    /// then person, or an error on failure, e.g. `NOT_FOUND` for an unknown awesome person.
+    async fn get_conjugation_stats(&self, awesome_id: i32) -> Result<Vec<ConjugationAccuracy>> {
+        let stats =
+            run_blocking(move || VocabFuzzyMatch::instance().get_conjugation_stats(awesome_id))
+                .await
+                .map_err(|err| err.extend())?;
+
+        Ok(stats
+            .iter()
+            .filter_map(ConjugationAccuracy::from_stat)
+            .collect())
+    }
 }
 
-/// GraphQL Mutations
-pub struct MutationRoot;
+/// GraphQL Mutations of studying
+#[derive(Default)]
+pub struct StudyMutation;
 
 #[Object]
-impl MutationRoot {
+impl StudyMutation {
     /// Checks the user's response for a given vocabulary study session.
     ///
     /// This function compares the user's entered response against the correct answer for the specified vocabulary.
@@ -346,22 +833,156 @@ impl MutationRoot {
     /// * `vocab_id` - The identifier of the vocabulary item being studied.
     /// * `vocab_study_id` - The identifier of the vocab study session, linking the user and the vocab item.
     /// * `entered` - The response entered by the user for the vocabulary item.
+    /// * `with_article` - Optional. The response to a noun must include its article, see
+    ///   [`LearnVocab::check_response_with_article`]. A wrong article is always reported.
+    /// * `session_id` - Optional. The open study session the response is given in, see `startSession`.
     ///
     /// # Returns
     ///
     /// Returns a `Result<String>` where:
     /// - `Ok(String)` contains the feedback or prompt based on the comparison of the entered response and the correct answer.
-    /// - `Err` contains an error message if the operation fails.
+    /// - `Err` contains an error with a `code` extension if the operation fails, e.g. `CONFLICT` when the
+    ///   session is already finished.
     async fn check_response(
         &self,
         vocab_id: i32,
         vocab_study_id: i32,
         entered: String,
+        with_article: Option<bool>,
+        session_id: Option<i32>,
     ) -> Result<String> {
-        let match_service = VocabFuzzyMatch::instance();
+        let prompt = run_blocking(move || {
+            let service = VocabFuzzyMatch::instance();
+            let with_article = with_article.unwrap_or_default();
+            match session_id {
+                Some(session_id) => service.check_session_response(
+                    session_id,
+                    vocab_id,
+                    vocab_study_id,
+                    entered,
+                    Answering::Learning { with_article },
+                ),
+                None if with_article => {
+                    service.check_response_with_article(vocab_id, vocab_study_id, entered)
+                }
+                None => service.check_response(vocab_id, vocab_study_id, entered),
+            }
+        })
+        .await
+        .map_err(|err| err.extend())?;
 
-        let prompt = match_service.check_response(vocab_id, vocab_study_id, entered)?;
+        Ok(prompt)
+    }
+
+    /// Checks the user's response to a conjugation challenge.
+    ///
+    /// The response is graded with the same fuzzy matching as `checkResponse`, and the result is
+    /// recorded in the awesome person's conjugation stats, see `getConjugationStats`.
+    ///
+    /// # Arguments
+    ///
+    /// * `vocab_id` - The identifier of the verb being drilled.
+    /// * `vocab_study_id` - The identifier of the vocab study, linking the user and the verb.
+    /// * `person` - The person of the challenge.
+    /// * `tense` - The tense of the challenge.
+    /// * `entered` - The conjugated form entered by the user.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result<String>` where:
+    /// - `Ok(String)` contains the feedback based on the comparison of the entered and correct forms.
+    /// - `Err` contains an error with a `code` extension if the operation fails, e.g. `VALIDATION`
+    ///   when the vocab has no infinitive.
+    async fn check_conjugation(
+        &self,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        person: ConjugationPerson,
+        tense: ConjugationTense,
+        entered: String,
+    ) -> Result<String> {
+        let prompt = run_blocking(move || {
+            VocabFuzzyMatch::instance().check_conjugation(
+                vocab_id,
+                vocab_study_id,
+                person.into(),
+                tense.into(),
+                entered,
+            )
+        })
+        .await
+        .map_err(|err| err.extend())?;
 
         Ok(prompt)
     }
+
+    /// Changes the grading thresholds applied to an awesome person's responses.
+    ///
+    /// Only the thresholds provided are changed, the others keep their current values.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person.
+    /// * `max_distance` - Optional, the distance given to the worst possible answer.
+    /// * `well_known_threshold` - Optional, percentage correct above which a vocab is well known.
+    /// * `close_distance` - Optional, answers up to this distance are reported as close.
+    /// * `perfect_weight` - Optional, how many answers a perfect match counts as.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the updated `GradingThresholds`, or an error on failure,
+    /// e.g. `VALIDATION` when a threshold is out of range.
+    async fn update_grading_thresholds(
+        &self,
+        awesome_id: i32,
+        max_distance: Option<i32>,
+        well_known_threshold: Option<f64>,
+        close_distance: Option<i32>,
+        perfect_weight: Option<f64>,
+    ) -> Result<GradingThresholds> {
+        let policy = run_blocking(move || {
+            let service = VocabFuzzyMatch::instance();
+            let current = service.get_grading_policy(awesome_id)?;
+
+            service.update_grading_policy(GradingPolicy {
+                max_distance: max_distance.unwrap_or(current.max_distance),
+                well_known_threshold: well_known_threshold.unwrap_or(current.well_known_threshold),
+                close_distance: close_distance.unwrap_or(current.close_distance),
+                perfect_weight: perfect_weight.unwrap_or(current.perfect_weight),
+                ..current
+            })
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(policy.into())
+    }
+
+    /// Sets the regional variant an awesome person learns.
+    ///
+    /// Answers are then expected in that variant, and answers from other variants are accepted with a note,
+    /// e.g. `Perfect Match! ordenador — in Latin America: computadora`.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person.
+    /// * `lang_variant` - A language and region code such as `es-MX` or `es-419`, empty to prefer no variant.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the updated `AwesomeProfile`, or an error on failure,
+    /// e.g. `VALIDATION` when the code is not a language and region.
+    async fn update_preferred_variant(
+        &self,
+        awesome_id: i32,
+        lang_variant: String,
+    ) -> Result<AwesomeProfile> {
+        let awesome_person = run_blocking(move || {
+            VocabFuzzyMatch::instance().update_preferred_variant(awesome_id, &lang_variant)
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(awesome_person.into())
+    }
 }
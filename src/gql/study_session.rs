@@ -0,0 +1,280 @@
+use crate::dal::db_connection::run_blocking;
+use crate::gql::studies::{Challenge, StudyMode};
+use crate::models::{StudySessionItem, Vocab};
+use crate::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use crate::sl::study_session::{SessionSummary, StudySessionService, StudySessions};
+use async_graphql::*;
+
+/// Represents a vocab answered in a study session.
+///
+/// # Fields
+///
+/// - `vocab_id`: The identifier of the vocab answered.
+/// - `vocab_study_id`: The identifier of the vocab study answered.
+/// - `learning`: The vocab in the learning language.
+/// - `first_lang`: The vocab in the known language.
+/// - `attempts`: The number of answers given in the session.
+/// - `correct_attempts`: The number of answers given in the session with a perfect match.
+/// - `percentage_before`: The percentage correct before the first answer in the session.
+/// - `percentage_after`: The percentage correct after the last answer in the session.
+/// - `missed`: Whether any answer in the session was not a perfect match.
+/// - `redrilled`: Whether the vocab was answered again after it was missed.
+#[derive(Clone)]
+pub struct SessionWord {
+    pub vocab_id: i32,
+    pub vocab_study_id: i32,
+    pub learning: String,
+    pub first_lang: String,
+    pub attempts: i32,
+    pub correct_attempts: i32,
+    pub percentage_before: f64,
+    pub percentage_after: f64,
+    pub missed: bool,
+    pub redrilled: bool,
+}
+
+impl From<&(StudySessionItem, Vocab)> for SessionWord {
+    fn from((item, vocab): &(StudySessionItem, Vocab)) -> Self {
+        SessionWord {
+            vocab_id: vocab.id,
+            vocab_study_id: item.vocab_study_id,
+            learning: vocab.learning_lang.clone(),
+            first_lang: vocab.first_lang.clone(),
+            attempts: item.attempts,
+            correct_attempts: item.correct_attempts,
+            percentage_before: item.percentage_before,
+            percentage_after: item.percentage_after,
+            missed: item.missed,
+            redrilled: item.redrilled,
+        }
+    }
+}
+
+#[Object]
+impl SessionWord {
+    async fn vocab_id(&self) -> i32 {
+        self.vocab_id
+    }
+
+    async fn vocab_study_id(&self) -> i32 {
+        self.vocab_study_id
+    }
+
+    async fn learning(&self) -> String {
+        self.learning.clone()
+    }
+
+    async fn first_lang(&self) -> String {
+        self.first_lang.clone()
+    }
+
+    async fn attempts(&self) -> i32 {
+        self.attempts
+    }
+
+    async fn correct_attempts(&self) -> i32 {
+        self.correct_attempts
+    }
+
+    async fn percentage_before(&self) -> f64 {
+        self.percentage_before
+    }
+
+    async fn percentage_after(&self) -> f64 {
+        self.percentage_after
+    }
+
+    /// The change of the percentage correct over the session.
+    async fn percentage_change(&self) -> f64 {
+        self.percentage_after - self.percentage_before
+    }
+
+    async fn missed(&self) -> bool {
+        self.missed
+    }
+
+    async fn redrilled(&self) -> bool {
+        self.redrilled
+    }
+}
+
+/// Represents a study session and what was answered in it.
+///
+/// # Fields
+///
+/// - `id`: The identifier of the session, passed to `checkResponse` as `sessionId`.
+/// - `awesome_person_id`: The awesome person studying.
+/// - `started`: The timestamp the session was started.
+/// - `finished`: The timestamp the session was finished, empty while it is open.
+/// - `num_answers`: The number of answers given, re-drills included.
+/// - `num_correct`: The number of answers given with a perfect match.
+/// - `percentage_correct`: `num_correct` over `num_answers`.
+/// - `words`: Each vocab answered, in the order first answered.
+/// - `redrill`: The missed vocab to answer again before the session closes, empty once it is finished.
+#[derive(Clone)]
+pub struct StudySessionSummary {
+    pub id: i32,
+    pub awesome_person_id: i32,
+    pub started: String,
+    pub finished: String,
+    pub num_answers: i32,
+    pub num_correct: i32,
+    pub percentage_correct: f64,
+    pub words: Vec<SessionWord>,
+    pub redrill: Vec<Challenge>,
+}
+
+impl StudySessionSummary {
+    // The summary of a session, the re-drills answered in the preferred variant when there is one.
+    fn new(summary: SessionSummary, preferred_variant: Option<String>) -> Self {
+        let format = |timestamp: chrono::DateTime<chrono::Utc>| {
+            timestamp.format("%Y-%m-%d %H:%M:%S %Z").to_string()
+        };
+        StudySessionSummary {
+            id: summary.session.id,
+            awesome_person_id: summary.session.awesome_person_id,
+            started: format(summary.session.started),
+            finished: summary.session.finished.map(format).unwrap_or_default(),
+            num_answers: summary.session.num_answers,
+            num_correct: summary.session.num_correct,
+            percentage_correct: summary.session.percentage_correct(),
+            words: summary.items.iter().map(SessionWord::from).collect(),
+            redrill: summary
+                .redrill
+                .into_iter()
+                .map(|(vs, v)| {
+                    Challenge::new(
+                        vs,
+                        v,
+                        StudyMode::Translate,
+                        None,
+                        false,
+                        preferred_variant.clone(),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[Object]
+impl StudySessionSummary {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    async fn awesome_person_id(&self) -> i32 {
+        self.awesome_person_id
+    }
+
+    async fn started(&self) -> String {
+        self.started.clone()
+    }
+
+    async fn finished(&self) -> String {
+        self.finished.clone()
+    }
+
+    async fn num_items(&self) -> i32 {
+        self.words.len() as i32
+    }
+
+    async fn num_answers(&self) -> i32 {
+        self.num_answers
+    }
+
+    async fn num_correct(&self) -> i32 {
+        self.num_correct
+    }
+
+    async fn percentage_correct(&self) -> f64 {
+        self.percentage_correct
+    }
+
+    async fn words(&self) -> Vec<SessionWord> {
+        self.words.clone()
+    }
+
+    /// The vocab missed in the session.
+    async fn missed(&self) -> Vec<SessionWord> {
+        self.words
+            .iter()
+            .filter(|word| word.missed)
+            .cloned()
+            .collect()
+    }
+
+    async fn redrill(&self) -> Vec<Challenge> {
+        self.redrill.clone()
+    }
+}
+
+/// GraphQL Mutations of study sessions
+#[derive(Default)]
+pub struct StudySessionMutation;
+
+#[Object]
+impl StudySessionMutation {
+    /// Starts a study session, the responses checked with its `id` as the `sessionId` of `checkResponse`
+    /// are recorded in it.
+    ///
+    /// # Arguments
+    ///
+    /// * `awesome_id` - The unique identifier of the awesome person studying.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the open `StudySessionSummary`, or an error on failure,
+    /// e.g. `NOT_FOUND` for an unknown awesome person.
+    async fn start_session(&self, awesome_id: i32) -> Result<StudySessionSummary> {
+        let session =
+            run_blocking(move || StudySessionService::instance().start_study_session(awesome_id))
+                .await
+                .map_err(|err| err.extend())?;
+
+        Ok(StudySessionSummary::new(
+            SessionSummary {
+                session,
+                items: Vec::new(),
+                redrill: Vec::new(),
+            },
+            None,
+        ))
+    }
+
+    /// Finishes a study session and summarizes it, the words missed and the change of their percentage correct.
+    ///
+    /// Missed words are re-drilled before the session closes. While any are left the session stays open
+    /// and they are returned as `redrill` challenges, answered with `checkResponse` in the session. Finishing
+    /// again once they are answered closes the session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_id` - The identifier of the study session.
+    /// * `skip_redrill` - Optional. Close the session without re-drilling the missed words.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the `StudySessionSummary`, or an error on failure,
+    /// e.g. `NOT_FOUND` for an unknown session.
+    async fn finish_session(
+        &self,
+        session_id: i32,
+        skip_redrill: Option<bool>,
+    ) -> Result<StudySessionSummary> {
+        let (summary, preferred_variant) = run_blocking(move || {
+            let summary = StudySessionService::instance()
+                .finish_study_session(session_id, skip_redrill.unwrap_or_default())?;
+            let preferred_variant = VocabFuzzyMatch::instance()
+                .get_awesome_person(summary.session.awesome_person_id)?
+                .preferred_variant
+                .filter(|variant| !variant.is_empty());
+
+            Ok((summary, preferred_variant))
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(StudySessionSummary::new(summary, preferred_variant))
+    }
+}
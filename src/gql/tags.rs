@@ -0,0 +1,207 @@
+use crate::dal::db_connection::run_blocking;
+use crate::gql::paging::{decode_after, keyset_connection, page_size};
+use crate::models::{Tag, Vocab};
+use crate::sl::tags::{ManageTags, TagService};
+use async_graphql::connection::Connection;
+use async_graphql::*;
+
+/// A tag of vocab with the number of vocab tagged with it.
+///
+/// # Fields
+///
+/// - `id`: The identifier of the tag.
+/// - `name`: The name of the tag, in lowercase.
+/// - `vocab_count`: The number of vocab tagged with it.
+#[derive(Clone)]
+pub struct TagSummary {
+    pub id: i32,
+    pub name: String,
+    pub vocab_count: i64,
+}
+
+impl From<(Tag, i64)> for TagSummary {
+    fn from((tag, vocab_count): (Tag, i64)) -> Self {
+        TagSummary {
+            id: tag.id,
+            name: tag.name,
+            vocab_count,
+        }
+    }
+}
+
+#[Object]
+impl TagSummary {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn vocab_count(&self) -> i64 {
+        self.vocab_count
+    }
+}
+
+/// A vocab with its tags.
+///
+/// # Fields
+///
+/// - `vocab_id`: The identifier of the vocab.
+/// - `learning`: The vocab in the learning language.
+/// - `first_lang`: The vocab in the known language.
+/// - `pos`: The part of speech of the vocab.
+/// - `skill`: The skill of the vocab, also one of its tags.
+/// - `tags`: The names of the tags of the vocab.
+#[derive(Clone)]
+pub struct TaggedVocab {
+    pub vocab_id: i32,
+    pub learning: String,
+    pub first_lang: String,
+    pub pos: String,
+    pub skill: String,
+    pub tags: Vec<String>,
+}
+
+impl From<(Vocab, Vec<Tag>)> for TaggedVocab {
+    fn from((vocab, tags): (Vocab, Vec<Tag>)) -> Self {
+        TaggedVocab {
+            vocab_id: vocab.id,
+            learning: vocab.learning_lang,
+            first_lang: vocab.first_lang,
+            pos: vocab.pos.unwrap_or_default(),
+            skill: vocab.skill.unwrap_or_default(),
+            tags: tags.into_iter().map(|tag| tag.name).collect(),
+        }
+    }
+}
+
+#[Object]
+impl TaggedVocab {
+    async fn vocab_id(&self) -> i32 {
+        self.vocab_id
+    }
+
+    async fn learning(&self) -> String {
+        self.learning.clone()
+    }
+
+    async fn first_lang(&self) -> String {
+        self.first_lang.clone()
+    }
+
+    async fn pos(&self) -> String {
+        self.pos.clone()
+    }
+
+    async fn skill(&self) -> String {
+        self.skill.clone()
+    }
+
+    async fn tags(&self) -> Vec<String> {
+        self.tags.clone()
+    }
+}
+
+/// GraphQL Queries of tags
+#[derive(Default)]
+pub struct TagQuery;
+
+#[Object]
+impl TagQuery {
+    /// Retrieves every tag of vocab, by name.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping each `TagSummary` with the number of vocab tagged with it, or an error on failure.
+    async fn get_tags(&self) -> Result<Vec<TagSummary>> {
+        let tags = run_blocking(move || TagService::instance().get_tags())
+            .await
+            .map_err(|err| err.extend())?;
+
+        Ok(tags.into_iter().map(TagSummary::from).collect())
+    }
+
+    /// Retrieves the vocab tagged with every one of the tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - The names of the tags, compared ignoring case.
+    /// * `first` - Optional. The maximum number of vocab in the page, see [`page_size`].
+    /// * `after` - Optional. The cursor of the last vocab of the previous page.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping a connection of each `TaggedVocab` with all of its tags, by vocab id, or an error
+    /// on failure, e.g. `VALIDATION` without any tags.
+    async fn get_tagged_vocab(
+        &self,
+        tags: Vec<String>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> Result<Connection<i32, TaggedVocab>> {
+        let size = page_size(first).map_err(|err| err.extend())?;
+        let after = decode_after::<i32>(after).map_err(|err| err.extend())?;
+
+        let tagged = run_blocking(move || {
+            TagService::instance().get_tagged_vocab(&tags, after, size as i64 + 1)
+        })
+        .await
+        .map_err(|err| err.extend())?;
+
+        Ok(keyset_connection(
+            tagged,
+            size,
+            after.is_some(),
+            |(vocab, _)| vocab.id,
+            TaggedVocab::from,
+        ))
+    }
+}
+
+/// GraphQL Mutations of tags
+#[derive(Default)]
+pub struct TagMutation;
+
+#[Object]
+impl TagMutation {
+    /// Tags vocab, every vocab gets every tag. Tags are in lowercase and created when they do not exist yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `vocab_ids` - The unique identifiers of the vocab to tag.
+    /// * `tags` - The names of the tags.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the number of tags added, or an error on failure,
+    /// e.g. `NOT_FOUND` for an unknown vocab.
+    async fn tag_vocab(&self, vocab_ids: Vec<i32>, tags: Vec<String>) -> Result<i32> {
+        let num_tagged = run_blocking(move || TagService::instance().tag_vocab(&vocab_ids, &tags))
+            .await
+            .map_err(|err| err.extend())?;
+
+        Ok(num_tagged as i32)
+    }
+
+    /// Removes tags from vocab.
+    ///
+    /// # Arguments
+    ///
+    /// * `vocab_ids` - The unique identifiers of the vocab.
+    /// * `tags` - The names of the tags, compared ignoring case.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` wrapping the number of tags removed, or an error on failure,
+    /// e.g. `VALIDATION` for an empty tag.
+    async fn untag_vocab(&self, vocab_ids: Vec<i32>, tags: Vec<String>) -> Result<i32> {
+        let num_untagged =
+            run_blocking(move || TagService::instance().untag_vocab(&vocab_ids, &tags))
+                .await
+                .map_err(|err| err.extend())?;
+
+        Ok(num_untagged as i32)
+    }
+}
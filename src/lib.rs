@@ -1,9 +1,12 @@
+#[cfg(feature = "aws")]
 pub mod aws;
 pub mod config;
 pub mod dal;
+pub mod error;
 pub mod gql;
 pub mod models;
 pub mod schema;
+pub mod secrets;
 pub mod sl;
 
 #[cfg(test)]
@@ -1,7 +1,12 @@
 use dotenv::dotenv;
-use palabras::aws::glue::find_the_database;
-use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::config::app_config::init_app_config;
+use palabras::config::init_language_packs;
+use palabras::dal::db_connection::{
+    establish_connection_pool_with_config, verify_connection_migrate_db,
+};
 use palabras::gql::router::start_axum;
+use palabras::secrets::find_the_database;
+use std::env;
 use std::error::Error;
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
@@ -9,31 +14,28 @@ use tokio::net::TcpListener;
 /// Starts the web server using Axum framework to handle GraphQL queries.
 ///
 /// This program initializes the database connection, sets up routing for GraphQL queries,
-/// and starts an HTTP server to listen for requests. The server's listening address and port
-/// are configured through the `PAL_SERVER_ADDR` environment variable. Otherwise, it defaults to `127.0.0.1:3000`.
-/// The database connection is established based on the `PALABRA_DATABASE_URL`
-/// environment variable.
+/// and starts an HTTP server to listen for requests. All settings come from the
+/// [`AppConfig`](palabras::config::app_config::AppConfig), the server's listening address and port
+/// are `server.addr`, defaulting to `0.0.0.0:3000`.
 ///
-/// # Environment Variables
+/// # Configuration
 ///
-/// - `PAL_DB_LINK`: The link used to get the database connection info.
-/// - `PAL_REGION`: Region where the database connection info is expected to be located.
-/// - `PAL_DATABASE_URL`: Specifies the fallback database URL for connecting to the PostgreSQL database.
-///   If neither of the former env vars are set, this will attempt to be used.
+/// Settings are loaded in layers, each overriding the one before:
 ///
+/// 1. The built-in defaults.
+/// 2. The config file, given with `--config <file>`, otherwise named by `PAL_CONFIG_FILE`, otherwise
+///    `palabras.toml` if it exists. Files ending in `.json` are read as JSON, others as TOML.
+/// 3. Env vars, e.g. `PAL_SERVER_ADDR` or `PAL_DB_POOL_SIZE`,
+///    see [`OVERRIDABLE_SETTINGS`](palabras::config::app_config::OVERRIDABLE_SETTINGS).
+/// 4. Command line arguments, `--set key=value`, e.g. `--set server.addr=127.0.0.1:3000`.
 ///
-/// - `PAL_SERVER_ADDR`: Defines the IP address and port where the server will listen for incoming HTTP requests.
-///   The format should be `IP:PORT`, e.g., `127.0.0.1:3000`.
+/// The database URL is a secret and not part of the config, it is found by the secret provider chosen by
+/// `database.secret_provider`, see [`palabras::secrets::secret_provider_for`]. The `env` provider reads
+/// `PAL_DATABASE_URL`.
 ///
 /// # Panics
 ///
-/// The function will panic if:
-///
-/// - The there is no method available to connect to the database.
-///
-/// - The `PAL_SERVER_ADDR`  cannot be parsed as a `SocketAddr`.
-///
-/// - The TCP listener fails to bind to the specified address.
+/// The function will panic if the TCP listener fails to bind to the specified address.
 ///
 /// # Examples
 ///
@@ -44,34 +46,31 @@ use tokio::net::TcpListener;
 /// PAL_SERVER_ADDR=127.0.0.1:3000
 /// ```
 ///
-/// Run the server with `cargo run`, and it will start listening on the specified `PAL_SERVER_ADDR`.
+/// Run the server with `cargo run`, and it will start listening on the configured `server.addr`.
+/// Settings can also be given on the command line:
+///
+/// ```sh
+/// cargo run -- --config palabras.toml --set database.pool_size=20
+/// ```
 ///
 /// # Errors
 ///
-/// Returns an error if any operation within the function fails, encapsulated within a `Box<dyn Error>`.
+/// Returns an error if any operation within the function fails, encapsulated within a `Box<dyn Error>`,
+/// including an invalid configuration or when there is no method available to connect to the database.
 #[tokio::main]
 pub async fn main() -> Result<(), Box<dyn Error>> {
     println!("Num CPUs {}", num_cpus::get());
 
-    // Returning the PROD database URL defined in the env var: PALABRA_DATABASE_URL
     dotenv().ok(); // Load environment variables from .env file
+    let (config, _) = init_app_config(env::args().skip(1).collect())?;
+    init_language_packs()?;
 
-    let db_url = find_the_database().await;
-    establish_connection_pool(db_url);
+    let db_url = find_the_database(&config.database).await?;
+    establish_connection_pool_with_config(db_url, &config.database.pool_config());
     verify_connection_migrate_db()?;
 
-    // Get the server address from the `PAL_SERVER_ADDR` environment variable
-    let env_server_addr = std::env::var("PAL_SERVER_ADDR").unwrap_or_default();
-    let server_addr = if env_server_addr.is_empty() {
-        "0.0.0.0:3000".to_string()
-    } else {
-        env_server_addr
-    };
-
-    // Parse the address as a `SocketAddr`
-    let addr: SocketAddr = server_addr
-        .parse()
-        .expect("Failed to parse SERVER_ADDR as SocketAddr");
+    // The address was validated when the config was loaded
+    let addr: SocketAddr = config.server.addr.parse()?;
 
     println!("Started server running on {addr}");
 
@@ -1,20 +1,30 @@
 use crate::schema::palabras::awesome_person;
+use crate::schema::palabras::conjugation_stat;
+use crate::schema::palabras::deck;
+use crate::schema::palabras::deck_vocab;
+use crate::schema::palabras::grading_policy;
+use crate::schema::palabras::study_session;
+use crate::schema::palabras::study_session_item;
+use crate::schema::palabras::tag;
 use crate::schema::palabras::vocab;
+use crate::schema::palabras::vocab_alternative;
+use crate::schema::palabras::vocab_attempt;
 use crate::schema::palabras::vocab_study;
+use crate::schema::palabras::vocab_tag;
 use chrono::prelude::*;
 use diesel::prelude::*;
 
 /// A struct representing a vocabulary item in a language learning application.
 ///
 /// This struct is used to store information about words or phrases that users are learning,
-/// including translations, alternatives, and metadata to assist in the learning process.
+/// including translations and metadata to assist in the learning process. Additional correct answers are
+/// kept as [`VocabAlternative`] records.
 ///
 /// # Fields
 /// - `id`: Primary key used to uniquely identify a vocabulary item in the data layer.
 /// - `learning_lang`: The word or phrase in the language being learned.
 /// - `first_lang`: The translation of the word or phrase into the user's first language, used as a prompt.
 /// - `created`: Timestamp when the vocabulary item was created. It is typically set automatically to the current time.
-/// - `alternatives`: Optional. Additional correct answers or variations in the learning language.
 /// - `skill`: Optional. The skill or category associated with the vocabulary item, used for organizing content.
 /// - `infinitive`: Optional. For verbs, the infinitive form of the word. `None` for non-verb vocabulary items.
 /// - `pos`: Optional. The part of speech of the vocabulary item, aiding in the application of grammatical rules.
@@ -22,6 +32,9 @@ use diesel::prelude::*;
 /// - `num_learning_words`: The number of words contained in the `learning_lang` field, calculated for analytical purposes.
 /// - `known_lang_code`: Language code for this known language.
 /// - `learning_lang_code`: Language code for this learning language.
+/// - `gender`: Optional. For nouns, the grammatical gender, `m` or `f`, used to check the article of answers.
+/// - `lang_variant`: Optional. The regional variant the word is used in, a BCP-47 style code such as `es-ES`.
+///   `None` or empty when the word is used in every variant of the learning language.
 ///
 /// # Usage
 /// This struct is primarily used with Diesel ORM for querying and manipulating vocabulary data in a PostgreSQL database.
@@ -34,7 +47,6 @@ pub struct Vocab {
     pub learning_lang: String,
     pub first_lang: String,
     pub created: DateTime<Utc>,
-    pub alternatives: Option<String>,
     pub skill: Option<String>,
     pub infinitive: Option<String>,
     pub pos: Option<String>,
@@ -42,6 +54,8 @@ pub struct Vocab {
     pub num_learning_words: i32,
     pub known_lang_code: String,
     pub learning_lang_code: String,
+    pub gender: Option<String>,
+    pub lang_variant: Option<String>,
 }
 
 impl Default for Vocab {
@@ -51,7 +65,6 @@ impl Default for Vocab {
             learning_lang: Default::default(),
             first_lang: Default::default(),
             created: Utc::now(),
-            alternatives: Default::default(),
             skill: Default::default(),
             infinitive: Default::default(),
             pos: Default::default(),
@@ -59,6 +72,8 @@ impl Default for Vocab {
             num_learning_words: 1,
             known_lang_code: Default::default(),
             learning_lang_code: Default::default(),
+            gender: Default::default(),
+            lang_variant: Default::default(),
         }
     }
 }
@@ -77,14 +92,13 @@ impl Default for Vocab {
 ///
 /// This struct streamlines the process of adding new vocabulary items by organizing all relevant information into a single data structure,
 /// making it easy to maintain and extend the vocabulary database.
-#[derive(Insertable)]
+#[derive(Insertable, Clone)]
 #[diesel(table_name = vocab)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct NewVocab {
     pub learning_lang: String,
     pub first_lang: String,
     pub created: DateTime<Utc>,
-    pub alternatives: Option<String>,
     pub skill: Option<String>,
     pub infinitive: Option<String>,
     pub pos: Option<String>,
@@ -92,6 +106,8 @@ pub struct NewVocab {
     pub num_learning_words: i32,
     pub known_lang_code: String,
     pub learning_lang_code: String,
+    pub gender: Option<String>,
+    pub lang_variant: Option<String>,
 }
 
 impl Default for NewVocab {
@@ -100,7 +116,6 @@ impl Default for NewVocab {
             learning_lang: Default::default(),
             first_lang: Default::default(),
             created: Utc::now(),
-            alternatives: Default::default(),
             skill: Default::default(),
             infinitive: Default::default(),
             pos: Default::default(),
@@ -108,10 +123,116 @@ impl Default for NewVocab {
             num_learning_words: 1,
             known_lang_code: Default::default(),
             learning_lang_code: Default::default(),
+            gender: Default::default(),
+            lang_variant: Default::default(),
         }
     }
 }
 
+/// An additional correct answer of a vocab, in the learning language.
+///
+/// # Fields
+/// - `id`: Primary key used to uniquely identify the alternative.
+/// - `vocab_id`: The vocab the alternative answers.
+/// - `alternative`: The alternative answer, unique for its vocab.
+/// - `register`: Optional. The register the alternative is used in, e.g. `formal` or `informal`.
+/// - `lang_variant`: Optional. The regional variant the alternative is used in, a BCP-47 style code such as
+///   `es-MX` or `es-ES`.
+///
+/// The register is mentioned in the feedback when a response matches the alternative, and the variant when it is
+/// not the awesome person's preferred variant.
+#[derive(Queryable, Selectable, Identifiable, Associations, Clone, Debug, PartialEq)]
+#[diesel(belongs_to(Vocab))]
+#[diesel(table_name = vocab_alternative)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct VocabAlternative {
+    pub id: i32,
+    pub vocab_id: i32,
+    pub alternative: String,
+    pub register: Option<String>,
+    pub lang_variant: Option<String>,
+}
+
+/// A struct for inserting a new alternative answer of a vocab, see [`VocabAlternative`] for details.
+#[derive(Insertable, Default, Clone)]
+#[diesel(table_name = vocab_alternative)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewVocabAlternative {
+    pub vocab_id: i32,
+    pub alternative: String,
+    pub register: Option<String>,
+    pub lang_variant: Option<String>,
+}
+
+/// A tag of vocab, e.g. `food`, `a2` or `false-friend`. A vocab can have any number of tags and the skill of
+/// each vocab is also one of its tags.
+///
+/// # Fields
+/// - `id`: Primary key used to uniquely identify the tag.
+/// - `name`: The name of the tag, unique, trimmed and in lowercase.
+#[derive(Queryable, Selectable, Identifiable, Clone, Debug, PartialEq)]
+#[diesel(table_name = tag)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Tag {
+    pub id: i32,
+    pub name: String,
+}
+
+/// A struct for inserting a new tag, see [`Tag`] for details.
+#[derive(Insertable, Clone, Debug)]
+#[diesel(table_name = tag)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewTag {
+    pub name: String,
+}
+
+/// A tag of a vocab.
+///
+/// # Fields
+/// - `vocab_id`: The vocab tagged.
+/// - `tag_id`: The tag of the vocab.
+#[derive(Queryable, Selectable, Insertable, Clone, Debug, PartialEq)]
+#[diesel(table_name = vocab_tag)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct VocabTag {
+    pub vocab_id: i32,
+    pub tag_id: i32,
+}
+
+/// The criteria of a vocab search.
+///
+/// # Fields
+/// - `text`: The text searched for in the learning language, alternatives and first language, accents are ignored.
+/// - `learning_lang_code`: Optional. Only vocab of this learning language is found.
+/// - `pos`: Optional. Only vocab with this part of speech is found, case is ignored.
+/// - `tag`: Optional. Only vocab with this tag is found.
+/// - `awesome_person_id`: Optional. The learner checked for enrolled vocab.
+#[derive(Clone, Debug, Default)]
+pub struct VocabSearch {
+    pub text: String,
+    pub learning_lang_code: Option<String>,
+    pub pos: Option<String>,
+    pub tag: Option<String>,
+    pub awesome_person_id: Option<i32>,
+}
+
+/// A vocab found by a [`VocabSearch`].
+///
+/// # Fields
+/// - `vocab`: The vocab found.
+/// - `rank`: How well the vocab matches, an exact match is 3, a prefix 2 plus its trigram similarity and
+///   any other match its trigram similarity alone.
+/// - `enrolled`: True when the learner searching has a `VocabStudy` of the vocab.
+#[derive(QueryableByName, Clone)]
+pub struct VocabMatch {
+    #[diesel(embed)]
+    pub vocab: Vocab,
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub rank: f64,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub enrolled: bool,
+}
+
 /// Represents a record of study progress for a specific vocabulary item by an awesome person (user).
 ///
 /// This struct is used to query and manipulate data in the `vocab_study` table and provides a comprehensive
@@ -131,6 +252,10 @@ impl Default for NewVocab {
 /// - `last_tested`: The timestamp of the last attempt to study this vocabulary word, used to schedule future reviews.
 /// - `well_known`: A boolean flag indicating whether the user has mastered this vocabulary word to the extent that it can be considered "well known" and potentially deprioritized in future study sessions.
 /// - `user_notes`: Optional notes added by the user to aid in recall or provide additional context for the vocabulary word.
+/// - `lapses`: The number of answers in a row that were not a perfect match, reset by a perfect match.
+/// - `leech`: Whether the vocabulary word was failed so often that it is considered a leech, see [`crate::config::LeechConfig`].
+/// - `suspended`: A suspended vocabulary word is left out of the study set, leeches are suspended when detected.
+/// - `user_hint`: Optional hint of the user's own, e.g. a mnemonic for a leech, unlike the hint of the vocab it is not shared.
 ///
 /// # Usage
 /// The `VocabStudy` struct is integral to the operation of a language learning application, as it captures and reflects
@@ -151,6 +276,10 @@ pub struct VocabStudy {
     pub well_known: bool,
     pub user_notes: Option<String>,
     pub correct_attempts: Option<i32>,
+    pub lapses: i32,
+    pub leech: bool,
+    pub suspended: bool,
+    pub user_hint: Option<String>,
 }
 
 impl Default for VocabStudy {
@@ -167,6 +296,10 @@ impl Default for VocabStudy {
             user_notes: None,
             attempts: None,
             correct_attempts: None,
+            lapses: 0,
+            leech: false,
+            suspended: false,
+            user_hint: None,
         }
     }
 }
@@ -188,6 +321,72 @@ pub struct NewVocabStudy {
     pub well_known: bool,
     pub user_notes: Option<String>,
     pub correct_attempts: Option<i32>,
+    pub lapses: i32,
+    pub leech: bool,
+    pub suspended: bool,
+    pub user_hint: Option<String>,
+}
+
+/// The filters of the vocab an awesome person studies, every filter that is set must pass.
+///
+/// # Fields
+/// - `well_known`: Optional. Only the vocab that is, or is not, well known.
+/// - `learning_lang_code`: Optional. Only vocab of this learning language.
+/// - `pos`: Optional. Only vocab with this part of speech, case is ignored.
+#[derive(Clone, Debug, Default)]
+pub struct VocabStudyFilter {
+    pub well_known: Option<bool>,
+    pub learning_lang_code: Option<String>,
+    pub pos: Option<String>,
+}
+
+/// The orders of the vocab an awesome person studies, each breaks ties by the vocab study id.
+///
+/// - `Weakness`: The lowest percentage correct first, vocab never answered counts as 0.
+/// - `Recency`: The vocab tested last first, vocab never answered last.
+/// - `Alphabetic`: By the vocab in the learning language.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum VocabStudyOrder {
+    #[default]
+    Weakness,
+    Recency,
+    Alphabetic,
+}
+
+/// The position of a vocab study in a [`VocabStudyOrder`], the value it is ordered by and its id.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VocabStudyKey {
+    Weakness(f64, i32),
+    Recency(DateTime<Utc>, i32),
+    Alphabetic(String, i32),
+}
+
+impl VocabStudyKey {
+    /// The key of a vocab study and its vocab in the order.
+    pub fn of(order: VocabStudyOrder, vocab_study: &VocabStudy, vocab: &Vocab) -> Self {
+        match order {
+            VocabStudyOrder::Weakness => VocabStudyKey::Weakness(
+                vocab_study.percentage_correct.unwrap_or_default(),
+                vocab_study.id,
+            ),
+            VocabStudyOrder::Recency => VocabStudyKey::Recency(
+                vocab_study.last_tested.unwrap_or(DateTime::UNIX_EPOCH),
+                vocab_study.id,
+            ),
+            VocabStudyOrder::Alphabetic => {
+                VocabStudyKey::Alphabetic(vocab.learning_lang.clone(), vocab_study.id)
+            }
+        }
+    }
+
+    /// The order the key is a position in.
+    pub fn order(&self) -> VocabStudyOrder {
+        match self {
+            VocabStudyKey::Weakness(..) => VocabStudyOrder::Weakness,
+            VocabStudyKey::Recency(..) => VocabStudyOrder::Recency,
+            VocabStudyKey::Alphabetic(..) => VocabStudyOrder::Alphabetic,
+        }
+    }
 }
 
 /// Represents an awesome person (user) in the language learning application, tracking their progress and personal details.
@@ -207,6 +406,10 @@ pub struct NewVocabStudy {
 /// - `sec_code`: A unique code assigned to the user, particularly useful during the alpha testing phase for easy identification without requiring authentication.
 /// - `smallest_vocab`: Specifies the smallest size of vocabulary word that the user is comfortable with, assisting in customizing the difficulty level of the tests.
 /// - `max_learning_words`: The maximum number of new words (learning words) the user is comfortable being tested on in a single session, helping tailor the learning experience to the user's capacity.
+/// - `preferred_variant`: Optional. The regional variant the user learns, a BCP-47 style code such as `es-MX`. Answers are
+///   expected in this variant, and answers from other variants are accepted with a note.
+/// - `new_per_day`: The maximum number of new words, never answered before, introduced in the study list each day.
+/// - `reviews_per_day`: The maximum number of answers to words answered before, reviews, each day.
 ///
 /// # Usage
 /// The `AwesomePerson` struct plays a crucial role in the personalized adaptation of the language learning application to the user's
@@ -226,6 +429,9 @@ pub struct AwesomePerson {
     pub sec_code: String,
     pub smallest_vocab: i32,
     pub max_learning_words: i32,
+    pub preferred_variant: Option<String>,
+    pub new_per_day: i32,
+    pub reviews_per_day: i32,
 }
 
 impl Default for AwesomePerson {
@@ -241,6 +447,9 @@ impl Default for AwesomePerson {
             sec_code: "".to_string(),
             smallest_vocab: 1,
             max_learning_words: 5,
+            preferred_variant: None,
+            new_per_day: 20,
+            reviews_per_day: 200,
         }
     }
 }
@@ -261,6 +470,7 @@ pub struct NewAwesomePerson {
     pub sec_code: String,
     pub smallest_vocab: i32,
     pub max_learning_words: i32,
+    pub preferred_variant: Option<String>,
 }
 
 impl Default for NewAwesomePerson {
@@ -275,10 +485,215 @@ impl Default for NewAwesomePerson {
             sec_code: "".to_string(),
             smallest_vocab: 1,
             max_learning_words: 5,
+            preferred_variant: None,
         }
     }
 }
 
+/// The grading thresholds used when checking an awesome person's responses.
+///
+/// Each awesome person may store their own policy, otherwise the default from
+/// [`crate::config::GradingPolicyConfig`] applies.
+///
+/// # Fields
+/// - `awesome_person_id`: The awesome person this policy belongs to, also the primary key.
+/// - `max_distance`: The distance given to the worst possible answer, larger distances are capped to it.
+/// - `well_known_threshold`: Once the percentage correct is above this, the vocab is considered well known.
+/// - `close_distance`: Answers up to this distance are reported as close.
+/// - `perfect_weight`: How many answers a perfect match counts as when averaging the percentage correct.
+/// - `updated`: The timestamp of the last change to the policy.
+#[derive(Queryable, Selectable, Identifiable, Insertable, AsChangeset, Clone, Debug, PartialEq)]
+#[diesel(table_name = grading_policy)]
+#[diesel(primary_key(awesome_person_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GradingPolicy {
+    pub awesome_person_id: i32,
+    pub max_distance: i32,
+    pub well_known_threshold: f64,
+    pub close_distance: i32,
+    pub perfect_weight: f64,
+    pub updated: DateTime<Utc>,
+}
+
+/// An awesome person's accuracy on one person and tense of the conjugation drills.
+///
+/// # Fields
+/// - `awesome_person_id`: The awesome person drilling.
+/// - `tense`: The tense drilled, the key of a [`crate::sl::conjugation::Tense`], e.g. `presente_subjuntivo`.
+/// - `person`: The person drilled, the key of a [`crate::sl::conjugation::Person`], e.g. `nosotros`.
+/// - `attempts`: The number of drills answered.
+/// - `correct_attempts`: The number of drills answered with a perfect match.
+/// - `updated`: The timestamp of the last answer.
+#[derive(Queryable, Selectable, Insertable, Clone, Debug, PartialEq)]
+#[diesel(table_name = conjugation_stat)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ConjugationStat {
+    pub awesome_person_id: i32,
+    pub tense: String,
+    pub person: String,
+    pub attempts: i32,
+    pub correct_attempts: i32,
+    pub updated: DateTime<Utc>,
+}
+
+impl ConjugationStat {
+    /// The share of attempts answered correctly, 0 without any attempts.
+    pub fn percentage_correct(&self) -> f64 {
+        if self.attempts > 0 {
+            self.correct_attempts as f64 / self.attempts as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A struct for inserting an answer into the attempt history of an awesome person, the history the
+/// daily limits are counted against.
+///
+/// # Fields
+/// - `awesome_person_id`: The awesome person answering.
+/// - `vocab_study_id`: The vocab study answered.
+/// - `new_word`: Whether it was the first answer of the vocab study, introducing a new word.
+/// - `correct`: Whether the answer was a perfect match.
+/// - `attempted`: The timestamp of the answer.
+#[derive(Insertable, Clone, Debug, PartialEq)]
+#[diesel(table_name = vocab_attempt)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewVocabAttempt {
+    pub awesome_person_id: i32,
+    pub vocab_study_id: i32,
+    pub new_word: bool,
+    pub correct: bool,
+    pub attempted: DateTime<Utc>,
+}
+
+/// A study session of an awesome person, the answers given between starting and finishing it.
+///
+/// # Fields
+/// - `id`: Primary key used to uniquely identify the session.
+/// - `awesome_person_id`: The awesome person studying.
+/// - `started`: The timestamp the session was started.
+/// - `finished`: Optional. The timestamp the session was finished, `None` while it is open.
+/// - `num_answers`: The number of answers given in the session, re-drills included.
+/// - `num_correct`: The number of answers given in the session with a perfect match.
+#[derive(Queryable, Selectable, Identifiable, AsChangeset, Clone, Debug, PartialEq)]
+#[diesel(table_name = study_session)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct StudySession {
+    pub id: i32,
+    pub awesome_person_id: i32,
+    pub started: DateTime<Utc>,
+    pub finished: Option<DateTime<Utc>>,
+    pub num_answers: i32,
+    pub num_correct: i32,
+}
+
+impl StudySession {
+    /// The share of answers with a perfect match, 0 without any answers.
+    pub fn percentage_correct(&self) -> f64 {
+        if self.num_answers > 0 {
+            self.num_correct as f64 / self.num_answers as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A struct for inserting a new study session, see [`StudySession`] for details.
+#[derive(Insertable)]
+#[diesel(table_name = study_session)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewStudySession {
+    pub awesome_person_id: i32,
+    pub started: DateTime<Utc>,
+}
+
+/// A vocab study answered in a study session.
+///
+/// # Fields
+/// - `study_session_id`: The session the vocab study was answered in.
+/// - `vocab_study_id`: The vocab study answered.
+/// - `attempts`: The number of answers given in the session.
+/// - `correct_attempts`: The number of answers given in the session with a perfect match.
+/// - `percentage_before`: The percentage correct of the vocab study before its first answer in the session.
+/// - `percentage_after`: The percentage correct of the vocab study after its last answer in the session.
+/// - `missed`: Whether any answer in the session was not a perfect match.
+/// - `redrilled`: Whether the vocab study was answered again after it was missed.
+/// - `created`: The timestamp of the first answer in the session.
+#[derive(Queryable, Selectable, Identifiable, Insertable, AsChangeset, Clone, Debug, PartialEq)]
+#[diesel(table_name = study_session_item)]
+#[diesel(primary_key(study_session_id, vocab_study_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct StudySessionItem {
+    pub study_session_id: i32,
+    pub vocab_study_id: i32,
+    pub attempts: i32,
+    pub correct_attempts: i32,
+    pub percentage_before: f64,
+    pub percentage_after: f64,
+    pub missed: bool,
+    pub redrilled: bool,
+    pub created: DateTime<Utc>,
+}
+
+impl StudySessionItem {
+    /// The change of the percentage correct over the session.
+    pub fn percentage_change(&self) -> f64 {
+        self.percentage_after - self.percentage_before
+    }
+}
+
+/// A deck of vocab put together by an awesome person, studied one deck at a time.
+///
+/// # Fields
+/// - `id`: Primary key used to uniquely identify the deck.
+/// - `owner_id`: The awesome person the deck belongs to.
+/// - `name`: The name of the deck, unique for its owner.
+/// - `description`: Optional. What the deck is about.
+/// - `known_lang_code`: The language code of the language known, e.g. `en`.
+/// - `learning_lang_code`: The language code of the language learned, e.g. `es`.
+/// - `created`: The timestamp the deck was created.
+#[derive(Queryable, Selectable, Identifiable, AsChangeset, Clone, Debug, PartialEq)]
+#[diesel(table_name = deck)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Deck {
+    pub id: i32,
+    pub owner_id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub known_lang_code: String,
+    pub learning_lang_code: String,
+    pub created: DateTime<Utc>,
+}
+
+/// A struct for inserting a new deck, see [`Deck`] for details.
+#[derive(Insertable, Clone, Debug, Default)]
+#[diesel(table_name = deck)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewDeck {
+    pub owner_id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub known_lang_code: String,
+    pub learning_lang_code: String,
+}
+
+/// A vocab in a deck, the same vocab can be in any number of decks.
+///
+/// # Fields
+/// - `deck_id`: The deck the vocab is in.
+/// - `vocab_id`: The vocab in the deck.
+/// - `added`: The timestamp the vocab was added to the deck.
+#[derive(Queryable, Selectable, Identifiable, Insertable, Clone, Debug, PartialEq)]
+#[diesel(table_name = deck_vocab)]
+#[diesel(primary_key(deck_id, vocab_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DeckVocab {
+    pub deck_id: i32,
+    pub vocab_id: i32,
+    pub added: DateTime<Utc>,
+}
+
 /// Represents the relationship between a vocabulary item (`Vocab`) and its study metrics (`VocabStudy`) for an individual user
 /// (`AwesomePerson`) in the language learning application.
 ///
@@ -13,6 +13,83 @@ pub mod palabras {
             sec_code -> Varchar,
             smallest_vocab -> Int4,
             max_learning_words -> Int4,
+            preferred_variant -> Nullable<Varchar>,
+            new_per_day -> Int4,
+            reviews_per_day -> Int4,
+        }
+    }
+
+    diesel::table! {
+        palabras.conjugation_stat (awesome_person_id, tense, person) {
+            awesome_person_id -> Int4,
+            tense -> Varchar,
+            person -> Varchar,
+            attempts -> Int4,
+            correct_attempts -> Int4,
+            updated -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        palabras.deck (id) {
+            id -> Int4,
+            owner_id -> Int4,
+            name -> Varchar,
+            description -> Nullable<Varchar>,
+            known_lang_code -> Varchar,
+            learning_lang_code -> Varchar,
+            created -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        palabras.deck_vocab (deck_id, vocab_id) {
+            deck_id -> Int4,
+            vocab_id -> Int4,
+            added -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        palabras.grading_policy (awesome_person_id) {
+            awesome_person_id -> Int4,
+            max_distance -> Int4,
+            well_known_threshold -> Float8,
+            close_distance -> Int4,
+            perfect_weight -> Float8,
+            updated -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        palabras.study_session (id) {
+            id -> Int4,
+            awesome_person_id -> Int4,
+            started -> Timestamptz,
+            finished -> Nullable<Timestamptz>,
+            num_answers -> Int4,
+            num_correct -> Int4,
+        }
+    }
+
+    diesel::table! {
+        palabras.study_session_item (study_session_id, vocab_study_id) {
+            study_session_id -> Int4,
+            vocab_study_id -> Int4,
+            attempts -> Int4,
+            correct_attempts -> Int4,
+            percentage_before -> Float8,
+            percentage_after -> Float8,
+            missed -> Bool,
+            redrilled -> Bool,
+            created -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        palabras.tag (id) {
+            id -> Int4,
+            name -> Varchar,
         }
     }
 
@@ -22,7 +99,6 @@ pub mod palabras {
             learning_lang -> Varchar,
             first_lang -> Varchar,
             created -> Timestamptz,
-            alternatives -> Nullable<Varchar>,
             skill -> Nullable<Varchar>,
             infinitive -> Nullable<Varchar>,
             pos -> Nullable<Varchar>,
@@ -30,6 +106,36 @@ pub mod palabras {
             num_learning_words -> Int4,
             known_lang_code -> Varchar,
             learning_lang_code -> Varchar,
+            gender -> Nullable<Varchar>,
+            lang_variant -> Nullable<Varchar>,
+        }
+    }
+
+    diesel::table! {
+        palabras.vocab_attempt (id) {
+            id -> Int4,
+            awesome_person_id -> Int4,
+            vocab_study_id -> Int4,
+            new_word -> Bool,
+            correct -> Bool,
+            attempted -> Timestamptz,
+        }
+    }
+
+    diesel::table! {
+        palabras.vocab_alternative (id) {
+            id -> Int4,
+            vocab_id -> Int4,
+            alternative -> Varchar,
+            register -> Nullable<Varchar>,
+            lang_variant -> Nullable<Varchar>,
+        }
+    }
+
+    diesel::table! {
+        palabras.vocab_tag (vocab_id, tag_id) {
+            vocab_id -> Int4,
+            tag_id -> Int4,
         }
     }
 
@@ -46,11 +152,42 @@ pub mod palabras {
             well_known -> Bool,
             user_notes -> Nullable<Varchar>,
             correct_attempts -> Nullable<Int4>,
+            lapses -> Int4,
+            leech -> Bool,
+            suspended -> Bool,
+            user_hint -> Nullable<Varchar>,
         }
     }
 
+    diesel::joinable!(conjugation_stat -> awesome_person (awesome_person_id));
+    diesel::joinable!(deck -> awesome_person (owner_id));
+    diesel::joinable!(deck_vocab -> deck (deck_id));
+    diesel::joinable!(deck_vocab -> vocab (vocab_id));
+    diesel::joinable!(grading_policy -> awesome_person (awesome_person_id));
+    diesel::joinable!(study_session -> awesome_person (awesome_person_id));
+    diesel::joinable!(study_session_item -> study_session (study_session_id));
+    diesel::joinable!(study_session_item -> vocab_study (vocab_study_id));
+    diesel::joinable!(vocab_alternative -> vocab (vocab_id));
+    diesel::joinable!(vocab_attempt -> awesome_person (awesome_person_id));
+    diesel::joinable!(vocab_attempt -> vocab_study (vocab_study_id));
+    diesel::joinable!(vocab_tag -> tag (tag_id));
+    diesel::joinable!(vocab_tag -> vocab (vocab_id));
     diesel::joinable!(vocab_study -> awesome_person (awesome_person_id));
     diesel::joinable!(vocab_study -> vocab (vocab_id));
 
-    diesel::allow_tables_to_appear_in_same_query!(awesome_person, vocab, vocab_study,);
+    diesel::allow_tables_to_appear_in_same_query!(
+        awesome_person,
+        conjugation_stat,
+        deck,
+        deck_vocab,
+        grading_policy,
+        study_session,
+        study_session_item,
+        tag,
+        vocab,
+        vocab_alternative,
+        vocab_attempt,
+        vocab_study,
+        vocab_tag,
+    );
 }
@@ -1,2 +1,13 @@
+pub mod articles;
+pub mod conjugation;
+pub mod daily_quota;
+pub mod decks;
 pub mod fuzzy_match_vocab;
+pub mod leeches;
+pub mod my_vocab;
+pub mod phrase_match;
+pub mod search;
+pub mod study_composer;
+pub mod study_session;
 pub mod sync_vocab;
+pub mod tags;
@@ -0,0 +1,90 @@
+use crate::config::VocabConfig;
+
+// Checks the article given with a noun against the noun's grammatical gender.
+// The articles of each gender come from the language pack of the learning language.
+
+/// The articles of a language pack, for checking the article of a noun with a known gender.
+///
+/// # Example
+///
+/// ```
+/// use palabras::config::VocabConfig;
+/// use palabras::sl::articles::GenderArticles;
+/// use std::collections::HashMap;
+///
+/// let spanish = VocabConfig {
+///     articles: Some(HashMap::from([
+///         ("m".to_string(), "el, un".to_string()),
+///         ("f".to_string(), "la, una".to_string()),
+///     ])),
+///     ..Default::default()
+/// };
+///
+/// let articles = GenderArticles::for_gender(&spanish, "f").expect("Spanish has feminine articles");
+/// assert_eq!(articles.expected(), "la");
+/// assert_eq!(articles.split("el mano"), (Some("el"), "mano"));
+/// assert_eq!(articles.split("mano"), (None, "mano"));
+/// assert!(articles.is_right("una"));
+/// assert!(!articles.is_right("el"));
+/// ```
+pub struct GenderArticles<'a> {
+    right: Vec<&'a str>,
+    all: Vec<&'a str>,
+}
+
+impl<'a> GenderArticles<'a> {
+    /// The articles of the pack for a gender.
+    ///
+    /// # Returns
+    ///
+    /// `None` when the pack has no articles for `gender`, in which case articles are not checked.
+    pub fn for_gender(pack: &'a VocabConfig, gender: &str) -> Option<Self> {
+        let articles = pack.articles.as_ref()?;
+        let split = |instances: &'a String| {
+            instances
+                .split(',')
+                .map(str::trim)
+                .filter(|article| !article.is_empty())
+        };
+
+        let right: Vec<&str> = split(articles.get(gender.trim())?).collect();
+        if right.is_empty() {
+            return None;
+        }
+        let all = articles.values().flat_map(split).collect();
+
+        Some(GenderArticles { right, all })
+    }
+
+    /// The article shown in feedback, the first listed for the gender.
+    pub fn expected(&self) -> &str {
+        self.right[0]
+    }
+
+    /// Whether `article` agrees with the gender.
+    pub fn is_right(&self, article: &str) -> bool {
+        self.right
+            .iter()
+            .any(|right| right.eq_ignore_ascii_case(article))
+    }
+
+    /// Splits a leading article, of any gender, from an answer.
+    ///
+    /// # Returns
+    ///
+    /// The article, if the answer starts with one, and the rest of the answer.
+    pub fn split<'r>(&self, answer: &'r str) -> (Option<&'r str>, &'r str) {
+        let answer = answer.trim();
+        if let Some((first, rest)) = answer.split_once(char::is_whitespace) {
+            if self
+                .all
+                .iter()
+                .any(|article| article.eq_ignore_ascii_case(first))
+            {
+                return (Some(first), rest.trim());
+            }
+        }
+
+        (None, answer)
+    }
+}
@@ -0,0 +1,934 @@
+use std::fmt;
+
+// Conjugates Spanish verbs and works out which verb form a learning word is.
+// Regular -ar, -er and -ir verbs follow the ending tables, the common irregular verbs are
+// described by the IRREGULAR_VERBS table. Forms are identified by reversing the endings and
+// then conjugating each candidate infinitive, so only forms the engine can produce are reported.
+
+/// The grammatical person of a conjugated verb.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Person {
+    Yo,
+    Tu,
+    El,
+    Nosotros,
+    Vosotros,
+    Ellos,
+}
+
+impl Person {
+    /// Every person, in the order of the conjugation tables.
+    pub const ALL: [Person; 6] = [
+        Person::Yo,
+        Person::Tu,
+        Person::El,
+        Person::Nosotros,
+        Person::Vosotros,
+        Person::Ellos,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The pronouns of this person, e.g. `él/ella/usted`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Person::Yo => "yo",
+            Person::Tu => "tú",
+            Person::El => "él/ella/usted",
+            Person::Nosotros => "nosotros",
+            Person::Vosotros => "vosotros",
+            Person::Ellos => "ellos/ellas/ustedes",
+        }
+    }
+
+    /// A stable name for storing the person, e.g. `nosotros`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Person::Yo => "yo",
+            Person::Tu => "tu",
+            Person::El => "el",
+            Person::Nosotros => "nosotros",
+            Person::Vosotros => "vosotros",
+            Person::Ellos => "ellos",
+        }
+    }
+
+    /// The person stored under `key`, see [`Person::key`].
+    pub fn from_key(key: &str) -> Option<Person> {
+        Person::ALL.into_iter().find(|person| person.key() == key)
+    }
+
+    // The persons whose stem changes in the present tense, e.g. pienso but pensamos.
+    fn has_stem_change(self) -> bool {
+        !matches!(self, Person::Nosotros | Person::Vosotros)
+    }
+}
+
+impl fmt::Display for Person {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// The tenses the engine can conjugate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Tense {
+    Presente,
+    Preterito,
+    Imperfecto,
+    Futuro,
+    Condicional,
+    PresenteSubjuntivo,
+}
+
+impl Tense {
+    /// Every tense, the most common first.
+    pub const ALL: [Tense; 6] = [
+        Tense::Presente,
+        Tense::Preterito,
+        Tense::Imperfecto,
+        Tense::Futuro,
+        Tense::Condicional,
+        Tense::PresenteSubjuntivo,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The Spanish name of the tense, e.g. `pretérito`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Tense::Presente => "presente",
+            Tense::Preterito => "pretérito",
+            Tense::Imperfecto => "imperfecto",
+            Tense::Futuro => "futuro",
+            Tense::Condicional => "condicional",
+            Tense::PresenteSubjuntivo => "presente de subjuntivo",
+        }
+    }
+
+    /// A stable name for storing the tense, e.g. `presente_subjuntivo`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Tense::Presente => "presente",
+            Tense::Preterito => "preterito",
+            Tense::Imperfecto => "imperfecto",
+            Tense::Futuro => "futuro",
+            Tense::Condicional => "condicional",
+            Tense::PresenteSubjuntivo => "presente_subjuntivo",
+        }
+    }
+
+    /// The tense stored under `key`, see [`Tense::key`].
+    pub fn from_key(key: &str) -> Option<Tense> {
+        Tense::ALL.into_iter().find(|tense| tense.key() == key)
+    }
+}
+
+impl fmt::Display for Tense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// A conjugated form of a verb.
+///
+/// # Fields
+///
+/// - `infinitive`: The verb, e.g. `salir`.
+/// - `person`: Who the form is for.
+/// - `tense`: The tense of the form.
+/// - `form`: The conjugated word, e.g. `salgo`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerbForm {
+    pub infinitive: String,
+    pub person: Person,
+    pub tense: Tense,
+    pub form: String,
+}
+
+/// A conjugation drill, asking for one person and tense of a verb.
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::conjugation::{ConjugationDrill, Person, Tense};
+///
+/// let drill = ConjugationDrill {
+///     infinitive: "aprender".to_string(),
+///     person: Person::Nosotros,
+///     tense: Tense::Preterito,
+/// };
+/// assert_eq!(drill.prompt(), "aprender — nosotros, pretérito");
+/// assert_eq!(drill.answer(), Some("aprendimos".to_string()));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConjugationDrill {
+    pub infinitive: String,
+    pub person: Person,
+    pub tense: Tense,
+}
+
+impl ConjugationDrill {
+    /// The prompt shown to the learner, e.g. `aprender — nosotros, pretérito`.
+    pub fn prompt(&self) -> String {
+        format!("{} — {}, {}", self.infinitive, self.person, self.tense)
+    }
+
+    /// The expected answer, `None` if the infinitive cannot be conjugated.
+    pub fn answer(&self) -> Option<String> {
+        conjugate(&self.infinitive, self.person, self.tense)
+    }
+}
+
+/// Whether a word can be conjugated, i.e. it ends in -ar, -er or -ir.
+pub fn is_infinitive(word: &str) -> bool {
+    VerbClass::of(&word.trim().to_lowercase()).is_some()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum VerbClass {
+    Ar,
+    Er,
+    Ir,
+}
+
+impl VerbClass {
+    const ALL: [VerbClass; 3] = [VerbClass::Ar, VerbClass::Er, VerbClass::Ir];
+
+    fn of(infinitive: &str) -> Option<VerbClass> {
+        if infinitive.ends_with("ar") {
+            Some(VerbClass::Ar)
+        } else if infinitive.ends_with("er") {
+            Some(VerbClass::Er)
+        } else if infinitive.ends_with("ir") {
+            Some(VerbClass::Ir)
+        } else {
+            None
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            VerbClass::Ar => "ar",
+            VerbClass::Er => "er",
+            VerbClass::Ir => "ir",
+        }
+    }
+
+    // The endings added to the stem, the future and conditional are added to the infinitive instead.
+    fn endings(self, tense: Tense) -> [&'static str; 6] {
+        match (self, tense) {
+            (VerbClass::Ar, Tense::Presente) => ["o", "as", "a", "amos", "áis", "an"],
+            (VerbClass::Er, Tense::Presente) => ["o", "es", "e", "emos", "éis", "en"],
+            (VerbClass::Ir, Tense::Presente) => ["o", "es", "e", "imos", "ís", "en"],
+            (VerbClass::Ar, Tense::Preterito) => ["é", "aste", "ó", "amos", "asteis", "aron"],
+            (_, Tense::Preterito) => ["í", "iste", "ió", "imos", "isteis", "ieron"],
+            (VerbClass::Ar, Tense::Imperfecto) => ["aba", "abas", "aba", "ábamos", "abais", "aban"],
+            (_, Tense::Imperfecto) => ["ía", "ías", "ía", "íamos", "íais", "ían"],
+            (_, Tense::Futuro) => ["é", "ás", "á", "emos", "éis", "án"],
+            (_, Tense::Condicional) => ["ía", "ías", "ía", "íamos", "íais", "ían"],
+            (VerbClass::Ar, Tense::PresenteSubjuntivo) => ["e", "es", "e", "emos", "éis", "en"],
+            (_, Tense::PresenteSubjuntivo) => ["a", "as", "a", "amos", "áis", "an"],
+        }
+    }
+}
+
+// The endings of irregular preterite stems, e.g. tuv-e, tuv-iste.
+static STRONG_PRETERITE_ENDINGS: [&str; 6] = ["e", "iste", "o", "imos", "isteis", "ieron"];
+
+// How a verb differs from the regular pattern of its class, anything left as None is regular.
+struct IrregularVerb {
+    infinitive: &'static str,
+    // The vowel change of the stressed stem, e.g. ("e", "ie") for pensar -> pienso
+    stem_change: Option<(&'static str, &'static str)>,
+    yo_presente: Option<&'static str>,
+    presente: Option<[&'static str; 6]>,
+    preterite_stem: Option<&'static str>,
+    preterito: Option<[&'static str; 6]>,
+    imperfecto: Option<[&'static str; 6]>,
+    future_stem: Option<&'static str>,
+    subjuntivo: Option<[&'static str; 6]>,
+}
+
+const REGULAR: IrregularVerb = IrregularVerb {
+    infinitive: "",
+    stem_change: None,
+    yo_presente: None,
+    presente: None,
+    preterite_stem: None,
+    preterito: None,
+    imperfecto: None,
+    future_stem: None,
+    subjuntivo: None,
+};
+
+const E_IE: Option<(&str, &str)> = Some(("e", "ie"));
+const E_I: Option<(&str, &str)> = Some(("e", "i"));
+const O_UE: Option<(&str, &str)> = Some(("o", "ue"));
+
+static IRREGULAR_VERBS: &[IrregularVerb] = &[
+    IrregularVerb {
+        infinitive: "ser",
+        presente: Some(["soy", "eres", "es", "somos", "sois", "son"]),
+        preterito: Some(["fui", "fuiste", "fue", "fuimos", "fuisteis", "fueron"]),
+        imperfecto: Some(["era", "eras", "era", "éramos", "erais", "eran"]),
+        subjuntivo: Some(["sea", "seas", "sea", "seamos", "seáis", "sean"]),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "estar",
+        presente: Some(["estoy", "estás", "está", "estamos", "estáis", "están"]),
+        preterite_stem: Some("estuv"),
+        subjuntivo: Some(["esté", "estés", "esté", "estemos", "estéis", "estén"]),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "ir",
+        presente: Some(["voy", "vas", "va", "vamos", "vais", "van"]),
+        preterito: Some(["fui", "fuiste", "fue", "fuimos", "fuisteis", "fueron"]),
+        imperfecto: Some(["iba", "ibas", "iba", "íbamos", "ibais", "iban"]),
+        subjuntivo: Some(["vaya", "vayas", "vaya", "vayamos", "vayáis", "vayan"]),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "haber",
+        presente: Some(["he", "has", "ha", "hemos", "habéis", "han"]),
+        preterite_stem: Some("hub"),
+        future_stem: Some("habr"),
+        subjuntivo: Some(["haya", "hayas", "haya", "hayamos", "hayáis", "hayan"]),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "tener",
+        stem_change: E_IE,
+        yo_presente: Some("tengo"),
+        preterite_stem: Some("tuv"),
+        future_stem: Some("tendr"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "hacer",
+        yo_presente: Some("hago"),
+        preterito: Some([
+            "hice",
+            "hiciste",
+            "hizo",
+            "hicimos",
+            "hicisteis",
+            "hicieron",
+        ]),
+        future_stem: Some("har"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "decir",
+        stem_change: E_I,
+        yo_presente: Some("digo"),
+        preterite_stem: Some("dij"),
+        future_stem: Some("dir"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "poder",
+        stem_change: O_UE,
+        preterite_stem: Some("pud"),
+        future_stem: Some("podr"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "poner",
+        yo_presente: Some("pongo"),
+        preterite_stem: Some("pus"),
+        future_stem: Some("pondr"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "querer",
+        stem_change: E_IE,
+        preterite_stem: Some("quis"),
+        future_stem: Some("querr"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "saber",
+        yo_presente: Some("sé"),
+        preterite_stem: Some("sup"),
+        future_stem: Some("sabr"),
+        subjuntivo: Some(["sepa", "sepas", "sepa", "sepamos", "sepáis", "sepan"]),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "salir",
+        yo_presente: Some("salgo"),
+        future_stem: Some("saldr"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "venir",
+        stem_change: E_IE,
+        yo_presente: Some("vengo"),
+        preterite_stem: Some("vin"),
+        future_stem: Some("vendr"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "ver",
+        presente: Some(["veo", "ves", "ve", "vemos", "veis", "ven"]),
+        preterito: Some(["vi", "viste", "vio", "vimos", "visteis", "vieron"]),
+        imperfecto: Some(["veía", "veías", "veía", "veíamos", "veíais", "veían"]),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "dar",
+        presente: Some(["doy", "das", "da", "damos", "dais", "dan"]),
+        preterito: Some(["di", "diste", "dio", "dimos", "disteis", "dieron"]),
+        subjuntivo: Some(["dé", "des", "dé", "demos", "deis", "den"]),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "traer",
+        yo_presente: Some("traigo"),
+        preterite_stem: Some("traj"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "conocer",
+        yo_presente: Some("conozco"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "parecer",
+        yo_presente: Some("parezco"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "seguir",
+        stem_change: E_I,
+        yo_presente: Some("sigo"),
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "pensar",
+        stem_change: E_IE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "entender",
+        stem_change: E_IE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "empezar",
+        stem_change: E_IE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "comenzar",
+        stem_change: E_IE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "cerrar",
+        stem_change: E_IE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "despertar",
+        stem_change: E_IE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "perder",
+        stem_change: E_IE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "preferir",
+        stem_change: E_IE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "sentir",
+        stem_change: E_IE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "pedir",
+        stem_change: E_I,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "repetir",
+        stem_change: E_I,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "servir",
+        stem_change: E_I,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "vestir",
+        stem_change: E_I,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "dormir",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "morir",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "volver",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "encontrar",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "contar",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "costar",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "recordar",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "mostrar",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "almorzar",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "llover",
+        stem_change: O_UE,
+        ..REGULAR
+    },
+    IrregularVerb {
+        infinitive: "jugar",
+        stem_change: Some(("u", "ue")),
+        ..REGULAR
+    },
+];
+
+// Common regular verbs. When a word could be several verbs, the known ones are preferred.
+static COMMON_REGULAR_VERBS: &[&str] = &[
+    "abrir",
+    "aprender",
+    "ayudar",
+    "bailar",
+    "beber",
+    "buscar",
+    "caminar",
+    "cambiar",
+    "cantar",
+    "cocinar",
+    "coger",
+    "comer",
+    "compartir",
+    "comprar",
+    "comprender",
+    "correr",
+    "cumplir",
+    "decidir",
+    "deber",
+    "dejar",
+    "desear",
+    "describir",
+    "descansar",
+    "enseñar",
+    "entrar",
+    "escribir",
+    "escuchar",
+    "esperar",
+    "estudiar",
+    "existir",
+    "ganar",
+    "gustar",
+    "hablar",
+    "llamar",
+    "llegar",
+    "llevar",
+    "limpiar",
+    "mirar",
+    "necesitar",
+    "olvidar",
+    "pagar",
+    "pasar",
+    "permitir",
+    "practicar",
+    "preguntar",
+    "preparar",
+    "quedar",
+    "recibir",
+    "responder",
+    "romper",
+    "subir",
+    "terminar",
+    "tocar",
+    "tomar",
+    "trabajar",
+    "usar",
+    "vender",
+    "viajar",
+    "vivir",
+];
+
+fn irregular(infinitive: &str) -> Option<&'static IrregularVerb> {
+    IRREGULAR_VERBS
+        .iter()
+        .find(|verb| verb.infinitive == infinitive)
+}
+
+/// Whether the engine knows the verb, either from its irregular verbs or its list of common regular verbs.
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::conjugation::is_known_verb;
+///
+/// assert!(is_known_verb("salir"));
+/// assert!(is_known_verb("hablar"));
+/// assert!(!is_known_verb("gatar"));
+/// ```
+pub fn is_known_verb(infinitive: &str) -> bool {
+    let infinitive = infinitive.trim().to_lowercase();
+    irregular(&infinitive).is_some() || COMMON_REGULAR_VERBS.contains(&infinitive.as_str())
+}
+
+// Replaces the last occurrence of `from` in the stem, e.g. pens -> piens.
+fn change_stem(stem: &str, (from, to): (&str, &str)) -> String {
+    match stem.rfind(from) {
+        Some(index) => format!("{}{}{}", &stem[..index], to, &stem[index + from.len()..]),
+        None => stem.to_string(),
+    }
+}
+
+// The smaller change -ir verbs make where the stem is not stressed, e.g. durmió, pidamos.
+fn narrow_change(
+    stem_change: (&'static str, &'static str),
+) -> Option<(&'static str, &'static str)> {
+    match stem_change {
+        ("e", _) => Some(("e", "i")),
+        ("o", _) => Some(("o", "u")),
+        _ => None,
+    }
+}
+
+// Joins a stem and an ending, keeping the sound of the stem's last consonant, e.g. busqué, cojo.
+fn join(infinitive: &str, stem: &str, ending: &str) -> String {
+    let front_vowel = ending.starts_with(['e', 'é', 'i', 'í']);
+    let back_vowel = ending.starts_with(['a', 'á', 'o', 'ó']);
+
+    let stem = if front_vowel && infinitive.ends_with("car") && stem.ends_with('c') {
+        format!("{}qu", &stem[..stem.len() - 1])
+    } else if front_vowel && infinitive.ends_with("gar") && stem.ends_with('g') {
+        format!("{}gu", &stem[..stem.len() - 1])
+    } else if front_vowel && infinitive.ends_with("zar") && stem.ends_with('z') {
+        format!("{}c", &stem[..stem.len() - 1])
+    } else if back_vowel && infinitive.ends_with("guir") && stem.ends_with("gu") {
+        stem[..stem.len() - 1].to_string()
+    } else if back_vowel
+        && (infinitive.ends_with("ger") || infinitive.ends_with("gir"))
+        && stem.ends_with('g')
+    {
+        format!("{}j", &stem[..stem.len() - 1])
+    } else {
+        stem.to_string()
+    };
+
+    format!("{}{}", stem, ending)
+}
+
+/// Conjugates a Spanish verb.
+///
+/// # Parameters
+///
+/// * `infinitive` - The verb, e.g. `hablar`.
+/// * `person` - Who the form is for.
+/// * `tense` - The tense of the form.
+///
+/// # Returns
+///
+/// The conjugated form, or `None` if `infinitive` does not end in -ar, -er or -ir.
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::conjugation::{conjugate, Person, Tense};
+///
+/// assert_eq!(conjugate("hablar", Person::Nosotros, Tense::Imperfecto), Some("hablábamos".to_string()));
+/// assert_eq!(conjugate("salir", Person::Yo, Tense::Presente), Some("salgo".to_string()));
+/// assert_eq!(conjugate("dormir", Person::El, Tense::Preterito), Some("durmió".to_string()));
+/// assert_eq!(conjugate("buscar", Person::Yo, Tense::Preterito), Some("busqué".to_string()));
+/// assert_eq!(conjugate("gato", Person::Yo, Tense::Presente), None);
+/// ```
+pub fn conjugate(infinitive: &str, person: Person, tense: Tense) -> Option<String> {
+    let infinitive = infinitive.trim().to_lowercase();
+    let class = VerbClass::of(&infinitive)?;
+    let stem = &infinitive[..infinitive.len() - 2];
+    let verb = irregular(&infinitive).unwrap_or(&REGULAR);
+    let index = person.index();
+    let ending = class.endings(tense)[index];
+
+    let stressed_stem = verb
+        .stem_change
+        .map_or(stem.to_string(), |change| change_stem(stem, change));
+    let narrow_stem = match (class, verb.stem_change.and_then(narrow_change)) {
+        (VerbClass::Ir, Some(change)) => change_stem(stem, change),
+        _ => stem.to_string(),
+    };
+
+    let form = match tense {
+        Tense::Presente => match (verb.presente, verb.yo_presente) {
+            (Some(forms), _) => forms[index].to_string(),
+            (None, Some(yo)) if person == Person::Yo => yo.to_string(),
+            _ if person.has_stem_change() => join(&infinitive, &stressed_stem, ending),
+            _ => join(&infinitive, stem, ending),
+        },
+        Tense::Preterito => match (verb.preterito, verb.preterite_stem) {
+            (Some(forms), _) => forms[index].to_string(),
+            (None, Some(strong_stem)) => {
+                let strong_ending = STRONG_PRETERITE_ENDINGS[index];
+                // dijeron, trajeron
+                let strong_ending = if strong_stem.ends_with('j') && person == Person::Ellos {
+                    "eron"
+                } else {
+                    strong_ending
+                };
+                format!("{}{}", strong_stem, strong_ending)
+            }
+            _ if matches!(person, Person::El | Person::Ellos) => {
+                join(&infinitive, &narrow_stem, ending)
+            }
+            _ => join(&infinitive, stem, ending),
+        },
+        Tense::Imperfecto => match verb.imperfecto {
+            Some(forms) => forms[index].to_string(),
+            None => join(&infinitive, stem, ending),
+        },
+        Tense::Futuro | Tense::Condicional => {
+            format!("{}{}", verb.future_stem.unwrap_or(&infinitive), ending)
+        }
+        Tense::PresenteSubjuntivo => {
+            if let Some(forms) = verb.subjuntivo {
+                forms[index].to_string()
+            } else if verb.presente.is_some() || verb.yo_presente.is_some() {
+                // Built on the irregular yo form, e.g. salgo -> salga, salgamos
+                let yo = conjugate(&infinitive, Person::Yo, Tense::Presente)?;
+                let yo_stem = yo.strip_suffix('o').unwrap_or(&yo).to_string();
+                join(&infinitive, &yo_stem, ending)
+            } else if person.has_stem_change() {
+                join(&infinitive, &stressed_stem, ending)
+            } else {
+                join(&infinitive, &narrow_stem, ending)
+            }
+        }
+    };
+
+    Some(form)
+}
+
+/// Every person and tense form of a verb.
+///
+/// # Returns
+///
+/// The forms ordered by tense then person, empty if `infinitive` does not end in -ar, -er or -ir.
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::conjugation::{conjugations, Person, Tense};
+///
+/// let forms = conjugations("tener");
+/// assert_eq!(forms.len(), 36);
+/// assert_eq!(forms[0].form, "tengo");
+/// assert!(forms
+///     .iter()
+///     .any(|form| form.form == "tuvieron" && form.person == Person::Ellos && form.tense == Tense::Preterito));
+/// ```
+pub fn conjugations(infinitive: &str) -> Vec<VerbForm> {
+    let infinitive = infinitive.trim().to_lowercase();
+    let mut forms = Vec::new();
+
+    for tense in Tense::ALL {
+        for person in Person::ALL {
+            if let Some(form) = conjugate(&infinitive, person, tense) {
+                forms.push(VerbForm {
+                    infinitive: infinitive.clone(),
+                    person,
+                    tense,
+                    form,
+                });
+            }
+        }
+    }
+
+    forms
+}
+
+// Infinitives that could have produced `word`, undoing each ending and spelling change.
+fn regular_candidates(word: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut add = |candidate: String| {
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    };
+
+    for class in VerbClass::ALL {
+        for tense in Tense::ALL {
+            for ending in class.endings(tense) {
+                let Some(stem) = word.strip_suffix(ending) else {
+                    continue;
+                };
+                if matches!(tense, Tense::Futuro | Tense::Condicional) {
+                    if VerbClass::of(stem) == Some(class) {
+                        add(stem.to_string());
+                    }
+                    continue;
+                }
+                if stem.chars().count() < 2 {
+                    continue;
+                }
+
+                add(format!("{}{}", stem, class.suffix()));
+                for (spelled, sound) in [("qu", "c"), ("gu", "g"), ("c", "z"), ("j", "g")] {
+                    if let Some(base) = stem.strip_suffix(spelled) {
+                        add(format!("{}{}{}", base, sound, class.suffix()));
+                    }
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Identifies which verb forms a word could be.
+///
+/// Forms of the irregular verbs come first, then forms of other known verbs, see [`is_known_verb`].
+/// Within those groups the more common tenses come first.
+///
+/// # Parameters
+///
+/// * `word` - A single learning word, e.g. `salgo`.
+///
+/// # Returns
+///
+/// Every form that conjugates to `word`, empty if it is not a verb form the engine can produce.
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::conjugation::{identify, Person, Tense};
+///
+/// let forms = identify("salgo");
+/// assert_eq!(forms[0].infinitive, "salir");
+/// assert_eq!(forms[0].person, Person::Yo);
+/// assert_eq!(forms[0].tense, Tense::Presente);
+///
+/// let forms = identify("aprenden");
+/// assert_eq!(forms[0].infinitive, "aprender");
+/// assert_eq!(forms[0].person, Person::Ellos);
+/// ```
+pub fn identify(word: &str) -> Vec<VerbForm> {
+    let word = word.trim().to_lowercase();
+    let mut found: Vec<(usize, VerbForm)> = Vec::new();
+
+    let irregular_infinitives = IRREGULAR_VERBS
+        .iter()
+        .map(|verb| verb.infinitive.to_string());
+    let regular_infinitives = regular_candidates(&word)
+        .into_iter()
+        .filter(|candidate| irregular(candidate).is_none());
+
+    for infinitive in irregular_infinitives.chain(regular_infinitives) {
+        let rank = if irregular(&infinitive).is_some() {
+            0
+        } else if is_known_verb(&infinitive) {
+            1
+        } else {
+            2
+        };
+        for form in conjugations(&infinitive) {
+            if form.form == word
+                && !found.iter().any(|(_, existing)| {
+                    existing.infinitive == form.infinitive
+                        && existing.person == form.person
+                        && existing.tense == form.tense
+                })
+            {
+                found.push((rank, form));
+            }
+        }
+    }
+
+    found.sort_by_key(|(rank, form)| (*rank, form.tense.index()));
+    found.into_iter().map(|(_, form)| form).collect()
+}
+
+/// The most likely verb form of a word, see [`identify`].
+pub fn analyse(word: &str) -> Option<VerbForm> {
+    identify(word).into_iter().next()
+}
+
+/// Describes the most likely verb form of a word, for use as a hint.
+///
+/// When the same form is shared by several persons, e.g. the subjunctive `trabaje`, they are all listed.
+///
+/// # Returns
+///
+/// A hint of the form `infinitive, person, tense`, or `None` if the word is not a verb form.
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::conjugation::verb_hint;
+///
+/// assert_eq!(verb_hint("salgo"), Some("salir, yo, presente".to_string()));
+/// assert_eq!(
+///     verb_hint("trabaje"),
+///     Some("trabajar, yo or él/ella/usted, presente de subjuntivo".to_string())
+/// );
+/// assert_eq!(verb_hint("xyz"), None);
+/// ```
+pub fn verb_hint(word: &str) -> Option<String> {
+    let forms = identify(word);
+    let best = forms.first()?;
+
+    let persons: Vec<&str> = forms
+        .iter()
+        .filter(|form| form.infinitive == best.infinitive && form.tense == best.tense)
+        .map(|form| form.person.label())
+        .collect();
+
+    Some(format!(
+        "{}, {}, {}",
+        best.infinitive,
+        persons.join(" or "),
+        best.tense
+    ))
+}
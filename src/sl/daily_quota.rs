@@ -0,0 +1,193 @@
+use crate::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use crate::error::PalabrasError;
+use crate::models::AwesomePerson;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+
+// The daily limits of an awesome person, how many new words and reviews the study list takes each day.
+// They are counted against the attempt history since the start of the day in UTC.
+
+/// The daily limits of an awesome person and how much of them is used today, see [`DailyLimits::get_daily_quota`].
+///
+/// # Fields
+///
+/// - `new_per_day`: The maximum number of new words, never answered before, introduced each day.
+/// - `reviews_per_day`: The maximum number of reviews, answers to words answered before, each day.
+/// - `new_today`: The number of new words introduced today.
+/// - `reviews_today`: The number of reviews answered today.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DailyQuota {
+    pub new_per_day: i64,
+    pub reviews_per_day: i64,
+    pub new_today: i64,
+    pub reviews_today: i64,
+}
+
+impl DailyQuota {
+    /// The number of new words that can still be introduced today.
+    pub fn new_remaining(&self) -> i64 {
+        (self.new_per_day - self.new_today).max(0)
+    }
+
+    /// The number of reviews that can still be answered today.
+    pub fn reviews_remaining(&self) -> i64 {
+        (self.reviews_per_day - self.reviews_today).max(0)
+    }
+}
+
+pub trait DailyLimits {
+    /// Retrieves an awesome person's daily limits and how much of them is used today, counted against
+    /// their attempt history since the start of the day in UTC.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_person_id` - The primary key of the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `DailyQuota`, `PalabrasError::NotFound` if the awesome person does not exist
+    /// or another error if the query fails.
+    fn get_daily_quota(&self, awesome_person_id: i32) -> Result<DailyQuota, PalabrasError>;
+
+    /// Sets an awesome person's daily limits, the new words introduced and the reviews in the study list each day.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_person_id` - The primary key of the awesome person.
+    /// * `new_per_day` - The maximum number of new words introduced each day.
+    /// * `reviews_per_day` - The maximum number of reviews each day.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated `AwesomePerson`.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if a limit is negative.
+    /// - `PalabrasError::NotFound` if the awesome person does not exist.
+    /// - Another error if the update fails.
+    fn update_daily_limits(
+        &self,
+        awesome_person_id: i32,
+        new_per_day: i32,
+        reviews_per_day: i32,
+    ) -> Result<AwesomePerson, PalabrasError>;
+}
+
+pub struct DailyQuotaService {
+    awesome_person_repo: Box<dyn AwesomePersonRepository>,
+}
+
+lazy_static! {
+    static ref DAILY_QUOTA_SERVICE: DailyQuotaService =
+        DailyQuotaService::new(Box::new(DbAwesomePersonRepository),);
+}
+
+impl DailyQuotaService {
+    // The constructor takes Box<dyn Repos>
+    pub fn new(awesome_person_repo: Box<dyn AwesomePersonRepository>) -> Self {
+        DailyQuotaService {
+            awesome_person_repo,
+        }
+    }
+
+    // Method to access the singleton instance, shared across threads like the other services.
+    pub fn instance() -> &'static DailyQuotaService {
+        &DAILY_QUOTA_SERVICE
+    }
+}
+
+// The daily limits of an awesome person and the new words and reviews answered since the start of the day, in UTC.
+pub(crate) fn daily_quota(
+    awesome_person_repo: &dyn AwesomePersonRepository,
+    awesome_person: &AwesomePerson,
+) -> Result<DailyQuota, PalabrasError> {
+    let (new_today, reviews_today) =
+        awesome_person_repo.count_attempts_since(awesome_person.id, start_of_today())?;
+
+    Ok(DailyQuota {
+        new_per_day: awesome_person.new_per_day as i64,
+        reviews_per_day: awesome_person.reviews_per_day as i64,
+        new_today,
+        reviews_today,
+    })
+}
+
+// The start of the current day in UTC, the daily limits reset then.
+fn start_of_today() -> DateTime<Utc> {
+    Utc::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+}
+
+/// The daily limits of the awesome people, stored with each awesome person.
+impl DailyLimits for DailyQuotaService {
+    /// Implementation, see trait for details [`DailyLimits::get_daily_quota`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn get_daily_quota(&self, awesome_person_id: i32) -> Result<DailyQuota, PalabrasError> {
+        let awesome_person = self
+            .awesome_person_repo
+            .get_awesome_person_by_id(awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
+
+        daily_quota(self.awesome_person_repo.as_ref(), &awesome_person)
+    }
+
+    /// Implementation, see trait for details [`DailyLimits::update_daily_limits`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn update_daily_limits(
+        &self,
+        awesome_person_id: i32,
+        new_per_day: i32,
+        reviews_per_day: i32,
+    ) -> Result<AwesomePerson, PalabrasError> {
+        if new_per_day < 0 || reviews_per_day < 0 {
+            return Err(PalabrasError::Validation(format!(
+                "daily limits must be at least 0, found {} new and {} reviews",
+                new_per_day, reviews_per_day
+            )));
+        }
+
+        // Only the limits are written, a grading committed meanwhile keeps its progress.
+        self.awesome_person_repo.update_daily_limits(
+            awesome_person_id,
+            new_per_day,
+            reviews_per_day,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::fixture_setup;
+
+    #[test]
+    fn unit_test_daily_limits() {
+        let daily_quota_service = fixture_setup().daily_quota_service;
+
+        let quota = daily_quota_service
+            .get_daily_quota(1)
+            .expect("No error results expected fn get_daily_quota with mocked repos");
+        assert_eq!((quota.new_today, quota.reviews_today), (3, 10));
+        assert_eq!(quota.new_remaining(), 17);
+        assert_eq!(quota.reviews_remaining(), 190);
+
+        let awesome_person = daily_quota_service
+            .update_daily_limits(1, 5, 50)
+            .expect("No error results expected fn update_daily_limits with mocked repos");
+        assert_eq!(
+            (awesome_person.new_per_day, awesome_person.reviews_per_day),
+            (5, 50)
+        );
+
+        let result = daily_quota_service.update_daily_limits(1, -1, 50);
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+    }
+}
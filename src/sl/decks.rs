@@ -0,0 +1,296 @@
+use crate::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use crate::dal::unit_of_work::{DbUnitOfWork, UnitOfWork};
+use crate::error::PalabrasError;
+use crate::models::{Deck, NewDeck, Vocab};
+use crate::sl::fuzzy_match_vocab::non_empty;
+use lazy_static::lazy_static;
+
+// Decks of vocab, named sets an awesome person studies one at a time, see `StudyFilter::deck_id`.
+
+pub trait ManageDecks {
+    /// Creates a deck of vocab for an awesome person, studied one deck at a time with a [`crate::sl::fuzzy_match_vocab::StudyFilter`].
+    ///
+    /// # Parameters
+    ///
+    /// * `new_deck` - The deck to create. The name and description are trimmed, an empty description is left out.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `Deck` with a newly assigned `id`.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if the name or a language code is empty.
+    /// - `PalabrasError::NotFound` if the owner does not exist.
+    /// - `PalabrasError::Conflict` if the owner already has a deck of the same name.
+    fn create_deck(&self, new_deck: NewDeck) -> Result<Deck, PalabrasError>;
+
+    /// Retrieves the decks of an awesome person, by name.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_person_id` - The primary key of the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// Each `Deck` with the number of vocab in it, `PalabrasError::NotFound` if the awesome person does not exist
+    /// or another error if the query fails.
+    fn get_decks(&self, awesome_person_id: i32) -> Result<Vec<(Deck, i64)>, PalabrasError>;
+
+    /// Retrieves a deck with the vocab in it, in the order it was added.
+    ///
+    /// # Parameters
+    ///
+    /// * `deck_id` - The primary key of the deck.
+    ///
+    /// # Returns
+    ///
+    /// The `Deck` and its `Vocab`, `PalabrasError::NotFound` if the deck does not exist or another error if the query fails.
+    fn get_deck_vocab(&self, deck_id: i32) -> Result<(Deck, Vec<Vocab>), PalabrasError>;
+
+    /// Adds vocab to a deck. The owner of the deck gets a vocab study for each vocab they are not yet studying,
+    /// so the whole deck can be studied.
+    ///
+    /// # Parameters
+    ///
+    /// * `deck_id` - The primary key of the deck.
+    /// * `vocab_ids` - The primary keys of the vocab to add, vocab already in the deck is skipped.
+    ///
+    /// # Returns
+    ///
+    /// The number of vocab added.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if a vocab is in other languages than the deck.
+    /// - `PalabrasError::NotFound` if the deck or a vocab does not exist.
+    /// - Another error if the save fails.
+    ///
+    /// The vocab is added to the deck and its vocab studies created in a single unit of work, see
+    /// [`UnitOfWork::deck_import`], an error leaves the deck unchanged.
+    fn add_to_deck(&self, deck_id: i32, vocab_ids: &[i32]) -> Result<usize, PalabrasError>;
+
+    /// Removes vocab from a deck, the vocab and its vocab studies are kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `deck_id` - The primary key of the deck.
+    /// * `vocab_ids` - The primary keys of the vocab to remove.
+    ///
+    /// # Returns
+    ///
+    /// The number of vocab removed, `PalabrasError::NotFound` if the deck does not exist or another error if the delete fails.
+    fn remove_from_deck(&self, deck_id: i32, vocab_ids: &[i32]) -> Result<usize, PalabrasError>;
+}
+
+pub struct DeckService {
+    awesome_person_repo: Box<dyn AwesomePersonRepository>,
+    unit_of_work: Box<dyn UnitOfWork>,
+}
+
+lazy_static! {
+    static ref DECK_SERVICE: DeckService =
+        DeckService::new(Box::new(DbAwesomePersonRepository), Box::new(DbUnitOfWork),);
+}
+
+impl DeckService {
+    // The constructor takes Box<dyn Repos>, vocab is added to decks in a unit of work
+    pub fn new(
+        awesome_person_repo: Box<dyn AwesomePersonRepository>,
+        unit_of_work: Box<dyn UnitOfWork>,
+    ) -> Self {
+        DeckService {
+            awesome_person_repo,
+            unit_of_work,
+        }
+    }
+
+    // Method to access the singleton instance, shared across threads like the other services.
+    pub fn instance() -> &'static DeckService {
+        &DECK_SERVICE
+    }
+}
+
+// Vocab in a deck is in the languages of the deck.
+pub(crate) fn check_deck_languages(deck: &Deck, vocab: &Vocab) -> Result<(), PalabrasError> {
+    if vocab.known_lang_code != deck.known_lang_code
+        || vocab.learning_lang_code != deck.learning_lang_code
+    {
+        return Err(PalabrasError::Validation(format!(
+            "vocab {} is {} to {}, deck {} is {} to {}",
+            vocab.id,
+            vocab.known_lang_code,
+            vocab.learning_lang_code,
+            deck.id,
+            deck.known_lang_code,
+            deck.learning_lang_code
+        )));
+    }
+
+    Ok(())
+}
+
+// A deck of the awesome person, a deck of someone else is not found.
+pub(crate) fn owned_deck(
+    awesome_person_repo: &dyn AwesomePersonRepository,
+    deck_id: i32,
+    awesome_id: i32,
+) -> Result<Deck, PalabrasError> {
+    awesome_person_repo
+        .get_deck(deck_id)?
+        .filter(|deck| deck.owner_id == awesome_id)
+        .ok_or_else(|| PalabrasError::not_found("deck", deck_id))
+}
+
+/// The decks of the awesome people, stored with the awesome person repository.
+impl ManageDecks for DeckService {
+    /// Implementation, see trait for details [`ManageDecks::create_deck`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn create_deck(&self, new_deck: NewDeck) -> Result<Deck, PalabrasError> {
+        let name = new_deck.name.trim().to_string();
+        let known_lang_code = new_deck.known_lang_code.trim().to_string();
+        let learning_lang_code = new_deck.learning_lang_code.trim().to_string();
+        if name.is_empty() || known_lang_code.is_empty() || learning_lang_code.is_empty() {
+            return Err(PalabrasError::Validation(
+                "a deck needs a name, a known and a learning language code".to_string(),
+            ));
+        }
+
+        self.awesome_person_repo
+            .get_awesome_person_by_id(new_deck.owner_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", new_deck.owner_id))?;
+
+        self.awesome_person_repo.create_deck(&NewDeck {
+            name,
+            description: non_empty(new_deck.description.as_deref()).map(str::to_string),
+            known_lang_code,
+            learning_lang_code,
+            ..new_deck
+        })
+    }
+
+    /// Implementation, see trait for details [`ManageDecks::get_decks`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn get_decks(&self, awesome_person_id: i32) -> Result<Vec<(Deck, i64)>, PalabrasError> {
+        self.awesome_person_repo
+            .get_awesome_person_by_id(awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
+
+        self.awesome_person_repo.get_decks(awesome_person_id)
+    }
+
+    /// Implementation, see trait for details [`ManageDecks::get_deck_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn get_deck_vocab(&self, deck_id: i32) -> Result<(Deck, Vec<Vocab>), PalabrasError> {
+        let deck = self
+            .awesome_person_repo
+            .get_deck(deck_id)?
+            .ok_or_else(|| PalabrasError::not_found("deck", deck_id))?;
+        let vocabs = self.awesome_person_repo.get_deck_vocab(deck.id)?;
+
+        Ok((deck, vocabs))
+    }
+
+    /// Implementation, see trait for details [`ManageDecks::add_to_deck`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn add_to_deck(&self, deck_id: i32, vocab_ids: &[i32]) -> Result<usize, PalabrasError> {
+        let mut num_added = 0;
+        self.unit_of_work.deck_import(&mut |tx| {
+            let deck = tx.get_deck(deck_id)?;
+            if vocab_ids.is_empty() {
+                return Ok(());
+            }
+
+            for vocab_id in vocab_ids {
+                check_deck_languages(&deck, &tx.get_vocab(*vocab_id)?)?;
+            }
+
+            num_added = tx.add_vocab_to_deck(deck.id, vocab_ids)?;
+
+            // The owner studies the whole deck, vocab new to them starts as an untested vocab study.
+            tx.start_vocab_studies(vocab_ids, deck.owner_id)?;
+
+            Ok(())
+        })?;
+
+        Ok(num_added)
+    }
+
+    /// Implementation, see trait for details [`ManageDecks::remove_from_deck`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn remove_from_deck(&self, deck_id: i32, vocab_ids: &[i32]) -> Result<usize, PalabrasError> {
+        let deck = self
+            .awesome_person_repo
+            .get_deck(deck_id)?
+            .ok_or_else(|| PalabrasError::not_found("deck", deck_id))?;
+
+        self.awesome_person_repo
+            .remove_vocab_from_deck(deck.id, vocab_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::fixture_setup;
+
+    #[test]
+    fn unit_test_manage_decks() {
+        let deck_service = fixture_setup().deck_service;
+
+        let deck = deck_service
+            .create_deck(NewDeck {
+                owner_id: 1,
+                name: "  Kitchen ".to_string(),
+                description: Some(" ".to_string()),
+                known_lang_code: "en".to_string(),
+                learning_lang_code: "es".to_string(),
+            })
+            .expect("No error results expected fn create_deck with mocked repos");
+        assert_eq!(deck.name, "Kitchen");
+        assert_eq!(deck.description, None);
+
+        let result = deck_service.create_deck(NewDeck {
+            owner_id: 1,
+            name: "".to_string(),
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+
+        let decks = deck_service
+            .get_decks(1)
+            .expect("No error results expected fn get_decks with mocked repos");
+        assert_eq!(decks.len(), 1);
+        assert_eq!(decks[0].1, 1);
+
+        let num_added = deck_service
+            .add_to_deck(1, &[1, 2])
+            .expect("No error results expected fn add_to_deck with mocked repos");
+        assert_eq!(num_added, 2);
+        let result = deck_service.add_to_deck(3, &[1]);
+        assert!(matches!(result, Err(PalabrasError::NotFound { .. })));
+
+        let (deck, vocabs) = deck_service
+            .get_deck_vocab(1)
+            .expect("No error results expected fn get_deck_vocab with mocked repos");
+        assert_eq!(deck.id, 1);
+        assert_eq!(vocabs.len(), 1);
+
+        let num_removed = deck_service
+            .remove_from_deck(1, &[1])
+            .expect("No error results expected fn remove_from_deck with mocked repos");
+        assert_eq!(num_removed, 1);
+    }
+}
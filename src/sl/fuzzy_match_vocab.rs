@@ -1,13 +1,29 @@
+use crate::config::app_config::app_config;
+use crate::config::{language_packs, GradingPolicyConfig, LanguagePackRegistry, LeechConfig};
 use crate::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use crate::dal::unit_of_work::{DbUnitOfWork, UnitOfWork};
 use crate::dal::vocab::{DbVocabRepository, VocabRepository};
 use crate::dal::vocab_study::{DbVocabStudyRepository, VocabStudyRepository};
-use crate::models::{AwesomePerson, Vocab, VocabStudy};
+use crate::error::PalabrasError;
+use crate::models::{
+    AwesomePerson, ConjugationStat, GradingPolicy, NewVocabAttempt, StudySession, StudySessionItem,
+    Vocab, VocabAlternative, VocabStudy,
+};
+use crate::sl::articles::GenderArticles;
+use crate::sl::conjugation::{is_infinitive, ConjugationDrill, Person, Tense};
+use crate::sl::daily_quota::{daily_quota, DailyQuota};
+use crate::sl::decks::owned_deck;
+use crate::sl::phrase_match::{align_phrase, phrase_words};
+use crate::sl::study_composer::StudyComposer;
+use crate::sl::tags::tag_names;
 use chrono::Utc;
 use core::option::Option;
 use lazy_static::lazy_static;
-use std::sync::{Mutex, MutexGuard};
+use std::collections::HashSet;
 use strsim::levenshtein;
 
+// The built-in grading defaults, each awesome person may override them with a `GradingPolicy`.
+
 /// #[derive(Clone)]
 /// Represents the worst possible answer possible, and thus, it caps the distance.
 /// It is used in calculations as well.
@@ -16,6 +32,141 @@ pub static MAX_DISTANCE: usize = 10;
 /// Once percentage correct get higher, the pair is to be marked known or even too easy.
 pub static WELL_KNOWN_THRESHOLD: f64 = 0.98;
 
+/// Answers up to this distance are reported as close.
+pub static CLOSE_DISTANCE: usize = 3;
+
+/// A perfect match counts as this many answers when averaging the percentage correct.
+pub static PERFECT_WEIGHT: f64 = 2.0;
+
+/// How the known state of a vocab study changed with the latest answer.
+///
+/// The awesome person's `num_known` only moves when a vocab study crosses the
+/// [`WELL_KNOWN_THRESHOLD`], answering an already known word again leaves it alone.
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::fuzzy_match_vocab::KnownTransition;
+///
+/// assert_eq!(KnownTransition::between(false, true), KnownTransition::BecameKnown);
+/// assert_eq!(KnownTransition::between(true, false).num_known_delta(), -1);
+/// assert_eq!(KnownTransition::between(true, true).num_known_delta(), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownTransition {
+    Unchanged,
+    BecameKnown,
+    Forgot,
+}
+
+impl KnownTransition {
+    /// Determines the transition from the known state before and after an answer.
+    pub fn between(was_known: bool, now_known: bool) -> Self {
+        match (was_known, now_known) {
+            (false, true) => KnownTransition::BecameKnown,
+            (true, false) => KnownTransition::Forgot,
+            _ => KnownTransition::Unchanged,
+        }
+    }
+
+    /// The change to apply to the awesome person's `num_known`.
+    pub fn num_known_delta(&self) -> i32 {
+        match self {
+            KnownTransition::Unchanged => 0,
+            KnownTransition::BecameKnown => 1,
+            KnownTransition::Forgot => -1,
+        }
+    }
+}
+
+/// The outcome of matching a response against a vocab.
+///
+/// # Fields
+///
+/// - `distance`: How far the response is from the answer, 0 is correct, capped at the policy's `max_distance`.
+/// - `prompt`: The feedback to display to the awesome person.
+/// - `wrong_words`: For phrases, the words of the answer that were misspelled or replaced.
+/// - `missing_words`: For phrases, the words of the answer that were left out.
+/// - `extra_words`: For phrases, the words of the response that are not in the answer.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchResult {
+    pub distance: usize,
+    pub prompt: String,
+    pub wrong_words: Vec<String>,
+    pub missing_words: Vec<String>,
+    pub extra_words: Vec<String>,
+}
+
+/// Narrows the vocab of a study set, each filter that is set must match.
+///
+/// # Fields
+///
+/// - `pos`: Optional. The part of speech, e.g. `noun` or `Verb`, compared ignoring case.
+/// - `skill`: Optional. The skill or category of the vocab, compared ignoring case.
+/// - `learning_lang_code`: Optional. The language being learned, e.g. `es`.
+/// - `deck_id`: Optional. Only the vocab in this deck of the awesome person,
+///   see [`crate::sl::decks::ManageDecks::create_deck`].
+/// - `tags`: Only the vocab with every one of these tags, see [`crate::sl::tags::ManageTags::tag_vocab`].
+///   Empty for any vocab.
+///
+/// The deck and tags limit the study set, they are not checked by [`StudyFilter::matches`].
+///
+/// # Example
+///
+/// ```
+/// use palabras::models::Vocab;
+/// use palabras::sl::fuzzy_match_vocab::StudyFilter;
+///
+/// let vocab = Vocab {
+///     pos: Some("Verb".to_string()),
+///     learning_lang_code: "es".to_string(),
+///     ..Default::default()
+/// };
+/// assert!(StudyFilter::default().matches(&vocab));
+///
+/// let verbs = StudyFilter {
+///     pos: Some("verb".to_string()),
+///     ..Default::default()
+/// };
+/// assert!(verbs.matches(&vocab));
+///
+/// let french = StudyFilter {
+///     learning_lang_code: Some("fr".to_string()),
+///     ..Default::default()
+/// };
+/// assert!(!french.matches(&vocab));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StudyFilter {
+    pub pos: Option<String>,
+    pub skill: Option<String>,
+    pub learning_lang_code: Option<String>,
+    pub deck_id: Option<i32>,
+    pub tags: Vec<String>,
+}
+
+impl StudyFilter {
+    /// Whether the vocab passes every filter that is set.
+    pub fn matches(&self, vocab: &Vocab) -> bool {
+        let passes = |filter: &Option<String>, value: Option<&str>| match filter {
+            Some(filter) => filter.eq_ignore_ascii_case(value.unwrap_or_default().trim()),
+            None => true,
+        };
+
+        passes(&self.pos, vocab.pos.as_deref())
+            && passes(&self.skill, vocab.skill.as_deref())
+            && passes(&self.learning_lang_code, Some(&vocab.learning_lang_code))
+    }
+}
+
+/// What a response answers, the learning language, possibly with the article of a noun, or the known
+/// language when studying in the reverse direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Answering {
+    Learning { with_article: bool },
+    Known,
+}
+
 pub trait LearnVocab {
     /// Retrieves a prioritized list of vocabulary sets for learning or review for a specified awesome person.
     ///
@@ -33,25 +184,56 @@ pub trait LearnVocab {
     /// A `Result` containing either:
     /// - `Ok(Vec<(VocabStudy, Vocab)>)`: A vector of tuples, each containing a `VocabStudy` record
     ///   and its corresponding `Vocab` record, limited by the specified `limit`.
-    /// - `Err(String)`: An error message string if the retrieval process fails.
+    /// - `Err(PalabrasError)`: If the retrieval process fails.
     ///
     /// # Details
     ///
     /// The function first filters the vocabulary pairs to separate them into two groups based on their
     /// learning priority. Then, it sorts the high-priority group by the `last_tested` date to prioritize
-    /// the most recently tested items. If the high-priority group contains fewer items than the specified limit,
-    /// additional pairs from the secondary group are added to the result set. The final list is then truncated
-    /// to meet the specified `limit` and reversed to ensure variety in presentation.
+    /// the most recently tested items, followed by the pairs of the secondary group. The list is composed
+    /// from these, interleaving part of speech, skill and length by the configured ratios, see
+    /// [`crate::sl::study_composer::StudyComposer`], up to the specified `limit`. The ratios balance each
+    /// group in turn: the high-priority pairs are chosen first and the secondary group fills the rest of
+    /// the list, so due reviews are never left out for new words.
+    ///
+    /// The awesome person's daily limits apply, see [`crate::sl::daily_quota::DailyLimits::get_daily_quota`].
+    /// Untested pairs are new words and every other pair is a review, only as many of each as are left today
+    /// are included.
     ///
     /// # Errors
     ///
     /// This function will return an error if:
+    /// - `limit` is less than 1, `PalabrasError::Validation`.
+    /// - The awesome person does not exist, `PalabrasError::NotFound`.
     /// - The retrieval of the study set from the database fails.
     fn get_vocab_to_learn(
         &self,
         awesome_id: i32,
         limit: i64,
-    ) -> Result<Vec<(VocabStudy, Vocab)>, String>;
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError>;
+
+    /// Retrieves a set of vocab to learn that passes a filter, otherwise the same as
+    /// [`LearnVocab::get_vocab_to_learn`].
+    ///
+    /// The filter is applied before the study set is prioritized and limited, so up to `limit`
+    /// matching vocab are returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `awesome_id`: The identifier of the awesome person for whom the vocabulary set is being retrieved.
+    /// - `limit`: The maximum size of the vocabulary set to return.
+    /// - `filter`: The [`StudyFilter`] each vocab must pass.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`LearnVocab::get_vocab_to_learn`], and `PalabrasError::NotFound` if the filter has a deck
+    /// that does not exist or belongs to another awesome person.
+    fn get_filtered_vocab_to_learn(
+        &self,
+        awesome_id: i32,
+        limit: i64,
+        filter: &StudyFilter,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError>;
 
     /// Constructs a translation prompt string for a given vocab.
     ///
@@ -86,13 +268,18 @@ pub trait LearnVocab {
     ///
     /// # Returns
     /// - `Ok(String)`: A string indicating the result of the match. Can provide feedback such as a perfect match, close match, or incorrect match.
-    /// - `Err(String)`: An error message if any step in the process fails.
+    /// - `Err(PalabrasError)`: If any step in the process fails.
     ///
     /// # Errors
     /// This function returns an error if:
-    /// - It fails to retrieve the vocabulary item based on the provided `vocab_id`.
+    /// - It fails to retrieve the vocabulary item based on the provided `vocab_id`, `PalabrasError::NotFound`.
+    /// - The vocab study is not a study of the vocabulary item, `PalabrasError::Validation`.
     /// - There are issues updating the vocabulary study statistics or the overall progress.
     ///
+    /// All reads and writes are done in a single unit of work, see [`UnitOfWork`]. The vocab study and the
+    /// awesome person are locked while being graded, so concurrent responses do not lose updates, and a
+    /// failure part way through leaves both records unchanged.
+    ///
     /// This function is intended to be used as part of a vocabulary learning application where users are presented
     /// with vocabulary words to translate or identify. The function assesses the accuracy of their responses and
     /// updates their learning progress accordingly.
@@ -101,36 +288,146 @@ pub trait LearnVocab {
         vocab_id: i32,
         vocab_study_id: i32,
         response: String,
-    ) -> Result<String, String>;
+    ) -> Result<String, PalabrasError>;
+
+    /// Checks a response that must include the article of a noun, otherwise the same as [`LearnVocab::check_response`].
+    ///
+    /// Nouns with a known gender are answered with their article, e.g. `la mano`. A right noun with a
+    /// missing or wrong article gets partial credit and feedback naming the article. For vocabs without
+    /// a gender, or languages without articles in their language pack, the response is checked as usual.
+    ///
+    /// # Parameters
+    /// - `vocab_id`: The identifier for the vocabulary item being studied.
+    /// - `vocab_study_id`: The identifier for the vocabulary study record.
+    /// - `response`: The user's response, the article and the noun.
+    ///
+    /// # Returns
+    /// - `Ok(String)`: A string indicating the result of the match.
+    /// - `Err(PalabrasError)`: If any step in the process fails.
+    ///
+    /// # Errors
+    /// The same as [`LearnVocab::check_response`].
+    fn check_response_with_article(
+        &self,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        response: String,
+    ) -> Result<String, PalabrasError>;
+
+    /// Checks a response in the known language, for studying in the reverse direction, otherwise the same
+    /// as [`LearnVocab::check_response`].
+    ///
+    /// The learning language is shown and the `first_lang` is answered. A `first_lang` listing several
+    /// translations separated by commas, e.g. `to check, to examine`, accepts each of them.
+    ///
+    /// # Parameters
+    /// - `vocab_id`: The identifier for the vocabulary item being studied.
+    /// - `vocab_study_id`: The identifier for the vocabulary study record.
+    /// - `response`: The user's response in their known language.
+    ///
+    /// # Returns
+    /// - `Ok(String)`: A string indicating the result of the match.
+    /// - `Err(PalabrasError)`: If any step in the process fails.
+    ///
+    /// # Errors
+    /// The same as [`LearnVocab::check_response`].
+    fn check_known_response(
+        &self,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        response: String,
+    ) -> Result<String, PalabrasError>;
+
+    /// Checks a response given in a study session, otherwise the same as [`LearnVocab::check_response`],
+    /// [`LearnVocab::check_response_with_article`] or [`LearnVocab::check_known_response`] depending on `answering`.
+    ///
+    /// The session is started with [`crate::sl::study_session::StudySessions::start_study_session`]. It
+    /// records, per vocab study, the answers given, the percentage correct before the first and after the
+    /// last answer, whether it was missed and whether it was answered again after a miss. The session is
+    /// updated in the same unit of work as the vocab study.
+    ///
+    /// # Parameters
+    /// - `study_session_id`: The identifier of the open study session.
+    /// - `vocab_id`: The identifier for the vocabulary item being studied.
+    /// - `vocab_study_id`: The identifier for the vocabulary study record.
+    /// - `response`: The user's response.
+    /// - `answering`: What the response answers, see [`Answering`].
+    ///
+    /// # Returns
+    /// - `Ok(String)`: A string indicating the result of the match.
+    /// - `Err(PalabrasError)`: If any step in the process fails.
+    ///
+    /// # Errors
+    /// The same as [`LearnVocab::check_response`], and:
+    /// - `PalabrasError::NotFound` if the study session does not exist.
+    /// - `PalabrasError::Validation` if the session belongs to another awesome person than the vocab study.
+    /// - `PalabrasError::Conflict` if the session is already finished.
+    fn check_session_response(
+        &self,
+        study_session_id: i32,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        response: String,
+        answering: Answering,
+    ) -> Result<String, PalabrasError>;
 
     /// Evaluates the guessed word against potential correct answers, returning the "distance" from an exact match.
     ///
     /// This function considers both the primary `learning_lang` string and any additional `alternatives` as possible correct answers.
     /// It calculates the Levenshtein distance between the guess and each possible match to find the closest one.
-    /// A distance of 0 indicates a perfect match, whereas a distance of 10 represents the worst-case scenario,
+    /// A distance of 0 indicates a perfect match, whereas the policy's `max_distance` represents the worst-case scenario,
     /// meaning no similarity between the guess and possible answers.
     ///
     /// # Parameters
     ///
+    /// * `policy` - The grading policy, its `max_distance` caps the distance.
     /// * `learning_lang` - The primary correct answer string.
-    /// * `alternatives` - A comma-separated string of alternative correct answers.
+    /// * `alternatives` - The alternative correct answers, each matched whole even when it holds a comma.
     /// * `guess` - The user's guessed word.
     ///
     /// # Returns
     ///
-    /// The smallest Levenshtein distance between the guess and the set of possible correct answers, capped at a maximum of 10.
+    /// The smallest Levenshtein distance between the guess and the set of possible correct answers, capped at the policy's `max_distance`.
     fn check_vocab_match(
         &self,
-        learning_lang: &String,
-        alternatives: &String,
-        guess: &String,
+        policy: &GradingPolicy,
+        learning_lang: &str,
+        alternatives: &[&str],
+        guess: &str,
     ) -> usize;
 
+    /// Evaluates a guessed phrase word by word, against the phrase and its alternatives.
+    ///
+    /// Each word of the guess is aligned with a word of a possible answer, so a single wrong word costs
+    /// that word rather than the whole phrase. The distance is the policy's `max_distance` scaled by the
+    /// share of the phrase that was wrong, and is at least 1 when any word is wrong, missing or extra.
+    /// Languages whose pack allows a `free_word_order` accept the words in another order.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy` - The grading policy, its `max_distance` scales the distance.
+    /// * `learning_lang_code` - The language of the phrase, choosing the language pack.
+    /// * `learning_lang` - The primary correct phrase.
+    /// * `alternatives` - The alternative correct phrases, each matched whole even when it holds a comma.
+    /// * `guess` - The user's guessed phrase.
+    ///
+    /// # Returns
+    ///
+    /// A [`MatchResult`] for the closest possible answer, with the words that were wrong, missing or extra.
+    fn check_phrase_match(
+        &self,
+        policy: &GradingPolicy,
+        learning_lang_code: &str,
+        learning_lang: &str,
+        alternatives: &[&str],
+        guess: &str,
+    ) -> MatchResult;
+
     /// Updates the statistics for a specific vocab based on the latest guess's distance from the correct answer.
     ///
     /// This function retrieves the current statistics for a vocab, calculates the new percentage of correctness
     /// based on the distance provided, updates the pair's stats including whether it's now considered fully known, and then
-    /// saves these changes. It also updates global stats accordingly.
+    /// saves these changes. The grading policy of the vocab study's awesome person is applied.
     ///
     /// # Parameters
     ///
@@ -141,24 +438,25 @@ pub trait LearnVocab {
     ///
     /// A `Result` containing either:
     /// - `Ok(Vocab)`: The updated `Vocab` record.
-    /// - `Err(String)`: An error message string if the operation fails.
+    /// - `Err(PalabrasError)`: If the operation fails.
     ///
     /// # Errors
     ///
     /// Returns an error if there's an issue fetching the current pair stats, performing the calculation, updating the record in the database,
-    /// or updating global progress stats. The error is returned as a `String` describing the failure.
+    /// or updating global progress stats.
     fn update_vocab_study_stats(
         &self,
         vocab_study_id: i32,
         distance: usize,
-    ) -> Result<VocabStudy, String>;
+    ) -> Result<VocabStudy, PalabrasError>;
 
     /// Calculates the new average correctness based on the previous correctness value and the distance
-    /// of the latest guess. A distance of 0 indicates a perfect match and is given a heavier weighting
-    /// in the calculation to favor accuracy.
+    /// of the latest guess. A distance of 0 indicates a perfect match and is given a heavier weighting,
+    /// the policy's `perfect_weight`, in the calculation to favor accuracy.
     ///
     /// # Parameters
     ///
+    /// * `policy` - The grading policy, providing `max_distance` and `perfect_weight`.
     /// * `previous` - The previous correctness percentage as a floating point number where 1.0
     ///   represents 100% correctness.
     /// * `distance` - The distance from the correct answer for the latest guess, where 0 indicates
@@ -169,36 +467,35 @@ pub trait LearnVocab {
     /// The new correctness percentage as a floating point number. This represents the averaged
     /// correctness taking into account the latest guess and applying a heavier weight to perfect
     /// matches.
-    fn calc_correctness(&self, previous: f64, distance: usize) -> f64;
+    fn calc_correctness(&self, policy: &GradingPolicy, previous: f64, distance: usize) -> f64;
 
     /// Updates the overall progress stats based on the latest quiz result.
     ///
     /// This function calculates the new values for the number of correct and incorrect answers,
     /// the total percentage of correct answers, and updates the progress stats record accordingly.
-    /// If the `last_fully_known` flag is true, it also increments the count of known items.
+    /// The count of known items goes up when the item became known and down when it was forgotten.
     ///
     /// # Parameters
     ///
     /// * `correct` - A boolean indicating whether the latest answer was correct.
-    /// * `last_fully_known` - A boolean indicating whether the last item is now fully known.
+    /// * `known_transition` - How the known state of the answered item changed, see [`KnownTransition`].
     ///
     /// # Returns
     ///
     /// A `Result` containing either:
     /// - `Ok(ProgressStats)`: The updated `ProgressStats` record.
-    /// - `Err(String)`: An error message string if the operation fails.
+    /// - `Err(PalabrasError)`: If the operation fails.
     ///
     /// # Errors
     ///
-    /// Returns an error if there's an issue fetching the current progress stats,
-    /// performing the calculation, or updating the record in the database.
-    /// The error is returned as a `String` describing the failure.
+    /// Returns `PalabrasError::NotFound` if the awesome person does not exist, or another
+    /// error if there's an issue fetching or updating the record in the database.
     fn update_overall_progress(
         &self,
         awesome_person_id: i32,
         correct: bool,
-        last_fully_known: bool,
-    ) -> Result<Option<AwesomePerson>, String>;
+        known_transition: KnownTransition,
+    ) -> Result<Option<AwesomePerson>, PalabrasError>;
 
     /// Determines the match prompt based on the distance between the correct answer and the user's response.
     ///
@@ -206,6 +503,7 @@ pub trait LearnVocab {
     /// It returns a string indicating the quality of the match.
     ///
     /// # Parameters
+    /// - `policy`: The grading policy, providing the `close_distance` cut-off.
     /// - `correct`: The correct answer as a string slice.
     /// - `user_response`: The user's response as a string slice.
     /// - `distance`: The Levenshtein distance between the correct answer and the user's response, as an usize.
@@ -213,10 +511,15 @@ pub trait LearnVocab {
     /// # Returns
     /// A `String` that provides feedback on how close the user's response was to the correct answer.
     /// - Returns "Perfect Match!" if the distance is 0.
-    /// - Returns "Close, it was '[correct]', you entered '[user_response]'" if the distance is 3 or less.
+    /// - Returns "Close, it was '[correct]', you entered '[user_response]'" if the distance is `close_distance` or less.
     /// - Otherwise, returns "It was '[correct]', you entered '[user_response]'".
-    fn determine_match_prompt(&self, correct: &str, user_response: &str, distance: usize)
-        -> String;
+    fn determine_match_prompt(
+        &self,
+        policy: &GradingPolicy,
+        correct: &str,
+        user_response: &str,
+        distance: usize,
+    ) -> String;
 
     /// Retrieves a single awesome person record by its primary key.
     ///
@@ -226,9 +529,9 @@ pub trait LearnVocab {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(Some(AwesomePerson))` if an awesome person record with the specified `id` exists,
-    /// Ok(None) if not found or an error if the query fails.
-    fn get_awesome_person(&self, awesome_person_id: i32) -> Result<Option<AwesomePerson>, String>;
+    /// Returns `Ok(AwesomePerson)` if an awesome person record with the specified `id` exists,
+    /// `PalabrasError::NotFound` if not found or another error if the query fails.
+    fn get_awesome_person(&self, awesome_person_id: i32) -> Result<AwesomePerson, PalabrasError>;
 
     /// Retrieves a single awesome person record by its unique code field.
     ///
@@ -238,12 +541,13 @@ pub trait LearnVocab {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(Some(AwesomePerson))` if an awesome person record with the specified `id` exists,
-    /// Ok(None) if not found or an error if the query fails.
+    /// Returns `Ok(AwesomePerson)` if an awesome person record with the specified `code` exists,
+    /// `PalabrasError::NotFound` without the code in its message if not found or another error if the
+    /// query fails.
     fn get_awesome_person_by_code(
         &self,
         look_up_code: String,
-    ) -> Result<Option<AwesomePerson>, String>;
+    ) -> Result<AwesomePerson, PalabrasError>;
 
     /// Retrieves a single tuple of vocab study and vocab by the vocab study id.
     ///
@@ -254,22 +558,196 @@ pub trait LearnVocab {
     /// # Returns
     ///
     /// Returns `Ok((VocabStudy, Vocab))` if the both records were found.
-    /// Err if either are not found or if the query fails.
-    fn get_vocab_stats(&self, vocab_study_id: i32) -> Result<(VocabStudy, Vocab), String>;
+    /// `PalabrasError::NotFound` if either are not found or another error if the query fails.
+    fn get_vocab_stats(&self, vocab_study_id: i32) -> Result<(VocabStudy, Vocab), PalabrasError>;
+
+    /// Retrieves the alternative answers of a vocab, e.g. to show them to a learner.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_id` - The primary key of the vocab.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `VocabAlternative` records in the order they were added, empty if there are none,
+    /// or an error if the query fails.
+    fn get_alternatives(&self, vocab_id: i32) -> Result<Vec<VocabAlternative>, PalabrasError>;
+
+    /// Replaces the notes an awesome person keeps for a vocab they study.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_study_id` - The primary key of the vocab study.
+    /// * `user_notes` - The new notes, empty to remove them.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated `VocabStudy`, `PalabrasError::NotFound` if the vocab study does not exist
+    /// or another error if the update fails.
+    fn update_user_notes(
+        &self,
+        vocab_study_id: i32,
+        user_notes: &str,
+    ) -> Result<VocabStudy, PalabrasError>;
+
+    /// Retrieves the grading policy applied to an awesome person's responses.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_person_id` - The primary key of the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// Returns the awesome person's own `GradingPolicy`, or the configured default when they have none.
+    /// `PalabrasError::NotFound` if the awesome person does not exist or another error if the query fails.
+    fn get_grading_policy(&self, awesome_person_id: i32) -> Result<GradingPolicy, PalabrasError>;
+
+    /// Stores a grading policy for an awesome person, replacing any previous one.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy` - The policy to store, keyed by its `awesome_person_id`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the stored `GradingPolicy`.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if `max_distance` is below 1, `well_known_threshold` is not above 0 and at most 1,
+    ///   `close_distance` is negative or above `max_distance`, or `perfect_weight` is not positive.
+    /// - `PalabrasError::NotFound` if the awesome person does not exist.
+    /// - Another error if the save fails.
+    fn update_grading_policy(&self, policy: GradingPolicy) -> Result<GradingPolicy, PalabrasError>;
+
+    /// Sets the regional variant an awesome person learns, e.g. `es-MX`.
+    ///
+    /// Answers are then expected in that variant when a vocab has one, and answers from other
+    /// variants are accepted with a note naming the preferred answer.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_person_id` - The primary key of the awesome person.
+    /// * `lang_variant` - A BCP-47 style code, a language and a region such as `es-MX` or `es-419`,
+    ///   or empty to prefer no variant. The code is normalised, e.g. `es-mx` becomes `es-MX`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated `AwesomePerson`.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if `lang_variant` is not a language and region code.
+    /// - `PalabrasError::NotFound` if the awesome person does not exist.
+    /// - Another error if the update fails.
+    fn update_preferred_variant(
+        &self,
+        awesome_person_id: i32,
+        lang_variant: &str,
+    ) -> Result<AwesomePerson, PalabrasError>;
+
+    /// Retrieves a list of conjugation drills for an awesome person.
+    ///
+    /// The verbs are chosen from the awesome person's study set, prioritized the same way as
+    /// [`LearnVocab::get_vocab_to_learn`]. A vocab is a verb when it has an `infinitive` and its
+    /// `pos` is `Verb` or not set, only Spanish verbs can be drilled. Each verb is drilled on a person
+    /// and tense, those never drilled come first, then those with the lowest accuracy, see
+    /// [`LearnVocab::get_conjugation_stats`].
+    ///
+    /// # Parameters
+    ///
+    /// - `awesome_id`: The identifier of the awesome person drilling.
+    /// - `limit`: The maximum number of drills to return.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either:
+    /// - `Ok(Vec<(VocabStudy, Vocab, ConjugationDrill)>)`: The drills with the vocab study and vocab of each verb.
+    /// - `Err(PalabrasError)`: If the retrieval process fails.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if `limit` is less than 1.
+    /// - `PalabrasError::NotFound` if the awesome person does not exist.
+    /// - Another error if the retrieval of the study set fails.
+    fn get_conjugation_drills(
+        &self,
+        awesome_id: i32,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab, ConjugationDrill)>, PalabrasError>;
+
+    /// Checks the response to a conjugation drill and records it in the awesome person's conjugation stats.
+    ///
+    /// The response is compared with the conjugated form using the fuzzy matching of
+    /// [`LearnVocab::check_vocab_match`] and the awesome person's grading policy. Only a perfect match
+    /// counts as correct. Drills do not change the vocab study, which tracks the translation of the vocab.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_id` - The identifier of the verb drilled.
+    /// * `vocab_study_id` - The identifier of the vocab study, linking the awesome person and the verb.
+    /// * `person` - The person drilled.
+    /// * `tense` - The tense drilled.
+    /// * `response` - The form entered by the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// The feedback to display, see [`LearnVocab::determine_match_prompt`].
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if the vocab study is not the study of the vocab, or the vocab has no
+    ///   infinitive that can be conjugated.
+    /// - `PalabrasError::NotFound` if the vocab or vocab study does not exist.
+    /// - Another error if recording the answer fails.
+    fn check_conjugation(
+        &self,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        person: Person,
+        tense: Tense,
+        response: String,
+    ) -> Result<String, PalabrasError>;
+
+    /// Retrieves an awesome person's conjugation drill accuracy.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_person_id` - The primary key of the awesome person.
+    ///
+    /// # Returns
+    ///
+    /// The `ConjugationStat` of each person and tense drilled, ordered by tense then person.
+    /// `PalabrasError::NotFound` if the awesome person does not exist or another error if the query fails.
+    fn get_conjugation_stats(
+        &self,
+        awesome_person_id: i32,
+    ) -> Result<Vec<ConjugationStat>, PalabrasError>;
 }
 
 pub struct VocabFuzzyMatch {
     awesome_person_repo: Box<dyn AwesomePersonRepository>,
     vocab_study_repo: Box<dyn VocabStudyRepository>,
     vocab_repo: Box<dyn VocabRepository>,
+    unit_of_work: Box<dyn UnitOfWork>,
+    default_grading_policy: GradingPolicyConfig,
+    language_packs: &'static LanguagePackRegistry,
+    leech_rule: LeechConfig,
+    composer: StudyComposer,
 }
 
 lazy_static! {
-    static ref FUZZY_MATCH_SERVICE: Mutex<VocabFuzzyMatch> = Mutex::new(VocabFuzzyMatch::new(
+    static ref FUZZY_MATCH_SERVICE: VocabFuzzyMatch = VocabFuzzyMatch::new(
         Box::new(DbAwesomePersonRepository),
         Box::new(DbVocabStudyRepository),
         Box::new(DbVocabRepository),
-    ));
+        Box::new(DbUnitOfWork),
+    )
+    .with_default_grading_policy(app_config().grading.clone())
+    .with_language_packs(language_packs())
+    .with_leech_rule(app_config().leech.clone())
+    .with_composer(StudyComposer::new(app_config().composer.clone()));
+    static ref NO_LANGUAGE_PACKS: LanguagePackRegistry = LanguagePackRegistry::default();
 }
 
 impl VocabFuzzyMatch {
@@ -278,147 +756,803 @@ impl VocabFuzzyMatch {
         awesome_person_repo: Box<dyn AwesomePersonRepository>,
         vocab_study_repo: Box<dyn VocabStudyRepository>,
         vocab_repo: Box<dyn VocabRepository>,
+        unit_of_work: Box<dyn UnitOfWork>,
     ) -> Self {
         VocabFuzzyMatch {
             awesome_person_repo,
             vocab_study_repo,
             vocab_repo,
+            unit_of_work,
+            default_grading_policy: GradingPolicyConfig::default(),
+            language_packs: &NO_LANGUAGE_PACKS,
+            leech_rule: LeechConfig::default(),
+            composer: StudyComposer::default(),
         }
     }
 
-    // Method to access the singleton instance, but one thread at a time
-    pub fn instance() -> MutexGuard<'static, VocabFuzzyMatch> {
-        FUZZY_MATCH_SERVICE.lock().unwrap()
+    // Replaces the grading policy used for awesome people without their own.
+    pub fn with_default_grading_policy(self, default_grading_policy: GradingPolicyConfig) -> Self {
+        VocabFuzzyMatch {
+            default_grading_policy,
+            ..self
+        }
     }
-}
 
-/// An implementation of the LearnVocabs service. Using fuzzy logic to check word
-/// matching calculate the distance, The distance from a perfect match to decides how to change the vocab
-/// correctness.
-impl LearnVocab for VocabFuzzyMatch {
-    /// Implementation, see trait for details [`LearnVocab::get_vocab_to_learn`]
-    ///
-    /// For advanced usage and mock implementations, please refer to
-    /// the unit integration tests in this module.
-    fn get_vocab_to_learn(
-        &self,
-        awesome_id: i32,
-        limit: i64,
-    ) -> Result<Vec<(VocabStudy, Vocab)>, String> {
-        let ap = self
-            .awesome_person_repo
-            .get_awesome_person_by_id(awesome_id)
-            .unwrap_or_default();
-        if ap.is_none() {
-            return Err(format!(
-                "Failed to find awesome person with id {}",
-                awesome_id
-            ));
+    // Replaces the language packs, used for the articles of nouns. Without them articles are not checked.
+    pub fn with_language_packs(self, language_packs: &'static LanguagePackRegistry) -> Self {
+        VocabFuzzyMatch {
+            language_packs,
+            ..self
         }
-        let max_words_in_phrase = ap.unwrap().max_learning_words;
+    }
 
-        // TODO limit the number of results returned by the db, perhaps with a MV.
-        let study_set = self
-            .vocab_study_repo
-            .get_study_set(awesome_id, max_words_in_phrase)?;
+    // Replaces the rule for detecting leeches.
+    pub fn with_leech_rule(self, leech_rule: LeechConfig) -> Self {
+        VocabFuzzyMatch { leech_rule, ..self }
+    }
+
+    // Replaces the composer that balances the study lists.
+    pub fn with_composer(self, composer: StudyComposer) -> Self {
+        VocabFuzzyMatch { composer, ..self }
+    }
+
+    // Method to access the singleton instance. The repos are stateless and pull a connection
+    // from the pool per operation, so the service can be shared across threads without a lock.
+    pub fn instance() -> &'static VocabFuzzyMatch {
+        &FUZZY_MATCH_SERVICE
+    }
+
+    // Orders a study set for learning, studied words that are not yet well known first, and composes
+    // a balanced list from it, the due reviews before new words. With a daily quota only as many new words and reviews as are left today are taken.
+    fn prioritize_study_set(
+        &self,
+        study_set: Vec<(VocabStudy, Vocab)>,
+        limit: i64,
+        quota: Option<&DailyQuota>,
+    ) -> Vec<(VocabStudy, Vocab)> {
+        let (mut new_left, mut reviews_left) = quota.map_or((i64::MAX, i64::MAX), |quota| {
+            (quota.new_remaining(), quota.reviews_remaining())
+        });
 
         // Separate tuples into two groups for prioritization.
         let (mut target_group, secondary_group): (Vec<_>, Vec<_>) = study_set
             .into_iter()
-            .filter(|(_, v)| !v.first_lang.is_empty())
             .partition(|(vs, _)| vs.last_tested.is_some() && !vs.well_known);
 
         // Sorts the list by last_tested to find the most recently studied in the target group.
         target_group.sort_by(|(a_study, _), (b_study, _)| {
             b_study
                 .last_tested
-                .clone()
                 .unwrap_or_default()
-                .cmp(&a_study.last_tested.clone().unwrap_or_default())
+                .cmp(&a_study.last_tested.unwrap_or_default())
         });
 
-        // Grab more pairs from the secondary group as needed.
-        if target_group.len() < limit as usize {
-            target_group.extend(
-                secondary_group
-                    .into_iter()
-                    .take(limit as usize - target_group.len()),
-            );
-        } else {
-            target_group.truncate(limit as usize);
-        }
+        // Untested pairs are new words, only as many of each as the quota has left.
+        let mut within_quota = |(vs, _): &(VocabStudy, Vocab)| {
+            let left = if vs.last_tested.is_none() {
+                &mut new_left
+            } else {
+                &mut reviews_left
+            };
+            *left -= 1;
+            *left >= 0
+        };
+        let due: Vec<_> = target_group.into_iter().filter(&mut within_quota).collect();
+        let rest: Vec<_> = secondary_group
+            .into_iter()
+            .filter(&mut within_quota)
+            .collect();
 
-        // Reverse the order to keep from presenting last word testing in the last set first in this set.
-        target_group.reverse();
+        // Composed tier by tier, the due reviews first and the secondary group fills what is left of the
+        // list, so a group with a heavy ratio cannot crowd out the due reviews.
+        let limit = limit as usize;
+        let mut chosen: HashSet<i32> = self
+            .composer
+            .compose(due.clone(), limit)
+            .iter()
+            .map(|(vs, _)| vs.id)
+            .collect();
+        let left = limit.saturating_sub(chosen.len());
+        chosen.extend(
+            self.composer
+                .compose(rest.clone(), left)
+                .iter()
+                .map(|(vs, _)| vs.id),
+        );
 
-        // Returning a curated vocab lesson
-        Ok(target_group)
+        // The chosen pairs, still in priority order, are balanced as one list.
+        let candidates = due
+            .into_iter()
+            .chain(rest)
+            .filter(|(vs, _)| chosen.contains(&vs.id))
+            .collect();
+        self.composer.compose(candidates, limit)
     }
 
-    /// Implementation, see trait for details [`LearnVocab::determine_prompt`]
-    ///
-    /// For advanced usage and mock implementations, please refer to
-    /// the unit tests in this module.
-    fn determine_prompt(&self, vocab: &Vocab, user_notes: &str) -> String {
-        let mut prompt = format!("Translate: '{}'", &vocab.first_lang);
-        if !vocab.hint.clone().unwrap_or_default().is_empty() {
-            prompt = format!(
-                "{}    hint: {}",
-                prompt,
-                vocab.hint.clone().unwrap_or_default()
-            );
-        }
-
-        if !vocab.pos.clone().unwrap_or_default().is_empty() {
-            prompt = format!(
-                "{}    pos: {}",
-                prompt,
-                vocab.pos.clone().unwrap_or_default()
-            );
-        }
-
-        if !user_notes.is_empty() {
-            prompt = format!("{}    your notes: {}", prompt, user_notes);
-        }
+    // The persons and tenses to drill, those never drilled first, then the lowest accuracy.
+    fn drill_order(&self, stats: &[ConjugationStat]) -> Vec<(Person, Tense)> {
+        let mut order: Vec<(Person, Tense, f64)> = Tense::ALL
+            .into_iter()
+            .flat_map(|tense| Person::ALL.into_iter().map(move |person| (person, tense)))
+            .map(|(person, tense)| {
+                let accuracy = stats
+                    .iter()
+                    .find(|stat| stat.person == person.key() && stat.tense == tense.key())
+                    .map_or(-1.0, |stat| stat.percentage_correct());
+                (person, tense, accuracy)
+            })
+            .collect();
+        order.sort_by(|a, b| a.2.total_cmp(&b.2));
 
-        prompt
+        order
+            .into_iter()
+            .map(|(person, tense, _)| (person, tense))
+            .collect()
     }
 
-    /// Implementation, see trait for details [`LearnVocab::check_response`]
-    ///
-    /// For advanced usage and mock implementations, please refer to
-    /// the unit and integration tests for this module.
-    fn check_response(
+    // Grades a response in a single unit of work, see LearnVocab::check_response. With a study session
+    // the answer is also recorded in it, see LearnVocab::check_session_response.
+    fn grade_response(
         &self,
+        study_session_id: Option<i32>,
         vocab_id: i32,
         vocab_study_id: i32,
-        response: String,
-    ) -> Result<String, String> {
-        // Get the vocab containing the possible correct responses.
-        let vocab = self
-            .vocab_repo
-            .get_vocab_by_id(vocab_id)
-            .map_err(|e| e.to_string())?;
+        response: &str,
+        answering: Answering,
+    ) -> Result<String, PalabrasError> {
+        let mut prompt = String::new();
+
+        self.unit_of_work.grading(&mut |tx| {
+            // Get the vocab and its alternatives, the possible correct responses.
+            let vocab = tx.get_vocab(vocab_id)?;
+            let alternatives = tx.get_vocab_alternatives(vocab_id)?;
+            let current = tx.lock_vocab_study(vocab_study_id)?;
+            if current.vocab_id != vocab_id {
+                return Err(PalabrasError::Validation(format!(
+                    "vocab study {} is not a study of vocab {}",
+                    vocab_study_id, vocab_id
+                )));
+            }
 
-        // Use the fuzzy matching logic to see how much "distance" the response, 0 is correct.
-        let distance = self.check_vocab_match(
-            &vocab.learning_lang,
-            &vocab.alternatives.unwrap_or_default(),
-            &response,
+            // The awesome person, locked for the update of their progress below.
+            let awesome_person = tx.lock_awesome_person(current.awesome_person_id)?;
+
+            // The awesome person's own grading policy, or the default.
+            let policy = tx
+                .get_grading_policy(current.awesome_person_id)?
+                .unwrap_or_else(|| {
+                    self.default_grading_policy
+                        .policy_for(current.awesome_person_id)
+                });
+
+            // How much "distance" the response is from the answer, 0 is correct.
+            let matched = match answering {
+                Answering::Learning { with_article } => self.match_response(
+                    &policy,
+                    &vocab,
+                    &alternatives,
+                    awesome_person.preferred_variant.as_deref(),
+                    response,
+                    with_article,
+                ),
+                Answering::Known => {
+                    // Each of the translations listed in the first language is right.
+                    let translations: Vec<&str> = vocab.first_lang.split(',').collect();
+                    let distance =
+                        self.check_vocab_match(&policy, &vocab.first_lang, &translations, response);
+                    MatchResult {
+                        distance,
+                        prompt: self.determine_match_prompt(
+                            &policy,
+                            &vocab.first_lang,
+                            response,
+                            distance,
+                        ),
+                        ..Default::default()
+                    }
+                }
+            };
+            let distance = matched.distance;
+
+            // Update the awesome person's stats for this vocab word.
+            let (was_known, was_leech) = (current.well_known, current.leech);
+            let new_word = current.last_tested.is_none();
+            let percentage_before = current.percentage_correct.unwrap_or_default();
+            let vocab_study = self.grade_vocab_study(&policy, current, distance);
+            tx.update_vocab_study(vocab_study.clone())?;
+
+            // Add the answer to the attempt history the daily limits are counted against.
+            tx.record_vocab_attempt(&NewVocabAttempt {
+                awesome_person_id: vocab_study.awesome_person_id,
+                vocab_study_id: vocab_study.id,
+                new_word,
+                correct: distance == 0,
+                attempted: Utc::now(),
+            })?;
+
+            // Record the answer in the study session, locked after the awesome person.
+            if let Some(study_session_id) = study_session_id {
+                let session = tx.lock_study_session(study_session_id)?;
+                if session.awesome_person_id != vocab_study.awesome_person_id {
+                    return Err(PalabrasError::Validation(format!(
+                        "study session {} belongs to another awesome person than vocab study {}",
+                        study_session_id, vocab_study.id
+                    )));
+                }
+                if session.finished.is_some() {
+                    return Err(PalabrasError::Conflict(format!(
+                        "study session {} is already finished",
+                        study_session_id
+                    )));
+                }
+
+                let item = tx.get_study_session_item(study_session_id, vocab_study.id)?;
+                tx.save_study_session_item(&self.grade_session_item(
+                    item,
+                    &session,
+                    &vocab_study,
+                    percentage_before,
+                    distance == 0,
+                ))?;
+                tx.update_study_session(StudySession {
+                    num_answers: session.num_answers + 1,
+                    num_correct: session.num_correct + (distance == 0) as i32,
+                    ..session
+                })?;
+            }
+
+            // Update the awesome person's overall status.
+            tx.update_awesome_person(self.grade_overall_progress(
+                awesome_person,
+                distance == 0,
+                KnownTransition::between(was_known, vocab_study.well_known),
+            ))?;
+
+            // For the response text to be displayed to the awesome person
+            prompt = matched.prompt;
+            if vocab_study.leech && !was_leech {
+                prompt = format!(
+                    "{} This one keeps slipping, it is now a leech{}.",
+                    prompt,
+                    if vocab_study.suspended {
+                        " and suspended"
+                    } else {
+                        ""
+                    }
+                );
+            }
+            Ok(())
+        })?;
+
+        Ok(prompt)
+    }
+
+    // The distance of a response and the feedback for it. The answer in the preferred variant is the
+    // one expected and shown in feedback, when the vocab has one. A perfect answer given as an
+    // alternative with a register says so, e.g. "'vocablo' is formal", and one from another variant
+    // names the answer in the preferred variant, e.g. "ordenador — in Latin America: computadora".
+    fn match_response(
+        &self,
+        policy: &GradingPolicy,
+        vocab: &Vocab,
+        alternatives: &[VocabAlternative],
+        preferred_variant: Option<&str>,
+        response: &str,
+        with_article: bool,
+    ) -> MatchResult {
+        // Every correct answer with its variant and register, the vocab itself first.
+        let answers: Vec<(&str, Option<&str>, Option<&str>)> = std::iter::once((
+            vocab.learning_lang.as_str(),
+            vocab.lang_variant.as_deref(),
+            None,
+        ))
+        .chain(alternatives.iter().map(|alternative| {
+            (
+                alternative.alternative.as_str(),
+                alternative.lang_variant.as_deref(),
+                alternative.register.as_deref(),
+            )
+        }))
+        .map(|(answer, variant, register)| (answer, non_empty(variant), non_empty(register)))
+        .collect();
+        let preferred_variant = non_empty(preferred_variant);
+        let in_preferred = |variant: Option<&str>| {
+            variant
+                .zip(preferred_variant)
+                .map(|(variant, preferred)| variant.eq_ignore_ascii_case(preferred))
+        };
+
+        let expected = answers
+            .iter()
+            .position(|(_, variant, _)| in_preferred(*variant) == Some(true))
+            .unwrap_or(0);
+        let expected_vocab = Vocab {
+            learning_lang: answers[expected].0.to_string(),
+            ..vocab.clone()
+        };
+        let others = answers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != expected)
+            .map(|(_, (answer, _, _))| *answer)
+            .collect::<Vec<_>>();
+        let mut matched =
+            self.match_answer(policy, &expected_vocab, &others, response, with_article);
+        if matched.distance > 0 {
+            return matched;
+        }
+
+        let words = phrase_words(response);
+        let Some((answer, variant, register)) = answers
+            .iter()
+            .find(|(answer, _, _)| phrase_words(answer) == words)
+        else {
+            return matched;
+        };
+        if let Some(register) = register {
+            matched
+                .prompt
+                .push_str(&format!(" '{}' is {}", answer.trim(), register));
+        }
+        if in_preferred(*variant) == Some(false) {
+            let pack = self.language_packs.pack_for(&vocab.learning_lang_code);
+            let note = if in_preferred(answers[expected].1) == Some(true) {
+                format!(
+                    " {} — in {}: {}",
+                    answer.trim(),
+                    pack.variant_name(preferred_variant.unwrap_or_default()),
+                    answers[expected].0.trim()
+                )
+            } else {
+                format!(
+                    " '{}' is used in {}",
+                    answer.trim(),
+                    pack.variant_name(variant.unwrap_or_default())
+                )
+            };
+            matched.prompt.push_str(&note);
+        }
+
+        matched
+    }
+
+    // Matches a response against the vocab and its alternatives. The article of a
+    // noun with a known gender is checked separately, a right noun with a wrong or missing article
+    // is partial credit. Phrases are matched word by word.
+    fn match_answer(
+        &self,
+        policy: &GradingPolicy,
+        vocab: &Vocab,
+        alternatives: &[&str],
+        response: &str,
+        with_article: bool,
+    ) -> MatchResult {
+        let pack = self.language_packs.pack_for(&vocab.learning_lang_code);
+
+        // Vocabs that already include their article are matched as a whole.
+        let articles = vocab
+            .gender
+            .as_deref()
+            .and_then(|gender| GenderArticles::for_gender(pack, gender))
+            .filter(|articles| articles.split(&vocab.learning_lang).0.is_none());
+        let Some(articles) = articles else {
+            if vocab.num_learning_words > 1 {
+                return self.check_phrase_match(
+                    policy,
+                    &vocab.learning_lang_code,
+                    &vocab.learning_lang,
+                    alternatives,
+                    response,
+                );
+            }
+            let distance =
+                self.check_vocab_match(policy, &vocab.learning_lang, alternatives, response);
+            return MatchResult {
+                distance,
+                prompt: self.determine_match_prompt(
+                    policy,
+                    &vocab.learning_lang,
+                    response,
+                    distance,
+                ),
+                ..Default::default()
+            };
+        };
+
+        let expected = format!("{} {}", articles.expected(), vocab.learning_lang.trim());
+        let (article, noun) = articles.split(response);
+        let distance = self.check_vocab_match(policy, &vocab.learning_lang, alternatives, noun);
+        if distance > 0 {
+            let correct = if with_article {
+                &expected
+            } else {
+                &vocab.learning_lang
+            };
+            return MatchResult {
+                distance,
+                prompt: self.determine_match_prompt(policy, correct, response, distance),
+                ..Default::default()
+            };
+        }
+
+        let partial_credit = (policy.close_distance as usize).max(1);
+        let (distance, prompt) = match article {
+            Some(article) if !articles.is_right(article) => (
+                partial_credit,
+                format!(
+                    "Right word, wrong article, it is '{}', you entered '{}'",
+                    expected,
+                    response.trim()
+                ),
+            ),
+            None if with_article => (
+                partial_credit,
+                format!(
+                    "Right word, but the article is missing, it is '{}'",
+                    expected
+                ),
+            ),
+            _ => (
+                0,
+                self.determine_match_prompt(policy, &expected, response, 0),
+            ),
+        };
+
+        MatchResult {
+            distance,
+            prompt,
+            ..Default::default()
+        }
+    }
+
+    // Applies the distance of the latest guess to the vocab study stats, and the leech rule.
+    fn grade_vocab_study(
+        &self,
+        policy: &GradingPolicy,
+        current: VocabStudy,
+        distance: usize,
+    ) -> VocabStudy {
+        let updated_percentage_correct = self.calc_correctness(
+            policy,
+            current.percentage_correct.unwrap_or_default(),
+            distance,
         );
 
-        // Update the awesome person's stats for this vocab word.
-        let vocab_study = self.update_vocab_study_stats(vocab_study_id, distance)?;
+        let last_change =
+            updated_percentage_correct - current.percentage_correct.unwrap_or_default();
 
-        // Update the awesome person's overall status.
-        self.update_overall_progress(
-            vocab_study.awesome_person_id,
-            distance == 0,
-            vocab_study.well_known.clone(),
-        )?;
+        let correct_attempts = if distance.eq(&0) {
+            current.correct_attempts.unwrap_or_default() + 1
+        } else {
+            current.correct_attempts.unwrap_or_default()
+        };
+
+        let graded = VocabStudy {
+            percentage_correct: Option::from(updated_percentage_correct),
+            last_change: Option::from(last_change),
+            last_tested: Option::from(Utc::now()),
+            well_known: updated_percentage_correct > policy.well_known_threshold,
+            attempts: Option::from(current.attempts.unwrap_or_default() + 1),
+            correct_attempts: Some(correct_attempts),
+            // Only the misses since the last perfect match are lapses, old misses of a known word are not held against it.
+            lapses: if distance == 0 { 0 } else { current.lapses + 1 },
+            ..current
+        };
+
+        // A vocab study breaking the leech rule is flagged once, an unsuspended leech is not suspended again.
+        if !graded.leech && distance > 0 && self.leech_rule.is_leech(&graded) {
+            VocabStudy {
+                leech: true,
+                suspended: self.leech_rule.suspend,
+                ..graded
+            }
+        } else {
+            graded
+        }
+    }
+
+    // Applies the latest answer to the session record of a vocab study, creating it on the first answer.
+    // An answer after a miss is the re-drill of the vocab.
+    fn grade_session_item(
+        &self,
+        item: Option<StudySessionItem>,
+        session: &StudySession,
+        graded: &VocabStudy,
+        percentage_before: f64,
+        correct: bool,
+    ) -> StudySessionItem {
+        let percentage_after = graded.percentage_correct.unwrap_or_default();
+        match item {
+            Some(item) => StudySessionItem {
+                attempts: item.attempts + 1,
+                correct_attempts: item.correct_attempts + correct as i32,
+                percentage_after,
+                redrilled: item.redrilled || item.missed,
+                missed: item.missed || !correct,
+                ..item
+            },
+            None => StudySessionItem {
+                study_session_id: session.id,
+                vocab_study_id: graded.id,
+                attempts: 1,
+                correct_attempts: correct as i32,
+                percentage_before,
+                percentage_after,
+                missed: !correct,
+                redrilled: false,
+                created: Utc::now(),
+            },
+        }
+    }
+
+    // Applies the latest result to the awesome person's overall progress.
+    fn grade_overall_progress(
+        &self,
+        awesome_person: AwesomePerson,
+        correct: bool,
+        known_transition: KnownTransition,
+    ) -> AwesomePerson {
+        // Increment counters based on whether the answer was correct
+        let (num_correct, num_incorrect) = (
+            awesome_person.num_correct.unwrap_or(0) + correct as i32,
+            awesome_person.num_incorrect.unwrap_or(0) + (!correct) as i32,
+        );
+
+        // Calculate the total percentage
+        let total_percentage = num_correct as f64 / (num_correct + num_incorrect) as f64;
+
+        AwesomePerson {
+            num_known: Some(
+                (awesome_person.num_known.unwrap_or(0) + known_transition.num_known_delta()).max(0),
+            ),
+            num_correct: Some(num_correct),
+            num_incorrect: Some(num_incorrect),
+            total_percentage: Some(total_percentage),
+            updated: Utc::now(),
+            ..awesome_person
+        }
+    }
+}
+
+// A BCP-47 style language and region code, e.g. es-MX or es-419, with the language in lowercase and
+// the region in uppercase. Empty for no variant.
+fn normalize_variant(lang_variant: &str) -> Result<String, PalabrasError> {
+    let lang_variant = lang_variant.trim();
+    if lang_variant.is_empty() {
+        return Ok(String::new());
+    }
+
+    let valid = match lang_variant.split_once('-') {
+        Some((language, region)) => {
+            (2..=3).contains(&language.len())
+                && language.chars().all(|c| c.is_ascii_alphabetic())
+                && ((region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()))
+                    || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit())))
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(PalabrasError::Validation(format!(
+            "lang_variant must be a language and region code such as es-MX, found {}",
+            lang_variant
+        )));
+    }
+
+    let (language, region) = lang_variant.split_once('-').unwrap_or_default();
+    Ok(format!(
+        "{}-{}",
+        language.to_ascii_lowercase(),
+        region.to_ascii_uppercase()
+    ))
+}
+
+// Treats an empty or blank label as missing, e.g. a variant stored as ''.
+pub(crate) fn non_empty(label: Option<&str>) -> Option<&str> {
+    label.map(str::trim).filter(|label| !label.is_empty())
+}
+
+// Rejects policies that would make grading meaningless, e.g. a zero max distance.
+pub(crate) fn validate_grading_policy(policy: &GradingPolicy) -> Result<(), PalabrasError> {
+    if policy.max_distance < 1 {
+        return Err(PalabrasError::Validation(format!(
+            "max_distance must be at least 1, found {}",
+            policy.max_distance
+        )));
+    }
+    if policy.well_known_threshold <= 0.0 || policy.well_known_threshold > 1.0 {
+        return Err(PalabrasError::Validation(format!(
+            "well_known_threshold must be above 0 and at most 1, found {}",
+            policy.well_known_threshold
+        )));
+    }
+    if policy.close_distance < 0 || policy.close_distance > policy.max_distance {
+        return Err(PalabrasError::Validation(format!(
+            "close_distance must be between 0 and max_distance {}, found {}",
+            policy.max_distance, policy.close_distance
+        )));
+    }
+    if policy.perfect_weight <= 0.0 {
+        return Err(PalabrasError::Validation(format!(
+            "perfect_weight must be above 0, found {}",
+            policy.perfect_weight
+        )));
+    }
+
+    Ok(())
+}
+
+/// An implementation of the LearnVocabs service. Using fuzzy logic to check word
+/// matching calculate the distance, The distance from a perfect match to decides how to change the vocab
+/// correctness.
+impl LearnVocab for VocabFuzzyMatch {
+    /// Implementation, see trait for details [`LearnVocab::get_vocab_to_learn`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit integration tests in this module.
+    fn get_vocab_to_learn(
+        &self,
+        awesome_id: i32,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        self.get_filtered_vocab_to_learn(awesome_id, limit, &StudyFilter::default())
+    }
 
-        // For the response text to be displayed to the awesome person
-        Ok(self.determine_match_prompt(&vocab.learning_lang, &response, distance))
+    /// Implementation, see trait for details [`LearnVocab::get_filtered_vocab_to_learn`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn get_filtered_vocab_to_learn(
+        &self,
+        awesome_id: i32,
+        limit: i64,
+        filter: &StudyFilter,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        if limit < 1 {
+            return Err(PalabrasError::Validation(format!(
+                "limit must be at least 1, found {}",
+                limit
+            )));
+        }
+
+        let ap = self
+            .awesome_person_repo
+            .get_awesome_person_by_id(awesome_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_id))?;
+        let max_words_in_phrase = ap.max_learning_words;
+
+        // TODO limit the number of results returned by the db, perhaps with a MV.
+        let study_set = match filter.deck_id {
+            Some(deck_id) => {
+                let deck = owned_deck(self.awesome_person_repo.as_ref(), deck_id, awesome_id)?;
+                self.vocab_study_repo.get_deck_study_set(
+                    awesome_id,
+                    max_words_in_phrase,
+                    deck.id,
+                )?
+            }
+            None => self
+                .vocab_study_repo
+                .get_study_set(awesome_id, max_words_in_phrase)?,
+        };
+        let tagged: Option<HashSet<i32>> = if filter.tags.is_empty() {
+            None
+        } else {
+            let vocabs =
+                self.vocab_repo
+                    .find_vocab_by_tags(&tag_names(&filter.tags)?, None, i64::MAX)?;
+            Some(vocabs.into_iter().map(|vocab| vocab.id).collect())
+        };
+        let study_set = study_set
+            .into_iter()
+            .filter(|(vs, v)| !vs.suspended && !v.first_lang.is_empty() && filter.matches(v))
+            .filter(|(_, v)| tagged.as_ref().is_none_or(|ids| ids.contains(&v.id)))
+            .collect();
+        let quota = daily_quota(self.awesome_person_repo.as_ref(), &ap)?;
+
+        Ok(self.prioritize_study_set(study_set, limit, Some(&quota)))
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::determine_prompt`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn determine_prompt(&self, vocab: &Vocab, user_notes: &str) -> String {
+        let mut prompt = format!("Translate: '{}'", &vocab.first_lang);
+        if !vocab.hint.clone().unwrap_or_default().is_empty() {
+            prompt = format!(
+                "{}    hint: {}",
+                prompt,
+                vocab.hint.clone().unwrap_or_default()
+            );
+        }
+
+        if !vocab.pos.clone().unwrap_or_default().is_empty() {
+            prompt = format!(
+                "{}    pos: {}",
+                prompt,
+                vocab.pos.clone().unwrap_or_default()
+            );
+        }
+
+        if !user_notes.is_empty() {
+            prompt = format!("{}    your notes: {}", prompt, user_notes);
+        }
+
+        prompt
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::check_response`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn check_response(
+        &self,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        response: String,
+    ) -> Result<String, PalabrasError> {
+        self.grade_response(
+            None,
+            vocab_id,
+            vocab_study_id,
+            &response,
+            Answering::Learning {
+                with_article: false,
+            },
+        )
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::check_response_with_article`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn check_response_with_article(
+        &self,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        response: String,
+    ) -> Result<String, PalabrasError> {
+        self.grade_response(
+            None,
+            vocab_id,
+            vocab_study_id,
+            &response,
+            Answering::Learning { with_article: true },
+        )
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::check_known_response`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn check_known_response(
+        &self,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        response: String,
+    ) -> Result<String, PalabrasError> {
+        self.grade_response(None, vocab_id, vocab_study_id, &response, Answering::Known)
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::check_session_response`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn check_session_response(
+        &self,
+        study_session_id: i32,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        response: String,
+        answering: Answering,
+    ) -> Result<String, PalabrasError> {
+        self.grade_response(
+            Some(study_session_id),
+            vocab_id,
+            vocab_study_id,
+            &response,
+            answering,
+        )
     }
 
     /// Implementation, see trait for details [`LearnVocab::check_vocab_match`]
@@ -427,24 +1561,27 @@ impl LearnVocab for VocabFuzzyMatch {
     /// the unit tests in this module.
     fn check_vocab_match(
         &self,
-        learning_lang: &String,
-        alternatives: &String,
-        guess: &String,
+        policy: &GradingPolicy,
+        learning_lang: &str,
+        alternatives: &[&str],
+        guess: &str,
     ) -> usize {
+        let max_distance = policy.max_distance as usize;
         if guess.trim().is_empty() {
-            return MAX_DISTANCE;
+            return max_distance;
         }
 
         let guess = guess.to_lowercase();
 
+        // An empty alternative must not become an empty possible match.
         let mut possible_matches: Vec<String> = alternatives
-            .to_lowercase()
-            .split(",")
-            .map(|s| s.trim().to_string())
+            .iter()
+            .map(|s| s.to_lowercase().trim().to_string())
+            .filter(|s| !s.is_empty())
             .collect();
-        possible_matches.push(learning_lang.clone().to_lowercase().trim().to_string());
+        possible_matches.push(learning_lang.to_lowercase().trim().to_string());
 
-        let mut distance = MAX_DISTANCE;
+        let mut distance = max_distance;
         for possible_match in possible_matches {
             let score = levenshtein(&possible_match, guess.to_lowercase().trim());
 
@@ -454,10 +1591,71 @@ impl LearnVocab for VocabFuzzyMatch {
             }
         }
 
-        if distance > MAX_DISTANCE {
-            MAX_DISTANCE
+        distance.min(max_distance)
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::check_phrase_match`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn check_phrase_match(
+        &self,
+        policy: &GradingPolicy,
+        learning_lang_code: &str,
+        learning_lang: &str,
+        alternatives: &[&str],
+        guess: &str,
+    ) -> MatchResult {
+        let max_distance = policy.max_distance as usize;
+        let free_word_order = self
+            .language_packs
+            .pack_for(learning_lang_code)
+            .free_word_order
+            .unwrap_or_default();
+
+        // The closest of the phrase and its alternatives.
+        let (correct, alignment) = std::iter::once(learning_lang)
+            .chain(alternatives.iter().copied())
+            .map(str::trim)
+            .filter(|possible| !possible.is_empty())
+            .map(|possible| (possible, align_phrase(possible, guess, free_word_order)))
+            .reduce(|best, candidate| {
+                if candidate.1.accuracy > best.1.accuracy {
+                    candidate
+                } else {
+                    best
+                }
+            })
+            .unwrap_or((learning_lang, Default::default()));
+
+        let distance = if alignment.accuracy >= 1.0 {
+            0
         } else {
-            distance
+            // Any mistake is at least 1, so only a fully right phrase is a perfect match.
+            (((1.0 - alignment.accuracy) * max_distance as f64).round() as usize)
+                .clamp(1, max_distance)
+        };
+
+        let mut prompt = self.determine_match_prompt(policy, correct, guess.trim(), distance);
+        if distance == 0 && alignment.reordered {
+            prompt = format!("{} Usually said '{}'", prompt, correct);
+        }
+        for (label, words) in [
+            ("Wrong", &alignment.wrong_words),
+            ("Missing", &alignment.missing_words),
+            ("Extra", &alignment.extra_words),
+        ] {
+            if !words.is_empty() {
+                prompt = format!("{}. {}: {}", prompt, label, words.join(", "));
+            }
+        }
+
+        MatchResult {
+            distance,
+            prompt,
+            wrong_words: alignment.wrong_words,
+            missing_words: alignment.missing_words,
+            extra_words: alignment.extra_words,
         }
     }
 
@@ -469,42 +1667,19 @@ impl LearnVocab for VocabFuzzyMatch {
         &self,
         vocab_study_id: i32,
         distance: usize,
-    ) -> Result<VocabStudy, String> {
+    ) -> Result<VocabStudy, PalabrasError> {
         let current = self
             .vocab_study_repo
-            .get_vocab_study_by_id(vocab_study_id)
-            .map_err(|err| err.to_string())?;
-
-        let updated_percentage_correct =
-            self.calc_correctness(current.percentage_correct.unwrap_or_default(), distance);
-
-        let last_change =
-            updated_percentage_correct - current.percentage_correct.unwrap_or_default();
-
-        let correct_attempts = if distance.eq(&0) {
-            current.correct_attempts.unwrap_or_default() + 1
-        } else {
-            current.correct_attempts.unwrap_or_default()
-        };
+            .get_vocab_study_by_id(vocab_study_id)?;
+        let policy = self.get_grading_policy(current.awesome_person_id)?;
 
-        let updating = VocabStudy {
-            percentage_correct: Option::from(updated_percentage_correct),
-            last_change: Option::from(last_change),
-            last_tested: Option::from(Utc::now()),
-            well_known: updated_percentage_correct > WELL_KNOWN_THRESHOLD,
-            attempts: Option::from(current.attempts.unwrap_or_default() + 1),
-            correct_attempts: Some(correct_attempts),
-            ..current
-        };
+        let updating = self.grade_vocab_study(&policy, current, distance);
 
         // Save changes to dal.
-        self.vocab_study_repo
-            .update_vocab_study(updating)
-            .map_err(|err| err.to_string())?;
+        self.vocab_study_repo.update_vocab_study(updating)?;
         let updated = self
             .vocab_study_repo
-            .get_vocab_study_by_id(vocab_study_id)
-            .map_err(|err| err.to_string())?;
+            .get_vocab_study_by_id(vocab_study_id)?;
 
         Ok(updated)
     }
@@ -513,16 +1688,16 @@ impl LearnVocab for VocabFuzzyMatch {
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the unit tests in this module.
-    fn calc_correctness(&self, previous: f64, distance: usize) -> f64 {
-        let mut score =
-            ((MAX_DISTANCE as f64 - distance as f64) / MAX_DISTANCE as f64 + previous) / 2.0;
+    fn calc_correctness(&self, policy: &GradingPolicy, previous: f64, distance: usize) -> f64 {
+        let max_distance = policy.max_distance as f64;
+        let distance = (distance as f64).min(max_distance);
 
-        if distance == 0 {
-            // weights a perfect match as two perfect answers instead of one.
-            score = (2.0 + previous) / 3.0;
+        if distance == 0.0 {
+            // weights a perfect match as several perfect answers instead of one.
+            return (policy.perfect_weight + previous) / (policy.perfect_weight + 1.0);
         }
 
-        score
+        ((max_distance - distance) / max_distance + previous) / 2.0
     }
 
     /// Implementation, see trait for details [`LearnVocab::update_overall_progress`]
@@ -533,53 +1708,21 @@ impl LearnVocab for VocabFuzzyMatch {
         &self,
         awesome_person_id: i32,
         correct: bool,
-        last_fully_known: bool,
-    ) -> Result<Option<AwesomePerson>, String> {
+        known_transition: KnownTransition,
+    ) -> Result<Option<AwesomePerson>, PalabrasError> {
         let awesome_person = self
             .awesome_person_repo
-            .get_awesome_person_by_id(awesome_person_id)
-            .map_err(|err| err.to_string())?;
-
-        let awesome_person = if awesome_person.is_some() {
-            awesome_person.unwrap_or_default()
-        } else {
-            return Err(format!(
-                "Failed to find awesome person with id {}",
-                awesome_person_id
-            ));
-        };
-
-        // Increment counters based on whether the answer was correct
-        let (num_correct, num_incorrect) = (
-            awesome_person.num_correct.unwrap_or(0) + correct as i32,
-            awesome_person.num_incorrect.unwrap_or(0) + (!correct) as i32,
-        );
-
-        // Calculate the total percentage
-        let total_percentage = num_correct as f64 / (num_correct + num_incorrect) as f64;
+            .get_awesome_person_by_id(awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
 
         // Prepare the updated stats
-        let updating = AwesomePerson {
-            num_known: if last_fully_known {
-                Some(awesome_person.num_known.unwrap_or(0) + 1)
-            } else {
-                awesome_person.num_known
-            },
-            num_correct: Some(num_correct),
-            num_incorrect: Some(num_incorrect),
-            total_percentage: Some(total_percentage),
-            updated: Utc::now(),
-            ..awesome_person
-        };
+        let updating = self.grade_overall_progress(awesome_person, correct, known_transition);
 
         // Update the stats and return the updated record
-        self.awesome_person_repo
-            .update_awesome_person(updating)
-            .map_err(|err| err.to_string())?;
+        self.awesome_person_repo.update_awesome_person(updating)?;
 
         self.awesome_person_repo
             .get_awesome_person_by_id(awesome_person_id)
-            .map_err(|err| err.to_string())
     }
 
     /// Implementation, see trait for details [`LearnVocab::determine_match_prompt`]
@@ -588,90 +1731,645 @@ impl LearnVocab for VocabFuzzyMatch {
     /// the unit tests in this module.
     fn determine_match_prompt(
         &self,
+        policy: &GradingPolicy,
         correct: &str,
         user_response: &str,
         distance: usize,
     ) -> String {
-        return if distance == 0 {
+        if distance == 0 {
             "Perfect Match!".to_string()
-        } else if distance <= 3 {
+        } else if distance <= policy.close_distance as usize {
             format!(
                 "Close, it was '{}', you entered '{}'",
                 correct, user_response
             )
         } else {
             format!("It was '{}', you entered '{}'", correct, user_response)
-        };
+        }
     }
 
     /// Implementation, see trait for details [`LearnVocab::get_awesome_person`]
     ///
     /// For advanced usage and mock implementations, please refer to
     /// the integration tests in this module.
-    fn get_awesome_person(&self, awesome_person_id: i32) -> Result<Option<AwesomePerson>, String> {
+    fn get_awesome_person(&self, awesome_person_id: i32) -> Result<AwesomePerson, PalabrasError> {
         let awesome_person = self
             .awesome_person_repo
-            .get_awesome_person_by_id(awesome_person_id)
-            .map_err(|e| e.to_string())?;
+            .get_awesome_person_by_id(awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
+
+        // Keep sec matters private
+        let pub_awesome_person = AwesomePerson {
+            sec_code: "".to_string(),
+            ..awesome_person
+        };
+
+        Ok(pub_awesome_person)
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::get_awesome_person_by_code`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests in this module.
+    fn get_awesome_person_by_code(
+        &self,
+        look_up_code: String,
+    ) -> Result<AwesomePerson, PalabrasError> {
+        let awesome_person = self
+            .awesome_person_repo
+            .get_awesome_person_by_code(look_up_code)?
+            .ok_or_else(|| PalabrasError::not_found_by_secret("awesome person"))?;
+
+        // Keep sec matters private
+        let pub_awesome_person = AwesomePerson {
+            sec_code: "".to_string(),
+            ..awesome_person
+        };
+
+        Ok(pub_awesome_person)
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::get_vocab_stats`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the integration tests in this module.
+    fn get_vocab_stats(&self, vocab_study_id: i32) -> Result<(VocabStudy, Vocab), PalabrasError> {
+        let vocab_study = self
+            .vocab_study_repo
+            .get_vocab_study_by_id(vocab_study_id)?;
+
+        let vocab = self.vocab_repo.get_vocab_by_id(vocab_study.vocab_id)?;
+
+        Ok((vocab_study, vocab))
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::get_alternatives`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn get_alternatives(&self, vocab_id: i32) -> Result<Vec<VocabAlternative>, PalabrasError> {
+        self.vocab_repo.get_alternatives(vocab_id)
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::update_user_notes`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn update_user_notes(
+        &self,
+        vocab_study_id: i32,
+        user_notes: &str,
+    ) -> Result<VocabStudy, PalabrasError> {
+        // Only the notes are written, a grade committed meanwhile is kept.
+        self.vocab_study_repo
+            .update_user_notes(vocab_study_id, user_notes.trim())
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::get_grading_policy`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn get_grading_policy(&self, awesome_person_id: i32) -> Result<GradingPolicy, PalabrasError> {
+        self.awesome_person_repo
+            .get_awesome_person_by_id(awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
+
+        Ok(self
+            .awesome_person_repo
+            .get_grading_policy(awesome_person_id)?
+            .unwrap_or_else(|| self.default_grading_policy.policy_for(awesome_person_id)))
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::update_grading_policy`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn update_grading_policy(&self, policy: GradingPolicy) -> Result<GradingPolicy, PalabrasError> {
+        validate_grading_policy(&policy)?;
+
+        self.awesome_person_repo
+            .get_awesome_person_by_id(policy.awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", policy.awesome_person_id))?;
+
+        self.awesome_person_repo
+            .save_grading_policy(&GradingPolicy {
+                updated: Utc::now(),
+                ..policy
+            })
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::update_preferred_variant`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn update_preferred_variant(
+        &self,
+        awesome_person_id: i32,
+        lang_variant: &str,
+    ) -> Result<AwesomePerson, PalabrasError> {
+        let lang_variant = normalize_variant(lang_variant)?;
+
+        // Only the variant is written, a grading committed meanwhile keeps its progress.
+        self.awesome_person_repo
+            .update_preferred_variant(awesome_person_id, &lang_variant)
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::get_conjugation_drills`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn get_conjugation_drills(
+        &self,
+        awesome_id: i32,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab, ConjugationDrill)>, PalabrasError> {
+        if limit < 1 {
+            return Err(PalabrasError::Validation(format!(
+                "limit must be at least 1, found {}",
+                limit
+            )));
+        }
+
+        let ap = self
+            .awesome_person_repo
+            .get_awesome_person_by_id(awesome_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_id))?;
+
+        let verbs = self
+            .vocab_study_repo
+            .get_study_set(awesome_id, ap.max_learning_words)?
+            .into_iter()
+            .filter(|(_, v)| {
+                let pos = v.pos.clone().unwrap_or_default();
+                v.learning_lang_code == "es"
+                    && (pos.is_empty() || pos.eq_ignore_ascii_case("verb"))
+                    && is_infinitive(&v.infinitive.clone().unwrap_or_default())
+            })
+            .collect();
+
+        let order = self.drill_order(&self.awesome_person_repo.get_conjugation_stats(awesome_id)?);
+
+        let drills = self
+            .prioritize_study_set(verbs, limit, None)
+            .into_iter()
+            .zip(order.into_iter().cycle())
+            .map(|((vs, v), (person, tense))| {
+                let drill = ConjugationDrill {
+                    infinitive: v.infinitive.clone().unwrap_or_default().to_lowercase(),
+                    person,
+                    tense,
+                };
+                (vs, v, drill)
+            })
+            .collect();
+
+        Ok(drills)
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::check_conjugation`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn check_conjugation(
+        &self,
+        vocab_id: i32,
+        vocab_study_id: i32,
+        person: Person,
+        tense: Tense,
+        response: String,
+    ) -> Result<String, PalabrasError> {
+        let vocab = self.vocab_repo.get_vocab_by_id(vocab_id)?;
+        let vocab_study = self
+            .vocab_study_repo
+            .get_vocab_study_by_id(vocab_study_id)?;
+        if vocab_study.vocab_id != vocab_id {
+            return Err(PalabrasError::Validation(format!(
+                "vocab study {} is not the study of vocab {}",
+                vocab_study_id, vocab_id
+            )));
+        }
+
+        let drill = ConjugationDrill {
+            infinitive: vocab.infinitive.unwrap_or_default().to_lowercase(),
+            person,
+            tense,
+        };
+        let answer = drill.answer().ok_or_else(|| {
+            PalabrasError::Validation(format!("vocab {} has no infinitive to conjugate", vocab_id))
+        })?;
+
+        let policy = self.get_grading_policy(vocab_study.awesome_person_id)?;
+        // A conjugated form has no alternatives.
+        let distance = self.check_vocab_match(&policy, &answer, &[], &response);
+
+        self.awesome_person_repo.record_conjugation_attempt(
+            vocab_study.awesome_person_id,
+            tense.key(),
+            person.key(),
+            distance == 0,
+        )?;
+
+        Ok(self.determine_match_prompt(&policy, &answer, response.trim(), distance))
+    }
+
+    /// Implementation, see trait for details [`LearnVocab::get_conjugation_stats`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn get_conjugation_stats(
+        &self,
+        awesome_person_id: i32,
+    ) -> Result<Vec<ConjugationStat>, PalabrasError> {
+        self.awesome_person_repo
+            .get_awesome_person_by_id(awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
+
+        let mut stats = self
+            .awesome_person_repo
+            .get_conjugation_stats(awesome_person_id)?;
+        let position = |stat: &ConjugationStat| {
+            (
+                Tense::ALL
+                    .iter()
+                    .position(|tense| tense.key() == stat.tense),
+                Person::ALL
+                    .iter()
+                    .position(|person| person.key() == stat.person),
+            )
+        };
+        stats.sort_by_key(position);
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ComposerConfig;
+    use crate::test_fixtures::{fixture_setup, fixture_setup_with_policy};
+
+    // The grading tests are run against each of these: the default, a strict and a lenient policy.
+    fn test_policies() -> Vec<GradingPolicy> {
+        let default = GradingPolicyConfig::default().policy_for(1);
+        vec![
+            default.clone(),
+            GradingPolicy {
+                max_distance: 5,
+                well_known_threshold: 0.99,
+                close_distance: 1,
+                perfect_weight: 1.0,
+                ..default.clone()
+            },
+            GradingPolicy {
+                max_distance: 20,
+                well_known_threshold: 0.9,
+                close_distance: 5,
+                perfect_weight: 3.0,
+                ..default
+            },
+        ]
+    }
+
+    #[test]
+    fn unit_test_get_vocab_to_learn() {
+        // get the mocked service complete with mocked repos data test data
+        let fuzzy_service = fixture_setup().fuzzy_service;
+        let result = fuzzy_service
+            .get_vocab_to_learn(1, 1)
+            .expect("No issues expected with mocked data");
+        assert!(!result.is_empty(), "Mocked data expected");
+    }
+
+    #[test]
+    fn unit_test_get_vocab_to_learn_invalid_limit() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+        let result = fuzzy_service.get_vocab_to_learn(1, 0);
+        assert!(
+            matches!(result, Err(PalabrasError::Validation(_))),
+            "A limit below 1 should be rejected"
+        );
+    }
+
+    #[test]
+    fn unit_test_get_filtered_vocab_to_learn() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        let verbs = StudyFilter {
+            pos: Some("verb".to_string()),
+            ..Default::default()
+        };
+        let result = fuzzy_service
+            .get_filtered_vocab_to_learn(1, 10, &verbs)
+            .expect("No issues expected with mocked data");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].1.learning_lang, "aprendimos");
+
+        let french = StudyFilter {
+            learning_lang_code: Some("fr".to_string()),
+            ..Default::default()
+        };
+        let result = fuzzy_service
+            .get_filtered_vocab_to_learn(1, 10, &french)
+            .expect("No issues expected with mocked data");
+        assert!(result.is_empty(), "No French vocab in the mocked data");
+    }
+
+    #[test]
+    fn unit_test_prioritize_daily_quota() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+        let vocab_study = fuzzy_service
+            .vocab_study_repo
+            .get_vocab_study_by_id(1)
+            .expect("No error results expected with mocked repos");
+        let vocab = fuzzy_service
+            .vocab_repo
+            .get_vocab_by_id(1)
+            .expect("No error results expected with mocked repos");
+
+        // Ids 1 to 3 are reviews, 4 to 6 are new words
+        let study_set: Vec<_> = (1..=6)
+            .map(|id| {
+                let study = VocabStudy {
+                    id,
+                    last_tested: (id <= 3).then(Utc::now),
+                    well_known: false,
+                    ..vocab_study.clone()
+                };
+                (study, vocab.clone())
+            })
+            .collect();
+        let ids = |set: Vec<(VocabStudy, Vocab)>| {
+            let mut ids: Vec<i32> = set.iter().map(|(vs, _)| vs.id).collect();
+            ids.sort();
+            ids
+        };
+
+        let unlimited = fuzzy_service.prioritize_study_set(study_set.clone(), 10, None);
+        assert_eq!(ids(unlimited), vec![1, 2, 3, 4, 5, 6]);
+
+        let quota = DailyQuota {
+            new_per_day: 5,
+            reviews_per_day: 12,
+            new_today: 4,
+            reviews_today: 10,
+        };
+        // 2 reviews and 1 new word are left
+        let limited = ids(fuzzy_service.prioritize_study_set(study_set.clone(), 10, Some(&quota)));
+        assert_eq!(limited.iter().filter(|id| **id <= 3).count(), 2);
+        assert_eq!(
+            limited.iter().filter(|id| **id > 3).collect::<Vec<_>>(),
+            vec![&4]
+        );
+
+        let used_up = DailyQuota {
+            new_today: 5,
+            reviews_today: 12,
+            ..quota
+        };
+        let limited = fuzzy_service.prioritize_study_set(study_set, 10, Some(&used_up));
+        assert!(limited.is_empty(), "Nothing is left today");
+    }
+
+    #[test]
+    fn unit_test_prioritize_reviews_before_new_words() {
+        let fuzzy_service = fixture_setup()
+            .fuzzy_service
+            .with_composer(StudyComposer::new(ComposerConfig {
+                seed: Some(7),
+                ..Default::default()
+            }));
+        let vocab_study = fuzzy_service
+            .vocab_study_repo
+            .get_vocab_study_by_id(1)
+            .expect("No error results expected with mocked repos");
+        let vocab = fuzzy_service
+            .vocab_repo
+            .get_vocab_by_id(1)
+            .expect("No error results expected with mocked repos");
+
+        // Ids 1 to 3 are verb reviews, 4 to 12 are new nouns, a group with a heavier ratio
+        let study_set: Vec<_> = (1..=12)
+            .map(|id| {
+                let study = VocabStudy {
+                    id,
+                    last_tested: (id <= 3).then(Utc::now),
+                    well_known: false,
+                    ..vocab_study.clone()
+                };
+                let pos = if id <= 3 { "verb" } else { "noun" };
+                let vocab = Vocab {
+                    id,
+                    pos: Some(pos.to_string()),
+                    infinitive: None,
+                    ..vocab.clone()
+                };
+                (study, vocab)
+            })
+            .collect();
+
+        let mut ids: Vec<i32> = fuzzy_service
+            .prioritize_study_set(study_set, 4, None)
+            .iter()
+            .map(|(vs, _)| vs.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids.len(), 4);
+        assert_eq!(ids[..3], [1, 2, 3], "The due reviews come before new words");
+        assert!(ids[3] > 3);
+    }
+
+    #[test]
+    fn unit_test_study_deck() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        let deck = StudyFilter {
+            deck_id: Some(1),
+            ..Default::default()
+        };
+        let result = fuzzy_service
+            .get_filtered_vocab_to_learn(1, 10, &deck)
+            .expect("No issues expected with mocked data");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].1.id, 1, "Only the noun is in the mocked deck");
+
+        let someone_elses = StudyFilter {
+            deck_id: Some(2),
+            ..Default::default()
+        };
+        let result = fuzzy_service.get_filtered_vocab_to_learn(1, 10, &someone_elses);
+        assert!(matches!(result, Err(PalabrasError::NotFound { .. })));
+    }
+
+    #[test]
+    fn unit_test_study_tags() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        let food = StudyFilter {
+            tags: vec![" Food ".to_string()],
+            ..Default::default()
+        };
+        let result = fuzzy_service
+            .get_filtered_vocab_to_learn(1, 10, &food)
+            .expect("No issues expected with mocked data");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].1.id, 1, "Only the noun is tagged food");
 
-        // Keep sec matters private
-        let pub_awesome_person = AwesomePerson {
-            sec_code: "".to_string(),
-            ..awesome_person.unwrap_or_default()
+        let both = StudyFilter {
+            tags: vec!["food".to_string(), "a2".to_string()],
+            ..Default::default()
         };
+        let result = fuzzy_service
+            .get_filtered_vocab_to_learn(1, 10, &both)
+            .expect("No issues expected with mocked data");
+        assert!(result.is_empty(), "No vocab has both tags");
+    }
+
+    #[test]
+    fn unit_test_check_known_response() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        let match_prompt = fuzzy_service
+            .check_known_response(1, 1, "Word".to_string())
+            .expect("No error results expected fn check_known_response with mocked repos");
+        assert_eq!(match_prompt, "Perfect Match!");
 
-        Ok(Some(pub_awesome_person))
+        // The learning language is not the answer in this direction
+        let match_prompt = fuzzy_service
+            .check_known_response(1, 1, "palabra".to_string())
+            .expect("No error results expected fn check_known_response with mocked repos");
+        assert_ne!(match_prompt, "Perfect Match!");
     }
 
-    /// Implementation, see trait for details [`LearnVocab::get_awesome_person_by_code`]
-    ///
-    /// For advanced usage and mock implementations, please refer to
-    /// the integration tests in this module.
-    fn get_awesome_person_by_code(
-        &self,
-        look_up_code: String,
-    ) -> Result<Option<AwesomePerson>, String> {
-        let awesome_person = self
-            .awesome_person_repo
-            .get_awesome_person_by_code(look_up_code)?;
+    #[test]
+    fn unit_test_update_user_notes() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
 
-        // Keep sec matters private
-        let pub_awesome_person = AwesomePerson {
-            sec_code: "".to_string(),
-            ..awesome_person.unwrap_or_default()
-        };
+        let vocab_study = fuzzy_service
+            .update_user_notes(3, " like a password ")
+            .expect("No error results expected fn update_user_notes with mocked repos");
+        assert_eq!(vocab_study.id, 3);
+        assert_eq!(vocab_study.user_notes, Some("like a password".to_string()));
 
-        Ok(Some(pub_awesome_person))
+        let alternatives = fuzzy_service
+            .get_alternatives(1)
+            .expect("No error results expected fn get_alternatives with mocked repos");
+        assert_eq!(alternatives[0].alternative, "vocablo");
     }
 
-    /// Implementation, see trait for details [`LearnVocab::get_vocab_stats`]
-    ///
-    /// For advanced usage and mock implementations, please refer to
-    /// the integration tests in this module.
-    fn get_vocab_stats(&self, vocab_study_id: i32) -> Result<(VocabStudy, Vocab), String> {
-        let vocab_study = self
+    #[test]
+    fn unit_test_grade_leech() {
+        let strict = LeechConfig {
+            max_lapses: 2,
+            ..Default::default()
+        };
+        let fuzzy_service = fixture_setup().fuzzy_service.with_leech_rule(strict);
+        let policy = GradingPolicyConfig::default().policy_for(1);
+        let vocab_study = fuzzy_service
             .vocab_study_repo
-            .get_vocab_study_by_id(vocab_study_id)?;
-
-        let vocab = self.vocab_repo.get_vocab_by_id(vocab_study.vocab_id)?;
+            .get_vocab_study_by_id(1)
+            .expect("No error results expected with mocked repos");
+
+        // The first lapse is not enough, the second makes it a leech and suspends it
+        let graded = fuzzy_service.grade_vocab_study(&policy, vocab_study, 5);
+        assert_eq!(graded.lapses, 1);
+        assert!(!graded.leech && !graded.suspended);
+        let graded = fuzzy_service.grade_vocab_study(&policy, graded, 5);
+        assert_eq!(graded.lapses, 2);
+        assert!(graded.leech && graded.suspended);
+
+        // A right answer resets the lapses
+        let graded = fuzzy_service.grade_vocab_study(&policy, graded, 0);
+        assert_eq!(graded.lapses, 0);
+
+        // An unsuspended leech stays unsuspended
+        let unsuspended = VocabStudy {
+            suspended: false,
+            ..graded
+        };
+        let graded = fuzzy_service.grade_vocab_study(&policy, unsuspended, 5);
+        let graded = fuzzy_service.grade_vocab_study(&policy, graded, 5);
+        assert_eq!(graded.lapses, 2);
+        assert!(graded.leech && !graded.suspended);
+    }
 
-        Ok((vocab_study, vocab))
+    #[test]
+    fn unit_test_grade_well_known_not_leech() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+        let policy = GradingPolicyConfig::default().policy_for(1);
+
+        // Answered right 95% of 200 times, the old misses are no lapses and a typo does not make it a leech
+        let well_known = VocabStudy {
+            attempts: Some(200),
+            correct_attempts: Some(190),
+            percentage_correct: Some(0.99),
+            well_known: true,
+            ..Default::default()
+        };
+        let graded = fuzzy_service.grade_vocab_study(&policy, well_known, 1);
+        assert_eq!(graded.lapses, 1);
+        assert!(!graded.leech && !graded.suspended);
+        let graded = fuzzy_service.grade_vocab_study(&policy, graded, 0);
+        assert_eq!(graded.lapses, 0);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::test_fixtures::fixture_setup;
+    #[test]
+    fn unit_test_check_session_response() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        let learning = Answering::Learning {
+            with_article: false,
+        };
+        let prompt = fuzzy_service
+            .check_session_response(1, 1, 1, "palabra".to_string(), learning)
+            .expect("No error results expected fn check_session_response with mocked repos");
+        assert!(prompt.contains("Perfect"), "{}", prompt);
+
+        // A finished session, another awesome person's session and a missing session
+        let result = fuzzy_service.check_session_response(3, 1, 1, "palabra".to_string(), learning);
+        assert!(matches!(result, Err(PalabrasError::Conflict(_))));
+        let result = fuzzy_service.check_session_response(4, 1, 1, "palabra".to_string(), learning);
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+        let result =
+            fuzzy_service.check_session_response(99, 1, 1, "palabra".to_string(), learning);
+        assert!(matches!(result, Err(PalabrasError::NotFound { .. })));
+    }
 
     #[test]
-    fn unit_test_get_vocab_to_learn() {
-        // get the mocked service complete with mocked repos data test data
+    fn unit_test_grade_session_item() {
         let fuzzy_service = fixture_setup().fuzzy_service;
-        let result = fuzzy_service
-            .get_vocab_to_learn(1, 1)
-            .expect("No issues expected with mocked data");
-        assert!(result.len() >= 1, "Mocked data expected");
+        let session = StudySession {
+            id: 1,
+            awesome_person_id: 1,
+            started: Utc::now(),
+            finished: None,
+            num_answers: 0,
+            num_correct: 0,
+        };
+        let graded = |percentage_correct: f64| VocabStudy {
+            id: 7,
+            percentage_correct: Some(percentage_correct),
+            ..Default::default()
+        };
+
+        // Missed on the first answer, the next answer is the re-drill
+        let item = fuzzy_service.grade_session_item(None, &session, &graded(0.4), 0.5, false);
+        assert_eq!((item.study_session_id, item.vocab_study_id), (1, 7));
+        assert_eq!((item.attempts, item.correct_attempts), (1, 0));
+        assert!(item.missed && !item.redrilled);
+        assert!((item.percentage_change() + 0.1).abs() < 1e-9);
+
+        let item = fuzzy_service.grade_session_item(Some(item), &session, &graded(0.6), 0.4, true);
+        assert_eq!((item.attempts, item.correct_attempts), (2, 1));
+        assert!(item.missed && item.redrilled);
+        assert_eq!(item.percentage_before, 0.5);
+        assert!((item.percentage_change() - 0.1).abs() < 1e-9);
+
+        // Right at first, a later miss still needs its re-drill
+        let item = fuzzy_service.grade_session_item(None, &session, &graded(0.6), 0.5, true);
+        assert!(!item.missed && !item.redrilled);
+        let item = fuzzy_service.grade_session_item(Some(item), &session, &graded(0.5), 0.6, false);
+        assert!(item.missed && !item.redrilled);
     }
 
     #[test]
@@ -743,101 +2441,132 @@ mod tests {
 
     #[test]
     fn unit_test_fuzzy_pair_match() {
-        let test_cases = vec![
-            ("comprendimos", "", "comprendimos", 0),
-            ("comprendimos", "", "", 10),
-            ("comprendimos", "entendemos, intiendemos", "comprendimos", 0),
-            ("comprendimos", "entendemos, intiendemos", "entendemos", 0),
-            ("comprendimos", "entendemos, intiendemos", "intiendemos", 0),
-            ("comprendimos", "entendemos, intiendemos", "intiendemo", 1),
-            // (learning_lang, alternatives, guess, expected)
-        ];
-
         // Note: the mocked repos aren't used in this test
         let fuzzy_service = fixture_setup().fuzzy_service;
 
-        for (learning_lang, alternatives, guess, expected) in test_cases {
-            let result = fuzzy_service.check_vocab_match(
-                &learning_lang.to_string(),
-                &alternatives.to_string(),
-                &guess.to_string(),
-            );
-            assert!(
-                result.le(&expected),
-                "Calculated distance was not as expected. Result: {}, Expected: {} for learning_lang: {}, alternatives: {}, guess: {}",
-                result, expected, &learning_lang, &alternatives, &guess
-            )
+        for policy in test_policies() {
+            let test_cases = vec![
+                ("comprendimos", vec![], "comprendimos", 0),
+                ("comprendimos", vec![], "", policy.max_distance as usize),
+                (
+                    "comprendimos",
+                    vec!["entendemos", "intiendemos"],
+                    "comprendimos",
+                    0,
+                ),
+                (
+                    "comprendimos",
+                    vec!["entendemos", "intiendemos"],
+                    "entendemos",
+                    0,
+                ),
+                (
+                    "comprendimos",
+                    vec!["entendemos", "intiendemos"],
+                    "intiendemos",
+                    0,
+                ),
+                (
+                    "comprendimos",
+                    vec!["entendemos", "intiendemos"],
+                    "intiendemo",
+                    1,
+                ),
+                // (learning_lang, alternatives, guess, expected)
+            ];
+
+            for (learning_lang, alternatives, guess, expected) in test_cases {
+                let result =
+                    fuzzy_service.check_vocab_match(&policy, learning_lang, &alternatives, guess);
+                assert!(
+                    result.le(&expected),
+                    "Calculated distance was not as expected. Result: {}, Expected: {} for learning_lang: {}, alternatives: {:?}, guess: {}, policy: {:?}",
+                    result, expected, &learning_lang, &alternatives, &guess, policy
+                )
+            }
         }
     }
 
     #[test]
     fn unit_test_calc_correctness() {
         let test_cases = vec![
-            (0.5, 0, 0.83),
-            (0.5, 2, 0.65),
-            (0.4, 6, 0.4),
-            (1.0, 10, 0.5),
-            // (previous, distance, expected)
+            (0.5, 0, [0.83, 0.75, 0.875]),
+            (0.5, 2, [0.65, 0.55, 0.7]),
+            (0.4, 6, [0.4, 0.2, 0.55]),
+            (1.0, 10, [0.5, 0.5, 0.75]),
+            // (previous, distance, expected for each of the test policies)
         ];
 
         let tolerance = 0.01; // Define a suitable tolerance for the comparison of floats
 
         let fuzzy_service = fixture_setup().fuzzy_service;
 
-        for (previous, distance, expected) in test_cases {
-            let result = fuzzy_service.calc_correctness(previous, distance);
-            assert!(
-                (result - expected).abs() < tolerance,
-                "Calculated correctness was not as expected. Result: {}, Expected: {} for previous: {}, distance: {}",
-                result, expected, previous, distance
-            );
+        for (index, policy) in test_policies().iter().enumerate() {
+            for (previous, distance, expected) in &test_cases {
+                let expected = expected[index];
+                let result = fuzzy_service.calc_correctness(policy, *previous, *distance);
+                assert!(
+                    (result - expected).abs() < tolerance,
+                    "Calculated correctness was not as expected. Result: {}, Expected: {} for previous: {}, distance: {}, policy: {:?}",
+                    result, expected, previous, distance, policy
+                );
+            }
         }
     }
 
     #[test]
     fn unit_test_update_correctness() {
-        // Testing a miss, but the match was close
-        let previous_correctness = 0.99; // 99% correctness prior to the latest guess
-        let distance_for_latest_guess = 2; // The guess was fairly close, but not perfect
-
         let fuzzy_service = fixture_setup().fuzzy_service;
 
-        let new_correctness =
-            fuzzy_service.calc_correctness(previous_correctness, distance_for_latest_guess);
-        assert!(
-            new_correctness < previous_correctness,
-            "Expected correctness to go down"
-        );
+        for policy in test_policies() {
+            // Testing a miss, but the match was close
+            let previous_correctness = 0.99; // 99% correctness prior to the latest guess
+            let distance_for_latest_guess = 2; // The guess was fairly close, but not perfect
 
-        // Demonstrating the effect of a perfect guess
-        let previous_correctness = 0.5; // 50% correctness prior to the latest guess
-        let perfect_distance = 0; // A perfect guess
-        let new_correctness =
-            fuzzy_service.calc_correctness(previous_correctness, perfect_distance);
-        assert!(
-            new_correctness > previous_correctness,
-            "Expected correctness to go up"
-        );
+            let new_correctness = fuzzy_service.calc_correctness(
+                &policy,
+                previous_correctness,
+                distance_for_latest_guess,
+            );
+            assert!(
+                new_correctness < previous_correctness,
+                "Expected correctness to go down for {:?}",
+                policy
+            );
 
-        // Demonstrating a miss, but the guess was better than before
-        let previous_correctness = 0.3; // 50% correctness prior to the latest guess
-        let perfect_distance = 2; // A close guess
-        let new_correctness =
-            fuzzy_service.calc_correctness(previous_correctness, perfect_distance);
-        assert!(
-            new_correctness > previous_correctness,
-            "Expected correctness to go up even on miss"
-        );
+            // Demonstrating the effect of a perfect guess
+            let previous_correctness = 0.5; // 50% correctness prior to the latest guess
+            let perfect_distance = 0; // A perfect guess
+            let new_correctness =
+                fuzzy_service.calc_correctness(&policy, previous_correctness, perfect_distance);
+            assert!(
+                new_correctness > previous_correctness,
+                "Expected correctness to go up for {:?}",
+                policy
+            );
 
-        // Demonstrating a perfect guess with a previous low correctness percentage
-        let previous_correctness = 0.1; // 50% correctness prior to the latest guess
-        let perfect_distance = 0; // A perfect guess
-        let new_correctness =
-            fuzzy_service.calc_correctness(previous_correctness, perfect_distance);
-        assert!(
-            new_correctness > 0.5,
-            "Expected correctness to to be above 0.5"
-        );
+            // Demonstrating a miss, but the guess was better than before
+            let previous_correctness = 0.3; // 50% correctness prior to the latest guess
+            let perfect_distance = 2; // A close guess
+            let new_correctness =
+                fuzzy_service.calc_correctness(&policy, previous_correctness, perfect_distance);
+            assert!(
+                new_correctness > previous_correctness,
+                "Expected correctness to go up even on miss for {:?}",
+                policy
+            );
+
+            // Demonstrating a perfect guess with a previous low correctness percentage
+            let previous_correctness = 0.1; // 50% correctness prior to the latest guess
+            let perfect_distance = 0; // A perfect guess
+            let new_correctness =
+                fuzzy_service.calc_correctness(&policy, previous_correctness, perfect_distance);
+            assert!(
+                new_correctness > 0.5,
+                "Expected correctness to to be above 0.5 for {:?}",
+                policy
+            );
+        }
     }
 
     #[test]
@@ -846,41 +2575,120 @@ mod tests {
 
         let awesome_person_id = 1;
         let correct = true;
-        let last_fully_known = false;
         let awesome_person = fuzzy_service
-            .update_overall_progress(awesome_person_id, correct, last_fully_known)
+            .update_overall_progress(awesome_person_id, correct, KnownTransition::Unchanged)
             .expect("Expected default user");
         let _ = awesome_person.expect("Expected some value for default user");
     }
 
     #[test]
-    fn unit_test_check_pair_match() {
+    fn unit_test_grade_overall_progress_known_transitions() {
         let fuzzy_service = fixture_setup().fuzzy_service;
-        // Test a perfect guess
-        let learning_lang = "La gata es muy inteligente".to_string(); // The word to learn
-        let alternatives = "La felina es muy inteligente".to_string(); // Alternative correct answers
-        let guess = learning_lang.clone(); // A perfect guess
-        let distance = fuzzy_service.check_vocab_match(&learning_lang, &alternatives, &guess);
-        assert_eq!(
-            distance, 0,
-            "A perfect guess should return a distance of 0."
-        );
+        let awesome_person = AwesomePerson {
+            num_known: Some(1),
+            ..Default::default()
+        };
 
-        // Demonstrating the effect of a close, but not perfect, guess
-        let close_guess = "La gata es muy perezosa".to_string();
-        let distance_for_close_guess =
-            fuzzy_service.check_vocab_match(&learning_lang, &alternatives, &close_guess);
-        println!("Distance for a close guess: {}", distance_for_close_guess);
-        // Expecting a small distance greater than 0 but less than MAX_DISTANCE
-
-        // Demonstrating the effect of a guess with no similarity
-        let no_similarity_guess = "This isn't even spanish!".to_string();
-        let distance_for_no_similarity =
-            fuzzy_service.check_vocab_match(&learning_lang, &alternatives, &no_similarity_guess);
-        assert_eq!(
-            distance_for_no_similarity, MAX_DISTANCE,
-            "A guess with no similarity should return the maximum distance."
+        // Define test cases of the transition and the expected num_known
+        let test_cases = vec![
+            (KnownTransition::Unchanged, 1),
+            (KnownTransition::BecameKnown, 2),
+            (KnownTransition::Forgot, 0),
+        ];
+
+        for (known_transition, expected) in test_cases {
+            let graded = fuzzy_service.grade_overall_progress(
+                awesome_person.clone(),
+                true,
+                known_transition,
+            );
+            assert_eq!(
+                graded.num_known,
+                Some(expected),
+                "Unexpected num_known for {:?}",
+                known_transition
+            );
+        }
+
+        // Forgetting can never take the count below zero.
+        let graded = fuzzy_service.grade_overall_progress(
+            AwesomePerson {
+                num_known: Some(0),
+                ..Default::default()
+            },
+            false,
+            KnownTransition::Forgot,
         );
+        assert_eq!(graded.num_known, Some(0));
+    }
+
+    #[test]
+    fn unit_test_check_pair_match() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        for policy in test_policies() {
+            // Test a perfect guess
+            let learning_lang = "La gata es muy inteligente".to_string(); // The word to learn
+            let alternatives = ["La felina es muy inteligente"]; // Alternative correct answers
+            let guess = learning_lang.clone(); // A perfect guess
+            let distance =
+                fuzzy_service.check_vocab_match(&policy, &learning_lang, &alternatives, &guess);
+            assert_eq!(
+                distance, 0,
+                "A perfect guess should return a distance of 0."
+            );
+
+            // Demonstrating the effect of a close, but not perfect, guess
+            let close_guess = "La gata es muy perezosa".to_string();
+            let distance_for_close_guess = fuzzy_service.check_vocab_match(
+                &policy,
+                &learning_lang,
+                &alternatives,
+                &close_guess,
+            );
+            println!("Distance for a close guess: {}", distance_for_close_guess);
+            // Expecting a small distance greater than 0 but less than the max distance
+
+            // Demonstrating the effect of a guess with no similarity
+            let no_similarity_guess = "This isn't even spanish!".to_string();
+            let distance_for_no_similarity = fuzzy_service.check_vocab_match(
+                &policy,
+                &learning_lang,
+                &alternatives,
+                &no_similarity_guess,
+            );
+            assert_eq!(
+                distance_for_no_similarity, policy.max_distance as usize,
+                "A guess with no similarity should return the maximum distance."
+            );
+        }
+    }
+
+    #[test]
+    fn unit_test_check_match_without_alternatives() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        for policy in test_policies() {
+            // Empty alternatives are not possible answers, so a single letter is nowhere close
+            let expected = levenshtein("palabra", "x").min(policy.max_distance as usize);
+            for alternatives in [vec![], vec![" "], vec!["bocadillo", " ", ""]] {
+                let distance =
+                    fuzzy_service.check_vocab_match(&policy, "palabra", &alternatives, "x");
+                assert_eq!(
+                    distance, expected,
+                    "'x' should not match an empty alternative in {:?}",
+                    alternatives
+                );
+            }
+
+            // An alternative holding a comma is one answer, not one answer for each part
+            let distance =
+                fuzzy_service.check_vocab_match(&policy, "vale", &["sí, claro"], "claro");
+            assert!(distance > 0, "'claro' alone is not 'sí, claro'");
+            let distance =
+                fuzzy_service.check_vocab_match(&policy, "vale", &["sí, claro"], "sí, claro");
+            assert_eq!(distance, 0);
+        }
     }
 
     #[test]
@@ -904,71 +2712,543 @@ mod tests {
             ),
         ];
 
-        for (correct, guessed, distance, prompt) in test_cases {
-            let actual = fuzzy_service.determine_match_prompt(correct, guessed, distance);
+        for policy in test_policies() {
+            for (correct, guessed, distance, prompt) in &test_cases {
+                let actual =
+                    fuzzy_service.determine_match_prompt(&policy, correct, guessed, *distance);
+                assert!(
+                    actual.eq(prompt),
+                    "Expected {}, but got {} for parameters {}, {}, {}, {:?}",
+                    correct,
+                    actual,
+                    guessed,
+                    distance,
+                    prompt,
+                    policy
+                );
+            }
+        }
+
+        // The close cut-off follows the policy
+        let policy = GradingPolicy {
+            close_distance: 6,
+            ..GradingPolicyConfig::default().policy_for(1)
+        };
+        assert_eq!(
+            fuzzy_service.determine_match_prompt(&policy, "palabra", "idioma", 6),
+            "Close, it was 'palabra', you entered 'idioma'"
+        );
+    }
+
+    #[test]
+    fn unit_test_check_response() {
+        for policy in test_policies() {
+            let fuzzy_service = fixture_setup_with_policy(Some(policy)).fuzzy_service;
+
+            let vocab_test_data = fuzzy_service
+                .vocab_repo
+                .get_vocab_by_id(1)
+                .expect("Mocked repo should have returned an instance of vocab");
+
+            let vocab_study_test_data = fuzzy_service
+                .vocab_study_repo
+                .get_vocab_study_by_id(1)
+                .expect("Mocked repo should have returned an instance of vocab study");
+
+            // Test a perfect match
+            let match_prompt = fuzzy_service
+                .check_response(
+                    vocab_test_data.id,
+                    vocab_study_test_data.id,
+                    vocab_test_data.learning_lang.clone(),
+                )
+                .expect("No error results expected fn check_response with mocked repos");
+            assert_eq!(
+                match_prompt, "Perfect Match!",
+                "Expected perfect match from mocked data, but actual prompt was {}",
+                match_prompt
+            );
+
+            // Test an inaccurate answer, '123'
+            let match_prompt = fuzzy_service
+                .check_response(
+                    vocab_test_data.id,
+                    vocab_study_test_data.id,
+                    "123".to_string(),
+                )
+                .expect("No error results expected fn check_response with mocked repos");
+            assert_ne!(
+                match_prompt, "Perfect Match!",
+                "Expected a miss from mocked data, but actual prompt was {}",
+                match_prompt
+            );
+
+            // Test a close but incorrect answer
+            let test_response = format!("{}a", vocab_test_data.learning_lang.clone());
+            let match_prompt = fuzzy_service
+                .check_response(vocab_test_data.id, vocab_study_test_data.id, test_response)
+                .expect("No error results expected fn check_response with mocked repos");
+            assert_ne!(
+                match_prompt, "Perfect Match!",
+                "Expected a miss from mocked data, but actual prompt was {}",
+                match_prompt
+            );
+        }
+    }
+
+    #[test]
+    fn unit_test_check_response_other_vocab() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        // The mocked vocab study 1 is a study of vocab 1, not of vocab 2
+        let result = fuzzy_service.check_response(2, 1, "vocablo".to_string());
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+    }
+
+    #[test]
+    fn unit_test_check_response_with_alternative() {
+        for policy in test_policies() {
+            let fuzzy_service = fixture_setup_with_policy(Some(policy)).fuzzy_service;
+
+            // An alternative with a register says so
+            let match_prompt = fuzzy_service
+                .check_response(1, 1, "Vocablo".to_string())
+                .expect("No error results expected fn check_response with mocked repos");
+            assert_eq!(match_prompt, "Perfect Match! 'vocablo' is formal");
+
+            // An alternative without a register or region is just a match
+            let match_prompt = fuzzy_service
+                .check_response(1, 1, "término".to_string())
+                .expect("No error results expected fn check_response with mocked repos");
+            assert_eq!(match_prompt, "Perfect Match!");
+        }
+    }
+
+    #[test]
+    fn unit_test_match_response_with_variants() {
+        for policy in test_policies() {
+            let fuzzy_service = fixture_setup().fuzzy_service;
+            let vocab = Vocab {
+                learning_lang: "ordenador".to_string(),
+                first_lang: "computer".to_string(),
+                learning_lang_code: "es".to_string(),
+                lang_variant: Some("es-ES".to_string()),
+                ..Default::default()
+            };
+            let alternatives = [VocabAlternative {
+                id: 1,
+                vocab_id: vocab.id,
+                alternative: "computadora".to_string(),
+                register: None,
+                lang_variant: Some("es-419".to_string()),
+            }];
+
+            let test_cases = vec![
+                // (preferred_variant, response, expected prompt)
+                (
+                    Some("es-419"),
+                    "ordenador",
+                    "Perfect Match! ordenador — in Latin America: computadora",
+                ),
+                (Some("es-419"), "computadora", "Perfect Match!"),
+                (Some("es-ES"), "ordenador", "Perfect Match!"),
+                (None, "computadora", "Perfect Match!"),
+                (
+                    Some("es-MX"),
+                    "ordenador",
+                    "Perfect Match! 'ordenador' is used in Spain",
+                ),
+            ];
+            for (preferred_variant, response, expected_prompt) in test_cases {
+                let matched = fuzzy_service.match_response(
+                    &policy,
+                    &vocab,
+                    &alternatives,
+                    preferred_variant,
+                    response,
+                    false,
+                );
+                assert_eq!(matched.distance, 0, "Response '{}'", response);
+                assert_eq!(matched.prompt, expected_prompt, "Response '{}'", response);
+            }
+
+            // A miss is corrected with the answer in the preferred variant
+            let matched = fuzzy_service.match_response(
+                &policy,
+                &vocab,
+                &alternatives,
+                Some("es-419"),
+                "computadoro",
+                false,
+            );
+            assert!(matched.distance > 0);
+            assert_eq!(
+                matched.prompt,
+                fuzzy_service.determine_match_prompt(
+                    &policy,
+                    "computadora",
+                    "computadoro",
+                    matched.distance
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn unit_test_update_preferred_variant() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        let awesome_person = fuzzy_service
+            .update_preferred_variant(1, " es-mx ")
+            .expect("A language and region code should be accepted");
+        assert_eq!(awesome_person.preferred_variant, Some("es-MX".to_string()));
+
+        let awesome_person = fuzzy_service
+            .update_preferred_variant(1, "es-419")
+            .expect("A numeric region should be accepted");
+        assert_eq!(awesome_person.preferred_variant, Some("es-419".to_string()));
+
+        let awesome_person = fuzzy_service
+            .update_preferred_variant(1, "")
+            .expect("Empty should clear the preferred variant");
+        assert_eq!(awesome_person.preferred_variant, Some("".to_string()));
+
+        for invalid in ["es", "Spanish", "es-MEX", "es_MX", "e-MX"] {
+            let result = fuzzy_service.update_preferred_variant(1, invalid);
             assert!(
-                actual.eq(prompt),
-                "Expected {}, but got {} for parameters {}, {}, {}",
-                correct,
-                actual,
-                guessed,
-                distance,
-                prompt
+                matches!(result, Err(PalabrasError::Validation(_))),
+                "Expected a validation error for '{}'",
+                invalid
             );
         }
     }
 
     #[test]
-    fn unit_test_check_response() {
+    fn unit_test_check_response_with_article() {
+        for policy in test_policies() {
+            let fuzzy_service = fixture_setup_with_policy(Some(policy.clone())).fuzzy_service;
+            let partial_credit = (policy.close_distance as usize).max(1);
+
+            // The test noun is 'palabra', feminine
+            let vocab = fuzzy_service
+                .vocab_repo
+                .get_vocab_by_id(1)
+                .expect("Mocked repo should have returned an instance of vocab");
+
+            let test_cases = vec![
+                ("palabra", false, 0, "Perfect Match!".to_string()),
+                ("la palabra", false, 0, "Perfect Match!".to_string()),
+                ("La palabra", true, 0, "Perfect Match!".to_string()),
+                ("una palabra", true, 0, "Perfect Match!".to_string()),
+                (
+                    "el palabra",
+                    false,
+                    partial_credit,
+                    "Right word, wrong article, it is 'la palabra', you entered 'el palabra'"
+                        .to_string(),
+                ),
+                (
+                    "palabra",
+                    true,
+                    partial_credit,
+                    "Right word, but the article is missing, it is 'la palabra'".to_string(),
+                ),
+                (
+                    "la palabraa",
+                    true,
+                    1,
+                    fuzzy_service.determine_match_prompt(&policy, "la palabra", "la palabraa", 1),
+                ),
+            ];
+
+            for (response, with_article, expected_distance, expected_prompt) in test_cases {
+                let matched = fuzzy_service.match_response(
+                    &policy,
+                    &vocab,
+                    &[],
+                    None,
+                    response,
+                    with_article,
+                );
+                assert_eq!(
+                    matched.distance, expected_distance,
+                    "Response '{}'",
+                    response
+                );
+                assert_eq!(matched.prompt, expected_prompt, "Response '{}'", response);
+            }
+
+            // Without a gender the article is part of the answer
+            let no_gender = Vocab {
+                gender: None,
+                ..vocab.clone()
+            };
+            let matched =
+                fuzzy_service.match_response(&policy, &no_gender, &[], None, "el palabra", true);
+            assert_eq!(matched.distance, 3);
+
+            let match_prompt = fuzzy_service
+                .check_response_with_article(vocab.id, 1, "el palabra".to_string())
+                .expect(
+                    "No error results expected fn check_response_with_article with mocked repos",
+                );
+            assert!(
+                match_prompt.starts_with("Right word, wrong article"),
+                "Expected article feedback, but actual prompt was {}",
+                match_prompt
+            );
+        }
+    }
+
+    #[test]
+    fn unit_test_check_phrase_match() {
+        for policy in test_policies() {
+            let fuzzy_service = fixture_setup().fuzzy_service;
+            let max_distance = policy.max_distance as usize;
+
+            // A perfect phrase, punctuation and case do not matter
+            let matched = fuzzy_service.check_phrase_match(
+                &policy,
+                "fr",
+                "Où est la gare ?",
+                &[],
+                "où est la gare",
+            );
+            assert_eq!(matched.distance, 0);
+            assert_eq!(matched.prompt, "Perfect Match!");
+
+            // One wrong word in five costs about a fifth, not the whole phrase
+            let matched = fuzzy_service.check_phrase_match(
+                &policy,
+                "fr",
+                "je ne sais pas pourquoi",
+                &[],
+                "je ne sais rien pourquoi",
+            );
+            assert_eq!(matched.wrong_words, ["pas"]);
+            assert!(matched.missing_words.is_empty());
+            assert!(
+                matched.distance >= 1 && matched.distance < max_distance / 2,
+                "One wrong word should be a small distance, was {}",
+                matched.distance
+            );
+            assert!(
+                matched.prompt.ends_with(". Wrong: pas"),
+                "{}",
+                matched.prompt
+            );
+
+            // Missing and extra words are reported
+            let matched = fuzzy_service.check_phrase_match(
+                &policy,
+                "fr",
+                "je ne sais pas",
+                &[],
+                "je sais pas bien",
+            );
+            assert_eq!(matched.missing_words, ["ne"]);
+            assert_eq!(matched.extra_words, ["bien"]);
+            assert!(matched.distance > 0);
+
+            // Without a free word order, swapped words are wrong
+            let matched =
+                fuzzy_service.check_phrase_match(&policy, "fr", "la gare est", &[], "est la gare");
+            assert!(matched.distance > 0);
+
+            // The test Spanish pack allows another order
+            let matched = fuzzy_service.check_phrase_match(
+                &policy,
+                "es",
+                "dónde está Ana",
+                &[],
+                "dónde Ana está",
+            );
+            assert_eq!(matched.distance, 0);
+            assert_eq!(
+                matched.prompt,
+                "Perfect Match! Usually said 'dónde está Ana'"
+            );
+
+            // The closest alternative is used
+            let matched = fuzzy_service.check_phrase_match(
+                &policy,
+                "fr",
+                "il fait froid",
+                &["on gèle", "il fait très froid"],
+                "il fait tres froid",
+            );
+            assert_eq!(matched.wrong_words, ["très"]);
+
+            // Nothing entered
+            let matched =
+                fuzzy_service.check_phrase_match(&policy, "fr", "il fait froid", &[], " ");
+            assert_eq!(matched.distance, max_distance);
+            assert_eq!(matched.missing_words, ["il", "fait", "froid"]);
+        }
+    }
+
+    #[test]
+    fn unit_test_get_grading_policy() {
+        // Without a stored policy the configured default applies
         let fuzzy_service = fixture_setup().fuzzy_service;
+        let policy = fuzzy_service
+            .get_grading_policy(1)
+            .expect("Mocked awesome person should have a policy");
+        assert_eq!(policy.max_distance, MAX_DISTANCE as i32);
+        assert_eq!(policy.well_known_threshold, WELL_KNOWN_THRESHOLD);
+
+        // A stored policy wins over the default
+        for stored in test_policies() {
+            let fuzzy_service = fixture_setup_with_policy(Some(stored.clone())).fuzzy_service;
+            let policy = fuzzy_service
+                .get_grading_policy(1)
+                .expect("Mocked awesome person should have a policy");
+            assert_eq!(policy, stored);
+        }
+    }
 
-        let vocab_test_data = fuzzy_service
-            .vocab_repo
-            .get_vocab_by_id(1)
-            .expect("Mocked repo should have returned an instance of vocab");
+    #[test]
+    fn unit_test_update_grading_policy() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
 
-        let vocab_study_test_data = fuzzy_service
-            .vocab_study_repo
-            .get_vocab_study_by_id(1)
-            .expect("Mocked repo should have returned an instance of vocab study");
+        for policy in test_policies() {
+            let saved = fuzzy_service
+                .update_grading_policy(policy.clone())
+                .expect("Valid policies should be saved");
+            assert_eq!(saved.max_distance, policy.max_distance);
+        }
 
-        // Test a perfect match
-        let match_prompt = fuzzy_service
-            .check_response(
-                vocab_test_data.id,
-                vocab_study_test_data.id,
-                vocab_test_data.learning_lang.clone(),
-            )
-            .expect("No error results expected fn check_response with mocked repos");
-        assert_eq!(
-            match_prompt, "Perfect Match!",
-            "Expected perfect match from mocked data, but actual prompt was {}",
-            match_prompt
-        );
+        let default = GradingPolicyConfig::default().policy_for(1);
+        let invalid_policies = vec![
+            GradingPolicy {
+                max_distance: 0,
+                ..default.clone()
+            },
+            GradingPolicy {
+                well_known_threshold: 1.5,
+                ..default.clone()
+            },
+            GradingPolicy {
+                close_distance: 11,
+                ..default.clone()
+            },
+            GradingPolicy {
+                perfect_weight: 0.0,
+                ..default
+            },
+        ];
+        for policy in invalid_policies {
+            let result = fuzzy_service.update_grading_policy(policy.clone());
+            assert!(
+                matches!(result, Err(PalabrasError::Validation(_))),
+                "Expected {:?} to be rejected",
+                policy
+            );
+        }
+    }
 
-        // Test an inaccurate answer, '123'
-        let match_prompt = fuzzy_service
-            .check_response(
-                vocab_test_data.id,
-                vocab_study_test_data.id,
-                "123".to_string(),
-            )
-            .expect("No error results expected fn check_response with mocked repos");
-        assert_ne!(
-            match_prompt, "Perfect Match!",
-            "Expected a miss from mocked data, but actual prompt was {}",
-            match_prompt
-        );
+    #[test]
+    fn unit_test_get_conjugation_drills() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
 
-        // Test a close but incorrect answer
-        let test_response = format!("{}a", vocab_test_data.learning_lang.clone());
-        let match_prompt = fuzzy_service
-            .check_response(vocab_test_data.id, vocab_study_test_data.id, test_response)
-            .expect("No error results expected fn check_response with mocked repos");
-        assert_ne!(
-            match_prompt, "Perfect Match!",
-            "Expected a miss from mocked data, but actual prompt was {}",
-            match_prompt
+        let drills = fuzzy_service
+            .get_conjugation_drills(1, 10)
+            .expect("No issues expected with mocked data");
+        // Only the verb of the mocked study set is drilled
+        assert_eq!(drills.len(), 1);
+        let (_, vocab, drill) = &drills[0];
+        assert_eq!(vocab.id, 2);
+        assert_eq!(drill.infinitive, "aprender");
+        // The mocked stats only have presente yo, so the first never drilled is presente tú
+        assert_eq!((drill.person, drill.tense), (Person::Tu, Tense::Presente));
+
+        let result = fuzzy_service.get_conjugation_drills(1, 0);
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+    }
+
+    #[test]
+    fn unit_test_drill_order() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+        let stat = |person: Person, tense: Tense, correct_attempts: i32| ConjugationStat {
+            awesome_person_id: 1,
+            tense: tense.key().to_string(),
+            person: person.key().to_string(),
+            attempts: 4,
+            correct_attempts,
+            updated: Utc::now(),
+        };
+
+        // Everything drilled, vosotros futuro is the weakest followed by the subjunctive
+        let mut stats = Vec::new();
+        for tense in Tense::ALL {
+            for person in Person::ALL {
+                let correct_attempts = match (person, tense) {
+                    (Person::Vosotros, Tense::Futuro) => 0,
+                    (_, Tense::PresenteSubjuntivo) => 1,
+                    _ => 3,
+                };
+                stats.push(stat(person, tense, correct_attempts));
+            }
+        }
+
+        let order = fuzzy_service.drill_order(&stats);
+        assert_eq!(order.len(), 36);
+        assert_eq!(order[0], (Person::Vosotros, Tense::Futuro));
+        assert!(order[1..7]
+            .iter()
+            .all(|(_, tense)| *tense == Tense::PresenteSubjuntivo));
+    }
+
+    #[test]
+    fn unit_test_check_conjugation() {
+        let fuzzy_service = fixture_setup().fuzzy_service;
+
+        let test_cases = vec![
+            (
+                Person::Nosotros,
+                Tense::Preterito,
+                "aprendimos",
+                "Perfect Match!",
+            ),
+            (
+                Person::Nosotros,
+                Tense::Preterito,
+                "aprendemos",
+                "Close, it was 'aprendimos', you entered 'aprendemos'",
+            ),
+            (
+                Person::Yo,
+                Tense::PresenteSubjuntivo,
+                " Aprenda\n",
+                "Perfect Match!",
+            ),
+        ];
+        for (person, tense, response, expected) in test_cases {
+            let prompt = fuzzy_service
+                .check_conjugation(2, 2, person, tense, response.to_string())
+                .expect("No error results expected with mocked repos");
+            assert_eq!(prompt, expected);
+        }
+
+        // A single letter is nowhere close to the form
+        let prompt = fuzzy_service
+            .check_conjugation(2, 2, Person::Yo, Tense::Presente, "x".to_string())
+            .expect("No error results expected with mocked repos");
+        assert_eq!(prompt, "It was 'aprendo', you entered 'x'");
+
+        // The mocked noun has no infinitive
+        let result =
+            fuzzy_service.check_conjugation(1, 1, Person::Yo, Tense::Presente, "x".to_string());
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+
+        // The vocab study of the noun is not a study of the verb
+        let result = fuzzy_service.check_conjugation(
+            2,
+            1,
+            Person::Yo,
+            Tense::Presente,
+            "aprendo".to_string(),
         );
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
     }
 }
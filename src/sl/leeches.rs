@@ -0,0 +1,231 @@
+use crate::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use crate::dal::vocab::{DbVocabRepository, VocabRepository};
+use crate::dal::vocab_study::{DbVocabStudyRepository, VocabStudyRepository};
+use crate::error::PalabrasError;
+use crate::models::{Vocab, VocabStudy};
+use lazy_static::lazy_static;
+
+// Leeches, vocab an awesome person keeps failing. The leech rule is applied while grading, see
+// `LeechConfig`, these are the ways to review and edit them.
+
+pub trait ManageLeeches {
+    /// Retrieves an awesome person's leeches, vocab failed so often that they break the leech rule,
+    /// see [`crate::config::LeechConfig`], and any other suspended vocab.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_person_id` - The primary key of the awesome person.
+    /// * `after` - Optional. The lapses and id of the last vocab study of the previous page.
+    /// * `limit` - The maximum number of leeches to return.
+    ///
+    /// # Returns
+    ///
+    /// Returns each leech or suspended `VocabStudy` with its `Vocab`, the most lapses first then by id,
+    /// `PalabrasError::Validation` if `limit` is less than 1, `PalabrasError::NotFound` if the awesome person
+    /// does not exist or another error if the query fails.
+    fn get_leeches(
+        &self,
+        awesome_person_id: i32,
+        after: Option<(i32, i32)>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError>;
+
+    /// Suspends a vocab study, leaving it out of the study set, or unsuspends it.
+    ///
+    /// An unsuspended leech stays flagged as a leech and is not suspended again by the leech rule.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_study_id` - The primary key of the vocab study.
+    /// * `suspended` - Whether the vocab study is suspended.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated `VocabStudy`, `PalabrasError::NotFound` if the vocab study does not exist
+    /// or another error if the update fails.
+    fn suspend_vocab_study(
+        &self,
+        vocab_study_id: i32,
+        suspended: bool,
+    ) -> Result<VocabStudy, PalabrasError>;
+
+    /// Edits a leech to make it easier to remember, the notes and the awesome person's own hint of the vocab study.
+    ///
+    /// Both belong to the awesome person, the hint of the vocab shared by everyone studying it is left as it is.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_study_id` - The primary key of the vocab study.
+    /// * `user_notes` - Optional. The new notes, empty to remove them.
+    /// * `hint` - Optional. The new hint of the awesome person, empty to remove it.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated `VocabStudy` and `Vocab`, `PalabrasError::NotFound` if the vocab study does not exist
+    /// or another error if the update fails.
+    fn update_leech(
+        &self,
+        vocab_study_id: i32,
+        user_notes: Option<&str>,
+        hint: Option<&str>,
+    ) -> Result<(VocabStudy, Vocab), PalabrasError>;
+}
+
+pub struct LeechService {
+    awesome_person_repo: Box<dyn AwesomePersonRepository>,
+    vocab_study_repo: Box<dyn VocabStudyRepository>,
+    vocab_repo: Box<dyn VocabRepository>,
+}
+
+lazy_static! {
+    static ref LEECH_SERVICE: LeechService = LeechService::new(
+        Box::new(DbAwesomePersonRepository),
+        Box::new(DbVocabStudyRepository),
+        Box::new(DbVocabRepository),
+    );
+}
+
+impl LeechService {
+    // The constructor takes Box<dyn Repos>
+    pub fn new(
+        awesome_person_repo: Box<dyn AwesomePersonRepository>,
+        vocab_study_repo: Box<dyn VocabStudyRepository>,
+        vocab_repo: Box<dyn VocabRepository>,
+    ) -> Self {
+        LeechService {
+            awesome_person_repo,
+            vocab_study_repo,
+            vocab_repo,
+        }
+    }
+
+    // Method to access the singleton instance, shared across threads like the other services.
+    pub fn instance() -> &'static LeechService {
+        &LEECH_SERVICE
+    }
+}
+
+/// The leeches of the awesome people, flagged on their vocab studies.
+impl ManageLeeches for LeechService {
+    /// Implementation, see trait for details [`ManageLeeches::get_leeches`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn get_leeches(
+        &self,
+        awesome_person_id: i32,
+        after: Option<(i32, i32)>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        if limit < 1 {
+            return Err(PalabrasError::Validation(format!(
+                "limit must be at least 1, found {}",
+                limit
+            )));
+        }
+        self.awesome_person_repo
+            .get_awesome_person_by_id(awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
+
+        self.vocab_study_repo
+            .get_leeches(awesome_person_id, after, limit)
+    }
+
+    /// Implementation, see trait for details [`ManageLeeches::suspend_vocab_study`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn suspend_vocab_study(
+        &self,
+        vocab_study_id: i32,
+        suspended: bool,
+    ) -> Result<VocabStudy, PalabrasError> {
+        // Only the flag is written, a grade committed meanwhile is kept.
+        self.vocab_study_repo
+            .update_suspended(vocab_study_id, suspended)
+    }
+
+    /// Implementation, see trait for details [`ManageLeeches::update_leech`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn update_leech(
+        &self,
+        vocab_study_id: i32,
+        user_notes: Option<&str>,
+        hint: Option<&str>,
+    ) -> Result<(VocabStudy, Vocab), PalabrasError> {
+        let mut vocab_study = match user_notes {
+            Some(user_notes) => self
+                .vocab_study_repo
+                .update_user_notes(vocab_study_id, user_notes.trim())?,
+            None => self
+                .vocab_study_repo
+                .get_vocab_study_by_id(vocab_study_id)?,
+        };
+        if let Some(hint) = hint {
+            vocab_study = self
+                .vocab_study_repo
+                .update_user_hint(vocab_study_id, hint.trim())?;
+        }
+
+        let vocab = self.vocab_repo.get_vocab_by_id(vocab_study.vocab_id)?;
+
+        Ok((vocab_study, vocab))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::fixture_setup;
+
+    #[test]
+    fn unit_test_manage_leeches() {
+        let leech_service = fixture_setup().leech_service;
+
+        let leeches = leech_service
+            .get_leeches(1, None, 10)
+            .expect("No error results expected fn get_leeches with mocked repos");
+        assert!(!leeches.is_empty(), "Mocked data expected");
+        assert!(leeches.iter().all(|(vs, _)| vs.leech && vs.suspended));
+
+        let (last, _) = &leeches[0];
+        let next_page = leech_service
+            .get_leeches(1, Some((last.lapses, last.id)), 10)
+            .expect("No error results expected fn get_leeches with mocked repos");
+        assert_eq!(next_page.len(), leeches.len() - 1);
+        let result = leech_service.get_leeches(1, None, 0);
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+
+        let vocab_study = leech_service
+            .suspend_vocab_study(2, false)
+            .expect("No error results expected fn suspend_vocab_study with mocked repos");
+        assert_eq!(vocab_study.id, 2);
+        assert!(!vocab_study.suspended);
+
+        // The mocked repos do not keep the notes, so they are edited one at a time
+        let (vocab_study, vocab) = leech_service
+            .update_leech(1, Some(" sounds like parable "), None)
+            .expect("No error results expected fn update_leech with mocked repos");
+        assert_eq!(
+            vocab_study.user_notes,
+            Some("sounds like parable".to_string())
+        );
+        assert_eq!(vocab.id, vocab_study.vocab_id);
+
+        let (vocab_study, vocab) = leech_service
+            .update_leech(1, None, Some(" noun, not 'palabrota' "))
+            .expect("No error results expected fn update_leech with mocked repos");
+        assert_eq!(
+            vocab_study.user_hint,
+            Some("noun, not 'palabrota'".to_string())
+        );
+        assert_eq!(vocab.hint, None, "The shared hint of the vocab is kept");
+
+        let (vocab_study, _) = leech_service
+            .update_leech(1, None, None)
+            .expect("No error results expected fn update_leech with mocked repos");
+        assert_eq!(vocab_study.user_notes, None);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use crate::dal::vocab_study::{DbVocabStudyRepository, VocabStudyRepository};
+use crate::error::PalabrasError;
+use crate::models::{Vocab, VocabStudy, VocabStudyFilter, VocabStudyKey, VocabStudyOrder};
+use lazy_static::lazy_static;
+
+// The vocab an awesome person studies, listed with their progress a page at a time.
+
+pub trait MyVocab {
+    /// Retrieves the vocab an awesome person studies with its progress, a page at a time.
+    ///
+    /// # Parameters
+    ///
+    /// * `awesome_person_id` - The primary key of the awesome person.
+    /// * `filter` - The [`VocabStudyFilter`] each vocab must pass.
+    /// * `order` - The [`VocabStudyOrder`] of the vocab.
+    /// * `after` - Optional. The key of the last vocab study of the previous page, see [`VocabStudyKey::of`].
+    /// * `limit` - The maximum number of vocab to return.
+    ///
+    /// # Returns
+    ///
+    /// Each `VocabStudy` with its `Vocab` in the order, suspended vocab included.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if `limit` is less than 1 or `after` is a key of another order.
+    /// - `PalabrasError::NotFound` if the awesome person does not exist.
+    /// - Another error if the query fails.
+    fn get_enrolled_vocab(
+        &self,
+        awesome_person_id: i32,
+        filter: &VocabStudyFilter,
+        order: VocabStudyOrder,
+        after: Option<VocabStudyKey>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError>;
+}
+
+pub struct MyVocabService {
+    awesome_person_repo: Box<dyn AwesomePersonRepository>,
+    vocab_study_repo: Box<dyn VocabStudyRepository>,
+}
+
+lazy_static! {
+    static ref MY_VOCAB_SERVICE: MyVocabService = MyVocabService::new(
+        Box::new(DbAwesomePersonRepository),
+        Box::new(DbVocabStudyRepository),
+    );
+}
+
+impl MyVocabService {
+    // The constructor takes Box<dyn Repos>
+    pub fn new(
+        awesome_person_repo: Box<dyn AwesomePersonRepository>,
+        vocab_study_repo: Box<dyn VocabStudyRepository>,
+    ) -> Self {
+        MyVocabService {
+            awesome_person_repo,
+            vocab_study_repo,
+        }
+    }
+
+    // Method to access the singleton instance, shared across threads like the other services.
+    pub fn instance() -> &'static MyVocabService {
+        &MY_VOCAB_SERVICE
+    }
+}
+
+/// The enrolled vocab of the awesome people, from their vocab studies.
+impl MyVocab for MyVocabService {
+    /// Implementation, see trait for details [`MyVocab::get_enrolled_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn get_enrolled_vocab(
+        &self,
+        awesome_person_id: i32,
+        filter: &VocabStudyFilter,
+        order: VocabStudyOrder,
+        after: Option<VocabStudyKey>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        if limit < 1 {
+            return Err(PalabrasError::Validation(format!(
+                "limit must be at least 1, found {}",
+                limit
+            )));
+        }
+        if let Some(after_order) = after.as_ref().map(VocabStudyKey::order) {
+            if after_order != order {
+                return Err(PalabrasError::Validation(format!(
+                    "after is a key of the {:?} order, not {:?}",
+                    after_order, order
+                )));
+            }
+        }
+        self.awesome_person_repo
+            .get_awesome_person_by_id(awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
+
+        self.vocab_study_repo
+            .get_enrolled_vocab(awesome_person_id, filter, order, after, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::fixture_setup;
+
+    #[test]
+    fn unit_test_enrolled_vocab() {
+        let my_vocab_service = fixture_setup().my_vocab_service;
+
+        let alphabetic = my_vocab_service
+            .get_enrolled_vocab(
+                1,
+                &VocabStudyFilter::default(),
+                VocabStudyOrder::Alphabetic,
+                None,
+                1,
+            )
+            .expect("No error results expected fn get_enrolled_vocab with mocked repos");
+        assert_eq!(alphabetic.len(), 1);
+        assert_eq!(alphabetic[0].1.learning_lang, "aprendimos");
+
+        let (vs, v) = &alphabetic[0];
+        let after = VocabStudyKey::of(VocabStudyOrder::Alphabetic, vs, v);
+        let next_page = my_vocab_service
+            .get_enrolled_vocab(
+                1,
+                &VocabStudyFilter::default(),
+                VocabStudyOrder::Alphabetic,
+                Some(after.clone()),
+                10,
+            )
+            .expect("No error results expected fn get_enrolled_vocab with mocked repos");
+        assert_eq!(next_page.len(), 1);
+        assert_eq!(next_page[0].1.learning_lang, "palabra");
+
+        let nouns = my_vocab_service
+            .get_enrolled_vocab(
+                1,
+                &VocabStudyFilter {
+                    pos: Some("NOUN".to_string()),
+                    ..Default::default()
+                },
+                VocabStudyOrder::Weakness,
+                None,
+                10,
+            )
+            .expect("No error results expected fn get_enrolled_vocab with mocked repos");
+        assert_eq!(nouns.len(), 1);
+        assert_eq!(nouns[0].1.id, 1);
+
+        let result = my_vocab_service.get_enrolled_vocab(
+            1,
+            &VocabStudyFilter::default(),
+            VocabStudyOrder::Recency,
+            Some(after),
+            10,
+        );
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+        let result = my_vocab_service.get_enrolled_vocab(
+            1,
+            &VocabStudyFilter::default(),
+            VocabStudyOrder::Recency,
+            None,
+            0,
+        );
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+    }
+}
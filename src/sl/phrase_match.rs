@@ -0,0 +1,311 @@
+use strsim::levenshtein;
+
+// Word by word matching of phrases, so one wrong word costs that word rather than the whole phrase.
+// A response is aligned with the expected phrase, each expected word is either matched, possibly
+// with a typo, or missing, and response words left over are extra.
+
+// A response word below this similarity is not taken as an attempt at an expected word when the
+// word order is free.
+const MIN_WORD_SIMILARITY: f64 = 0.5;
+
+/// Splits a phrase into lowercase words, dropping punctuation such as the "¿" and "?" of a question.
+///
+/// Apostrophes are kept within words, e.g. "quelqu'un".
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::phrase_match::phrase_words;
+///
+/// assert_eq!(phrase_words("¿Qué es, Ana?"), ["qué", "es", "ana"]);
+/// assert_eq!(phrase_words("quelqu’un"), ["quelqu'un"]);
+/// ```
+pub fn phrase_words(phrase: &str) -> Vec<String> {
+    phrase
+        .to_lowercase()
+        .replace('’', "'")
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// How a response lines up with an expected phrase, word by word.
+///
+/// # Fields
+///
+/// - `accuracy`: The share of the phrase answered correctly, from 0.0 to 1.0. Each expected word scores its
+///   similarity to the response word aligned with it, extra words count as wrong.
+/// - `wrong_words`: The expected words answered with a different or misspelled word.
+/// - `missing_words`: The expected words left out of the response.
+/// - `extra_words`: The response words that are not in the phrase.
+/// - `reordered`: Whether the words were answered in a different order, only when the order is free.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhraseAlignment {
+    pub accuracy: f64,
+    pub wrong_words: Vec<String>,
+    pub missing_words: Vec<String>,
+    pub extra_words: Vec<String>,
+    pub reordered: bool,
+}
+
+/// Aligns the words of a response with the words of an expected phrase.
+///
+/// # Parameters
+///
+/// * `expected` - The correct phrase.
+/// * `response` - The learner's response.
+/// * `free_word_order` - Whether the language allows the words in another order. Otherwise the words are
+///   aligned in order, a word given in the wrong place is wrong where it was expected.
+///
+/// # Returns
+///
+/// The [`PhraseAlignment`], with an `accuracy` of 1.0 only when every word is right.
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::phrase_match::align_phrase;
+///
+/// let alignment = align_phrase("la casa es muy roja", "la caza es roja", false);
+/// assert_eq!(alignment.wrong_words, ["casa"]);
+/// assert_eq!(alignment.missing_words, ["muy"]);
+/// assert!(alignment.accuracy > 0.7 && alignment.accuracy < 1.0);
+///
+/// let alignment = align_phrase("¿Dónde está Ana?", "dónde Ana está", true);
+/// assert_eq!(alignment.accuracy, 1.0);
+/// assert!(alignment.reordered);
+/// ```
+pub fn align_phrase(expected: &str, response: &str, free_word_order: bool) -> PhraseAlignment {
+    let expected = phrase_words(expected);
+    let response = phrase_words(response);
+
+    // For each expected word, the response word aligned with it, if any.
+    let aligned = if free_word_order {
+        align_any_order(&expected, &response)
+    } else {
+        align_in_order(&expected, &response)
+    };
+
+    let mut alignment = PhraseAlignment::default();
+    let mut score = 0.0;
+    for (word, aligned_with) in expected.iter().zip(&aligned) {
+        match aligned_with {
+            Some(index) => {
+                let similarity = word_similarity(word, &response[*index]);
+                if similarity < 1.0 {
+                    alignment.wrong_words.push(word.clone());
+                }
+                score += similarity;
+            }
+            None => alignment.missing_words.push(word.clone()),
+        }
+    }
+    alignment.extra_words = response
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !aligned.contains(&Some(*index)))
+        .map(|(_, word)| word.clone())
+        .collect();
+
+    let matched: Vec<usize> = aligned.iter().flatten().copied().collect();
+    alignment.reordered = matched.windows(2).any(|pair| pair[0] > pair[1]);
+
+    let num_words = expected.len() + alignment.extra_words.len();
+    alignment.accuracy = if num_words == 0 {
+        0.0
+    } else {
+        score / num_words as f64
+    };
+
+    alignment
+}
+
+// 1.0 for the same word, down to 0.0 for nothing in common.
+fn word_similarity(expected: &str, given: &str) -> f64 {
+    let length = expected.chars().count().max(given.chars().count());
+    if length == 0 {
+        return 1.0;
+    }
+
+    1.0 - levenshtein(expected, given) as f64 / length as f64
+}
+
+// Aligns the words in order with the least cost, a substitution costs the dissimilarity of the
+// two words, a missing or extra word costs 1.
+fn align_in_order(expected: &[String], response: &[String]) -> Vec<Option<usize>> {
+    let (rows, columns) = (expected.len(), response.len());
+    let mut cost = vec![vec![0.0; columns + 1]; rows + 1];
+    for (row, costs) in cost.iter_mut().enumerate() {
+        costs[0] = row as f64;
+    }
+    cost[0] = (0..=columns).map(|column| column as f64).collect();
+
+    for row in 1..=rows {
+        for column in 1..=columns {
+            let substitute = cost[row - 1][column - 1]
+                + (1.0 - word_similarity(&expected[row - 1], &response[column - 1]));
+            let missing = cost[row - 1][column] + 1.0;
+            let extra = cost[row][column - 1] + 1.0;
+            cost[row][column] = substitute.min(missing).min(extra);
+        }
+    }
+
+    // Walk back from the end to find which choices were taken.
+    let mut aligned = vec![None; rows];
+    let (mut row, mut column) = (rows, columns);
+    while row > 0 && column > 0 {
+        let substitute = cost[row - 1][column - 1]
+            + (1.0 - word_similarity(&expected[row - 1], &response[column - 1]));
+        if (cost[row][column] - substitute).abs() < f64::EPSILON {
+            aligned[row - 1] = Some(column - 1);
+            row -= 1;
+            column -= 1;
+        } else if (cost[row][column] - (cost[row - 1][column] + 1.0)).abs() < f64::EPSILON {
+            row -= 1;
+        } else {
+            column -= 1;
+        }
+    }
+
+    aligned
+}
+
+// Pairs the most similar words first, wherever they are. Words left over are then paired in order,
+// as attempts at the same word.
+fn align_any_order(expected: &[String], response: &[String]) -> Vec<Option<usize>> {
+    let mut pairs: Vec<(usize, usize, f64)> = expected
+        .iter()
+        .enumerate()
+        .flat_map(|(row, word)| {
+            response
+                .iter()
+                .enumerate()
+                .map(move |(column, given)| (row, column, word_similarity(word, given)))
+        })
+        .filter(|(_, _, similarity)| *similarity >= MIN_WORD_SIMILARITY)
+        .collect();
+    // The most similar first, ties in order so repeated words keep their places.
+    pairs.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(&b.0)).then(a.1.cmp(&b.1)));
+
+    let mut aligned = vec![None; expected.len()];
+    let mut used = vec![false; response.len()];
+    for (row, column, _) in pairs {
+        if aligned[row].is_none() && !used[column] {
+            aligned[row] = Some(column);
+            used[column] = true;
+        }
+    }
+
+    let mut unused = (0..response.len()).filter(|column| !used[*column]);
+    for slot in aligned.iter_mut().filter(|slot| slot.is_none()) {
+        *slot = unused.next();
+    }
+
+    aligned
+}
+
+/// How a character of a response compares with the expected answer, see [`char_diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharDiff {
+    /// The character is right.
+    Same(char),
+    /// The response has `given` where `expected` was expected.
+    Wrong { given: char, expected: char },
+    /// The expected character is missing from the response.
+    Missing(char),
+    /// The response has a character that is not expected.
+    Extra(char),
+}
+
+/// Compares a response with the expected answer character by character, ignoring case.
+///
+/// The characters are aligned with the fewest edits, the same alignment the Levenshtein distance of
+/// the grading is based on, so the differences can be shown to the learner, e.g. in color.
+///
+/// # Parameters
+///
+/// * `expected` - The correct answer.
+/// * `given` - The learner's response.
+///
+/// # Returns
+///
+/// A [`CharDiff`] for each character of either, in order. Characters of the response keep their case.
+///
+/// # Example
+///
+/// ```
+/// use palabras::sl::phrase_match::{char_diff, CharDiff};
+///
+/// assert_eq!(
+///     char_diff("casa", "Caza"),
+///     [
+///         CharDiff::Same('C'),
+///         CharDiff::Same('a'),
+///         CharDiff::Wrong { given: 'z', expected: 's' },
+///         CharDiff::Same('a'),
+///     ]
+/// );
+/// assert_eq!(
+///     char_diff("hola", "hlas"),
+///     [
+///         CharDiff::Same('h'),
+///         CharDiff::Missing('o'),
+///         CharDiff::Same('l'),
+///         CharDiff::Same('a'),
+///         CharDiff::Extra('s'),
+///     ]
+/// );
+/// ```
+pub fn char_diff(expected: &str, given: &str) -> Vec<CharDiff> {
+    let expected: Vec<char> = expected.trim().chars().collect();
+    let given: Vec<char> = given.trim().chars().collect();
+    let same = |a: char, b: char| a == b || a.to_lowercase().eq(b.to_lowercase());
+
+    let (rows, columns) = (expected.len(), given.len());
+    let mut cost = vec![vec![0; columns + 1]; rows + 1];
+    for (row, costs) in cost.iter_mut().enumerate() {
+        costs[0] = row;
+    }
+    cost[0] = (0..=columns).collect();
+
+    for row in 1..=rows {
+        for column in 1..=columns {
+            let substitute = cost[row - 1][column - 1]
+                + usize::from(!same(expected[row - 1], given[column - 1]));
+            let missing = cost[row - 1][column] + 1;
+            let extra = cost[row][column - 1] + 1;
+            cost[row][column] = substitute.min(missing).min(extra);
+        }
+    }
+
+    // Walk back from the end to find which edits were made.
+    let mut diff = Vec::with_capacity(rows.max(columns));
+    let (mut row, mut column) = (rows, columns);
+    while row > 0 || column > 0 {
+        if row > 0 && column > 0 {
+            let (expected, given) = (expected[row - 1], given[column - 1]);
+            let is_same = same(expected, given);
+            if cost[row][column] == cost[row - 1][column - 1] + usize::from(!is_same) {
+                diff.push(if is_same {
+                    CharDiff::Same(given)
+                } else {
+                    CharDiff::Wrong { given, expected }
+                });
+                row -= 1;
+                column -= 1;
+                continue;
+            }
+        }
+        if row > 0 && cost[row][column] == cost[row - 1][column] + 1 {
+            diff.push(CharDiff::Missing(expected[row - 1]));
+            row -= 1;
+        } else {
+            diff.push(CharDiff::Extra(given[column - 1]));
+            column -= 1;
+        }
+    }
+    diff.reverse();
+
+    diff
+}
@@ -0,0 +1,146 @@
+use crate::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use crate::dal::vocab::{DbVocabRepository, VocabRepository};
+use crate::error::PalabrasError;
+use crate::models::{VocabMatch, VocabSearch};
+use lazy_static::lazy_static;
+
+// Searching the vocab, ignoring accents and case, by prefix or similarity with pg_trgm.
+
+pub trait SearchVocab {
+    /// Searches the vocab, ignoring accents and case, by the prefix of or the similarity to its learning
+    /// language, an alternative or its first language.
+    ///
+    /// # Parameters
+    ///
+    /// * `search` - The text searched for and the filters of the search, see [`VocabSearch`].
+    /// * `after` - Optional. The rank, learning language and id of the last match of the previous page.
+    /// * `limit` - The maximum number of matches to return.
+    ///
+    /// # Returns
+    ///
+    /// Each `VocabMatch` after the cursor, the best ranked first then by learning language and id.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if the text is empty or `limit` is less than 1.
+    /// - `PalabrasError::NotFound` if the awesome person searching does not exist.
+    /// - Another error if the query fails.
+    fn search_vocab(
+        &self,
+        search: &VocabSearch,
+        after: Option<(f64, String, i32)>,
+        limit: i64,
+    ) -> Result<Vec<VocabMatch>, PalabrasError>;
+}
+
+pub struct VocabSearchService {
+    awesome_person_repo: Box<dyn AwesomePersonRepository>,
+    vocab_repo: Box<dyn VocabRepository>,
+}
+
+lazy_static! {
+    static ref VOCAB_SEARCH_SERVICE: VocabSearchService = VocabSearchService::new(
+        Box::new(DbAwesomePersonRepository),
+        Box::new(DbVocabRepository),
+    );
+}
+
+impl VocabSearchService {
+    // The constructor takes Box<dyn Repos>
+    pub fn new(
+        awesome_person_repo: Box<dyn AwesomePersonRepository>,
+        vocab_repo: Box<dyn VocabRepository>,
+    ) -> Self {
+        VocabSearchService {
+            awesome_person_repo,
+            vocab_repo,
+        }
+    }
+
+    // Method to access the singleton instance, shared across threads like the other services.
+    pub fn instance() -> &'static VocabSearchService {
+        &VOCAB_SEARCH_SERVICE
+    }
+}
+
+/// Searches the vocab with the full text and trigram indexes of the vocab repository.
+impl SearchVocab for VocabSearchService {
+    /// Implementation, see trait for details [`SearchVocab::search_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn search_vocab(
+        &self,
+        search: &VocabSearch,
+        after: Option<(f64, String, i32)>,
+        limit: i64,
+    ) -> Result<Vec<VocabMatch>, PalabrasError> {
+        if search.text.trim().is_empty() {
+            return Err(PalabrasError::Validation(
+                "search text must not be empty".to_string(),
+            ));
+        }
+        if limit < 1 {
+            return Err(PalabrasError::Validation(format!(
+                "limit must be at least 1, found {}",
+                limit
+            )));
+        }
+        if let Some(awesome_person_id) = search.awesome_person_id {
+            self.awesome_person_repo
+                .get_awesome_person_by_id(awesome_person_id)?
+                .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
+        }
+
+        self.vocab_repo.search_vocab(search, after, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::fixture_setup;
+
+    #[test]
+    fn unit_test_search_vocab() {
+        let vocab_search_service = fixture_setup().vocab_search_service;
+
+        let search = VocabSearch {
+            text: " Pal".to_string(),
+            awesome_person_id: Some(1),
+            ..Default::default()
+        };
+        let matches = vocab_search_service
+            .search_vocab(&search, None, 20)
+            .expect("No error results expected fn search_vocab with mocked repos");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].vocab.id, 1);
+        assert!(matches[0].enrolled);
+
+        let verbs = VocabSearch {
+            text: "a".to_string(),
+            pos: Some("verb".to_string()),
+            ..Default::default()
+        };
+        let matches = vocab_search_service
+            .search_vocab(&verbs, None, 20)
+            .expect("No error results expected fn search_vocab with mocked repos");
+        assert_eq!(matches.len(), 1);
+        assert!(!matches[0].enrolled, "Nobody searched");
+        let last = &matches[0];
+        let after = (last.rank, last.vocab.learning_lang.clone(), last.vocab.id);
+        let matches = vocab_search_service
+            .search_vocab(&verbs, Some(after), 20)
+            .expect("No error results expected fn search_vocab with mocked repos");
+        assert!(matches.is_empty(), "No match after the last one");
+
+        let result = vocab_search_service.search_vocab(&search, None, 0);
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+        let blank = VocabSearch {
+            text: "  ".to_string(),
+            ..Default::default()
+        };
+        let result = vocab_search_service.search_vocab(&blank, None, 20);
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+    }
+}
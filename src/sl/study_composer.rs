@@ -0,0 +1,182 @@
+use crate::config::ComposerConfig;
+use crate::models::{Vocab, VocabStudy};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+// Composes a study list from vocab in priority order. The vocab is grouped by part of speech, skill and
+// length, and the groups take turns by their ratios with a smooth weighted round robin, so a list of
+// 3 nouns to 1 adjective reads noun, noun, adjective, noun rather than three nouns and then an adjective.
+
+/// Composes balanced study lists, see [`ComposerConfig`] for the ratios.
+///
+/// # Example
+///
+/// ```
+/// use palabras::config::ComposerConfig;
+/// use palabras::models::{Vocab, VocabStudy};
+/// use palabras::sl::study_composer::StudyComposer;
+///
+/// let composer = StudyComposer::new(ComposerConfig {
+///     seed: Some(7),
+///     ..Default::default()
+/// });
+/// let vocab = |id: i32, pos: &str| {
+///     let vocab = Vocab {
+///         id,
+///         pos: Some(pos.to_string()),
+///         num_learning_words: 1,
+///         ..Default::default()
+///     };
+///     (VocabStudy::default(), vocab)
+/// };
+/// let candidates = vec![vocab(1, "verb"), vocab(2, "verb"), vocab(3, "verb"), vocab(4, "noun")];
+///
+/// let study_list = composer.compose(candidates, 2);
+/// let mut ids: Vec<i32> = study_list.iter().map(|(_, vocab)| vocab.id).collect();
+/// ids.sort();
+/// assert_eq!(ids, vec![1, 4]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StudyComposer {
+    config: ComposerConfig,
+}
+
+// The vocab of one part of speech, skill and length, in priority order.
+struct Group {
+    ratio: i64,
+    pairs: Vec<(VocabStudy, Vocab)>,
+}
+
+impl StudyComposer {
+    /// Creates a composer with the given ratios.
+    pub fn new(config: ComposerConfig) -> Self {
+        StudyComposer { config }
+    }
+
+    /// Composes a study list of at most `limit` vocab from candidates in priority order.
+    ///
+    /// Each group gives its highest priority vocab, the groups are interleaved by their ratios and
+    /// vocab with the same infinitive is not presented back to back when another order is possible.
+    /// Within a group the vocab is presented lowest priority first, so the vocab studied last in the
+    /// previous list does not come first again.
+    ///
+    /// # Parameters
+    ///
+    /// * `candidates` - The vocab to compose the list from, highest priority first.
+    /// * `limit` - The maximum size of the study list.
+    ///
+    /// # Returns
+    ///
+    /// The study list, the same for the same candidates when the config has a `seed`.
+    pub fn compose(
+        &self,
+        candidates: Vec<(VocabStudy, Vocab)>,
+        limit: usize,
+    ) -> Vec<(VocabStudy, Vocab)> {
+        let mut rng = match self.config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        // Group the candidates, keeping their priority order, then vary which group goes first.
+        let mut keys: Vec<(String, String, bool)> = Vec::new();
+        let mut groups: Vec<Group> = Vec::new();
+        for pair in candidates {
+            let key = group_key(&pair.1);
+            match keys.iter().position(|existing| *existing == key) {
+                Some(index) => groups[index].pairs.push(pair),
+                None => {
+                    keys.push(key);
+                    groups.push(Group {
+                        ratio: self.config.ratio_of(&pair.1).max(1) as i64,
+                        pairs: vec![pair],
+                    });
+                }
+            }
+        }
+        groups.shuffle(&mut rng);
+
+        // Smooth weighted round robin, the group of each place in the list.
+        let mut available: Vec<usize> = groups.iter().map(|group| group.pairs.len()).collect();
+        let mut current = vec![0i64; groups.len()];
+        let mut turns = Vec::new();
+        while turns.len() < limit {
+            let open: Vec<usize> = (0..groups.len()).filter(|i| available[*i] > 0).collect();
+            if open.is_empty() {
+                break;
+            }
+            let total: i64 = open.iter().map(|i| groups[*i].ratio).sum();
+            for i in &open {
+                current[*i] += groups[*i].ratio;
+            }
+            let mut turn = open[0];
+            for i in &open {
+                if current[*i] > current[turn] {
+                    turn = *i;
+                }
+            }
+            current[turn] -= total;
+            available[turn] -= 1;
+            turns.push(turn);
+        }
+
+        // Each group gives its highest priority vocab, presented lowest priority first.
+        let mut taken: Vec<Vec<(VocabStudy, Vocab)>> = groups
+            .into_iter()
+            .enumerate()
+            .map(|(index, group)| {
+                let count = turns.iter().filter(|turn| **turn == index).count();
+                group.pairs.into_iter().take(count).collect()
+            })
+            .collect();
+        let mut study_list: Vec<(VocabStudy, Vocab)> =
+            turns.iter().filter_map(|turn| taken[*turn].pop()).collect();
+
+        // Vocab with the same infinitive as the one before it swaps with a later vocab, or when there is
+        // none moves up to the first place where neither neighbour is a form of the same verb.
+        for i in 1..study_list.len() {
+            if !same_infinitive(&study_list[i - 1].1, &study_list[i].1) {
+                continue;
+            }
+            if let Some(later) = (i + 1..study_list.len())
+                .find(|j| !same_infinitive(&study_list[i - 1].1, &study_list[*j].1))
+            {
+                study_list.swap(i, later);
+            } else if let Some(earlier) = (0..i).find(|k| {
+                (*k == 0 || !same_infinitive(&study_list[k - 1].1, &study_list[i].1))
+                    && !same_infinitive(&study_list[*k].1, &study_list[i].1)
+            }) {
+                let pair = study_list.remove(i);
+                study_list.insert(earlier, pair);
+            }
+        }
+
+        study_list
+    }
+}
+
+// The part of speech, skill and whether the vocab is a phrase, ignoring case.
+fn group_key(vocab: &Vocab) -> (String, String, bool) {
+    let label = |value: Option<&str>| value.unwrap_or_default().trim().to_lowercase();
+    (
+        label(vocab.pos.as_deref()),
+        label(vocab.skill.as_deref()),
+        vocab.num_learning_words > 1,
+    )
+}
+
+// Whether both vocab are forms of the same verb.
+fn same_infinitive(a: &Vocab, b: &Vocab) -> bool {
+    let infinitive = |vocab: &Vocab| {
+        vocab
+            .infinitive
+            .as_deref()
+            .map(|infinitive| infinitive.trim().to_lowercase())
+            .filter(|infinitive| !infinitive.is_empty())
+    };
+    match (infinitive(a), infinitive(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
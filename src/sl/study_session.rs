@@ -0,0 +1,203 @@
+use crate::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use crate::error::PalabrasError;
+use crate::models::{StudySession, StudySessionItem, Vocab, VocabStudy};
+use chrono::Utc;
+use lazy_static::lazy_static;
+
+// Study sessions, a sitting of answers that is summarized when it is finished. The answers are checked
+// with `LearnVocab::check_session_response`, the grading records them in the session.
+
+/// The outcome of a study session, see [`StudySessions::finish_study_session`].
+///
+/// # Fields
+///
+/// - `session`: The study session, `finished` is set once it is closed.
+/// - `items`: Each vocab answered in the session with its record of the session, in the order first answered.
+/// - `redrill`: The vocab missed and not yet answered again, to be re-drilled before the session closes.
+#[derive(Clone)]
+pub struct SessionSummary {
+    pub session: StudySession,
+    pub items: Vec<(StudySessionItem, Vocab)>,
+    pub redrill: Vec<(VocabStudy, Vocab)>,
+}
+
+impl SessionSummary {
+    /// Whether the session is closed.
+    pub fn is_finished(&self) -> bool {
+        self.session.finished.is_some()
+    }
+
+    /// The vocab missed in the session, answered at least once with less than a perfect match.
+    pub fn missed(&self) -> impl Iterator<Item = &(StudySessionItem, Vocab)> {
+        self.items.iter().filter(|(item, _)| item.missed)
+    }
+}
+
+pub trait StudySessions {
+    /// Starts a study session for an awesome person, the answers checked with
+    /// [`crate::sl::fuzzy_match_vocab::LearnVocab::check_session_response`] are recorded in it.
+    ///
+    /// # Parameters
+    /// - `awesome_person_id`: The identifier of the awesome person studying.
+    ///
+    /// # Returns
+    /// - `Ok(StudySession)`: The open session.
+    /// - `Err(PalabrasError)`: `PalabrasError::NotFound` if the awesome person does not exist, or another error
+    ///   if the session cannot be created.
+    fn start_study_session(&self, awesome_person_id: i32) -> Result<StudySession, PalabrasError>;
+
+    /// Finishes a study session and summarizes it.
+    ///
+    /// Vocab missed in the session must be answered again before it closes, so while any missed vocab
+    /// has not been re-drilled the session stays open and the summary lists them in `redrill`. Once
+    /// they are answered, finishing again closes the session. With `skip_redrill` the session closes
+    /// right away. Finishing a closed session returns its summary.
+    ///
+    /// # Parameters
+    /// - `study_session_id`: The identifier of the study session.
+    /// - `skip_redrill`: Close the session without re-drilling the missed vocab.
+    ///
+    /// # Returns
+    /// - `Ok(SessionSummary)`: The session, each vocab answered with its percentage correct before and after
+    ///   the session, and the vocab still to re-drill.
+    /// - `Err(PalabrasError)`: `PalabrasError::NotFound` if the study session does not exist, or another error
+    ///   if the session cannot be read or closed.
+    fn finish_study_session(
+        &self,
+        study_session_id: i32,
+        skip_redrill: bool,
+    ) -> Result<SessionSummary, PalabrasError>;
+}
+
+pub struct StudySessionService {
+    awesome_person_repo: Box<dyn AwesomePersonRepository>,
+}
+
+lazy_static! {
+    static ref STUDY_SESSION_SERVICE: StudySessionService =
+        StudySessionService::new(Box::new(DbAwesomePersonRepository),);
+}
+
+impl StudySessionService {
+    // The constructor takes Box<dyn Repos>
+    pub fn new(awesome_person_repo: Box<dyn AwesomePersonRepository>) -> Self {
+        StudySessionService {
+            awesome_person_repo,
+        }
+    }
+
+    // Method to access the singleton instance, shared across threads like the other services.
+    pub fn instance() -> &'static StudySessionService {
+        &STUDY_SESSION_SERVICE
+    }
+}
+
+/// The study sessions of the awesome people, stored with the awesome person repository.
+impl StudySessions for StudySessionService {
+    /// Implementation, see trait for details [`StudySessions::start_study_session`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn start_study_session(&self, awesome_person_id: i32) -> Result<StudySession, PalabrasError> {
+        self.awesome_person_repo
+            .get_awesome_person_by_id(awesome_person_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))?;
+
+        self.awesome_person_repo
+            .create_study_session(awesome_person_id)
+    }
+
+    /// Implementation, see trait for details [`StudySessions::finish_study_session`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn finish_study_session(
+        &self,
+        study_session_id: i32,
+        skip_redrill: bool,
+    ) -> Result<SessionSummary, PalabrasError> {
+        let mut session = self
+            .awesome_person_repo
+            .get_study_session(study_session_id)?
+            .ok_or_else(|| PalabrasError::not_found("study session", study_session_id))?;
+        let items = self
+            .awesome_person_repo
+            .get_study_session_items(study_session_id)?;
+
+        // Missed vocab are re-drilled before an open session closes.
+        let redrill: Vec<(VocabStudy, Vocab)> = if session.finished.is_none() && !skip_redrill {
+            items
+                .iter()
+                .filter(|(item, _, _)| item.missed && !item.redrilled)
+                .map(|(_, vocab_study, vocab)| (vocab_study.clone(), vocab.clone()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if session.finished.is_none() && redrill.is_empty() {
+            self.awesome_person_repo
+                .finish_study_session(study_session_id)?;
+            session.finished = Some(Utc::now());
+        }
+
+        Ok(SessionSummary {
+            session,
+            items: items
+                .into_iter()
+                .map(|(item, _, vocab)| (item, vocab))
+                .collect(),
+            redrill,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::fixture_setup;
+
+    #[test]
+    fn unit_test_start_study_session() {
+        let study_session_service = fixture_setup().study_session_service;
+
+        let session = study_session_service
+            .start_study_session(1)
+            .expect("No error results expected fn start_study_session with mocked repos");
+        assert_eq!(session.awesome_person_id, 1);
+        assert!(session.finished.is_none());
+    }
+
+    #[test]
+    fn unit_test_finish_study_session() {
+        let study_session_service = fixture_setup().study_session_service;
+
+        // The missed noun is not re-drilled yet, the session stays open
+        let summary = study_session_service
+            .finish_study_session(1, false)
+            .expect("No error results expected fn finish_study_session with mocked repos");
+        assert!(!summary.is_finished());
+        assert_eq!(summary.redrill.len(), 1);
+        assert_eq!(summary.redrill[0].1.learning_lang, "palabra");
+        assert_eq!(summary.missed().count(), 2);
+
+        let summary = study_session_service
+            .finish_study_session(1, true)
+            .expect("No error results expected fn finish_study_session with mocked repos");
+        assert!(summary.is_finished());
+        assert!(summary.redrill.is_empty());
+
+        // Everything missed was re-drilled, or the session is already finished
+        for study_session_id in [2, 3] {
+            let summary = study_session_service
+                .finish_study_session(study_session_id, false)
+                .expect("No error results expected fn finish_study_session with mocked repos");
+            assert!(summary.is_finished());
+            assert!(summary.redrill.is_empty());
+            assert_eq!(summary.items.len(), 2);
+        }
+
+        let result = study_session_service.finish_study_session(99, false);
+        assert!(matches!(result, Err(PalabrasError::NotFound { .. })));
+    }
+}
@@ -1,36 +1,58 @@
-use crate::config::{TranslationsConfig, VocabConfig};
+use crate::config::{language_packs, LanguagePackRegistry, TranslationsConfig};
 use crate::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
-use crate::dal::file_access::{find_first_lang_translations, write_missing_first_export};
+use crate::dal::file_access::{
+    find_deck_vocab, find_first_lang_translations, find_genders, write_deck_export,
+    write_missing_first_export,
+};
+use crate::dal::unit_of_work::{DbUnitOfWork, UnitOfWork};
 use crate::dal::vocab::{DbVocabRepository, VocabRepository};
 use crate::dal::vocab_study::{DbVocabStudyRepository, VocabStudyRepository};
-use crate::models::{AwesomePerson, NewVocabStudy, Vocab};
+use crate::error::PalabrasError;
+use crate::models::{AwesomePerson, NewVocab, NewVocabStudy, Vocab};
+use crate::sl::conjugation::{analyse, is_infinitive, is_known_verb, verb_hint};
+use crate::sl::decks::{check_deck_languages, DeckService, ManageDecks};
 use crate::sl::fuzzy_match_vocab::WELL_KNOWN_THRESHOLD;
-use diesel::result::Error as DieselError;
-use std::collections::HashMap;
+use crate::sl::phrase_match::phrase_words;
+use crate::sl::tags::{tag_names, ManageTags, TagService};
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
-/// Determines hints for a given phrase by analyzing its length and the presence of specific pronouns.
+// Whether the words contain any of the comma separated instances, an instance may be several words.
+fn contains_instance(words: &[String], instances: &str) -> bool {
+    instances.split(',').any(|instance| {
+        let instance = phrase_words(instance);
+        !instance.is_empty()
+            && words
+                .windows(instance.len())
+                .any(|window| window == instance)
+    })
+}
+
+/// Determines hints for a given phrase by analyzing its length and the word categories it contains.
 ///
-/// This function splits the input phrase into words, counts them, and searches for any specified pronouns
-/// within the phrase. If the phrase contains more than one word, it returns a hint consisting of the word count
-/// and the names of any pronoun categories found. This can help identify the grammatical structure or complexity
-/// of the phrase.
+/// This function splits the input phrase into words, ignoring punctuation, counts them, and searches for any
+/// pronouns and other word categories listed in the language pack of the learning language. If the phrase
+/// contains more than one word, it returns a hint consisting of "phrase" and the names of the categories found,
+/// pronouns first, each named once. This explains the grammatical structure the learner needs to produce,
+/// e.g. a question word or a negation.
 ///
 /// # Parameters
-/// - `vocab_config: &VocabConfig` - Configuration containing pronoun information.
+/// - `packs: &LanguagePackRegistry` - The language packs, containing the word categories of each language.
+/// - `learning_lang_code: &str` - The language code of the phrase, choosing the language pack.
 /// - `learning: &str` - The learning phrase to be analyzed.
 ///
 /// # Returns
-/// An `Option<String>` that contains a hint if the phrase has more than one word. The hint includes the word count
-/// and names of any matching pronoun categories found in the phrase. Returns `None` if the phrase consists of a single word.
+/// An `Option<String>` that contains a hint if the phrase has more than one word, and the number of words.
+/// Returns `None` for the hint if the phrase consists of a single word.
 ///
 /// # Examples
 /// ```
 ///
-/// use palabras::config::{Pronoun, VocabConfig};
+/// use palabras::config::{LanguagePackRegistry, Pronoun, VocabConfig, WordCategory};
 /// use palabras::sl::sync_vocab::determine_hint;
+/// use std::collections::HashMap;
 ///
-/// let vocab_config = VocabConfig {
+/// let spanish = VocabConfig {
 ///     plural_suffix: Some("s".to_string()),
 ///     non_verb_matching_suffixes: Some("o,a,os,as,e,es".to_string()),
 ///     pronouns: Some(vec![
@@ -38,36 +60,69 @@ use std::error::Error;
 ///             name: "reflexive pronoun".to_string(),
 ///             instances: "me, te, se, nos, os".to_string(),
 ///         },
+///         Pronoun {
+///             name: "relative pronoun".to_string(),
+///             instances: "que, quien, lo que".to_string(),
+///         },
 ///         // Additional pronouns not shown for brevity
 ///     ]),
+///     word_categories: Some(vec![
+///         WordCategory {
+///             name: "question word".to_string(),
+///             instances: "qué, cómo, dónde, por qué".to_string(),
+///         },
+///         WordCategory {
+///             name: "negation".to_string(),
+///             instances: "no, nunca".to_string(),
+///         },
+///     ]),
+///     ..Default::default()
 /// };
+/// let packs = LanguagePackRegistry::new(HashMap::from([("es".to_string(), spanish)]));
 ///
 /// let learning_phrase = "se acuerdan";
-/// let (hint, num_words) = determine_hint(&vocab_config, &learning_phrase);
+/// let (hint, num_words) = determine_hint(&packs, "es", &learning_phrase);
 /// let hint = hint.unwrap_or_default();
 /// assert_eq!(hint, "phrase, reflexive pronoun");
 /// assert_eq!(num_words, 2);
+///
+/// // Punctuation is ignored and instances may be several words
+/// let (hint, _) = determine_hint(&packs, "es", "¿Por qué no se van?");
+/// assert_eq!(hint.unwrap_or_default(), "phrase, reflexive pronoun, question word, negation");
+/// let (hint, _) = determine_hint(&packs, "es", "Es lo que quiero.");
+/// assert_eq!(hint.unwrap_or_default(), "phrase, relative pronoun");
+///
+/// // Without a language pack only the phrase is hinted
+/// let (hint, _) = determine_hint(&packs, "de", "sich erinnern");
+/// assert_eq!(hint.unwrap_or_default(), "phrase");
 /// ```
-/// This example demonstrates how `determine_hint` generates a hint for the phrase "tú y yo", indicating that it contains
-/// two words and matches the "subject pronoun" category.
-pub fn determine_hint(vocab_config: &VocabConfig, learning: &str) -> (Option<String>, i32) {
-    let binding = learning.to_lowercase();
-    let words: Vec<&str> = binding.split_whitespace().collect();
+/// This example demonstrates how `determine_hint` generates a hint for the phrase "se acuerdan", indicating that it contains
+/// two words and matches the "reflexive pronoun" category.
+pub fn determine_hint(
+    packs: &LanguagePackRegistry,
+    learning_lang_code: &str,
+    learning: &str,
+) -> (Option<String>, i32) {
+    let words = phrase_words(learning);
     let num_words = words.len() as i32;
 
     if num_words > 1 {
-        let mut hint = "phrase".to_string();
+        let mut hint = vec!["phrase"];
 
-        if let Some(pronouns) = &vocab_config.pronouns {
-            for pronoun in pronouns {
-                for instance in pronoun.instances.split(", ") {
-                    if words.contains(&instance) {
-                        hint = format!("{}, {}", hint, pronoun.name);
-                    }
-                }
+        let pack = packs.pack_for(learning_lang_code);
+        let categories = pack
+            .pronouns
+            .iter()
+            .chain(pack.word_categories.iter())
+            .flatten();
+        for category in categories {
+            if !hint.contains(&category.name.as_str())
+                && contains_instance(&words, &category.instances)
+            {
+                hint.push(&category.name);
             }
         }
-        return (Some(hint), num_words);
+        return (Some(hint.join(", ")), num_words);
     }
 
     (None, num_words)
@@ -77,43 +132,68 @@ pub fn determine_hint(vocab_config: &VocabConfig, learning: &str) -> (Option<Str
 ///
 /// This function updates the `current` translation pair by potentially swapping its
 /// `learning_lang` field with the `additional_learning` string, if the latter represents
-/// a singular form matching the plural form in `current.learning_lang`. If the `additional_learning`
-/// is not a match or a singular form of the current learning language, it's added to the list of
-/// alternatives, avoiding duplicates.
+/// a singular form matching the plural form in `current.learning_lang`. The word that is not kept as
+/// the learning language is added to the `alternatives`, avoiding duplicates.
 ///
 /// # Arguments
 ///
+/// * `packs` - The language packs, the plural suffix comes from the pack of `current.learning_lang_code`.
 /// * `current` - A mutable reference to the current translation pair being updated.
+/// * `alternatives` - The alternatives of the translation pair. They are not saved here, the caller adds each as a
+///   [`VocabAlternative`](crate::models::VocabAlternative) with
+///   [`VocabRepository::add_alternative`].
 /// * `additional_learning` - The new word or phrase to be integrated into the translation pair.
-/// * `plural_suffix` - The suffix indicating a plural form in the learning language.
 ///
 /// # Examples
 ///
 /// ```
+/// use palabras::config::{LanguagePackRegistry, VocabConfig};
 /// use palabras::models::Vocab;
 /// use palabras::sl::sync_vocab::merge_learning;
+/// use std::collections::HashMap;
+///
+/// let english = VocabConfig {
+///     plural_suffix: Some("s".to_string()),
+///     ..Default::default()
+/// };
+/// let packs = LanguagePackRegistry::new(HashMap::from([("en".to_string(), english)]));
+///
 /// let mut pair = Vocab {
 ///     learning_lang: "cats".to_string(),
-///     alternatives: None,
+///     learning_lang_code: "en".to_string(),
 ///     ..Default::default()
 /// };
-/// merge_learning(&mut pair, "cat".to_string(), "s");
+/// let mut alternatives = vec![];
+/// merge_learning(&packs, &mut pair, &mut alternatives, "cat".to_string());
 /// assert_eq!(pair.learning_lang, "cat");
-/// assert_eq!(pair.alternatives, Some("cats".to_string()));
+/// assert_eq!(alternatives, ["cats"]);
 ///
 /// // Adding a new alternative that is not a singular form or already listed
-/// merge_learning(&mut pair, "kitty".to_string(), "s");
+/// merge_learning(&packs, &mut pair, &mut alternatives, "kitty".to_string());
+/// merge_learning(&packs, &mut pair, &mut alternatives, "kitty".to_string());
 /// assert_eq!(pair.learning_lang, "cat");
-/// assert_eq!(pair.alternatives, Some("cats, kitty".to_string()));
+/// assert_eq!(alternatives, ["cats", "kitty"]);
 /// ```
-pub fn merge_learning(current: &mut Vocab, additional_learning: String, plural_suffix: &str) {
+pub fn merge_learning(
+    packs: &LanguagePackRegistry,
+    current: &mut Vocab,
+    alternatives: &mut Vec<String>,
+    additional_learning: String,
+) {
     if current.learning_lang.ne(&additional_learning) {
+        let plural_suffix = packs
+            .pack_for(&current.learning_lang_code)
+            .plural_suffix
+            .clone()
+            .unwrap_or_default();
+
         // See if the learning lang is in plural form and should be swapped with the new word.
-        let (learning, additional) = if current
-            .learning_lang
-            .strip_suffix(plural_suffix)
-            .unwrap_or_default()
-            .eq(&additional_learning)
+        let (learning, additional) = if !plural_suffix.is_empty()
+            && current
+                .learning_lang
+                .strip_suffix(&plural_suffix)
+                .unwrap_or_default()
+                .eq(&additional_learning)
         {
             (additional_learning, current.learning_lang.clone())
         } else {
@@ -121,18 +201,190 @@ pub fn merge_learning(current: &mut Vocab, additional_learning: String, plural_s
         };
         current.learning_lang = learning;
 
-        let current_alts = current.alternatives.clone().unwrap_or_default();
-        if !current_alts.contains(&additional) {
-            if current_alts.is_empty() {
-                current.alternatives = Some(additional);
-            } else {
-                current.alternatives = Some(format!("{}, {}", current_alts, additional));
-            }
+        if !alternatives.contains(&additional) {
+            alternatives.push(additional);
+        }
+    }
+}
+
+// Works out the infinitive and verb hint of a single Spanish word.
+// Words marked as another part of speech are skipped, unmarked words are only accepted for known verbs.
+fn analyse_verb(
+    learning_lang_code: &str,
+    learning: &str,
+    pos: Option<&str>,
+) -> Option<(String, Option<String>)> {
+    if learning_lang_code != "es" || learning.split_whitespace().count() != 1 {
+        return None;
+    }
+    let marked_verb = pos
+        .filter(|pos| !pos.trim().is_empty())
+        .map(|pos| pos.trim().eq_ignore_ascii_case("verb"));
+    if marked_verb == Some(false) {
+        return None;
+    }
+
+    let learning = learning.trim().to_lowercase();
+    match analyse(&learning) {
+        Some(form) if marked_verb == Some(true) || is_known_verb(&form.infinitive) => {
+            Some((form.infinitive, verb_hint(&learning)))
+        }
+        // A verb that is not conjugated is its own infinitive
+        None if marked_verb == Some(true) && is_infinitive(&learning) => Some((learning, None)),
+        _ => None,
+    }
+}
+
+/// Completes a new vocab before it is imported.
+///
+/// Counts the learning words and adds the phrase hint, see [`determine_hint`]. Single Spanish words
+/// that are verb forms get their `infinitive` filled and a hint naming the form, for example `salgo`
+/// gets the infinitive `salir` and the hint `salir, yo, presente`. Words are treated as verbs when
+/// `pos` is `Verb`, or when `pos` is not set and the verb is known to the conjugation engine, see
+/// [`is_known_verb`]. Values already set on the vocab are kept.
+///
+/// # Parameters
+///
+/// * `packs` - The language packs, used for the phrase hint.
+/// * `new_vocab` - The vocab about to be imported.
+///
+/// # Returns
+///
+/// The completed `NewVocab`.
+///
+/// # Example
+///
+/// ```
+/// use palabras::config::LanguagePackRegistry;
+/// use palabras::models::NewVocab;
+/// use palabras::sl::sync_vocab::analyse_new_vocab;
+/// use std::collections::HashMap;
+///
+/// let packs = LanguagePackRegistry::new(HashMap::new());
+/// let analysed = analyse_new_vocab(
+///     &packs,
+///     NewVocab {
+///         learning_lang: "salgo".to_string(),
+///         first_lang: "I leave".to_string(),
+///         learning_lang_code: "es".to_string(),
+///         ..Default::default()
+///     },
+/// );
+/// assert_eq!(analysed.infinitive, Some("salir".to_string()));
+/// assert_eq!(analysed.hint, Some("salir, yo, presente".to_string()));
+///
+/// let analysed = analyse_new_vocab(
+///     &packs,
+///     NewVocab {
+///         learning_lang: "salimos ahora".to_string(),
+///         learning_lang_code: "es".to_string(),
+///         ..Default::default()
+///     },
+/// );
+/// assert_eq!(analysed.infinitive, None);
+/// assert_eq!(analysed.hint, Some("phrase".to_string()));
+/// assert_eq!(analysed.num_learning_words, 2);
+/// ```
+pub fn analyse_new_vocab(packs: &LanguagePackRegistry, new_vocab: NewVocab) -> NewVocab {
+    let (phrase_hint, num_words) = determine_hint(
+        packs,
+        &new_vocab.learning_lang_code,
+        &new_vocab.learning_lang,
+    );
+    let (infinitive, hint) = match analyse_verb(
+        &new_vocab.learning_lang_code,
+        &new_vocab.learning_lang,
+        new_vocab.pos.as_deref(),
+    ) {
+        Some((infinitive, verb_hint)) => (Some(infinitive), verb_hint),
+        None => (None, phrase_hint),
+    };
+
+    NewVocab {
+        num_learning_words: num_words,
+        infinitive: new_vocab.infinitive.or(infinitive),
+        hint: new_vocab.hint.or(hint),
+        ..new_vocab
+    }
+}
+
+// The tag of a vocab with a skill, the skill itself.
+fn skill_tag(vocab: &Vocab) -> Option<String> {
+    vocab
+        .skill
+        .as_deref()
+        .filter(|skill| !skill.trim().is_empty())
+        .map(str::to_string)
+}
+
+/// Imports a new vocab, completing it with [`analyse_new_vocab`] before it is saved. A vocab with a
+/// skill is tagged with it, see [`ManageTags::tag_vocab`].
+///
+/// # Returns
+///
+/// Returns `Ok(Vocab)` with the saved vocab.
+///
+/// # Errors
+///
+/// Returns a `PalabrasError` if the vocab cannot be saved, see
+/// [`VocabRepository::create_vocab`](crate::dal::vocab::VocabRepository::create_vocab).
+pub fn import_vocab(new_vocab: NewVocab) -> Result<Vocab, PalabrasError> {
+    let analysed = analyse_new_vocab(language_packs(), new_vocab);
+    let vocab = DbVocabRepository.create_vocab(&analysed)?;
+
+    if let Some(skill) = skill_tag(&vocab) {
+        TagService::instance().tag_vocab(&[vocab.id], &[skill])?;
+    }
+
+    Ok(vocab)
+}
+
+/// Fills the missing infinitives of single word Spanish vocabs already in the database.
+///
+/// Vocabs imported before the conjugation engine existed have no infinitive, each is analysed the
+/// same way as [`analyse_new_vocab`], and a missing hint is also filled. Words that are not verb forms are left unchanged.
+///
+/// # Returns
+///
+/// Returns `Ok(usize)` with the number of vocabs updated.
+///
+/// # Errors
+///
+/// Returns a `PalabrasError` if the vocabs cannot be read or updated.
+///
+/// # Example
+///
+/// See integration test `tests/fill_infinitives_test.rs`
+pub fn fill_missing_infinitives() -> Result<usize, PalabrasError> {
+    let vocab_repo = DbVocabRepository;
+    let mut num_updated = 0;
+
+    for missing in vocab_repo.get_missing_infinitive(i64::MAX)? {
+        if let Some((infinitive, verb_hint)) = analyse_verb(
+            &missing.learning_lang_code,
+            &missing.learning_lang,
+            missing.pos.as_deref(),
+        ) {
+            num_updated += vocab_repo.update_vocab(Vocab {
+                infinitive: Some(infinitive),
+                hint: missing
+                    .hint
+                    .clone()
+                    .filter(|hint| !hint.is_empty())
+                    .or(verb_hint),
+                ..missing
+            })?;
         }
     }
+
+    Ok(num_updated)
 }
 
-pub fn create_vocab_study(vocab_id: i32, awesome_id: i32, percentage: f64) -> Result<(), String> {
+pub fn create_vocab_study(
+    vocab_id: i32,
+    awesome_id: i32,
+    percentage: f64,
+) -> Result<(), PalabrasError> {
     let vocab_study_repo = DbVocabStudyRepository;
 
     let new_vocab_study = NewVocabStudy {
@@ -154,24 +406,26 @@ pub fn create_vocab_study(vocab_id: i32, awesome_id: i32, percentage: f64) -> Re
 ///
 /// This function is intended to reduce redundancy in vocabulary by identifying and reusing existing translation
 /// pairs that represent the same word in different forms (e.g., singular/plural, masculine/feminine). It does so by
-/// iterating through a list of allowed suffix changes, attempting to find a match in the database.
+/// iterating through the allowed suffix changes of the learning language's pack, attempting to find a match
+/// in the database. Only vocab of the same learning language is matched.
 ///
 /// # Arguments
 ///
-/// * `non_verb_matching_suffixes` - A `&str` containing a comma-separated list of suffixes to be considered
-/// for matching similar words. Used to construct alternative word forms by replacing these suffixes in `learning_lang`.
+/// * `packs` - The language packs. The `non_verb_matching_suffixes` of the pack for `learning_lang_code`, a
+///   comma-separated list of suffixes, are used to construct alternative word forms by replacing these suffixes in `learning_lang`.
+/// * `learning_lang_code` - The language code of `learning_lang`.
 /// * `learning_lang` - A `&str` representing the word in the learning language for which a similar existing translation
-/// pair is being sought.
+///   pair is being sought.
 ///
 /// # Returns
 ///
 /// This function returns a `Result` object which, on success, contains an `Option<TranslationPair>`. The contained
 /// `Option` is `Some(TranslationPair)` if a translation pair with a similar word is found, or `None` if no similar
-/// word could be found. An error of type `DieselError` is returned in case of database access issues.
+/// word could be found. A `PalabrasError` is returned in case of database access issues.
 ///
 /// # Errors
 ///
-/// This function may return a `DieselError` if there is an issue during the database query operation, such as a
+/// This function may return a `PalabrasError` if there is an issue during the database query operation, such as a
 /// connection problem or a syntax error in the query.
 ///
 /// # Examples
@@ -183,10 +437,9 @@ pub fn create_vocab_study(vocab_id: i32, awesome_id: i32, percentage: f64) -> Re
 /// // Assume an existing `DbTranslationPairRepository` and a connection to a database
 ///
 /// // If "gato, gata or gatas" is in the database, its vocab will be found and returned.
-/// let non_verb_suffixes = "o,a,os,as,e,es";
 /// let learning_lang_word = "gatos";
 ///
-/// match find_similar(non_verb_suffixes, learning_lang_word) {
+/// match find_similar(language_packs(), "es", learning_lang_word) {
 ///     Ok(Some(vocab)) => {
 ///         println!("Found a similar word: {}", vocab.learning_lang);
 ///     },
@@ -202,10 +455,18 @@ pub fn create_vocab_study(vocab_id: i32, awesome_id: i32, percentage: f64) -> Re
 /// Please note: This example assumes a specific database schema and runtime environment, including an instantiated
 /// `DbTranslationPairRepository`, and thus is not directly runnable.
 fn _find_similar(
-    non_verb_matching_suffixes: &str,
+    packs: &LanguagePackRegistry,
+    learning_lang_code: &str,
     learning_lang: &str,
-) -> Result<Option<Vocab>, DieselError> {
+) -> Result<Option<Vocab>, PalabrasError> {
     let vocab_repo = DbVocabRepository;
+    let non_verb_matching_suffixes = match &packs
+        .pack_for(learning_lang_code)
+        .non_verb_matching_suffixes
+    {
+        Some(suffixes) if !suffixes.is_empty() => suffixes.as_str(),
+        _ => return Ok(None),
+    };
 
     let learning = learning_lang.to_lowercase();
 
@@ -228,7 +489,12 @@ fn _find_similar(
 
                 // Attempt to search for a translation pair using the newly contructed alternative
                 if let Ok(Some(vocab)) = vocab_repo.find_vocab_by_learning_language(alt_word) {
-                    return Ok(Some(vocab)); // Found a similar word form, return it
+                    if vocab
+                        .learning_lang_code
+                        .eq_ignore_ascii_case(learning_lang_code)
+                    {
+                        return Ok(Some(vocab)); // Found a similar word form, return it
+                    }
                 }
             }
         }
@@ -251,9 +517,9 @@ fn _find_similar(
 ///   - `header_lines`: Number of header lines to skip (useful for CSV files).
 ///   - `delimiter`: Delimiter used in CSV files. Leave empty for XML files.
 ///   - `learning_index` and `first_index`: In CSV files, the column indices of the learning and first languages.
-///      In XML, these are ignored.
+///     In XML, these are ignored.
 ///   - `learning_regex` and `first_regex`: Regular expressions to extract translation pairs from XML files. These
-///      should form capturing groups for the learning and first language terms. Ignored for CSV files.
+///     should form capturing groups for the learning and first language terms. Ignored for CSV files.
 ///
 /// # Returns
 ///
@@ -275,6 +541,7 @@ fn _find_similar(
 ///        first_index: 0,
 ///        learning_regex: Some("<src>([^<]+)</src>".to_string()),
 ///        first_regex: Some("<tgt>([^<]+)</tgt>".to_string()),
+///        gender_regex: None,
 ///    },
 ///    TranslationsConfig {
 ///        file_name: "tests/data/es_en_mapping/llm_import.csv".to_string(),
@@ -284,6 +551,7 @@ fn _find_similar(
 ///        first_index: 1,
 ///        learning_regex: None,
 ///        first_regex: None,
+///        gender_regex: None,
 ///    }
 /// ];
 /// let translations_map = load_translations(Some(configs));
@@ -302,8 +570,8 @@ pub fn load_translations(
 ) -> HashMap<String, String> {
     let mut translation_map: HashMap<String, String> = HashMap::new();
 
-    if translation_configs.is_some() {
-        for config in translation_configs.unwrap() {
+    if let Some(translation_configs) = translation_configs {
+        for config in translation_configs {
             if let Ok(map) = find_first_lang_translations(&config) {
                 for (key, value) in map {
                     translation_map.entry(key).or_insert(value);
@@ -315,11 +583,68 @@ pub fn load_translations(
     translation_map
 }
 
+/// Loads the grammatical gender of learning language words from the files of the configs with a `gender_regex`.
+///
+/// Like [`load_translations`], files that fail to load are skipped and the first gender found for a word is kept.
+///
+/// # Returns
+///
+/// Returns a `HashMap<String, String>` of lowercase learning language words to their gender, `m` or `f`.
+pub fn load_genders(
+    translation_configs: Option<Vec<TranslationsConfig>>,
+) -> HashMap<String, String> {
+    let mut gender_map: HashMap<String, String> = HashMap::new();
+
+    for config in translation_configs.unwrap_or_default() {
+        if let Ok(map) = find_genders(&config) {
+            for (key, value) in map {
+                gender_map.entry(key.trim().to_lowercase()).or_insert(value);
+            }
+        }
+    }
+
+    gender_map
+}
+
+/// Fills the missing grammatical gender of single word vocabs already in the database.
+///
+/// Each vocab without a gender is looked up in the genders loaded by [`load_genders`], vocabs
+/// that are not found, such as verbs, are left unchanged.
+///
+/// # Returns
+///
+/// Returns `Ok(usize)` with the number of vocabs updated.
+///
+/// # Errors
+///
+/// Returns a `PalabrasError` if the vocabs cannot be read or updated.
+///
+/// # Example
+///
+/// See integration test `tests/fill_genders_test.rs`
+pub fn fill_missing_genders(
+    translation_configs: Option<Vec<TranslationsConfig>>,
+) -> Result<usize, PalabrasError> {
+    let genders = load_genders(translation_configs);
+    let vocab_repo = DbVocabRepository;
+    let mut num_updated = 0;
+
+    for missing in vocab_repo.get_missing_gender(i64::MAX)? {
+        if let Some(gender) = genders.get(&missing.learning_lang.trim().to_lowercase()) {
+            num_updated += vocab_repo.update_vocab(Vocab {
+                gender: Some(gender.clone()),
+                ..missing
+            })?;
+        }
+    }
+
+    Ok(num_updated)
+}
+
 /// Verifies if an `AwesomePerson` exists by their ID.
 ///
 /// This function searches for an `AwesomePerson` in the database using a given ID. If the `AwesomePerson`
-/// is found, it returns `Ok(AwesomePerson)`. If not found, it returns an `Err` with a message indicating
-/// no AwesomePerson was found with the given ID.
+/// is found, it returns `Ok(AwesomePerson)`. If not found, it returns `PalabrasError::NotFound`.
 ///
 /// # Arguments
 ///
@@ -328,20 +653,12 @@ pub fn load_translations(
 /// # Returns
 ///
 /// * `Ok(AwesomePerson)` if the `AwesomePerson` is found.
-/// * `Err(String)` if no `AwesomePerson` is found, with a message including the ID.
-pub fn verify_awesome_person(awesome_person_id: i32) -> Result<AwesomePerson, String> {
+/// * `Err(PalabrasError::NotFound)` if no `AwesomePerson` is found.
+pub fn verify_awesome_person(awesome_person_id: i32) -> Result<AwesomePerson, PalabrasError> {
     let repo = DbAwesomePersonRepository;
 
-    let awesome_person = repo.get_awesome_person_by_id(awesome_person_id)?;
-
-    if awesome_person.is_none() {
-        Err(format!(
-            "No Awesome person was found with id {}",
-            awesome_person_id
-        ))
-    } else {
-        Ok(awesome_person.unwrap())
-    }
+    repo.get_awesome_person_by_id(awesome_person_id)?
+        .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))
 }
 
 /// Exports translation pairs with missing "first language" fields to a CSV file.
@@ -358,7 +675,7 @@ pub fn verify_awesome_person(awesome_person_id: i32) -> Result<AwesomePerson, St
 /// A `Result<(), Box<dyn Error>>` indicating the outcome of the operation:
 /// - `Ok(())` on success.
 /// - An error boxed in `Box<dyn Error>` on failure, which could arise from issues such as database access errors,
-/// file IO errors, or data serialization problems.
+///   file IO errors, or data serialization problems.
 ///
 /// # Example
 ///
@@ -373,3 +690,122 @@ pub fn export_missing_first_lang_pairs(file_path: &str) -> Result<(), Box<dyn Er
 
     Ok(())
 }
+
+/// Imports the vocab of a CSV file into a deck, see [`find_deck_vocab`] for the format.
+///
+/// A vocab already in the database is reused, matched by its learning language, and any other vocab is
+/// imported with the languages of the deck, the same as [`import_vocab`]. Each vocab gets the tags of its line and
+/// is added to the deck as with [`ManageDecks::add_to_deck`], so the owner of the deck starts studying it.
+/// The file is imported in a single unit of work, see [`UnitOfWork::deck_import`], a line that fails leaves
+/// nothing of the file saved.
+///
+/// # Returns
+///
+/// Returns `Ok(usize)` with the number of vocab added, vocab already in the deck is not counted.
+///
+/// # Errors
+///
+/// Returns a `PalabrasError` if the deck does not exist, the file cannot be read, or a vocab cannot be
+/// saved or is in other languages than the deck.
+///
+/// # Example
+///
+/// See integration test `tests/deck_test.rs`
+pub fn import_deck(deck_id: i32, file_path: &str) -> Result<usize, PalabrasError> {
+    let deck_vocab = find_deck_vocab(file_path)?;
+
+    let mut num_added = 0;
+    DbUnitOfWork.deck_import(&mut |tx| {
+        let deck = tx.get_deck(deck_id)?;
+
+        let mut vocab_ids = Vec::new();
+        for (new_vocab, tags) in &deck_vocab {
+            let vocab = match tx.find_vocab_by_learning_language(&new_vocab.learning_lang)? {
+                Some(existing) => existing,
+                None => {
+                    let analysed = analyse_new_vocab(
+                        language_packs(),
+                        NewVocab {
+                            known_lang_code: deck.known_lang_code.clone(),
+                            learning_lang_code: deck.learning_lang_code.clone(),
+                            ..new_vocab.clone()
+                        },
+                    );
+                    let vocab = tx.create_vocab(&analysed)?;
+                    if let Some(skill) = skill_tag(&vocab) {
+                        tx.tag_vocab(&[vocab.id], &tag_names(&[skill])?)?;
+                    }
+                    vocab
+                }
+            };
+            check_deck_languages(&deck, &vocab)?;
+            if !tags.is_empty() {
+                tx.tag_vocab(&[vocab.id], &tag_names(tags)?)?;
+            }
+            vocab_ids.push(vocab.id);
+        }
+
+        num_added = tx.add_vocab_to_deck(deck.id, &vocab_ids)?;
+
+        // The owner studies the whole deck, the same as adding vocab with ManageDecks::add_to_deck.
+        tx.start_vocab_studies(&vocab_ids, deck.owner_id)?;
+
+        Ok(())
+    })?;
+
+    Ok(num_added)
+}
+
+/// Exports the vocab of a deck to a new CSV file with the tags of each vocab, which [`import_deck`] reads back.
+///
+/// # Parameters
+/// - `deck_id: i32` - The deck to export.
+/// - `tags: &[String]` - Only the vocab with every one of these tags, empty for the whole deck.
+/// - `file_path: &str` - The path to the file, which must not already exist.
+///
+/// # Returns
+///
+/// Returns `Ok(usize)` with the number of vocab exported.
+///
+/// # Errors
+///
+/// Returns an error if the deck does not exist, the deck cannot be read, or the file already exists
+/// or cannot be written.
+///
+/// # Example
+///
+/// See integration test `tests/deck_test.rs`
+pub fn export_deck(
+    deck_id: i32,
+    tags: &[String],
+    file_path: &str,
+) -> Result<usize, Box<dyn Error>> {
+    let (_, mut vocabs) = DeckService::instance().get_deck_vocab(deck_id)?;
+
+    if !tags.is_empty() {
+        let tagged: HashSet<i32> = TagService::instance()
+            .get_tagged_vocab(tags, None, i64::MAX)?
+            .into_iter()
+            .map(|(vocab, _)| vocab.id)
+            .collect();
+        vocabs.retain(|vocab| tagged.contains(&vocab.id));
+    }
+
+    let vocab_ids: Vec<i32> = vocabs.iter().map(|vocab| vocab.id).collect();
+    let vocab_tags = DbVocabRepository.get_vocab_tags(&vocab_ids)?;
+    let exporting: Vec<(Vocab, Vec<String>)> = vocabs
+        .into_iter()
+        .map(|vocab| {
+            let tags = vocab_tags
+                .iter()
+                .filter(|(vocab_id, _)| *vocab_id == vocab.id)
+                .map(|(_, tag)| tag.name.clone())
+                .collect();
+            (vocab, tags)
+        })
+        .collect();
+
+    write_deck_export(file_path, &exporting)?;
+
+    Ok(exporting.len())
+}
@@ -0,0 +1,225 @@
+use crate::dal::vocab::{DbVocabRepository, VocabRepository};
+use crate::error::PalabrasError;
+use crate::models::{Tag, Vocab};
+use lazy_static::lazy_static;
+
+// Tags of vocab, shared by everyone, used to study and look up vocab by topic or level.
+
+pub trait ManageTags {
+    /// Retrieves every tag of vocab, by name.
+    ///
+    /// # Returns
+    ///
+    /// Each `Tag` with the number of vocab tagged with it, or an error if the query fails.
+    fn get_tags(&self) -> Result<Vec<(Tag, i64)>, PalabrasError>;
+
+    /// Retrieves the vocab tagged with every one of the tags, with all of its tags.
+    ///
+    /// # Parameters
+    ///
+    /// * `tags` - The names of the tags, compared ignoring case.
+    /// * `after` - Optional. Only the vocab with a greater id, the id of the last vocab of the previous page.
+    /// * `limit` - The maximum number of vocab to return.
+    ///
+    /// # Returns
+    ///
+    /// Each `Vocab` with its `Tag`s, by vocab id.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if `limit` is less than 1, there are no tags or a tag is empty.
+    /// - Another error if the query fails.
+    fn get_tagged_vocab(
+        &self,
+        tags: &[String],
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<(Vocab, Vec<Tag>)>, PalabrasError>;
+
+    /// Tags vocab, every vocab gets every tag. The tags are trimmed and in lowercase, a tag that does not
+    /// exist yet is created.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_ids` - The primary keys of the vocab to tag.
+    /// * `tags` - The names of the tags.
+    ///
+    /// # Returns
+    ///
+    /// The number of tags added, tags a vocab already has are not counted.
+    ///
+    /// # Errors
+    ///
+    /// - `PalabrasError::Validation` if there are no tags or a tag is empty.
+    /// - `PalabrasError::NotFound` if a vocab does not exist.
+    /// - Another error if the save fails.
+    fn tag_vocab(&self, vocab_ids: &[i32], tags: &[String]) -> Result<usize, PalabrasError>;
+
+    /// Removes tags from vocab.
+    ///
+    /// # Parameters
+    ///
+    /// * `vocab_ids` - The primary keys of the vocab.
+    /// * `tags` - The names of the tags, compared ignoring case.
+    ///
+    /// # Returns
+    ///
+    /// The number of tags removed, `PalabrasError::Validation` if there are no tags or a tag is empty,
+    /// or another error if the delete fails.
+    fn untag_vocab(&self, vocab_ids: &[i32], tags: &[String]) -> Result<usize, PalabrasError>;
+}
+
+pub struct TagService {
+    vocab_repo: Box<dyn VocabRepository>,
+}
+
+lazy_static! {
+    static ref TAG_SERVICE: TagService = TagService::new(Box::new(DbVocabRepository),);
+}
+
+impl TagService {
+    // The constructor takes Box<dyn Repos>
+    pub fn new(vocab_repo: Box<dyn VocabRepository>) -> Self {
+        TagService { vocab_repo }
+    }
+
+    // Method to access the singleton instance, shared across threads like the other services.
+    pub fn instance() -> &'static TagService {
+        &TAG_SERVICE
+    }
+}
+
+// The names of tags, trimmed and in lowercase, each named once.
+pub(crate) fn tag_names(tags: &[String]) -> Result<Vec<String>, PalabrasError> {
+    let mut names: Vec<String> = Vec::new();
+    for tag in tags {
+        let name = tag.trim().to_lowercase();
+        if name.is_empty() {
+            return Err(PalabrasError::Validation(
+                "a tag cannot be empty".to_string(),
+            ));
+        }
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    if names.is_empty() {
+        return Err(PalabrasError::Validation(
+            "at least one tag is needed".to_string(),
+        ));
+    }
+
+    Ok(names)
+}
+
+/// The tags of vocab, stored with the vocab repository.
+impl ManageTags for TagService {
+    /// Implementation, see trait for details [`ManageTags::get_tags`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit tests in this module.
+    fn get_tags(&self) -> Result<Vec<(Tag, i64)>, PalabrasError> {
+        self.vocab_repo.get_tags()
+    }
+
+    /// Implementation, see trait for details [`ManageTags::get_tagged_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn get_tagged_vocab(
+        &self,
+        tags: &[String],
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<(Vocab, Vec<Tag>)>, PalabrasError> {
+        if limit < 1 {
+            return Err(PalabrasError::Validation(format!(
+                "limit must be at least 1, found {}",
+                limit
+            )));
+        }
+
+        let vocabs = self
+            .vocab_repo
+            .find_vocab_by_tags(&tag_names(tags)?, after, limit)?;
+        let vocab_ids: Vec<i32> = vocabs.iter().map(|vocab| vocab.id).collect();
+        let vocab_tags = self.vocab_repo.get_vocab_tags(&vocab_ids)?;
+
+        Ok(vocabs
+            .into_iter()
+            .map(|vocab| {
+                let tags = vocab_tags
+                    .iter()
+                    .filter(|(vocab_id, _)| *vocab_id == vocab.id)
+                    .map(|(_, tag)| tag.clone())
+                    .collect();
+                (vocab, tags)
+            })
+            .collect())
+    }
+
+    /// Implementation, see trait for details [`ManageTags::tag_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn tag_vocab(&self, vocab_ids: &[i32], tags: &[String]) -> Result<usize, PalabrasError> {
+        let names = tag_names(tags)?;
+        for vocab_id in vocab_ids {
+            self.vocab_repo.get_vocab_by_id(*vocab_id)?;
+        }
+        if vocab_ids.is_empty() {
+            return Ok(0);
+        }
+
+        self.vocab_repo.tag_vocab(vocab_ids, &names)
+    }
+
+    /// Implementation, see trait for details [`ManageTags::untag_vocab`]
+    ///
+    /// For advanced usage and mock implementations, please refer to
+    /// the unit and integration tests for this module.
+    fn untag_vocab(&self, vocab_ids: &[i32], tags: &[String]) -> Result<usize, PalabrasError> {
+        let names = tag_names(tags)?;
+
+        self.vocab_repo.untag_vocab(vocab_ids, &names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::fixture_setup;
+
+    #[test]
+    fn unit_test_manage_tags() {
+        let tag_service = fixture_setup().tag_service;
+
+        let tags = tag_service
+            .get_tags()
+            .expect("No error results expected fn get_tags with mocked repos");
+        assert_eq!(tags.len(), 2);
+
+        let tagged = tag_service
+            .get_tagged_vocab(&["A2".to_string()], None, 10)
+            .expect("No error results expected fn get_tagged_vocab with mocked repos");
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].0.id, 2);
+        assert_eq!(tagged[0].1[0].name, "a2");
+
+        let num_tagged = tag_service
+            .tag_vocab(&[1, 2], &["travel".to_string(), "Travel".to_string()])
+            .expect("No error results expected fn tag_vocab with mocked repos");
+        assert_eq!(num_tagged, 2, "The same tag is added once");
+
+        let result = tag_service.tag_vocab(&[1], &[" ".to_string()]);
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+        let result = tag_service.untag_vocab(&[1], &[]);
+        assert!(matches!(result, Err(PalabrasError::Validation(_))));
+
+        let num_untagged = tag_service
+            .untag_vocab(&[1], &["food".to_string()])
+            .expect("No error results expected fn untag_vocab with mocked repos");
+        assert_eq!(num_untagged, 1);
+    }
+}
@@ -1,22 +1,134 @@
+use crate::config::{LanguagePackRegistry, VocabConfig};
 #[cfg(test)]
 use crate::dal::awesome_person::AwesomePersonRepository;
+use crate::dal::unit_of_work::{DeckImportTransaction, GradingTransaction, UnitOfWork};
 use crate::dal::vocab::VocabRepository;
 use crate::dal::vocab_study::VocabStudyRepository;
+use crate::error::PalabrasError;
 use crate::models::NewVocabStudy;
-use crate::models::{AwesomePerson, NewAwesomePerson, NewVocab, Vocab, VocabStudy};
+use crate::models::{
+    AwesomePerson, ConjugationStat, Deck, GradingPolicy, NewAwesomePerson, NewDeck, NewVocab,
+    NewVocabAlternative, NewVocabAttempt, StudySession, StudySessionItem, Tag, Vocab,
+    VocabAlternative, VocabMatch, VocabSearch, VocabStudy, VocabStudyFilter, VocabStudyKey,
+    VocabStudyOrder,
+};
+use crate::sl::daily_quota::DailyQuotaService;
+use crate::sl::decks::DeckService;
 use crate::sl::fuzzy_match_vocab::VocabFuzzyMatch;
+use crate::sl::leeches::LeechService;
+use crate::sl::my_vocab::MyVocabService;
+use crate::sl::search::VocabSearchService;
+use crate::sl::study_session::StudySessionService;
+use crate::sl::tags::TagService;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+lazy_static! {
+    // Spanish articles, for checking the article of the test noun, a free word order and variant names
+    static ref TEST_LANGUAGE_PACKS: LanguagePackRegistry = LanguagePackRegistry::new(HashMap::from([(
+        "es".to_string(),
+        VocabConfig {
+            articles: Some(HashMap::from([
+                ("m".to_string(), "el, un, los, unos".to_string()),
+                ("f".to_string(), "la, una, las, unas".to_string()),
+            ])),
+            free_word_order: Some(true),
+            variants: Some(HashMap::from([
+                ("es-ES".to_string(), "Spain".to_string()),
+                ("es-419".to_string(), "Latin America".to_string()),
+            ])),
+            ..Default::default()
+        },
+    )]));
+}
 
 pub struct TestFixtures {
     pub fuzzy_service: Box<VocabFuzzyMatch>,
+    pub daily_quota_service: Box<DailyQuotaService>,
+    pub deck_service: Box<DeckService>,
+    pub study_session_service: Box<StudySessionService>,
+    pub leech_service: Box<LeechService>,
+    pub tag_service: Box<TagService>,
+    pub vocab_search_service: Box<VocabSearchService>,
+    pub my_vocab_service: Box<MyVocabService>,
 }
 
 // Create a mocked fuzzy service for unit tests. Repos are mocked
 // and return test data
 pub fn fixture_setup() -> TestFixtures {
-    let awesome_person_repo = Box::new(MockAwesomePersonRepository);
+    fixture_setup_with_policy(None)
+}
+
+// Same as fixture_setup, but the mocked awesome person has the given grading policy stored.
+pub fn fixture_setup_with_policy(grading_policy: Option<GradingPolicy>) -> TestFixtures {
+    let (awesome_person_repo, vocab_study_repo, vocab_repo, unit_of_work) =
+        mock_repos(grading_policy.clone());
+    let fuzzy_service = Box::new(
+        VocabFuzzyMatch::new(
+            awesome_person_repo,
+            vocab_study_repo,
+            vocab_repo,
+            unit_of_work,
+        )
+        .with_language_packs(&TEST_LANGUAGE_PACKS),
+    );
+
+    // Each of the other services gets its own mocked repos with the same test data.
+    let repos = || mock_repos(grading_policy.clone());
+    let (awesome_person_repo, _, _, _) = repos();
+    let daily_quota_service = Box::new(DailyQuotaService::new(awesome_person_repo));
+    let (awesome_person_repo, _, _, unit_of_work) = repos();
+    let deck_service = Box::new(DeckService::new(awesome_person_repo, unit_of_work));
+    let (awesome_person_repo, _, _, _) = repos();
+    let study_session_service = Box::new(StudySessionService::new(awesome_person_repo));
+    let (awesome_person_repo, vocab_study_repo, vocab_repo, _) = repos();
+    let leech_service = Box::new(LeechService::new(
+        awesome_person_repo,
+        vocab_study_repo,
+        vocab_repo,
+    ));
+    let (_, _, vocab_repo, _) = repos();
+    let tag_service = Box::new(TagService::new(vocab_repo));
+    let (awesome_person_repo, _, vocab_repo, _) = repos();
+    let vocab_search_service = Box::new(VocabSearchService::new(awesome_person_repo, vocab_repo));
+    let (awesome_person_repo, vocab_study_repo, _, _) = repos();
+    let my_vocab_service = Box::new(MyVocabService::new(awesome_person_repo, vocab_study_repo));
+
+    TestFixtures {
+        fuzzy_service,
+        daily_quota_service,
+        deck_service,
+        study_session_service,
+        leech_service,
+        tag_service,
+        vocab_search_service,
+        my_vocab_service,
+    }
+}
+
+type MockRepos = (
+    Box<MockAwesomePersonRepository>,
+    Box<MockVocabStudyRepository>,
+    Box<MockVocabRepository>,
+    Box<MockUnitOfWork>,
+);
+
+// The mocked repos, returning the test data. The awesome person has the given grading policy stored.
+fn mock_repos(grading_policy: Option<GradingPolicy>) -> MockRepos {
+    let awesome_person_repo = Box::new(MockAwesomePersonRepository {
+        grading_policy: grading_policy.clone(),
+    });
 
     let (vocab_study, vocab_study_list, vocab, vocab_list, combo_list) = create_test_data();
 
+    let unit_of_work = Box::new(MockUnitOfWork {
+        vocab_study: vocab_study.clone(),
+        vocab: vocab.clone(),
+        grading_policy,
+    });
+
     let vocab_study_repo = Box::new(MockVocabStudyRepository {
         vocab_study,
         vocab_study_list,
@@ -25,22 +137,23 @@ pub fn fixture_setup() -> TestFixtures {
 
     let vocab_repo = Box::new(MockVocabRepository { vocab, vocab_list });
 
-    let fuzzy_service = Box::new(VocabFuzzyMatch::new(
+    (
         awesome_person_repo,
         vocab_study_repo,
         vocab_repo,
-    ));
-
-    TestFixtures { fuzzy_service }
+        unit_of_work,
+    )
 }
 
-fn create_test_data() -> (
+type TestData = (
     VocabStudy,
     Vec<VocabStudy>,
     Vocab,
     Vec<Vocab>,
     Vec<(VocabStudy, Vocab)>,
-) {
+);
+
+fn create_test_data() -> TestData {
     let vocab_study = VocabStudy {
         id: 1,
         vocab_id: 1,
@@ -53,6 +166,10 @@ fn create_test_data() -> (
         well_known: true,
         user_notes: None,
         correct_attempts: None,
+        lapses: 0,
+        leech: false,
+        suspended: false,
+        user_hint: None,
     };
 
     let vocab_study_list = vec![vocab_study.clone()];
@@ -62,7 +179,6 @@ fn create_test_data() -> (
         learning_lang: "palabra".to_string(),
         first_lang: "word".to_string(),
         created: Default::default(),
-        alternatives: None,
         skill: None,
         infinitive: None,
         pos: Some("noun".to_string()),
@@ -70,20 +186,135 @@ fn create_test_data() -> (
         num_learning_words: 1,
         known_lang_code: "en".to_string(),
         learning_lang_code: "es".to_string(),
+        gender: Some("f".to_string()),
+        lang_variant: None,
     };
 
-    let vocab_list = vec![vocab.clone()];
+    // A verb, for the conjugation drills
+    let verb = Vocab {
+        id: 2,
+        learning_lang: "aprendimos".to_string(),
+        first_lang: "we learned".to_string(),
+        infinitive: Some("aprender".to_string()),
+        pos: Some("Verb".to_string()),
+        gender: None,
+        ..vocab.clone()
+    };
+
+    let vocab_list = vec![vocab.clone(), verb.clone()];
 
-    let combo_list = vec![(vocab_study.clone(), vocab.clone())];
+    let combo_list = vec![
+        (vocab_study.clone(), vocab.clone()),
+        (
+            VocabStudy {
+                id: 2,
+                vocab_id: 2,
+                ..vocab_study.clone()
+            },
+            verb,
+        ),
+    ];
 
     (vocab_study, vocab_study_list, vocab, vocab_list, combo_list)
 }
 
+// The alternatives of the test noun, one with a register for the usage feedback
+fn test_alternatives() -> Vec<VocabAlternative> {
+    vec![
+        VocabAlternative {
+            id: 1,
+            vocab_id: 1,
+            alternative: "vocablo".to_string(),
+            register: Some("formal".to_string()),
+            lang_variant: None,
+        },
+        VocabAlternative {
+            id: 2,
+            vocab_id: 1,
+            alternative: "término".to_string(),
+            register: None,
+            lang_variant: None,
+        },
+    ]
+}
+
+// The study sessions of the test awesome person: session 3 is finished, session 4 belongs to another
+// awesome person and the others are open. Session 99 does not exist.
+fn test_study_session(study_session_id: i32) -> Option<StudySession> {
+    if study_session_id == 99 {
+        return None;
+    }
+
+    Some(StudySession {
+        id: study_session_id,
+        awesome_person_id: if study_session_id == 4 { 2 } else { 1 },
+        started: chrono::Utc::now(),
+        finished: (study_session_id == 3).then(chrono::Utc::now),
+        num_answers: 3,
+        num_correct: 1,
+    })
+}
+
+// The vocab answered in a test session, both missed. Only in session 1 is the noun still to re-drill.
+fn test_study_session_items(study_session_id: i32) -> Vec<(StudySessionItem, VocabStudy, Vocab)> {
+    let (_, _, _, _, combo_list) = create_test_data();
+    combo_list
+        .into_iter()
+        .map(|(vocab_study, vocab)| {
+            let item = StudySessionItem {
+                study_session_id,
+                vocab_study_id: vocab_study.id,
+                attempts: 1,
+                correct_attempts: 0,
+                percentage_before: 0.5,
+                percentage_after: 0.4,
+                missed: true,
+                redrilled: study_session_id != 1 || vocab_study.id != 1,
+                created: chrono::Utc::now(),
+            };
+            (item, vocab_study, vocab)
+        })
+        .collect()
+}
+
+// A test deck, deck 1 of awesome person 1 holds the noun and deck 2 belongs to awesome person 2.
+fn test_deck(deck_id: i32) -> Option<Deck> {
+    (1..=2).contains(&deck_id).then(|| Deck {
+        id: deck_id,
+        owner_id: deck_id,
+        name: format!("Deck {}", deck_id),
+        description: None,
+        known_lang_code: "en".to_string(),
+        learning_lang_code: "es".to_string(),
+        created: chrono::Utc::now(),
+    })
+}
+
+// The tags of the test vocab, the noun is food and the verb is a2.
+fn test_tags(vocab_id: i32) -> Vec<Tag> {
+    match vocab_id {
+        1 => vec![Tag {
+            id: 1,
+            name: "food".to_string(),
+        }],
+        2 => vec![Tag {
+            id: 2,
+            name: "a2".to_string(),
+        }],
+        _ => Vec::new(),
+    }
+}
+
 // Mock-up functions to simulate actual function behaviors
-pub struct MockAwesomePersonRepository;
+pub struct MockAwesomePersonRepository {
+    pub grading_policy: Option<GradingPolicy>,
+}
 
 impl AwesomePersonRepository for MockAwesomePersonRepository {
-    fn get_awesome_person_by_id(&self, stats_id: i32) -> Result<Option<AwesomePerson>, String> {
+    fn get_awesome_person_by_id(
+        &self,
+        stats_id: i32,
+    ) -> Result<Option<AwesomePerson>, PalabrasError> {
         Ok(Some(AwesomePerson {
             id: stats_id,
             num_known: Some(100),
@@ -95,13 +326,16 @@ impl AwesomePersonRepository for MockAwesomePersonRepository {
             sec_code: "3456".to_string(),
             smallest_vocab: 5,
             max_learning_words: 5,
+            preferred_variant: None,
+            new_per_day: 20,
+            reviews_per_day: 200,
         }))
     }
 
     fn get_awesome_person_by_code(
         &self,
         lookup_code: String,
-    ) -> Result<Option<AwesomePerson>, String> {
+    ) -> Result<Option<AwesomePerson>, PalabrasError> {
         Ok(Some(AwesomePerson {
             id: 23,
             num_known: Some(200),
@@ -113,17 +347,52 @@ impl AwesomePersonRepository for MockAwesomePersonRepository {
             sec_code: lookup_code,
             smallest_vocab: 2,
             max_learning_words: 5,
+            preferred_variant: None,
+            new_per_day: 20,
+            reviews_per_day: 200,
         }))
     }
 
-    fn update_awesome_person(&self, _stats: AwesomePerson) -> Result<usize, String> {
+    fn update_awesome_person(&self, _stats: AwesomePerson) -> Result<usize, PalabrasError> {
         Ok(1)
     }
 
+    fn update_daily_limits(
+        &self,
+        awesome_id: i32,
+        new_per_day: i32,
+        reviews_per_day: i32,
+    ) -> Result<AwesomePerson, PalabrasError> {
+        let awesome_person = self
+            .get_awesome_person_by_id(awesome_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_id))?;
+
+        Ok(AwesomePerson {
+            new_per_day,
+            reviews_per_day,
+            ..awesome_person
+        })
+    }
+
+    fn update_preferred_variant(
+        &self,
+        awesome_id: i32,
+        lang_variant: &str,
+    ) -> Result<AwesomePerson, PalabrasError> {
+        let awesome_person = self
+            .get_awesome_person_by_id(awesome_id)?
+            .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_id))?;
+
+        Ok(AwesomePerson {
+            preferred_variant: Some(lang_variant.to_string()),
+            ..awesome_person
+        })
+    }
+
     fn create_awesome_person(
         &self,
         new_awesome_person: &NewAwesomePerson,
-    ) -> Result<AwesomePerson, String> {
+    ) -> Result<AwesomePerson, PalabrasError> {
         Ok(AwesomePerson {
             id: 2,
             num_known: new_awesome_person.num_known,
@@ -135,6 +404,128 @@ impl AwesomePersonRepository for MockAwesomePersonRepository {
             ..Default::default()
         })
     }
+
+    fn recalculate_awesome_person_stats(&self) -> Result<usize, PalabrasError> {
+        Ok(1)
+    }
+
+    fn get_grading_policy(&self, _awesome_id: i32) -> Result<Option<GradingPolicy>, PalabrasError> {
+        Ok(self.grading_policy.clone())
+    }
+
+    fn save_grading_policy(&self, policy: &GradingPolicy) -> Result<GradingPolicy, PalabrasError> {
+        Ok(policy.clone())
+    }
+
+    fn get_conjugation_stats(
+        &self,
+        awesome_id: i32,
+    ) -> Result<Vec<ConjugationStat>, PalabrasError> {
+        Ok(vec![ConjugationStat {
+            awesome_person_id: awesome_id,
+            tense: "presente".to_string(),
+            person: "yo".to_string(),
+            attempts: 4,
+            correct_attempts: 4,
+            updated: chrono::Utc::now(),
+        }])
+    }
+
+    fn record_conjugation_attempt(
+        &self,
+        awesome_id: i32,
+        tense: &str,
+        person: &str,
+        correct: bool,
+    ) -> Result<ConjugationStat, PalabrasError> {
+        Ok(ConjugationStat {
+            awesome_person_id: awesome_id,
+            tense: tense.to_string(),
+            person: person.to_string(),
+            attempts: 1,
+            correct_attempts: correct as i32,
+            updated: chrono::Utc::now(),
+        })
+    }
+
+    fn create_study_session(&self, awesome_id: i32) -> Result<StudySession, PalabrasError> {
+        Ok(StudySession {
+            id: 5,
+            awesome_person_id: awesome_id,
+            started: chrono::Utc::now(),
+            finished: None,
+            num_answers: 0,
+            num_correct: 0,
+        })
+    }
+
+    fn get_study_session(
+        &self,
+        study_session_id: i32,
+    ) -> Result<Option<StudySession>, PalabrasError> {
+        Ok(test_study_session(study_session_id))
+    }
+
+    fn get_study_session_items(
+        &self,
+        study_session_id: i32,
+    ) -> Result<Vec<(StudySessionItem, VocabStudy, Vocab)>, PalabrasError> {
+        Ok(test_study_session_items(study_session_id))
+    }
+
+    fn finish_study_session(&self, study_session_id: i32) -> Result<usize, PalabrasError> {
+        Ok(test_study_session(study_session_id)
+            .map_or(0, |session| session.finished.is_none() as usize))
+    }
+
+    fn count_attempts_since(
+        &self,
+        _awesome_id: i32,
+        _since: DateTime<Utc>,
+    ) -> Result<(i64, i64), PalabrasError> {
+        // 3 new words and 10 reviews answered today
+        Ok((3, 10))
+    }
+
+    fn create_deck(&self, new_deck: &NewDeck) -> Result<Deck, PalabrasError> {
+        Ok(Deck {
+            id: 3,
+            owner_id: new_deck.owner_id,
+            name: new_deck.name.clone(),
+            description: new_deck.description.clone(),
+            known_lang_code: new_deck.known_lang_code.clone(),
+            learning_lang_code: new_deck.learning_lang_code.clone(),
+            created: chrono::Utc::now(),
+        })
+    }
+
+    fn get_deck(&self, deck_id: i32) -> Result<Option<Deck>, PalabrasError> {
+        Ok(test_deck(deck_id))
+    }
+
+    fn get_decks(&self, awesome_id: i32) -> Result<Vec<(Deck, i64)>, PalabrasError> {
+        Ok(test_deck(awesome_id)
+            .map(|deck| (deck, 1))
+            .into_iter()
+            .collect())
+    }
+
+    fn add_vocab_to_deck(&self, _deck_id: i32, vocab_ids: &[i32]) -> Result<usize, PalabrasError> {
+        Ok(vocab_ids.len())
+    }
+
+    fn remove_vocab_from_deck(
+        &self,
+        _deck_id: i32,
+        vocab_ids: &[i32],
+    ) -> Result<usize, PalabrasError> {
+        Ok(vocab_ids.len())
+    }
+
+    fn get_deck_vocab(&self, deck_id: i32) -> Result<Vec<Vocab>, PalabrasError> {
+        let (_, _, vocab, _, _) = create_test_data();
+        Ok(test_deck(deck_id).map(|_| vocab).into_iter().collect())
+    }
 }
 
 // Mock struct for VocabStudyRepository
@@ -146,8 +537,16 @@ pub struct MockVocabStudyRepository {
 
 // Mock implementation of VocabRepository
 impl VocabStudyRepository for MockVocabStudyRepository {
-    fn get_vocab_study_by_id(&self, vocab_id: i32) -> Result<VocabStudy, String> {
-        // Mock behavior: returns our previously setup test data
+    fn get_vocab_study_by_id(&self, vocab_id: i32) -> Result<VocabStudy, PalabrasError> {
+        // Vocab studies from the combo list when the id matches, otherwise the default test vocab study
+        if let Some((listed, _)) = self
+            .combo_list
+            .iter()
+            .find(|(listed, _)| listed.id == vocab_id)
+        {
+            return Ok(listed.clone());
+        }
+
         Ok(VocabStudy {
             id: vocab_id,
             ..self.vocab_study.clone()
@@ -158,7 +557,7 @@ impl VocabStudyRepository for MockVocabStudyRepository {
         &self,
         vocab_id: i32,
         awesome_person_id: i32,
-    ) -> Result<Option<VocabStudy>, String> {
+    ) -> Result<Option<VocabStudy>, PalabrasError> {
         // Mock behavior: Return an Ok result
         Ok(Some(VocabStudy {
             vocab_id,
@@ -171,24 +570,151 @@ impl VocabStudyRepository for MockVocabStudyRepository {
         &self,
         _awesome_person_id: i32,
         _max_words_in_phrase: i32,
-    ) -> Result<Vec<(VocabStudy, Vocab)>, String> {
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
         Ok(self.combo_list.clone()) // returns our test data from mem
     }
 
-    fn create_vocab_study(&self, new_vocab_study: &NewVocabStudy) -> Result<VocabStudy, String> {
+    fn get_deck_study_set(
+        &self,
+        _awesome_person_id: i32,
+        _max_words_in_phrase: i32,
+        _deck_id: i32,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        // The decks hold only the noun
+        Ok(self
+            .combo_list
+            .iter()
+            .filter(|(_, vocab)| vocab.id == 1)
+            .cloned()
+            .collect())
+    }
+
+    fn get_leeches(
+        &self,
+        _awesome_person_id: i32,
+        after: Option<(i32, i32)>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        // The test data as suspended leeches
+        Ok(self
+            .combo_list
+            .iter()
+            .map(|(vocab_study, vocab)| {
+                let leech = VocabStudy {
+                    lapses: 8,
+                    leech: true,
+                    suspended: true,
+                    ..vocab_study.clone()
+                };
+                (leech, vocab.clone())
+            })
+            .filter(|(leech, _)| after.is_none_or(|(_, after_id)| leech.id > after_id))
+            .take(limit as usize)
+            .collect())
+    }
+
+    fn get_enrolled_vocab(
+        &self,
+        _ap_id: i32,
+        filter: &VocabStudyFilter,
+        order: VocabStudyOrder,
+        after: Option<VocabStudyKey>,
+        limit: i64,
+    ) -> Result<Vec<(VocabStudy, Vocab)>, PalabrasError> {
+        // The test data sorted by its keys in the order
+        let mut enrolled: Vec<(VocabStudyKey, (VocabStudy, Vocab))> = self
+            .combo_list
+            .iter()
+            .filter(|(vs, _)| filter.well_known.is_none_or(|known| vs.well_known == known))
+            .filter(|(_, v)| {
+                filter.pos.as_ref().is_none_or(|pos| {
+                    v.pos
+                        .as_ref()
+                        .is_some_and(|v_pos| v_pos.eq_ignore_ascii_case(pos))
+                })
+            })
+            .map(|(vs, v)| (VocabStudyKey::of(order, vs, v), (vs.clone(), v.clone())))
+            .collect();
+        enrolled.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+
+        Ok(enrolled
+            .into_iter()
+            .filter(|(key, _)| {
+                after
+                    .as_ref()
+                    .is_none_or(|after| compare_keys(key, after) == Ordering::Greater)
+            })
+            .take(limit as usize)
+            .map(|(_, pair)| pair)
+            .collect())
+    }
+
+    fn create_vocab_study(
+        &self,
+        new_vocab_study: &NewVocabStudy,
+    ) -> Result<VocabStudy, PalabrasError> {
         let vocab_study = VocabStudy {
             id: 2,
-            vocab_id: new_vocab_study.vocab_id.clone(),
-            awesome_person_id: new_vocab_study.awesome_person_id.clone(),
+            vocab_id: new_vocab_study.vocab_id,
+            awesome_person_id: new_vocab_study.awesome_person_id,
             ..self.vocab_study.clone()
         };
 
         Ok(vocab_study)
     }
 
-    fn update_vocab_study(&self, _updating: VocabStudy) -> Result<usize, String> {
+    fn update_vocab_study(&self, _updating: VocabStudy) -> Result<usize, PalabrasError> {
         Ok(1)
     }
+
+    fn update_user_notes(
+        &self,
+        vocab_study_id: i32,
+        notes: &str,
+    ) -> Result<VocabStudy, PalabrasError> {
+        Ok(VocabStudy {
+            user_notes: Some(notes.to_string()),
+            ..self.get_vocab_study_by_id(vocab_study_id)?
+        })
+    }
+
+    fn update_user_hint(
+        &self,
+        vocab_study_id: i32,
+        hint: &str,
+    ) -> Result<VocabStudy, PalabrasError> {
+        Ok(VocabStudy {
+            user_hint: Some(hint.to_string()),
+            ..self.get_vocab_study_by_id(vocab_study_id)?
+        })
+    }
+
+    fn update_suspended(
+        &self,
+        vocab_study_id: i32,
+        suspended: bool,
+    ) -> Result<VocabStudy, PalabrasError> {
+        Ok(VocabStudy {
+            suspended,
+            ..self.get_vocab_study_by_id(vocab_study_id)?
+        })
+    }
+}
+
+// The order of two keys of the same vocab study order, the same as the db orders them
+fn compare_keys(a: &VocabStudyKey, b: &VocabStudyKey) -> Ordering {
+    match (a, b) {
+        (VocabStudyKey::Weakness(a, a_id), VocabStudyKey::Weakness(b, b_id)) => {
+            a.total_cmp(b).then(a_id.cmp(b_id))
+        }
+        (VocabStudyKey::Recency(a, a_id), VocabStudyKey::Recency(b, b_id)) => {
+            b.cmp(a).then(a_id.cmp(b_id))
+        }
+        (VocabStudyKey::Alphabetic(a, a_id), VocabStudyKey::Alphabetic(b, b_id)) => {
+            a.cmp(b).then(a_id.cmp(b_id))
+        }
+        _ => Ordering::Equal,
+    }
 }
 
 // Mock struct for VocabRepository
@@ -199,7 +725,12 @@ pub struct MockVocabRepository {
 
 // Mock implementation of VocabRepository
 impl VocabRepository for MockVocabRepository {
-    fn get_vocab_by_id(&self, vocab_id: i32) -> Result<Vocab, String> {
+    fn get_vocab_by_id(&self, vocab_id: i32) -> Result<Vocab, PalabrasError> {
+        // Vocabs from the list when the id matches, otherwise the default test vocab
+        if let Some(listed) = self.vocab_list.iter().find(|listed| listed.id == vocab_id) {
+            return Ok(listed.clone());
+        }
+
         Ok(Vocab {
             id: vocab_id,
             ..self.vocab.clone()
@@ -209,7 +740,7 @@ impl VocabRepository for MockVocabRepository {
     fn find_vocab_by_learning_language(
         &self,
         learning_lang_search: String,
-    ) -> Result<Option<Vocab>, String> {
+    ) -> Result<Option<Vocab>, PalabrasError> {
         Ok(Some(Vocab {
             learning_lang: learning_lang_search,
             ..self.vocab.clone()
@@ -219,32 +750,335 @@ impl VocabRepository for MockVocabRepository {
     fn find_vocab_by_alternative(
         &self,
         alternative_search: String,
-    ) -> Result<Option<Vocab>, String> {
-        Ok(Some(Vocab {
-            alternatives: Some(alternative_search),
-            ..self.vocab.clone()
-        }))
+    ) -> Result<Option<Vocab>, PalabrasError> {
+        let found = test_alternatives().iter().any(|alternative| {
+            alternative
+                .alternative
+                .eq_ignore_ascii_case(&alternative_search)
+        });
+
+        Ok(found.then(|| self.vocab.clone()))
+    }
+
+    fn get_alternatives(&self, vocab_id: i32) -> Result<Vec<VocabAlternative>, PalabrasError> {
+        Ok(test_alternatives()
+            .into_iter()
+            .map(|alternative| VocabAlternative {
+                vocab_id,
+                ..alternative
+            })
+            .collect())
+    }
+
+    fn add_alternative(
+        &self,
+        new_alternative: &NewVocabAlternative,
+    ) -> Result<VocabAlternative, PalabrasError> {
+        Ok(VocabAlternative {
+            id: 2,
+            vocab_id: new_alternative.vocab_id,
+            alternative: new_alternative.alternative.clone(),
+            register: new_alternative.register.clone(),
+            lang_variant: new_alternative.lang_variant.clone(),
+        })
+    }
+
+    fn get_tags(&self) -> Result<Vec<(Tag, i64)>, PalabrasError> {
+        Ok([2, 1]
+            .into_iter()
+            .flat_map(test_tags)
+            .map(|tag| (tag, 1))
+            .collect())
     }
 
-    fn get_empty_first_lang(&self, _limit: i64) -> Result<Vec<Vocab>, String> {
+    fn get_vocab_tags(&self, vocab_ids: &[i32]) -> Result<Vec<(i32, Tag)>, PalabrasError> {
+        Ok(vocab_ids
+            .iter()
+            .flat_map(|vocab_id| test_tags(*vocab_id).into_iter().map(|tag| (*vocab_id, tag)))
+            .collect())
+    }
+
+    fn find_vocab_by_tags(
+        &self,
+        tag_names: &[String],
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<Vocab>, PalabrasError> {
+        // The vocab of the list with every tag
+        Ok(self
+            .vocab_list
+            .iter()
+            .filter(|listed| after.is_none_or(|after| listed.id > after))
+            .filter(|listed| {
+                let tags = test_tags(listed.id);
+                !tag_names.is_empty()
+                    && tag_names
+                        .iter()
+                        .all(|name| tags.iter().any(|tag| tag.name == *name))
+            })
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    fn tag_vocab(&self, vocab_ids: &[i32], tag_names: &[String]) -> Result<usize, PalabrasError> {
+        Ok(vocab_ids.len() * tag_names.len())
+    }
+
+    fn untag_vocab(&self, vocab_ids: &[i32], tag_names: &[String]) -> Result<usize, PalabrasError> {
+        Ok(vocab_ids.len() * tag_names.len())
+    }
+
+    fn search_vocab(
+        &self,
+        search: &VocabSearch,
+        after: Option<(f64, String, i32)>,
+        limit: i64,
+    ) -> Result<Vec<VocabMatch>, PalabrasError> {
+        // The vocab of the list starting with the text, awesome person 1 studies vocab 1. Every match ranks 2,
+        // so the matches after the cursor follow it by learning language and id.
+        let text = search.text.trim().to_lowercase();
+        Ok(self
+            .vocab_list
+            .iter()
+            .filter(|listed| {
+                (listed.learning_lang.starts_with(&text) || listed.first_lang.starts_with(&text))
+                    && search.pos.as_ref().is_none_or(|pos| {
+                        listed
+                            .pos
+                            .as_ref()
+                            .is_some_and(|listed_pos| listed_pos.eq_ignore_ascii_case(pos))
+                    })
+            })
+            .filter(|listed| {
+                after.as_ref().is_none_or(|(_, learning, id)| {
+                    (&listed.learning_lang, listed.id) > (learning, *id)
+                })
+            })
+            .take(limit as usize)
+            .map(|listed| VocabMatch {
+                vocab: listed.clone(),
+                rank: 2.0,
+                enrolled: listed.id == 1 && search.awesome_person_id == Some(1),
+            })
+            .collect())
+    }
+
+    fn get_empty_first_lang(&self, _limit: i64) -> Result<Vec<Vocab>, PalabrasError> {
         Ok(vec![Vocab {
             first_lang: "".to_string(),
             ..self.vocab.clone()
         }])
     }
 
-    fn create_vocab(&self, new_vocab: &NewVocab) -> Result<Vocab, String> {
+    fn get_missing_infinitive(&self, _limit: i64) -> Result<Vec<Vocab>, PalabrasError> {
+        Ok(vec![Vocab {
+            infinitive: None,
+            ..self.vocab.clone()
+        }])
+    }
+
+    fn get_missing_gender(&self, _limit: i64) -> Result<Vec<Vocab>, PalabrasError> {
+        Ok(vec![Vocab {
+            gender: None,
+            ..self.vocab.clone()
+        }])
+    }
+
+    fn create_vocab(&self, new_vocab: &NewVocab) -> Result<Vocab, PalabrasError> {
         let vocab = Vocab {
             learning_lang: new_vocab.learning_lang.clone(),
             first_lang: new_vocab.first_lang.clone(),
-            alternatives: new_vocab.alternatives.clone(),
             ..self.vocab.clone()
         };
 
         Ok(vocab)
     }
 
-    fn update_vocab(&self, _updating: Vocab) -> Result<usize, String> {
+    fn update_vocab(&self, _updating: Vocab) -> Result<usize, PalabrasError> {
         Ok(1)
     }
 }
+
+// Mock struct for UnitOfWork, the work is run in memory against the test data
+pub struct MockUnitOfWork {
+    pub vocab_study: VocabStudy,
+    pub vocab: Vocab,
+    pub grading_policy: Option<GradingPolicy>,
+}
+
+impl UnitOfWork for MockUnitOfWork {
+    fn grading(
+        &self,
+        work: &mut dyn FnMut(&mut dyn GradingTransaction) -> Result<(), PalabrasError>,
+    ) -> Result<(), PalabrasError> {
+        work(&mut MockGradingTransaction {
+            vocab_study: self.vocab_study.clone(),
+            vocab: self.vocab.clone(),
+            grading_policy: self.grading_policy.clone(),
+        })
+    }
+
+    fn deck_import(
+        &self,
+        work: &mut dyn FnMut(&mut dyn DeckImportTransaction) -> Result<(), PalabrasError>,
+    ) -> Result<(), PalabrasError> {
+        work(&mut MockDeckImportTransaction {
+            vocab_study: self.vocab_study.clone(),
+            vocab: self.vocab.clone(),
+        })
+    }
+}
+
+pub struct MockGradingTransaction {
+    pub vocab_study: VocabStudy,
+    pub vocab: Vocab,
+    pub grading_policy: Option<GradingPolicy>,
+}
+
+impl GradingTransaction for MockGradingTransaction {
+    fn get_vocab(&mut self, vocab_id: i32) -> Result<Vocab, PalabrasError> {
+        Ok(Vocab {
+            id: vocab_id,
+            ..self.vocab.clone()
+        })
+    }
+
+    fn get_vocab_alternatives(
+        &mut self,
+        vocab_id: i32,
+    ) -> Result<Vec<VocabAlternative>, PalabrasError> {
+        Ok(test_alternatives()
+            .into_iter()
+            .map(|alternative| VocabAlternative {
+                vocab_id,
+                ..alternative
+            })
+            .collect())
+    }
+
+    fn lock_vocab_study(&mut self, vocab_study_id: i32) -> Result<VocabStudy, PalabrasError> {
+        Ok(VocabStudy {
+            id: vocab_study_id,
+            ..self.vocab_study.clone()
+        })
+    }
+
+    fn lock_awesome_person(
+        &mut self,
+        awesome_person_id: i32,
+    ) -> Result<AwesomePerson, PalabrasError> {
+        MockAwesomePersonRepository {
+            grading_policy: self.grading_policy.clone(),
+        }
+        .get_awesome_person_by_id(awesome_person_id)?
+        .ok_or_else(|| PalabrasError::not_found("awesome person", awesome_person_id))
+    }
+
+    fn lock_study_session(&mut self, study_session_id: i32) -> Result<StudySession, PalabrasError> {
+        test_study_session(study_session_id)
+            .ok_or_else(|| PalabrasError::not_found("study session", study_session_id))
+    }
+
+    fn get_study_session_item(
+        &mut self,
+        study_session_id: i32,
+        vocab_study_id: i32,
+    ) -> Result<Option<StudySessionItem>, PalabrasError> {
+        Ok(test_study_session_items(study_session_id)
+            .into_iter()
+            .map(|(item, _, _)| item)
+            .find(|item| item.vocab_study_id == vocab_study_id))
+    }
+
+    fn get_grading_policy(
+        &mut self,
+        _awesome_person_id: i32,
+    ) -> Result<Option<GradingPolicy>, PalabrasError> {
+        Ok(self.grading_policy.clone())
+    }
+
+    fn update_vocab_study(&mut self, updating: VocabStudy) -> Result<usize, PalabrasError> {
+        self.vocab_study = updating;
+        Ok(1)
+    }
+
+    fn update_awesome_person(&mut self, _updating: AwesomePerson) -> Result<usize, PalabrasError> {
+        Ok(1)
+    }
+
+    fn update_study_session(&mut self, _updating: StudySession) -> Result<usize, PalabrasError> {
+        Ok(1)
+    }
+
+    fn save_study_session_item(
+        &mut self,
+        _item: &StudySessionItem,
+    ) -> Result<usize, PalabrasError> {
+        Ok(1)
+    }
+
+    fn record_vocab_attempt(&mut self, _attempt: &NewVocabAttempt) -> Result<usize, PalabrasError> {
+        Ok(1)
+    }
+}
+
+pub struct MockDeckImportTransaction {
+    pub vocab_study: VocabStudy,
+    pub vocab: Vocab,
+}
+
+impl DeckImportTransaction for MockDeckImportTransaction {
+    fn get_deck(&mut self, deck_id: i32) -> Result<Deck, PalabrasError> {
+        test_deck(deck_id).ok_or_else(|| PalabrasError::not_found("deck", deck_id))
+    }
+
+    fn get_vocab(&mut self, vocab_id: i32) -> Result<Vocab, PalabrasError> {
+        Ok(Vocab {
+            id: vocab_id,
+            ..self.vocab.clone()
+        })
+    }
+
+    fn find_vocab_by_learning_language(
+        &mut self,
+        learning: &str,
+    ) -> Result<Option<Vocab>, PalabrasError> {
+        Ok(Some(Vocab {
+            learning_lang: learning.to_string(),
+            ..self.vocab.clone()
+        }))
+    }
+
+    fn create_vocab(&mut self, new_vocab: &NewVocab) -> Result<Vocab, PalabrasError> {
+        Ok(Vocab {
+            learning_lang: new_vocab.learning_lang.clone(),
+            first_lang: new_vocab.first_lang.clone(),
+            ..self.vocab.clone()
+        })
+    }
+
+    fn tag_vocab(
+        &mut self,
+        vocab_ids: &[i32],
+        tag_names: &[String],
+    ) -> Result<usize, PalabrasError> {
+        Ok(vocab_ids.len() * tag_names.len())
+    }
+
+    fn add_vocab_to_deck(
+        &mut self,
+        _deck_id: i32,
+        vocab_ids: &[i32],
+    ) -> Result<usize, PalabrasError> {
+        Ok(vocab_ids.len())
+    }
+
+    fn start_vocab_studies(
+        &mut self,
+        vocab_ids: &[i32],
+        _awesome_person_id: i32,
+    ) -> Result<usize, PalabrasError> {
+        Ok(vocab_ids.len())
+    }
+}
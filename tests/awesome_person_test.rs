@@ -1,7 +1,10 @@
+use chrono::Utc;
 use dotenv::dotenv;
 use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
 use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
-use palabras::models::{AwesomePerson, NewAwesomePerson};
+use palabras::error::PalabrasError;
+use palabras::models::{AwesomePerson, GradingPolicy, NewAwesomePerson};
+use palabras::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
 use rand::Rng;
 use std::env;
 
@@ -70,7 +73,7 @@ fn test_create_awesome_person() {
         name: Some(test_name.clone()),
         sec_code,
         max_learning_words: 2,
-
+        preferred_variant: Some("es-MX".to_string()),
         ..Default::default()
     };
 
@@ -95,6 +98,7 @@ fn test_create_awesome_person() {
         "Awesome person ids mismatched, expected {}, actual {}",
         created.id, found.id
     );
+    assert_eq!(found.preferred_variant, Some("es-MX".to_string()));
 
     let found = repo
         .get_awesome_person_by_code(created.sec_code)
@@ -106,3 +110,69 @@ fn test_create_awesome_person() {
         created.id, found.id
     );
 }
+
+#[test]
+fn test_unknown_code_kept_out_of_error() {
+    dotenv().ok(); // Load environment variables from .env file
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let look_up_code = format!("unknown-code{}", unique_num);
+
+    let Err(err) = VocabFuzzyMatch::instance().get_awesome_person_by_code(look_up_code.clone())
+    else {
+        panic!("An unknown code should not be found");
+    };
+    assert!(matches!(err, PalabrasError::NotFound { .. }));
+    assert!(
+        !err.to_string().contains(&look_up_code),
+        "The code should not reach clients, found: {}",
+        err
+    );
+}
+
+#[test]
+fn test_save_grading_policy() {
+    dotenv().ok(); // Load environment variables from .env file
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+    let repo = DbAwesomePersonRepository;
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+
+    let created = repo
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("policy-code{}", unique_num),
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+    let found = repo
+        .get_grading_policy(created.id)
+        .expect("Should look up the grading policy");
+    assert!(found.is_none(), "A new awesome person uses the default");
+
+    let policy = GradingPolicy {
+        awesome_person_id: created.id,
+        max_distance: 5,
+        well_known_threshold: 0.9,
+        close_distance: 1,
+        perfect_weight: 1.5,
+        updated: Utc::now(),
+    };
+    repo.save_grading_policy(&policy)
+        .expect("Grading policy should have been saved");
+
+    // Saving again replaces the stored policy
+    let saved = repo
+        .save_grading_policy(&GradingPolicy {
+            max_distance: 8,
+            ..policy
+        })
+        .expect("Grading policy should have been replaced");
+    let found = repo
+        .get_grading_policy(created.id)
+        .expect("Should look up the grading policy")
+        .expect("Grading policy should exist");
+    assert_eq!(found.max_distance, 8);
+    assert_eq!(found.close_distance, saved.close_distance);
+    assert_eq!(found.well_known_threshold, 0.9);
+}
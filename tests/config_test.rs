@@ -1,4 +1,7 @@
+use palabras::config::app_config::AppConfig;
 use palabras::config::load_translations_config;
+use palabras::error::PalabrasError;
+use std::env;
 
 #[test]
 fn test_load_translation_config() {
@@ -6,8 +9,86 @@ fn test_load_translation_config() {
         load_translations_config().expect("JSON file should have deserialized to a Vec of configs")
     {
         assert!(
-            configs.len() > 0,
+            !configs.is_empty(),
             "Expected at least one config object to load"
         );
     }
 }
+
+#[test]
+fn test_load_app_config_layers() {
+    let args = vec![
+        "--config".to_string(),
+        "tests/data/config/palabras.json".to_string(),
+        "--set".to_string(),
+        "database.pool_size=6".to_string(),
+        "export.csv".to_string(),
+    ];
+    env::set_var("PAL_GRADING_CLOSE_DISTANCE", "4");
+    let (config, remaining) = AppConfig::load(args).expect("Config should have loaded");
+    env::remove_var("PAL_GRADING_CLOSE_DISTANCE");
+
+    // From the file
+    assert_eq!(config.server.addr, "127.0.0.1:4000");
+    assert_eq!(config.database.secret_provider, Some("file".to_string()));
+    assert_eq!(config.grading.max_distance, 12);
+    // From the env var
+    assert_eq!(config.grading.close_distance, 4);
+    // The command line wins over the file
+    assert_eq!(config.database.pool_size, 6);
+    // Untouched settings keep their defaults
+    assert_eq!(config.files.language_packs, "lang");
+    assert_eq!(remaining, vec!["export.csv".to_string()]);
+}
+
+#[test]
+fn test_load_app_config_errors() {
+    let result = AppConfig::load(vec!["--config=tests/data/config/invalid.toml".to_string()]);
+    match result {
+        Err(PalabrasError::Config(msg)) => {
+            assert!(msg.contains("server.addr"), "Unexpected message {}", msg);
+            assert!(
+                msg.contains("database.pool_size"),
+                "Unexpected message {}",
+                msg
+            );
+        }
+        other => panic!("Expected a config error, got {:?}", other.map(|_| ())),
+    }
+
+    let result = AppConfig::load(vec!["--set".to_string(), "server.port=80".to_string()]);
+    assert!(
+        matches!(&result, Err(PalabrasError::Config(msg)) if msg.contains("server.port")),
+        "Unknown settings should be rejected"
+    );
+
+    let result = AppConfig::load(vec![
+        "--set".to_string(),
+        "grading.perfect_weight=heavy".to_string(),
+    ]);
+    assert!(
+        matches!(&result, Err(PalabrasError::Config(msg)) if msg.contains("grading.perfect_weight")),
+        "Unparsable values should be rejected"
+    );
+
+    let result = AppConfig::load(vec![
+        "--set".to_string(),
+        "leech.min_percentage=2".to_string(),
+    ]);
+    assert!(
+        matches!(&result, Err(PalabrasError::Config(msg)) if msg.contains("leech.min_percentage")),
+        "Invalid leech rules should be rejected"
+    );
+
+    let result = AppConfig::load(vec![
+        "--set".to_string(),
+        "composer.phrase_ratio=0".to_string(),
+    ]);
+    assert!(
+        matches!(&result, Err(PalabrasError::Config(msg)) if msg.contains("composer.phrase_ratio")),
+        "A ratio leaving a group out should be rejected"
+    );
+
+    let result = AppConfig::from_toml_str("[server]\nport = 80\n");
+    assert!(matches!(result, Err(PalabrasError::Config(_))));
+}
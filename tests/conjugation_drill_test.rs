@@ -0,0 +1,108 @@
+use dotenv::dotenv;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::error::PalabrasError;
+use palabras::models::{NewAwesomePerson, NewVocab};
+use palabras::sl::conjugation::{Person, Tense};
+use palabras::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use palabras::sl::sync_vocab::create_vocab_study;
+use rand::Rng;
+use std::env;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_conjugation_drills() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let awesome_person = DbAwesomePersonRepository
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("drill-code{}", unique_num),
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    let verb = DbVocabRepository
+        .create_vocab(&NewVocab {
+            learning_lang: format!("aprendimos{}", unique_num),
+            first_lang: "we learned".to_string(),
+            infinitive: Some("aprender".to_string()),
+            pos: Some("Verb".to_string()),
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created");
+    create_vocab_study(verb.id, awesome_person.id, 0.0)
+        .expect("New vocab study should have been created");
+
+    let service = VocabFuzzyMatch::instance();
+    let (vocab_study, vocab, drill) = service
+        .get_conjugation_drills(awesome_person.id, 5)
+        .expect("Drills should have loaded")
+        .pop()
+        .expect("The verb should be drilled");
+    assert_eq!(vocab.id, verb.id);
+    assert_eq!((drill.person, drill.tense), (Person::Yo, Tense::Presente));
+    assert_eq!(drill.prompt(), "aprender — yo, presente");
+
+    let prompt = service
+        .check_conjugation(
+            vocab.id,
+            vocab_study.id,
+            drill.person,
+            drill.tense,
+            "aprendo".to_string(),
+        )
+        .expect("Grading should have worked");
+    assert_eq!(prompt, "Perfect Match!");
+    for _ in 0..2 {
+        service
+            .check_conjugation(
+                vocab.id,
+                vocab_study.id,
+                Person::Nosotros,
+                Tense::PresenteSubjuntivo,
+                "aprendemos".to_string(),
+            )
+            .expect("Grading should have worked");
+    }
+
+    let stats = service
+        .get_conjugation_stats(awesome_person.id)
+        .expect("Stats should have loaded");
+    assert_eq!(stats.len(), 2);
+    assert_eq!(
+        (stats[0].tense.as_str(), stats[0].person.as_str()),
+        ("presente", "yo")
+    );
+    assert_eq!(stats[0].percentage_correct(), 1.0);
+    assert_eq!(
+        (stats[1].tense.as_str(), stats[1].person.as_str()),
+        ("presente_subjuntivo", "nosotros")
+    );
+    assert_eq!((stats[1].attempts, stats[1].correct_attempts), (2, 0));
+
+    // Presente yo has been drilled, so the next drill moves on
+    let (_, _, drill) = service
+        .get_conjugation_drills(awesome_person.id, 5)
+        .expect("Drills should have loaded")
+        .pop()
+        .expect("The verb should be drilled");
+    assert_eq!((drill.person, drill.tense), (Person::Tu, Tense::Presente));
+
+    let result = DbAwesomePersonRepository.record_conjugation_attempt(
+        -1,
+        Tense::Presente.key(),
+        Person::Yo.key(),
+        true,
+    );
+    assert!(matches!(result, Err(PalabrasError::Conflict(_))));
+}
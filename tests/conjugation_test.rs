@@ -0,0 +1,198 @@
+use palabras::sl::conjugation::{conjugate, conjugations, identify, verb_hint, Person, Tense};
+
+fn forms(infinitive: &str, tense: Tense) -> Vec<String> {
+    Person::ALL
+        .iter()
+        .map(|person| conjugate(infinitive, *person, tense).expect("Should be a verb"))
+        .collect()
+}
+
+#[test]
+fn test_conjugate_regular_verbs() {
+    assert_eq!(
+        forms("hablar", Tense::Presente),
+        ["hablo", "hablas", "habla", "hablamos", "habláis", "hablan"]
+    );
+    assert_eq!(
+        forms("comer", Tense::Preterito),
+        [
+            "comí",
+            "comiste",
+            "comió",
+            "comimos",
+            "comisteis",
+            "comieron"
+        ]
+    );
+    assert_eq!(
+        forms("vivir", Tense::Imperfecto),
+        ["vivía", "vivías", "vivía", "vivíamos", "vivíais", "vivían"]
+    );
+    assert_eq!(
+        forms("trabajar", Tense::Futuro),
+        [
+            "trabajaré",
+            "trabajarás",
+            "trabajará",
+            "trabajaremos",
+            "trabajaréis",
+            "trabajarán"
+        ]
+    );
+    assert_eq!(
+        forms("aprender", Tense::Condicional),
+        [
+            "aprendería",
+            "aprenderías",
+            "aprendería",
+            "aprenderíamos",
+            "aprenderíais",
+            "aprenderían"
+        ]
+    );
+    assert_eq!(
+        forms("escribir", Tense::PresenteSubjuntivo),
+        [
+            "escriba",
+            "escribas",
+            "escriba",
+            "escribamos",
+            "escribáis",
+            "escriban"
+        ]
+    );
+    assert_eq!(conjugations("estudiar").len(), 36);
+    assert!(conjugations("gato").is_empty());
+}
+
+#[test]
+fn test_conjugate_spelling_changes() {
+    assert_eq!(
+        conjugate("buscar", Person::Yo, Tense::Preterito),
+        Some("busqué".to_string())
+    );
+    assert_eq!(
+        conjugate("llegar", Person::Yo, Tense::Preterito),
+        Some("llegué".to_string())
+    );
+    assert_eq!(
+        conjugate("coger", Person::Yo, Tense::Presente),
+        Some("cojo".to_string())
+    );
+    assert_eq!(
+        forms("empezar", Tense::PresenteSubjuntivo),
+        [
+            "empiece",
+            "empieces",
+            "empiece",
+            "empecemos",
+            "empecéis",
+            "empiecen"
+        ]
+    );
+    assert_eq!(
+        conjugate("jugar", Person::Yo, Tense::Preterito),
+        Some("jugué".to_string())
+    );
+}
+
+#[test]
+fn test_conjugate_irregular_verbs() {
+    assert_eq!(
+        forms("ser", Tense::Presente),
+        ["soy", "eres", "es", "somos", "sois", "son"]
+    );
+    assert_eq!(
+        forms("tener", Tense::Presente),
+        ["tengo", "tienes", "tiene", "tenemos", "tenéis", "tienen"]
+    );
+    assert_eq!(
+        forms("decir", Tense::Preterito),
+        ["dije", "dijiste", "dijo", "dijimos", "dijisteis", "dijeron"]
+    );
+    assert_eq!(
+        forms("salir", Tense::PresenteSubjuntivo),
+        ["salga", "salgas", "salga", "salgamos", "salgáis", "salgan"]
+    );
+    assert_eq!(
+        forms("dormir", Tense::PresenteSubjuntivo),
+        ["duerma", "duermas", "duerma", "durmamos", "durmáis", "duerman"]
+    );
+    assert_eq!(
+        forms("pedir", Tense::Preterito),
+        [
+            "pedí",
+            "pediste",
+            "pidió",
+            "pedimos",
+            "pedisteis",
+            "pidieron"
+        ]
+    );
+    assert_eq!(
+        forms("seguir", Tense::Presente),
+        ["sigo", "sigues", "sigue", "seguimos", "seguís", "siguen"]
+    );
+    assert_eq!(
+        forms("jugar", Tense::Presente),
+        ["juego", "juegas", "juega", "jugamos", "jugáis", "juegan"]
+    );
+    assert_eq!(
+        conjugate("hacer", Person::Nosotros, Tense::Futuro),
+        Some("haremos".to_string())
+    );
+    assert_eq!(
+        conjugate("ir", Person::Nosotros, Tense::Imperfecto),
+        Some("íbamos".to_string())
+    );
+    assert_eq!(
+        conjugate("ver", Person::Yo, Tense::PresenteSubjuntivo),
+        Some("vea".to_string())
+    );
+}
+
+#[test]
+fn test_identify_verb_forms() {
+    let salgo = identify("salgo");
+    assert_eq!(salgo[0].infinitive, "salir");
+    assert_eq!(salgo[0].person, Person::Yo);
+    assert_eq!(salgo[0].tense, Tense::Presente);
+
+    // fui is a form of both ser and ir
+    let fui = identify("Fui");
+    let fui: Vec<&str> = fui.iter().map(|form| form.infinitive.as_str()).collect();
+    assert!(fui.contains(&"ser") && fui.contains(&"ir"), "{:?}", fui);
+
+    let busque = identify("busqué");
+    assert_eq!(busque[0].infinitive, "buscar");
+    assert_eq!(busque[0].person, Person::Yo);
+    assert_eq!(busque[0].tense, Tense::Preterito);
+
+    let duermen = identify("duermen");
+    assert_eq!(duermen[0].infinitive, "dormir");
+    assert_eq!(duermen[0].person, Person::Ellos);
+
+    // Regular forms of verbs the engine has never seen are still recognised
+    let bloguearon = identify("bloguearon");
+    assert_eq!(bloguearon[0].infinitive, "bloguear");
+    assert_eq!(bloguearon[0].tense, Tense::Preterito);
+
+    assert!(identify("casa")
+        .iter()
+        .all(|form| form.infinitive != "salir"));
+    assert!(identify("xyz").is_empty());
+}
+
+#[test]
+fn test_verb_hint() {
+    assert_eq!(verb_hint("tengo"), Some("tener, yo, presente".to_string()));
+    assert_eq!(
+        verb_hint("hablaba"),
+        Some("hablar, yo or él/ella/usted, imperfecto".to_string())
+    );
+    assert_eq!(
+        verb_hint("dijeron"),
+        Some("decir, ellos/ellas/ustedes, pretérito".to_string())
+    );
+    assert_eq!(verb_hint("hablar"), None);
+}
@@ -0,0 +1,85 @@
+use dotenv::dotenv;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::models::{NewAwesomePerson, NewVocab};
+use palabras::sl::daily_quota::{DailyLimits, DailyQuotaService};
+use palabras::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use palabras::sl::sync_vocab::create_vocab_study;
+use rand::Rng;
+use std::env;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_daily_limits() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let awesome_person = DbAwesomePersonRepository
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("daily-code{}", unique_num),
+            max_learning_words: 5,
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    for word in ["lunes", "martes", "miércoles"] {
+        let vocab = DbVocabRepository
+            .create_vocab(&NewVocab {
+                learning_lang: format!("{}{}", word, unique_num),
+                first_lang: format!("day {}", word),
+                known_lang_code: "en".to_string(),
+                learning_lang_code: "es".to_string(),
+                ..Default::default()
+            })
+            .expect("New vocab should have been created");
+        create_vocab_study(vocab.id, awesome_person.id, 0.0)
+            .expect("New vocab study should have been created");
+    }
+
+    let service = VocabFuzzyMatch::instance();
+    let quota_service = DailyQuotaService::instance();
+    let quota = quota_service
+        .get_daily_quota(awesome_person.id)
+        .expect("Daily quota should have loaded");
+    assert_eq!((quota.new_today, quota.reviews_today), (0, 0));
+    assert_eq!(quota.new_remaining(), 20, "The default limit of new words");
+
+    // One new word and no reviews a day
+    quota_service
+        .update_daily_limits(awesome_person.id, 1, 0)
+        .expect("Daily limits should have been updated");
+    let studies = service
+        .get_vocab_to_learn(awesome_person.id, 10)
+        .expect("Study list should have loaded");
+    assert_eq!(studies.len(), 1);
+
+    let (vocab_study, vocab) = &studies[0];
+    service
+        .check_response(vocab.id, vocab_study.id, "no idea".to_string())
+        .expect("Response should have been checked");
+    let quota = quota_service
+        .get_daily_quota(awesome_person.id)
+        .expect("Daily quota should have loaded");
+    assert_eq!((quota.new_today, quota.reviews_today), (1, 0));
+    let studies = service
+        .get_vocab_to_learn(awesome_person.id, 10)
+        .expect("Study list should have loaded");
+    assert!(studies.is_empty(), "Today's new word was introduced");
+
+    // With reviews allowed, the word answered today is reviewed but no other new word is introduced
+    quota_service
+        .update_daily_limits(awesome_person.id, 1, 5)
+        .expect("Daily limits should have been updated");
+    let studies = service
+        .get_vocab_to_learn(awesome_person.id, 10)
+        .expect("Study list should have loaded");
+    assert_eq!(studies.len(), 1);
+    assert_eq!(studies[0].0.id, vocab_study.id);
+}
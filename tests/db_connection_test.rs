@@ -0,0 +1,47 @@
+use diesel::{sql_query, RunQueryDsl};
+use dotenv::dotenv;
+use palabras::dal::db_connection::{
+    establish_connection_pool_with_config, get_connection, query_check, run_blocking,
+    verify_connection_migrate_db, DbPoolConfig,
+};
+use palabras::error::PalabrasError;
+use std::env;
+use std::time::Duration;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[tokio::test]
+async fn test_run_blocking_with_pool_config() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    let pool_config = DbPoolConfig {
+        max_size: 2,
+        connection_timeout: Duration::from_secs(5),
+        statement_timeout: Some(Duration::from_millis(500)),
+        ..Default::default()
+    };
+    establish_connection_pool_with_config(get_test_db_url(), &pool_config);
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    run_blocking(|| {
+        let mut conn = get_connection()?;
+        query_check(&mut conn).map_err(PalabrasError::from)
+    })
+    .await
+    .expect("A simple query should run on the blocking pool");
+
+    // Queries running longer than the statement timeout are cancelled by the database.
+    let slow = run_blocking(|| {
+        let mut conn = get_connection()?;
+        sql_query("SELECT pg_sleep(2)")
+            .execute(&mut conn)
+            .map_err(PalabrasError::from)
+    })
+    .await;
+    assert!(
+        matches!(slow, Err(PalabrasError::Database(_))),
+        "Expected the slow query to be cancelled by the statement timeout"
+    );
+}
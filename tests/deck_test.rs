@@ -0,0 +1,203 @@
+use dotenv::dotenv;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::error::PalabrasError;
+use palabras::models::{NewAwesomePerson, NewDeck, NewVocab, Vocab, VocabStudy};
+use palabras::sl::decks::{DeckService, ManageDecks};
+use palabras::sl::fuzzy_match_vocab::{LearnVocab, StudyFilter, VocabFuzzyMatch};
+use palabras::sl::sync_vocab::{create_vocab_study, export_deck, import_deck};
+use rand::Rng;
+use std::{env, fs};
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_study_import_and_export_deck() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let awesome_person = DbAwesomePersonRepository
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("deck-code{}", unique_num),
+            max_learning_words: 5,
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    let new_vocab = |learning: &str, first: &str| NewVocab {
+        learning_lang: format!("{}{}", learning, unique_num),
+        first_lang: first.to_string(),
+        known_lang_code: "en".to_string(),
+        learning_lang_code: "es".to_string(),
+        ..Default::default()
+    };
+    let cuchara = DbVocabRepository
+        .create_vocab(&new_vocab("cuchara", "spoon, for soup"))
+        .expect("New vocab should have been created");
+    let playa = DbVocabRepository
+        .create_vocab(&new_vocab("playa", "beach"))
+        .expect("New vocab should have been created");
+    create_vocab_study(playa.id, awesome_person.id, 0.0)
+        .expect("New vocab study should have been created");
+
+    let service = VocabFuzzyMatch::instance();
+    let deck_service = DeckService::instance();
+    let kitchen = NewDeck {
+        owner_id: awesome_person.id,
+        name: "Kitchen".to_string(),
+        description: Some("Things in the kitchen".to_string()),
+        known_lang_code: "en".to_string(),
+        learning_lang_code: "es".to_string(),
+    };
+    let deck = deck_service
+        .create_deck(kitchen.clone())
+        .expect("New deck should have been created");
+    let result = deck_service.create_deck(kitchen);
+    assert!(
+        matches!(result, Err(PalabrasError::Conflict(_))),
+        "Deck names are unique for their owner"
+    );
+
+    // The owner starts studying the vocab added
+    let num_added = deck_service
+        .add_to_deck(deck.id, &[cuchara.id, cuchara.id])
+        .expect("Vocab should have been added");
+    assert_eq!(num_added, 1);
+    let studying = StudyFilter {
+        deck_id: Some(deck.id),
+        ..Default::default()
+    };
+    let study_list = service
+        .get_filtered_vocab_to_learn(awesome_person.id, 10, &studying)
+        .expect("Study list should have loaded");
+    assert_eq!(study_list.len(), 1);
+    assert_eq!(study_list[0].1.id, cuchara.id);
+
+    let decks = deck_service
+        .get_decks(awesome_person.id)
+        .expect("Decks should have loaded");
+    assert_eq!(decks, vec![(deck.clone(), 1)]);
+
+    // Exporting and importing the deck into another deck keeps its vocab
+    let export_file = env::temp_dir().join(format!("deck_{}.csv", unique_num));
+    let export_file = export_file.to_str().expect("Temp path should be valid");
+    let num_exported =
+        export_deck(deck.id, &[], export_file).expect("Deck should have been exported");
+    assert_eq!(num_exported, 1);
+    let exported = fs::read_to_string(export_file).expect("Export should have been written");
+    assert!(exported.contains("\"spoon, for soup\""), "{}", exported);
+
+    let copy = deck_service
+        .create_deck(NewDeck {
+            owner_id: awesome_person.id,
+            name: "Kitchen copy".to_string(),
+            description: None,
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+        })
+        .expect("New deck should have been created");
+    fs::write(
+        export_file,
+        format!("{}tenedor{},,noun,Kitchen,fork\n", exported, unique_num),
+    )
+    .expect("Import should have been written");
+    let num_imported = import_deck(copy.id, export_file).expect("Deck should have been imported");
+    fs::remove_file(export_file).ok();
+    assert_eq!(num_imported, 2);
+
+    let (_, vocabs) = deck_service
+        .get_deck_vocab(copy.id)
+        .expect("Deck vocab should have loaded");
+    assert_eq!(vocabs[0].id, cuchara.id, "Existing vocab is reused");
+    assert_eq!(vocabs[1].first_lang, "fork");
+    assert_eq!(vocabs[1].learning_lang_code, "es");
+
+    // A deck is imported whole or not at all, the vocab in other languages fails the import
+    let cuisine = deck_service
+        .create_deck(NewDeck {
+            owner_id: awesome_person.id,
+            name: "Cuisine".to_string(),
+            description: None,
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "fr".to_string(),
+        })
+        .expect("New deck should have been created");
+    let (header, cuchara_line) = exported
+        .split_once('\n')
+        .expect("Export should have a header");
+    fs::write(
+        export_file,
+        format!(
+            "{}\ncouteau{},,noun,Kitchen,knife\n{}",
+            header, unique_num, cuchara_line
+        ),
+    )
+    .expect("Import should have been written");
+    let result = import_deck(cuisine.id, export_file);
+    fs::remove_file(export_file).ok();
+    assert!(
+        matches!(result, Err(PalabrasError::Validation(_))),
+        "Spanish vocab is not added to a French deck"
+    );
+    let couteau = DbVocabRepository
+        .find_vocab_by_learning_language(format!("couteau{}", unique_num))
+        .expect("Vocab should have been searched");
+    assert!(
+        couteau.is_none(),
+        "The vocab imported before is rolled back"
+    );
+
+    // Removing vocab keeps it, only the deck is smaller
+    let num_removed = deck_service
+        .remove_from_deck(copy.id, &[cuchara.id])
+        .expect("Vocab should have been removed");
+    assert_eq!(num_removed, 1);
+    let study_list = service
+        .get_filtered_vocab_to_learn(
+            awesome_person.id,
+            10,
+            &StudyFilter {
+                deck_id: Some(copy.id),
+                ..Default::default()
+            },
+        )
+        .expect("Study list should have loaded");
+    assert_eq!(study_list.len(), 1);
+    assert_ne!(study_list[0].1.id, cuchara.id);
+
+    // The owner keeps the vocab study of vocab they were already studying
+    let playa_study = |study_list: Vec<(VocabStudy, Vocab)>| {
+        study_list
+            .into_iter()
+            .find(|(_, vocab)| vocab.id == playa.id)
+            .map(|(study, _)| study.id)
+    };
+    let studied = playa_study(
+        service
+            .get_vocab_to_learn(awesome_person.id, 10)
+            .expect("Study list should have loaded"),
+    );
+    assert!(studied.is_some());
+    let num_added = deck_service
+        .add_to_deck(copy.id, &[playa.id])
+        .expect("Vocab should have been added");
+    assert_eq!(num_added, 1);
+    let study_list = service
+        .get_filtered_vocab_to_learn(
+            awesome_person.id,
+            10,
+            &StudyFilter {
+                deck_id: Some(copy.id),
+                ..Default::default()
+            },
+        )
+        .expect("Study list should have loaded");
+    assert_eq!(study_list.len(), 2);
+    assert_eq!(playa_study(study_list), studied);
+}
@@ -16,7 +16,7 @@ fn test_csv_parsing_with_commas() {
         find_first_lang_translations(&config).expect("Expected a hash map of translations");
 
     assert!(
-        learning_to_first_lang_map.len() > 0,
+        !learning_to_first_lang_map.is_empty(),
         "Expected map to have at least one translation."
     );
 
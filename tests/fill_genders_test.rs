@@ -0,0 +1,72 @@
+use dotenv::dotenv;
+use palabras::config::TranslationsConfig;
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::models::NewVocab;
+use palabras::sl::sync_vocab::fill_missing_genders;
+use rand::Rng;
+use std::env;
+use std::fs;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_fill_genders() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+    let repo = DbVocabRepository;
+
+    // learning_lang is unique, so each run makes up nouns that are not in the database
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let noun = format!("palabrita{}", unique_num);
+    let verb = format!("palabrear{}", unique_num);
+
+    let dictionary = env::temp_dir().join(format!("genders_{}.xml", unique_num));
+    fs::write(
+        &dictionary,
+        format!(
+            "<w>\n<c>{}</c>\n<d>to make up</d>\n<t>{{v}}</t>\n</w>\n\
+             <w>\n<c>{}</c>\n<d>a made up word</d>\n<t>{{f}}</t>\n</w>\n",
+            verb, noun
+        ),
+    )
+    .expect("The test dictionary should have been written");
+
+    let create = |learning_lang: &str| {
+        repo.create_vocab(&NewVocab {
+            learning_lang: learning_lang.to_string(),
+            first_lang: "made up".to_string(),
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created")
+    };
+    let noun_vocab = create(&noun);
+    let verb_vocab = create(&verb);
+
+    let config = TranslationsConfig {
+        file_name: dictionary.to_string_lossy().to_string(),
+        learning_regex: Some("<c>([^<]+)</c>".to_string()),
+        gender_regex: Some("<t>\\{([mf])\\}".to_string()),
+        ..Default::default()
+    };
+    let num_updated =
+        fill_missing_genders(Some(vec![config])).expect("Filling genders should have worked");
+    fs::remove_file(&dictionary).ok();
+    assert!(num_updated >= 1, "Expected at least one vocab to be filled");
+
+    let noun_vocab = repo
+        .get_vocab_by_id(noun_vocab.id)
+        .expect("Should find the noun");
+    assert_eq!(noun_vocab.gender, Some("f".to_string()));
+    // The verb's {v} is not a gender
+    let verb_vocab = repo
+        .get_vocab_by_id(verb_vocab.id)
+        .expect("Should find the verb");
+    assert_eq!(verb_vocab.gender.unwrap_or_default(), "");
+}
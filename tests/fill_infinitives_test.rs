@@ -0,0 +1,87 @@
+use dotenv::dotenv;
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::models::NewVocab;
+use palabras::sl::sync_vocab::{fill_missing_infinitives, import_vocab};
+use rand::Rng;
+use std::env;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+// learning_lang is unique, so each run makes up a regular -ar verb that is not in the database.
+fn unique_stem() -> String {
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let consonants: Vec<char> = unique_num
+        .to_string()
+        .chars()
+        .map(|digit| {
+            "bdflmnprst"
+                .chars()
+                .nth(digit as usize - '0' as usize)
+                .unwrap()
+        })
+        .collect();
+
+    format!("pal{}t", consonants.into_iter().collect::<String>())
+}
+
+#[test]
+fn test_import_and_fill_infinitives() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+    let repo = DbVocabRepository;
+
+    let stem = unique_stem();
+    let imported = import_vocab(NewVocab {
+        learning_lang: format!("{}é", stem),
+        first_lang: "I made up".to_string(),
+        pos: Some("Verb".to_string()),
+        known_lang_code: "en".to_string(),
+        learning_lang_code: "es".to_string(),
+        ..Default::default()
+    })
+    .expect("New vocab should have been imported");
+    assert_eq!(imported.infinitive, Some(format!("{}ar", stem)));
+    assert_eq!(imported.hint, Some(format!("{}ar, yo, pretérito", stem)));
+
+    // Vocabs created without the analysis, as earlier imports were
+    let stem = unique_stem();
+    let verb = repo
+        .create_vocab(&NewVocab {
+            learning_lang: format!("{}aron", stem),
+            first_lang: "they made up".to_string(),
+            pos: Some("Verb".to_string()),
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created");
+    let noun = repo
+        .create_vocab(&NewVocab {
+            learning_lang: format!("{}o", stem),
+            first_lang: "a made up thing".to_string(),
+            pos: Some("Noun".to_string()),
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created");
+
+    let num_updated = fill_missing_infinitives().expect("Filling infinitives should have worked");
+    assert!(num_updated >= 1, "Expected at least one vocab to be filled");
+
+    let verb = repo.get_vocab_by_id(verb.id).expect("Should find the verb");
+    assert_eq!(verb.infinitive, Some(format!("{}ar", stem)));
+    assert_eq!(
+        verb.hint,
+        Some(format!("{}ar, ellos/ellas/ustedes, pretérito", stem))
+    );
+    // Not a verb, even though it looks like one
+    let noun = repo.get_vocab_by_id(noun.id).expect("Should find the noun");
+    assert_eq!(noun.infinitive.unwrap_or_default(), "");
+    assert_eq!(noun.hint.unwrap_or_default(), "");
+}
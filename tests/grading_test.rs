@@ -0,0 +1,85 @@
+use dotenv::dotenv;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::models::{NewAwesomePerson, NewVocab};
+use palabras::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use palabras::sl::sync_vocab::create_vocab_study;
+use rand::Rng;
+use std::env;
+use std::thread;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_concurrent_check_response() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let awesome_person = DbAwesomePersonRepository
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("grading-code{}", unique_num),
+            max_learning_words: 5,
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    let vocab = DbVocabRepository
+        .create_vocab(&NewVocab {
+            learning_lang: format!("calificar {}", unique_num),
+            first_lang: format!("to grade {}", unique_num),
+            num_learning_words: 2,
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created");
+
+    create_vocab_study(vocab.id, awesome_person.id, 0.0)
+        .expect("New vocab study should have been created");
+    let (vocab_study, _) = VocabFuzzyMatch::instance()
+        .get_vocab_to_learn(awesome_person.id, 1)
+        .expect("Study list should have loaded")
+        .pop()
+        .expect("The new vocab study should be in the study list");
+
+    // Answer the same vocab from several threads at once, half right and half wrong.
+    let num_answers = 8;
+    let handles: Vec<_> = (0..num_answers)
+        .map(|i| {
+            let response = if i % 2 == 0 {
+                vocab.learning_lang.clone()
+            } else {
+                "no idea".to_string()
+            };
+            let (vocab_id, vocab_study_id) = (vocab.id, vocab_study.id);
+            thread::spawn(move || {
+                VocabFuzzyMatch::instance().check_response(vocab_id, vocab_study_id, response)
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle
+            .join()
+            .expect("Grading thread should not panic")
+            .expect("Grading should have worked");
+    }
+
+    // No answer should have been lost to a concurrent update.
+    let (graded_study, _) = VocabFuzzyMatch::instance()
+        .get_vocab_stats(vocab_study.id)
+        .expect("Vocab stats should have loaded");
+    assert_eq!(graded_study.attempts, Some(num_answers));
+    assert_eq!(graded_study.correct_attempts, Some(num_answers / 2));
+
+    let graded_person = VocabFuzzyMatch::instance()
+        .get_awesome_person(awesome_person.id)
+        .expect("Awesome person should have loaded");
+    assert_eq!(graded_person.num_correct, Some(num_answers / 2));
+    assert_eq!(graded_person.num_incorrect, Some(num_answers / 2));
+}
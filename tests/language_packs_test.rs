@@ -0,0 +1,108 @@
+use palabras::config::LanguagePackRegistry;
+use palabras::error::PalabrasError;
+use palabras::models::Vocab;
+use palabras::sl::sync_vocab::{determine_hint, merge_learning};
+
+fn shipped_packs() -> LanguagePackRegistry {
+    LanguagePackRegistry::load_from_dir("lang").expect("The shipped language packs should load")
+}
+
+#[test]
+fn test_load_shipped_language_packs() {
+    let packs = shipped_packs();
+    assert_eq!(packs.lang_codes(), vec!["es", "fr", "pt"]);
+
+    let result = LanguagePackRegistry::load_from_dir("tests/data/missing_lang");
+    assert!(matches!(result, Err(PalabrasError::Io(_))));
+}
+
+#[test]
+fn test_hints_use_the_learning_language_pack() {
+    let packs = shipped_packs();
+
+    let (hint, num_words) = determine_hint(&packs, "es", "ella se acuerda");
+    assert_eq!(
+        hint.unwrap_or_default(),
+        "phrase, subject pronoun, reflexive pronoun"
+    );
+    assert_eq!(num_words, 3);
+
+    let (hint, _) = determine_hint(&packs, "fr", "je me lave");
+    assert_eq!(
+        hint.unwrap_or_default(),
+        "phrase, subject pronoun, reflexive pronoun"
+    );
+
+    let (hint, _) = determine_hint(&packs, "pt", "ela é minha");
+    assert_eq!(
+        hint.unwrap_or_default(),
+        "phrase, subject pronoun, possessive pronoun"
+    );
+
+    // Spanish pronouns are not hinted for French
+    let (hint, _) = determine_hint(&packs, "fr", "usted mange");
+    assert_eq!(hint.unwrap_or_default(), "phrase");
+}
+
+#[test]
+fn test_hints_name_the_phrase_structure() {
+    let packs = shipped_packs();
+
+    // Punctuation does not hide the question word
+    let (hint, num_words) = determine_hint(&packs, "es", "¿Qué quieres?");
+    assert_eq!(
+        hint.unwrap_or_default(),
+        "phrase, interrogative pronoun, question word"
+    );
+    assert_eq!(num_words, 2);
+
+    let (hint, _) = determine_hint(&packs, "es", "No es lo que quiero.");
+    assert_eq!(
+        hint.unwrap_or_default(),
+        "phrase, object pronoun, relative pronoun, negation"
+    );
+
+    let (hint, _) = determine_hint(&packs, "es", "Vamos a la playa");
+    assert_eq!(
+        hint.unwrap_or_default(),
+        "phrase, object pronoun, article, preposition"
+    );
+
+    let (hint, _) = determine_hint(&packs, "fr", "Est-ce que tu ne manges pas?");
+    assert_eq!(
+        hint.unwrap_or_default(),
+        "phrase, subject pronoun, relative pronoun, interrogative pronoun, question word, negation"
+    );
+
+    let (hint, _) = determine_hint(&packs, "pt", "O que você não sabe?");
+    assert_eq!(
+        hint.unwrap_or_default(),
+        "phrase, formal subject pronoun, object pronoun, relative pronoun, interrogative pronoun, article, question word, negation"
+    );
+}
+
+#[test]
+fn test_merge_uses_the_learning_language_pack() {
+    let packs = shipped_packs();
+
+    let mut pair = Vocab {
+        learning_lang: "gatos".to_string(),
+        learning_lang_code: "pt".to_string(),
+        ..Default::default()
+    };
+    let mut alternatives = vec![];
+    merge_learning(&packs, &mut pair, &mut alternatives, "gato".to_string());
+    assert_eq!(pair.learning_lang, "gato");
+    assert_eq!(alternatives, ["gatos"]);
+
+    // Without a pack there is no plural suffix, so nothing is swapped
+    let mut pair = Vocab {
+        learning_lang: "Katzen".to_string(),
+        learning_lang_code: "de".to_string(),
+        ..Default::default()
+    };
+    let mut alternatives = vec![];
+    merge_learning(&packs, &mut pair, &mut alternatives, "Katze".to_string());
+    assert_eq!(pair.learning_lang, "Katzen");
+    assert_eq!(alternatives, ["Katze"]);
+}
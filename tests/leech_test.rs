@@ -0,0 +1,100 @@
+use dotenv::dotenv;
+use palabras::config::app_config::app_config;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::models::{NewAwesomePerson, NewVocab};
+use palabras::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use palabras::sl::leeches::{LeechService, ManageLeeches};
+use palabras::sl::sync_vocab::create_vocab_study;
+use rand::Rng;
+use std::env;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_leech_suspended_and_unsuspended() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let awesome_person = DbAwesomePersonRepository
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("leech-code{}", unique_num),
+            max_learning_words: 5,
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    let vocab = DbVocabRepository
+        .create_vocab(&NewVocab {
+            learning_lang: format!("sanguijuela{}", unique_num),
+            first_lang: "leech".to_string(),
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created");
+    create_vocab_study(vocab.id, awesome_person.id, 0.0)
+        .expect("New vocab study should have been created");
+
+    let service = VocabFuzzyMatch::instance();
+    let leech_service = LeechService::instance();
+    let studies = service
+        .get_vocab_to_learn(awesome_person.id, 1)
+        .expect("Study list should have loaded");
+    let vocab_study_id = studies[0].0.id;
+
+    // Missing it as often as the leech rule allows suspends it
+    let leech_rule = &app_config().leech;
+    for _ in 0..leech_rule.max_lapses {
+        service
+            .check_response(vocab.id, vocab_study_id, "no idea".to_string())
+            .expect("Response should have been checked");
+    }
+
+    let leeches = leech_service
+        .get_leeches(awesome_person.id, None, 10)
+        .expect("Leeches should have loaded");
+    assert_eq!(leeches.len(), 1);
+    let (leech, _) = &leeches[0];
+    let next_page = leech_service
+        .get_leeches(awesome_person.id, Some((leech.lapses, leech.id)), 10)
+        .expect("Leeches should have loaded");
+    assert!(next_page.is_empty(), "The only leech is on the first page");
+    let (leech, _) = &leeches[0];
+    assert!(leech.leech);
+    assert_eq!(leech.lapses, leech_rule.max_lapses);
+    assert_eq!(leech.suspended, leech_rule.suspend);
+    if leech_rule.suspend {
+        let studies = service
+            .get_vocab_to_learn(awesome_person.id, 1)
+            .expect("Study list should have loaded");
+        assert!(
+            studies.is_empty(),
+            "A suspended vocab should not be studied"
+        );
+    }
+
+    // Unsuspended and hinted, it is studied again
+    leech_service
+        .suspend_vocab_study(vocab_study_id, false)
+        .expect("Vocab study should have been unsuspended");
+    let (hinted, hinted_vocab) = leech_service
+        .update_leech(vocab_study_id, Some("blood sucker"), Some("sangre"))
+        .expect("Leech should have been updated");
+    assert_eq!(hinted.user_hint, Some("sangre".to_string()));
+    assert_eq!(hinted_vocab.hint, vocab.hint, "The shared hint is kept");
+
+    let studies = service
+        .get_vocab_to_learn(awesome_person.id, 1)
+        .expect("Study list should have loaded");
+    assert_eq!(studies.len(), 1);
+    assert!(studies[0].0.leech);
+    assert_eq!(studies[0].0.user_notes, Some("blood sucker".to_string()));
+    assert_eq!(studies[0].0.user_hint, Some("sangre".to_string()));
+}
@@ -0,0 +1,122 @@
+use dotenv::dotenv;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::models::{
+    NewAwesomePerson, NewVocab, Vocab, VocabStudy, VocabStudyFilter, VocabStudyKey, VocabStudyOrder,
+};
+use palabras::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use palabras::sl::my_vocab::{MyVocab, MyVocabService};
+use palabras::sl::sync_vocab::create_vocab_study;
+use rand::Rng;
+use std::env;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+fn learning(enrolled: &[(VocabStudy, Vocab)]) -> Vec<String> {
+    enrolled
+        .iter()
+        .map(|(_, vocab)| vocab.learning_lang.clone())
+        .collect()
+}
+
+#[test]
+fn test_my_vocab_sorted_and_paged() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let awesome_person = DbAwesomePersonRepository
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("my-vocab-code{}", unique_num),
+            max_learning_words: 5,
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    // Studied by percentage correct, zapato is well known
+    let words = [("casa", 0.5), ("arbol", 0.2), ("zapato", 0.99)];
+    for (word, percentage) in words {
+        let vocab = DbVocabRepository
+            .create_vocab(&NewVocab {
+                learning_lang: format!("{}{}", word, unique_num),
+                first_lang: word.to_string(),
+                pos: Some("noun".to_string()),
+                known_lang_code: "en".to_string(),
+                learning_lang_code: "es".to_string(),
+                ..Default::default()
+            })
+            .expect("New vocab should have been created");
+        create_vocab_study(vocab.id, awesome_person.id, percentage)
+            .expect("New vocab study should have been created");
+    }
+    let word = |word: &str| format!("{}{}", word, unique_num);
+
+    let service = VocabFuzzyMatch::instance();
+    let my_vocab_service = MyVocabService::instance();
+    let enrolled = |order: VocabStudyOrder, after: Option<VocabStudyKey>, limit: i64| {
+        my_vocab_service
+            .get_enrolled_vocab(
+                awesome_person.id,
+                &VocabStudyFilter::default(),
+                order,
+                after,
+                limit,
+            )
+            .expect("Enrolled vocab should have loaded")
+    };
+
+    // The weakest first, a page at a time
+    let first_page = enrolled(VocabStudyOrder::Weakness, None, 2);
+    assert_eq!(learning(&first_page), vec![word("arbol"), word("casa")]);
+    let (vs, v) = &first_page[1];
+    let after = VocabStudyKey::of(VocabStudyOrder::Weakness, vs, v);
+    let next_page = enrolled(VocabStudyOrder::Weakness, Some(after), 2);
+    assert_eq!(learning(&next_page), vec![word("zapato")]);
+
+    // The vocab tested last first, never tested last
+    for tested in ["zapato", "casa"] {
+        let (vocab_study, vocab) = first_page
+            .iter()
+            .chain(next_page.iter())
+            .find(|(_, vocab)| vocab.learning_lang == word(tested))
+            .expect("The vocab should have been listed");
+        service
+            .check_response(vocab.id, vocab_study.id, vocab.learning_lang.clone())
+            .expect("Response should have been checked");
+    }
+    let recent = enrolled(VocabStudyOrder::Recency, None, 10);
+    assert_eq!(
+        learning(&recent),
+        vec![word("casa"), word("zapato"), word("arbol")]
+    );
+    let (vs, v) = &recent[0];
+    let after = VocabStudyKey::of(VocabStudyOrder::Recency, vs, v);
+    let next_page = enrolled(VocabStudyOrder::Recency, Some(after), 10);
+    assert_eq!(learning(&next_page), vec![word("zapato"), word("arbol")]);
+
+    let alphabetic = enrolled(VocabStudyOrder::Alphabetic, None, 10);
+    assert_eq!(
+        learning(&alphabetic),
+        vec![word("arbol"), word("casa"), word("zapato")]
+    );
+
+    let learning_words = my_vocab_service
+        .get_enrolled_vocab(
+            awesome_person.id,
+            &VocabStudyFilter {
+                well_known: Some(false),
+                pos: Some("Noun".to_string()),
+                ..Default::default()
+            },
+            VocabStudyOrder::Alphabetic,
+            None,
+            10,
+        )
+        .expect("Enrolled vocab should have loaded");
+    assert_eq!(learning(&learning_words), vec![word("arbol"), word("casa")]);
+}
@@ -0,0 +1,80 @@
+use dotenv::dotenv;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::models::{AwesomePerson, NewAwesomePerson, NewVocab};
+use palabras::sl::fuzzy_match_vocab::{LearnVocab, VocabFuzzyMatch};
+use palabras::sl::sync_vocab::create_vocab_study;
+use rand::Rng;
+use std::env;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_recalculate_awesome_person_stats() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+    let repo = DbAwesomePersonRepository;
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let created = repo
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("recalc-code{}", unique_num),
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    let vocab = DbVocabRepository
+        .create_vocab(&NewVocab {
+            learning_lang: format!("recalcular {}", unique_num),
+            first_lang: format!("to recalculate {}", unique_num),
+            num_learning_words: 2,
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created");
+
+    // A well known word, answered correctly once.
+    create_vocab_study(vocab.id, created.id, 0.99)
+        .expect("New vocab study should have been created");
+    let (vocab_study, _) = VocabFuzzyMatch::instance()
+        .get_vocab_to_learn(created.id, 1)
+        .expect("Study list should have loaded")
+        .pop()
+        .expect("The new vocab study should be in the study list");
+    VocabFuzzyMatch::instance()
+        .check_response(vocab.id, vocab_study.id, vocab.learning_lang.clone())
+        .expect("Grading should have worked");
+
+    // Simulate totals that have drifted away from the vocab studies.
+    let drifted = repo
+        .get_awesome_person_by_id(created.id)
+        .expect("Should find the awesome person")
+        .expect("Awesome person should exist");
+    repo.update_awesome_person(AwesomePerson {
+        num_known: Some(50),
+        num_correct: Some(99),
+        num_incorrect: Some(7),
+        ..drifted
+    })
+    .expect("Should update the awesome person");
+
+    let num_updated = repo
+        .recalculate_awesome_person_stats()
+        .expect("Recalculation should have worked");
+    assert!(num_updated >= 1, "Expected at least one awesome person");
+
+    let recalculated = repo
+        .get_awesome_person_by_id(created.id)
+        .expect("Should find the awesome person")
+        .expect("Awesome person should exist");
+    assert_eq!(recalculated.num_known, Some(1));
+    assert_eq!(recalculated.num_correct, Some(1));
+    assert_eq!(recalculated.num_incorrect, Some(0));
+    assert_eq!(recalculated.total_percentage, Some(1.0));
+}
@@ -0,0 +1,172 @@
+use dotenv::dotenv;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::models::{NewAwesomePerson, NewVocab, NewVocabAlternative, VocabSearch};
+use palabras::sl::search::{SearchVocab, VocabSearchService};
+use palabras::sl::sync_vocab::create_vocab_study;
+use palabras::sl::tags::{ManageTags, TagService};
+use rand::Rng;
+use std::env;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_search_vocab() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let awesome_person = DbAwesomePersonRepository
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("search-code{}", unique_num),
+            max_learning_words: 5,
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    let cancion = DbVocabRepository
+        .create_vocab(&NewVocab {
+            learning_lang: format!("{}canción", unique_num),
+            first_lang: format!("{}song", unique_num),
+            pos: Some("noun".to_string()),
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created");
+    let cancionero = DbVocabRepository
+        .create_vocab(&NewVocab {
+            learning_lang: format!("{}cancionero", unique_num),
+            first_lang: format!("{}songbook", unique_num),
+            pos: Some("noun".to_string()),
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created");
+    DbVocabRepository
+        .add_alternative(&NewVocabAlternative {
+            vocab_id: cancionero.id,
+            alternative: format!("{}tonadas", unique_num),
+            ..Default::default()
+        })
+        .expect("New alternative should have been added");
+    create_vocab_study(cancion.id, awesome_person.id, 0.0)
+        .expect("New vocab study should have been created");
+
+    let tag_service = TagService::instance();
+    let search_service = VocabSearchService::instance();
+    let search = |search: VocabSearch| {
+        search_service
+            .search_vocab(&search, None, 20)
+            .expect("Vocab search should have worked")
+    };
+
+    // Accents and case are ignored, the exact match ranks before the prefix. Anything else is only
+    // similar, the unique number alone makes the other test vocab similar.
+    let found = search(VocabSearch {
+        text: format!("{}CANCION", unique_num),
+        awesome_person_id: Some(awesome_person.id),
+        ..Default::default()
+    });
+    assert_eq!(found[0].vocab.id, cancion.id);
+    assert_eq!(found[0].rank, 3.0);
+    assert!(found[0].enrolled);
+    assert_eq!(found[1].vocab.id, cancionero.id);
+    assert!(found[1].rank > 2.0 && found[1].rank < 3.0);
+    assert!(!found[1].enrolled);
+    assert!(found[2..].iter().all(|found| found.rank < 2.0));
+
+    // The next page starts right after the rank, learning language and id of the last match
+    let after = (
+        found[0].rank,
+        found[0].vocab.learning_lang.clone(),
+        found[0].vocab.id,
+    );
+    let next = search_service
+        .search_vocab(
+            &VocabSearch {
+                text: format!("{}CANCION", unique_num),
+                ..Default::default()
+            },
+            Some(after),
+            1,
+        )
+        .expect("Vocab search should have worked");
+    assert_eq!(next.len(), 1);
+    assert_eq!(next[0].vocab.id, cancionero.id);
+
+    // The alternatives and first language are searched too
+    let found = search(VocabSearch {
+        text: format!("{}tonada", unique_num),
+        ..Default::default()
+    });
+    assert_eq!(found[0].vocab.id, cancionero.id);
+    assert!(found[0].rank > 2.0);
+    let found = search(VocabSearch {
+        text: format!("{}songb", unique_num),
+        ..Default::default()
+    });
+    assert_eq!(found[0].vocab.id, cancionero.id);
+    assert!(found[0].rank > 2.0);
+
+    // A misspelled word is found by its similarity
+    let found = search(VocabSearch {
+        text: format!("{}cancoin", unique_num),
+        ..Default::default()
+    });
+    let misspelled = found
+        .iter()
+        .find(|found| found.vocab.id == cancion.id)
+        .expect("The similar vocab should have been found");
+    assert!(misspelled.rank < 2.0);
+
+    // Filters
+    tag_service
+        .tag_vocab(&[cancion.id], &[format!("music{}", unique_num)])
+        .expect("Vocab should have been tagged");
+    let found = search(VocabSearch {
+        text: format!("{}cancion", unique_num),
+        tag: Some(format!("Music{}", unique_num)),
+        ..Default::default()
+    });
+    assert_eq!(found.len(), 1, "Only one vocab has the tag");
+    assert_eq!(found[0].vocab.id, cancion.id);
+    let found = search(VocabSearch {
+        text: format!("{}cancion", unique_num),
+        pos: Some("Verb".to_string()),
+        ..Default::default()
+    });
+    assert!(found
+        .iter()
+        .all(|found| ![cancion.id, cancionero.id].contains(&found.vocab.id)));
+    let found = search(VocabSearch {
+        text: format!("{}cancion", unique_num),
+        learning_lang_code: Some("fr".to_string()),
+        pos: Some("NOUN".to_string()),
+        ..Default::default()
+    });
+    assert!(found
+        .iter()
+        .all(|found| ![cancion.id, cancionero.id].contains(&found.vocab.id)));
+    let found = search(VocabSearch {
+        text: format!("{}cancion", unique_num),
+        learning_lang_code: Some("es".to_string()),
+        pos: Some("NOUN".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(found[0].vocab.id, cancion.id);
+    assert_eq!(found[1].vocab.id, cancionero.id);
+
+    // Wildcards are searched for literally
+    let found = search(VocabSearch {
+        text: format!("{}%", unique_num),
+        ..Default::default()
+    });
+    assert!(found.iter().all(|found| found.rank < 2.0));
+}
@@ -0,0 +1,102 @@
+use palabras::config::ComposerConfig;
+use palabras::models::{Vocab, VocabStudy};
+use palabras::sl::study_composer::StudyComposer;
+use std::collections::HashMap;
+
+fn candidate(id: i32, pos: &str, infinitive: Option<&str>) -> (VocabStudy, Vocab) {
+    let vocab = Vocab {
+        id,
+        learning_lang: format!("word{}", id),
+        pos: Some(pos.to_string()),
+        infinitive: infinitive.map(str::to_string),
+        num_learning_words: 1,
+        ..Default::default()
+    };
+    (VocabStudy::default(), vocab)
+}
+
+fn pos_list(study_list: &[(VocabStudy, Vocab)]) -> Vec<String> {
+    study_list
+        .iter()
+        .map(|(_, vocab)| vocab.pos.clone().unwrap_or_default())
+        .collect()
+}
+
+fn seeded(seed: u64) -> StudyComposer {
+    StudyComposer::new(ComposerConfig {
+        pos_ratios: HashMap::from([("noun".to_string(), 1), ("verb".to_string(), 1)]),
+        seed: Some(seed),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_compose_interleaves_by_ratio() {
+    // Ten verbs in priority order, then the nouns
+    let candidates: Vec<_> = (1..=10)
+        .map(|id| candidate(id, "verb", None))
+        .chain((11..=20).map(|id| candidate(id, "noun", None)))
+        .collect();
+
+    let study_list = seeded(1).compose(candidates.clone(), 6);
+    let kinds = pos_list(&study_list);
+    assert_eq!(kinds.iter().filter(|pos| *pos == "verb").count(), 3);
+    assert!(
+        kinds.windows(2).all(|pair| pair[0] != pair[1]),
+        "Equal ratios should alternate, found {:?}",
+        kinds
+    );
+
+    // The highest priority of each group are taken
+    let mut ids: Vec<i32> = study_list.iter().map(|(_, vocab)| vocab.id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![1, 2, 3, 11, 12, 13]);
+
+    // With the default ratios there are 3 nouns for every 2 verbs
+    let composer = StudyComposer::new(ComposerConfig {
+        seed: Some(1),
+        ..Default::default()
+    });
+    let kinds = pos_list(&composer.compose(candidates, 10));
+    assert_eq!(kinds.iter().filter(|pos| *pos == "noun").count(), 6);
+}
+
+#[test]
+fn test_compose_is_repeatable_with_a_seed() {
+    let candidates: Vec<_> = (1..=12)
+        .map(|id| {
+            let pos = ["noun", "verb", "adjective", "adverb"][id as usize % 4];
+            candidate(id, pos, None)
+        })
+        .collect();
+
+    let first = seeded(42).compose(candidates.clone(), 8);
+    let again = seeded(42).compose(candidates, 8);
+    let ids = |list: &[(VocabStudy, Vocab)]| list.iter().map(|(_, v)| v.id).collect::<Vec<_>>();
+    assert_eq!(first.len(), 8);
+    assert_eq!(ids(&first), ids(&again));
+}
+
+#[test]
+fn test_compose_separates_same_infinitive() {
+    let candidates = vec![
+        candidate(1, "verb", Some("salir")),
+        candidate(2, "verb", Some("salir")),
+        candidate(3, "verb", Some("salir")),
+        candidate(4, "verb", Some("comer")),
+        candidate(5, "verb", Some("vivir")),
+    ];
+
+    for seed in 0..5 {
+        let study_list = seeded(seed).compose(candidates.clone(), 5);
+        let infinitives: Vec<_> = study_list
+            .iter()
+            .map(|(_, vocab)| vocab.infinitive.clone().unwrap_or_default())
+            .collect();
+        assert!(
+            infinitives.windows(2).all(|pair| pair[0] != pair[1]),
+            "Forms of one verb should not be back to back, found {:?}",
+            infinitives
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use dotenv::dotenv;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::error::PalabrasError;
+use palabras::models::{NewAwesomePerson, NewVocab};
+use palabras::sl::fuzzy_match_vocab::{Answering, LearnVocab, VocabFuzzyMatch};
+use palabras::sl::study_session::{StudySessionService, StudySessions};
+use palabras::sl::sync_vocab::create_vocab_study;
+use rand::Rng;
+use std::env;
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_study_session_redrill() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let awesome_person = DbAwesomePersonRepository
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("session-code{}", unique_num),
+            max_learning_words: 5,
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    let mut vocabs = Vec::new();
+    for word in ["sesión", "repaso"] {
+        let vocab = DbVocabRepository
+            .create_vocab(&NewVocab {
+                learning_lang: format!("{}{}", word, unique_num),
+                first_lang: format!("session word {}", word),
+                known_lang_code: "en".to_string(),
+                learning_lang_code: "es".to_string(),
+                ..Default::default()
+            })
+            .expect("New vocab should have been created");
+        create_vocab_study(vocab.id, awesome_person.id, 0.0)
+            .expect("New vocab study should have been created");
+        vocabs.push(vocab);
+    }
+
+    let service = VocabFuzzyMatch::instance();
+    let session_service = StudySessionService::instance();
+    let mut studies = service
+        .get_vocab_to_learn(awesome_person.id, 2)
+        .expect("Study list should have loaded");
+    studies.sort_by_key(|(_, vocab)| {
+        vocabs
+            .iter()
+            .position(|created| created.id == vocab.id)
+            .expect("Only the new vocab should be in the study list")
+    });
+    let session = session_service
+        .start_study_session(awesome_person.id)
+        .expect("Study session should have started");
+    assert!(session.finished.is_none());
+
+    // The first word is right, the second is missed.
+    let learning = Answering::Learning {
+        with_article: false,
+    };
+    let (right, missed) = (&studies[0], &studies[1]);
+    service
+        .check_session_response(
+            session.id,
+            right.1.id,
+            right.0.id,
+            right.1.learning_lang.clone(),
+            learning,
+        )
+        .expect("Response should have been checked");
+    service
+        .check_session_response(
+            session.id,
+            missed.1.id,
+            missed.0.id,
+            "no idea".to_string(),
+            learning,
+        )
+        .expect("Response should have been checked");
+
+    // The missed word is re-drilled before the session closes.
+    let summary = session_service
+        .finish_study_session(session.id, false)
+        .expect("Study session summary should have loaded");
+    assert!(!summary.is_finished());
+    assert_eq!(summary.session.num_answers, 2);
+    assert_eq!(summary.session.num_correct, 1);
+    assert_eq!(summary.items.len(), 2);
+    assert_eq!(summary.redrill.len(), 1);
+    assert_eq!(summary.redrill[0].0.id, missed.0.id);
+
+    service
+        .check_session_response(
+            session.id,
+            missed.1.id,
+            missed.0.id,
+            missed.1.learning_lang.clone(),
+            learning,
+        )
+        .expect("Re-drill should have been checked");
+
+    let summary = session_service
+        .finish_study_session(session.id, false)
+        .expect("Study session should have finished");
+    assert!(summary.is_finished());
+    assert!(summary.redrill.is_empty());
+    let missed_words: Vec<_> = summary.missed().collect();
+    assert_eq!(missed_words.len(), 1);
+    let (item, vocab) = missed_words[0];
+    assert_eq!(vocab.id, missed.1.id);
+    assert_eq!((item.attempts, item.correct_attempts), (2, 1));
+    assert!(item.redrilled);
+    assert_eq!(item.percentage_before, 0.0);
+    assert!(item.percentage_change() > 0.0);
+
+    // A finished session takes no more answers.
+    let result = service.check_session_response(
+        session.id,
+        right.1.id,
+        right.0.id,
+        right.1.learning_lang.clone(),
+        learning,
+    );
+    assert!(matches!(result, Err(PalabrasError::Conflict(_))));
+}
@@ -0,0 +1,148 @@
+use dotenv::dotenv;
+use palabras::dal::awesome_person::{AwesomePersonRepository, DbAwesomePersonRepository};
+use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
+use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
+use palabras::models::{NewAwesomePerson, NewDeck, NewVocab};
+use palabras::sl::decks::{DeckService, ManageDecks};
+use palabras::sl::fuzzy_match_vocab::{LearnVocab, StudyFilter, VocabFuzzyMatch};
+use palabras::sl::sync_vocab::{create_vocab_study, export_deck, import_deck, import_vocab};
+use palabras::sl::tags::{ManageTags, TagService};
+use rand::Rng;
+use std::{env, fs};
+
+fn get_test_db_url() -> String {
+    env::var("TEST_DATABASE_URL").expect("env var TEST_DATABASE_URL was not found")
+}
+
+#[test]
+fn test_tag_filter_and_export() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+
+    let unique_num = rand::thread_rng().gen_range(100000..=1000000000);
+    let awesome_person = DbAwesomePersonRepository
+        .create_awesome_person(&NewAwesomePerson {
+            sec_code: format!("tag-code{}", unique_num),
+            max_learning_words: 5,
+            ..Default::default()
+        })
+        .expect("New awesome person should have been created");
+
+    // An imported vocab is tagged with its skill
+    let skill = format!("Food{}", unique_num);
+    let pan = import_vocab(NewVocab {
+        learning_lang: format!("pan{}", unique_num),
+        first_lang: "bread".to_string(),
+        skill: Some(skill.clone()),
+        known_lang_code: "en".to_string(),
+        learning_lang_code: "es".to_string(),
+        ..Default::default()
+    })
+    .expect("New vocab should have been imported");
+    let embarazada = DbVocabRepository
+        .create_vocab(&NewVocab {
+            learning_lang: format!("embarazada{}", unique_num),
+            first_lang: "pregnant".to_string(),
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+            ..Default::default()
+        })
+        .expect("New vocab should have been created");
+    create_vocab_study(pan.id, awesome_person.id, 0.0)
+        .expect("New vocab study should have been created");
+    create_vocab_study(embarazada.id, awesome_person.id, 0.0)
+        .expect("New vocab study should have been created");
+
+    let service = VocabFuzzyMatch::instance();
+    let deck_service = DeckService::instance();
+    let tag_service = TagService::instance();
+    let false_friend = format!("False-Friend{}", unique_num);
+    let num_tagged = tag_service
+        .tag_vocab(
+            &[pan.id, embarazada.id],
+            &[false_friend.clone(), skill.clone()],
+        )
+        .expect("Vocab should have been tagged");
+    assert_eq!(num_tagged, 3, "pan already has its skill");
+
+    let tags = tag_service.get_tags().expect("Tags should have loaded");
+    let counted = tags
+        .iter()
+        .find(|(tag, _)| tag.name == false_friend.to_lowercase())
+        .expect("The new tag should be listed");
+    assert_eq!(counted.1, 2);
+
+    // Removing a tag narrows the vocab with both tags
+    let num_untagged = tag_service
+        .untag_vocab(&[embarazada.id], std::slice::from_ref(&skill))
+        .expect("Vocab should have been untagged");
+    assert_eq!(num_untagged, 1);
+    let tagged = tag_service
+        .get_tagged_vocab(&[false_friend.clone(), skill.clone()], None, 10)
+        .expect("Tagged vocab should have loaded");
+    assert_eq!(tagged.len(), 1);
+    assert_eq!(tagged[0].0.id, pan.id);
+    assert_eq!(tagged[0].1.len(), 2);
+    let next_page = tag_service
+        .get_tagged_vocab(&[false_friend.clone(), skill.clone()], Some(pan.id), 10)
+        .expect("Tagged vocab should have loaded");
+    assert!(
+        next_page.is_empty(),
+        "The page after the last vocab is empty"
+    );
+
+    let study_list = service
+        .get_filtered_vocab_to_learn(
+            awesome_person.id,
+            10,
+            &StudyFilter {
+                tags: vec![skill.clone()],
+                ..Default::default()
+            },
+        )
+        .expect("Study list should have loaded");
+    assert_eq!(study_list.len(), 1);
+    assert_eq!(study_list[0].1.id, pan.id);
+
+    // Only the tagged vocab of the deck is exported, with its tags
+    let deck = deck_service
+        .create_deck(NewDeck {
+            owner_id: awesome_person.id,
+            name: "Tricky".to_string(),
+            description: None,
+            known_lang_code: "en".to_string(),
+            learning_lang_code: "es".to_string(),
+        })
+        .expect("New deck should have been created");
+    deck_service
+        .add_to_deck(deck.id, &[pan.id, embarazada.id])
+        .expect("Vocab should have been added");
+    let export_file = env::temp_dir().join(format!("tags_{}.csv", unique_num));
+    let export_file = export_file.to_str().expect("Temp path should be valid");
+    let num_exported = export_deck(deck.id, std::slice::from_ref(&skill), export_file)
+        .expect("Deck should have been exported");
+    assert_eq!(num_exported, 1);
+    let exported = fs::read_to_string(export_file).expect("Export should have been written");
+    assert!(
+        exported.contains(&format!("{};{}", false_friend, skill).to_lowercase()),
+        "{}",
+        exported
+    );
+
+    // Importing applies the tags of each line
+    let a2 = format!("a2-{}", unique_num);
+    fs::write(
+        export_file,
+        format!("{}embarazada{},,,,pregnant,{}\n", exported, unique_num, a2),
+    )
+    .expect("Import should have been written");
+    import_deck(deck.id, export_file).expect("Deck should have been imported");
+    fs::remove_file(export_file).ok();
+    let tagged = tag_service
+        .get_tagged_vocab(&[a2], None, 10)
+        .expect("Tagged vocab should have loaded");
+    assert_eq!(tagged.len(), 1);
+    assert_eq!(tagged[0].0.id, embarazada.id);
+}
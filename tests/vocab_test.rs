@@ -1,7 +1,8 @@
 use dotenv::dotenv;
 use palabras::dal::db_connection::{establish_connection_pool, verify_connection_migrate_db};
 use palabras::dal::vocab::{DbVocabRepository, VocabRepository};
-use palabras::models::{NewVocab, Vocab};
+use palabras::error::PalabrasError;
+use palabras::models::{NewVocab, NewVocabAlternative, Vocab};
 use rand::Rng;
 use std::env;
 use std::string::ToString;
@@ -24,15 +25,14 @@ fn test_create_translation() {
 
     let current = repo
         .find_vocab_by_learning_language(new_vocab.learning_lang.clone())
-        .unwrap_or_else(|_| None);
+        .unwrap_or(None);
 
     // This is extremely likely
     if current.is_none() {
         let created = repo.create_vocab(&new_vocab).expect("Create failed");
 
-        let alternatives = "comprobar, examinar, examinar".to_string();
         let updating = Vocab {
-            alternatives: Some(alternatives.clone()),
+            learning_lang_code: "es".to_string(),
             ..created.clone()
         };
 
@@ -46,14 +46,6 @@ fn test_create_translation() {
             .expect("Lookup by learning lang should have worked")
             .expect("Lookup by learning lang option should unwrap.");
 
-        assert_eq!(
-            by_learning_lang.alternatives.clone().unwrap(),
-            alternatives,
-            "Expected alternatives to match, Result {} expected {}",
-            by_learning_lang.alternatives.unwrap(),
-            alternatives
-        );
-
         assert_eq!(
             by_learning_lang.learning_lang_code,
             updating.learning_lang_code,
@@ -62,23 +54,86 @@ fn test_create_translation() {
             created.learning_lang_code.clone()
         );
 
-        alternatives.clone().split(',').for_each(|alt| {
+        // The alternatives are unique to this vocab, as the learning lang is
+        let unique = new_vocab.learning_lang.replace("probar ", "");
+        let alternatives = vec![
+            NewVocabAlternative {
+                vocab_id: created.id,
+                alternative: format!("comprobar {}", unique),
+                ..Default::default()
+            },
+            NewVocabAlternative {
+                vocab_id: created.id,
+                alternative: format!("testear {}", unique),
+                register: Some("informal".to_string()),
+                lang_variant: Some("es-MX".to_string()),
+            },
+        ];
+        for alternative in &alternatives {
+            repo.add_alternative(alternative)
+                .expect("Adding an alternative should have worked");
+        }
+
+        let duplicate = repo.add_alternative(&alternatives[0]);
+        assert!(
+            matches!(duplicate, Err(PalabrasError::Conflict(_))),
+            "Expected a conflict for a duplicate alternative, got {:?}",
+            duplicate
+        );
+
+        let saved = repo
+            .get_alternatives(created.id)
+            .expect("Getting the alternatives should have worked");
+        let saved_alternatives: Vec<&str> = saved
+            .iter()
+            .map(|alternative| alternative.alternative.as_str())
+            .collect();
+        assert_eq!(
+            saved_alternatives,
+            [
+                alternatives[0].alternative.as_str(),
+                alternatives[1].alternative.as_str()
+            ]
+        );
+        assert_eq!(saved[0].register, None);
+        assert_eq!(saved[1].register, Some("informal".to_string()));
+        assert_eq!(saved[1].lang_variant, Some("es-MX".to_string()));
+
+        // The lookup is exact, ignoring case
+        for alternative in &alternatives {
             let by_an_alternative = repo
-                .find_vocab_by_alternative(alt.to_string())
-                .expect("Lookup by learning lang should have worked")
-                .expect("Lookup by learning lang option should unwrap.");
-
-            assert_eq!(
-                by_an_alternative.alternatives.clone().unwrap(),
-                alternatives,
-                "Expected alternatives to match, Result {} expected {}",
-                by_learning_lang.alternatives.clone().unwrap(),
-                alternatives
-            );
-        });
+                .find_vocab_by_alternative(alternative.alternative.to_uppercase())
+                .expect("Lookup by alternative should have worked")
+                .expect("Lookup by alternative option should unwrap.");
+            assert_eq!(by_an_alternative.id, created.id);
+        }
+
+        // Part of 'comprobar', but not an alternative
+        let by_part_of_an_alternative = repo
+            .find_vocab_by_alternative(format!("probar {}", unique))
+            .expect("Lookup by alternative should have worked");
+        assert!(
+            by_part_of_an_alternative.is_none(),
+            "Expected no vocab for part of an alternative"
+        );
     }
 }
 
+#[test]
+fn test_get_vocab_by_id_not_found() {
+    dotenv().ok(); // Load environment variables from .env file
+
+    establish_connection_pool(get_test_db_url());
+    verify_connection_migrate_db().expect("connection and migration should have worked");
+    let repo = DbVocabRepository;
+
+    let result = repo.get_vocab_by_id(-1);
+    assert!(
+        matches!(result, Err(PalabrasError::NotFound { .. })),
+        "Expected a not found error for a negative id"
+    );
+}
+
 #[test]
 fn test_fix_first_lang() {
     dotenv::from_filename("test.env").ok();
@@ -106,7 +161,7 @@ fn test_fix_first_lang() {
     }
 
     let list = repo
-        .get_empty_first_lang(num_records.clone() + 1)
+        .get_empty_first_lang(num_records + 1)
         .expect("Should have gotten records with no first lang");
     assert!(
         list.len() >= num_records as usize,